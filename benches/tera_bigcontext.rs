@@ -55,6 +55,61 @@ fn bench_big_loop_big_object(c: &mut Criterion) {
         assert_eq!(rendering, "0123");
         b.iter(|| template.render(&root));
     });
+    group.bench_function(BenchmarkId::new("render", "liquid-compiled"), |b| {
+        let parser = liquid::ParserBuilder::with_stdlib().build().unwrap();
+        let template = parser
+            .parse(
+                "
+{%- for object in objects -%}
+{{ object.field_a.i }}
+{%- if object.field_a.i > 2 -%}
+{%- break -%}
+{%- endif -%}
+{%- endfor -%}
+            ",
+            )
+            .expect("Benchmark template parsing failed");
+
+        let mut root: std::collections::HashMap<&'static str, &[BigObject]> = Default::default();
+        root.insert("objects", &objects);
+        let root = liquid::to_object(&root).unwrap();
+
+        let template = template.compile();
+        let rendering = template.render(&root).unwrap();
+        assert_eq!(rendering, "0123");
+        b.iter(|| template.render(&root));
+    });
+    group.finish();
+}
+
+fn bench_select_big_object(c: &mut Criterion) {
+    const NUM_OBJECTS: usize = 100;
+    let mut objects = Vec::with_capacity(NUM_OBJECTS);
+    for i in 0..NUM_OBJECTS {
+        objects.push(BigObject::new(i));
+    }
+
+    let mut group = c.benchmark_group("bench_select_big_object");
+    group.bench_function(BenchmarkId::new("render", "liquid"), |b| {
+        let parser = liquid::ParserBuilder::with_stdlib().build().unwrap();
+        let template = parser
+            .parse(
+                "
+{{- objects | slice: 10, 5 | map: 'field_a' | map: 'i' | join: ',' -}}
+|
+{{- objects | last | default: nil | size -}}
+            ",
+            )
+            .expect("Benchmark template parsing failed");
+
+        let mut root: std::collections::HashMap<&'static str, &[BigObject]> = Default::default();
+        root.insert("objects", &objects);
+        let root = liquid::to_object(&root).unwrap();
+
+        let rendering = template.render(&root).unwrap();
+        assert_eq!(rendering, "10,11,12,13,14|6");
+        b.iter(|| template.render(&root));
+    });
     group.finish();
 }
 
@@ -100,5 +155,5 @@ With that in mind, let’s learn about borrowing.".into(),
     }
 }
 
-criterion_group!(benches, bench_big_loop_big_object,);
+criterion_group!(benches, bench_big_loop_big_object, bench_select_big_object);
 criterion_main!(benches);
@@ -98,28 +98,38 @@ impl<T: ValueView> ArrayView for Vec<T> {
     }
 }
 
-impl<'a, A: ArrayView + ?Sized> ArrayView for &'a A {
-    fn as_value(&self) -> &dyn ValueView {
-        <A as ArrayView>::as_value(self)
-    }
-
-    fn size(&self) -> i64 {
-        <A as ArrayView>::size(self)
-    }
-
-    fn values<'k>(&'k self) -> Box<dyn Iterator<Item = &'k dyn ValueView> + 'k> {
-        <A as ArrayView>::values(self)
-    }
-
-    fn contains_key(&self, index: i64) -> bool {
-        <A as ArrayView>::contains_key(self, index)
-    }
-
-    fn get(&self, index: i64) -> Option<&dyn ValueView> {
-        <A as ArrayView>::get(self, index)
-    }
+macro_rules! forward_array_view {
+    ([$($generics:tt)*] $ty:ty $(where $($bounds:tt)*)?) => {
+        impl<$($generics)*> ArrayView for $ty $(where $($bounds)*)? {
+            fn as_value(&self) -> &dyn ValueView {
+                <A as ArrayView>::as_value(self)
+            }
+
+            fn size(&self) -> i64 {
+                <A as ArrayView>::size(self)
+            }
+
+            fn values<'k>(&'k self) -> Box<dyn Iterator<Item = &'k dyn ValueView> + 'k> {
+                <A as ArrayView>::values(self)
+            }
+
+            fn contains_key(&self, index: i64) -> bool {
+                <A as ArrayView>::contains_key(self, index)
+            }
+
+            fn get(&self, index: i64) -> Option<&dyn ValueView> {
+                <A as ArrayView>::get(self, index)
+            }
+        }
+    };
 }
 
+forward_array_view!(['a, A: ArrayView + ?Sized] &'a A);
+forward_array_view!([A: ArrayView + ?Sized] Box<A>);
+forward_array_view!([A: ArrayView + ?Sized] std::rc::Rc<A>);
+forward_array_view!([A: ArrayView + ?Sized] std::sync::Arc<A>);
+forward_array_view!(['a, A: ArrayView + ToOwned + ?Sized] std::borrow::Cow<'a, A> where A::Owned: fmt::Debug);
+
 fn convert_value(s: &dyn ValueView) -> &dyn ValueView {
     s
 }
@@ -132,14 +142,23 @@ fn convert_index(index: i64, max_size: i64) -> i64 {
     }
 }
 
-struct ArraySource<'s, T: ValueView> {
-    s: &'s Vec<T>,
+#[derive(Debug)]
+/// Helper for `ArrayView::source`
+pub struct ArraySource<'s, A: ArrayView + ?Sized> {
+    s: &'s A,
 }
 
-impl<'s, T: ValueView> fmt::Display for ArraySource<'s, T> {
+impl<'s, A: ArrayView + ?Sized> ArraySource<'s, A> {
+    #[doc(hidden)]
+    pub fn new(other: &'s A) -> Self {
+        Self { s: other }
+    }
+}
+
+impl<'s, A: ArrayView + ?Sized> fmt::Display for ArraySource<'s, A> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "[")?;
-        for item in self.s {
+        for item in self.s.values() {
             write!(f, "{}, ", item.render())?;
         }
         write!(f, "]")?;
@@ -147,13 +166,22 @@ impl<'s, T: ValueView> fmt::Display for ArraySource<'s, T> {
     }
 }
 
-struct ArrayRender<'s, T: ValueView> {
-    s: &'s Vec<T>,
+#[derive(Debug)]
+/// Helper for `ArrayView::render`
+pub struct ArrayRender<'s, A: ArrayView + ?Sized> {
+    s: &'s A,
+}
+
+impl<'s, A: ArrayView + ?Sized> ArrayRender<'s, A> {
+    #[doc(hidden)]
+    pub fn new(other: &'s A) -> Self {
+        Self { s: other }
+    }
 }
 
-impl<'s, T: ValueView> fmt::Display for ArrayRender<'s, T> {
+impl<'s, A: ArrayView + ?Sized> fmt::Display for ArrayRender<'s, A> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        for item in self.s {
+        for item in self.s.values() {
             write!(f, "{}", item.render())?;
         }
         Ok(())
@@ -5,6 +5,7 @@ use std::slice;
 
 use crate::error::{Error, Result};
 use crate::model::KStringCow;
+use crate::model::Object;
 
 use super::ScalarCow;
 use super::Value;
@@ -134,8 +135,8 @@ fn try_find_borrowed<'o, 'i>(
     };
     let child = augmented_get(value, index)?;
     match child {
-        ValueCow::Owned(child) => try_find_owned(child, path),
         ValueCow::Borrowed(child) => try_find_borrowed(child, path),
+        child => try_find_owned(child.into_owned(), path),
     }
 }
 
@@ -151,10 +152,10 @@ fn try_find_owned<'o, 'i>(
     };
     let child = augmented_get(&value, index)?;
     match child {
-        ValueCow::Owned(child) => try_find_owned(child, path),
         ValueCow::Borrowed(child) => {
             try_find_borrowed(child, path).map(|v| ValueCow::Owned(v.into_owned()))
         }
+        child => try_find_owned(child.into_owned(), path),
     }
 }
 
@@ -172,8 +173,8 @@ fn augmented_get<'o>(value: &'o dyn ValueView, index: &ScalarCow<'_>) -> Option<
         }
     } else if let Some(obj) = value.as_object() {
         let index = index.to_kstr();
-        obj.get(index.as_str())
-            .map(ValueCow::Borrowed)
+        obj.get_cow(index.as_str())
+            .or_else(|| obj.get_missing(index.as_str()))
             .or_else(|| match index.as_str() {
                 "size" => Some(ValueCow::Owned(Value::scalar(obj.size()))),
                 _ => None,
@@ -191,6 +192,61 @@ fn augmented_get<'o>(value: &'o dyn ValueView, index: &ScalarCow<'_>) -> Option<
     }
 }
 
+/// Replace the `Value` nested in `value` at `path`, returning the previous one.
+///
+/// Missing keys are created along the way, with `nil` becoming an empty object.  Array
+/// indexes may be negative to count from the end, or the array length to append.
+pub fn set_path(value: &mut Value, path: &[ScalarCow<'_>], new: Value) -> Result<Option<Value>> {
+    let (index, rest) = match path.split_first() {
+        Some(split) => split,
+        None => return Ok(Some(std::mem::replace(value, new))),
+    };
+
+    if value.is_nil() {
+        *value = Value::Object(Object::new());
+    }
+    let type_name = value.type_name();
+    match value {
+        Value::Object(obj) => {
+            let key = index.to_kstr().into_owned();
+            if rest.is_empty() {
+                return Ok(obj.insert(key, new));
+            }
+            let child = obj.entry(key).or_insert(Value::Nil);
+            set_path(child, rest, new)
+        }
+        Value::Array(arr) => {
+            let len = arr.len() as i64;
+            let requested = index.to_integer().ok_or_else(|| {
+                Error::with_msg("Expected whole number index")
+                    .context("requested index", format!("{}", index.render()))
+            })?;
+            let i = if requested < 0 {
+                requested + len
+            } else {
+                requested
+            };
+            if i == len {
+                arr.push(Value::Nil);
+                let child = arr.last_mut().expect("Just pushed.");
+                set_path(child, rest, new)?;
+                return Ok(None);
+            }
+            if i < 0 || len < i {
+                return Error::with_msg("Index out of range")
+                    .context("requested index", format!("{}", requested))
+                    .context("available indexes", format!("{}..{}", -len, len))
+                    .into_err();
+            }
+            set_path(&mut arr[i as usize], rest, new)
+        }
+        _ => Error::with_msg("Cannot set index")
+            .context("requested index", format!("{}", index.render()))
+            .context("type", type_name)
+            .into_err(),
+    }
+}
+
 /// Find a `ValueView` nested in an `ObjectView`
 pub fn find<'o>(value: &'o dyn ValueView, path: &[ScalarCow<'_>]) -> Result<ValueCow<'o>> {
     if let Some(res) = try_find(value, path) {
@@ -234,3 +290,71 @@ pub fn find<'o>(value: &'o dyn ValueView, path: &[ScalarCow<'_>]) -> Result<Valu
         );
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::model::Scalar;
+
+    fn path(indexes: &[Scalar]) -> Vec<ScalarCow<'_>> {
+        indexes.iter().map(|i| i.as_ref()).collect()
+    }
+
+    #[test]
+    fn test_set_path() {
+        let mut value = crate::value!({"a": {"b": 1}, "list": [1, 2]});
+
+        let old = set_path(
+            &mut value,
+            &path(&[Scalar::new("a"), Scalar::new("b")]),
+            Value::scalar(2),
+        )
+        .unwrap();
+        assert_eq!(old, Some(Value::scalar(1)));
+
+        let old = set_path(
+            &mut value,
+            &path(&[Scalar::new("x"), Scalar::new("y")]),
+            Value::scalar(3),
+        )
+        .unwrap();
+        assert_eq!(old, None);
+
+        set_path(
+            &mut value,
+            &path(&[Scalar::new("list"), Scalar::new(-1)]),
+            Value::scalar(4),
+        )
+        .unwrap();
+        set_path(
+            &mut value,
+            &path(&[Scalar::new("list"), Scalar::new(2)]),
+            Value::scalar(5),
+        )
+        .unwrap();
+        assert_eq!(
+            value,
+            crate::value!({"a": {"b": 2}, "list": [1, 4, 5], "x": {"y": 3}})
+        );
+
+        let err = set_path(
+            &mut value,
+            &path(&[Scalar::new("list"), Scalar::new(4)]),
+            Value::Nil,
+        );
+        assert!(err.is_err());
+        let err = set_path(
+            &mut value,
+            &path(&[Scalar::new("list"), Scalar::new("k")]),
+            Value::Nil,
+        );
+        assert!(err.is_err());
+        let err = set_path(
+            &mut value,
+            &path(&[Scalar::new("a"), Scalar::new("b"), Scalar::new("c")]),
+            Value::Nil,
+        );
+        assert!(err.is_err());
+    }
+}
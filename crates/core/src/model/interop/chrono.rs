@@ -0,0 +1,68 @@
+use std::convert::TryFrom;
+
+use chrono::{Datelike, Offset, TimeZone};
+
+use crate::model::{Date, DateTime, Scalar};
+
+impl_scalar_view!([Tz: TimeZone] chrono::DateTime<Tz>, date_time_to_scalar);
+
+/// Out of range date times stay RFC 3339 strings.
+fn date_time_to_scalar<Tz: TimeZone>(datetime: &chrono::DateTime<Tz>) -> Scalar {
+    let fixed = datetime.with_timezone(&datetime.offset().fix());
+    let converted = time::UtcOffset::from_whole_seconds(fixed.offset().local_minus_utc())
+        .ok()
+        .and_then(|offset| {
+            let nanoseconds = time::Duration::nanoseconds(fixed.timestamp_subsec_nanos().into());
+            DateTime::from_unix_timestamp(fixed.timestamp())?
+                .checked_add(nanoseconds)
+                .map(|d| d.with_offset(offset))
+        });
+    match converted {
+        Some(x) => Scalar::new(x),
+        None => Scalar::new(fixed.to_rfc3339()),
+    }
+}
+
+impl_scalar_view!([] chrono::NaiveDate, date_to_scalar);
+
+/// Out of range dates stay ISO 8601 strings.
+fn date_to_scalar(date: &chrono::NaiveDate) -> Scalar {
+    let converted = u8::try_from(date.month())
+        .ok()
+        .and_then(|month| time::Month::try_from(month).ok())
+        .and_then(|month| {
+            time::Date::from_calendar_date(date.year(), month, date.day() as u8).ok()
+        });
+    match converted {
+        Some(inner) => Scalar::new(Date { inner }),
+        None => Scalar::new(date.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::model::{ValueView, ValueViewCmp};
+
+    #[test]
+    fn chrono_date_time() {
+        let offset = chrono::FixedOffset::east_opt(3600).unwrap();
+        let datetime = offset.with_ymd_and_hms(2021, 3, 4, 5, 6, 7).unwrap();
+
+        assert_eq!(datetime.type_name(), "date time");
+        assert_eq!(datetime.render().to_string(), "2021-03-04 05:06:07 +0100");
+        assert_eq!(
+            ValueViewCmp::new(&datetime),
+            DateTime::from_str("2021-03-04 04:06:07 +0000").unwrap()
+        );
+    }
+
+    #[test]
+    fn chrono_date() {
+        let date = chrono::NaiveDate::from_ymd_opt(2021, 3, 4).unwrap();
+
+        assert_eq!(date.type_name(), "date");
+        assert_eq!(ValueViewCmp::new(&date), Date::from_ymd(2021, 3, 4));
+    }
+}
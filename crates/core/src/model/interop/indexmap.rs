@@ -0,0 +1,96 @@
+use std::fmt;
+
+use indexmap::IndexMap;
+
+use crate::model::{
+    DisplayCow, KString, KStringCow, ObjectIndex, ObjectRender, ObjectSource, ObjectView, State,
+    Value, ValueView,
+};
+
+impl<K: ObjectIndex, V: ValueView, S: ::std::hash::BuildHasher> ValueView for IndexMap<K, V, S> {
+    fn as_debug(&self) -> &dyn fmt::Debug {
+        self
+    }
+
+    fn render(&self) -> DisplayCow<'_> {
+        DisplayCow::Owned(Box::new(ObjectRender::new(self)))
+    }
+    fn source(&self) -> DisplayCow<'_> {
+        DisplayCow::Owned(Box::new(ObjectSource::new(self)))
+    }
+    fn type_name(&self) -> &'static str {
+        "object"
+    }
+    fn query_state(&self, state: State) -> bool {
+        match state {
+            State::Truthy => true,
+            State::DefaultValue | State::Empty | State::Blank => self.is_empty(),
+        }
+    }
+
+    fn to_kstr(&self) -> KStringCow<'_> {
+        let s = ObjectRender::new(self).to_string();
+        KStringCow::from_string(s)
+    }
+    fn to_value(&self) -> Value {
+        Value::Object(
+            self.iter()
+                .map(|(k, v)| (KString::from_ref(k.as_index()), v.to_value()))
+                .collect(),
+        )
+    }
+
+    fn as_object(&self) -> Option<&dyn ObjectView> {
+        Some(self)
+    }
+}
+
+impl<K: ObjectIndex, V: ValueView, S: ::std::hash::BuildHasher> ObjectView for IndexMap<K, V, S> {
+    fn as_value(&self) -> &dyn ValueView {
+        self
+    }
+
+    fn size(&self) -> i64 {
+        self.len() as i64
+    }
+
+    fn keys<'k>(&'k self) -> Box<dyn Iterator<Item = KStringCow<'k>> + 'k> {
+        let keys = IndexMap::keys(self).map(|s| s.as_index().into());
+        Box::new(keys)
+    }
+
+    fn values<'k>(&'k self) -> Box<dyn Iterator<Item = &'k dyn ValueView> + 'k> {
+        let i = IndexMap::values(self).map(|v| v as &dyn ValueView);
+        Box::new(i)
+    }
+
+    fn iter<'k>(&'k self) -> Box<dyn Iterator<Item = (KStringCow<'k>, &'k dyn ValueView)> + 'k> {
+        let i = IndexMap::iter(self).map(|(k, v)| (k.as_index().into(), v as &dyn ValueView));
+        Box::new(i)
+    }
+
+    fn contains_key(&self, index: &str) -> bool {
+        IndexMap::contains_key(self, index)
+    }
+
+    fn get<'s>(&'s self, index: &str) -> Option<&'s dyn ValueView> {
+        IndexMap::get(self, index).map(|v| v as &dyn ValueView)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn index_map_keeps_order() {
+        let mut map = IndexMap::new();
+        map.insert(KString::from_static("b"), 1);
+        map.insert(KString::from_static("a"), 2);
+
+        assert_eq!(map.render().to_string(), "b1a2");
+        assert_eq!(map.get("a").unwrap().to_kstr(), "2");
+        let keys: Vec<_> = ObjectView::keys(&map).collect();
+        assert_eq!(keys, ["b", "a"]);
+    }
+}
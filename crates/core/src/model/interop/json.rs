@@ -0,0 +1,188 @@
+use std::fmt;
+
+use serde_json::{Map, Number, Value as JsonValue};
+
+use crate::model::{
+    ArrayView, DisplayCow, KString, KStringCow, ObjectRender, ObjectSource, ObjectView, Scalar,
+    ScalarCow, State, Value, ValueView,
+};
+
+static NIL: Value = Value::Nil;
+
+impl ValueView for JsonValue {
+    fn as_debug(&self) -> &dyn fmt::Debug {
+        self
+    }
+
+    fn render(&self) -> DisplayCow<'_> {
+        forward(self).render()
+    }
+    fn source(&self) -> DisplayCow<'_> {
+        forward(self).source()
+    }
+    fn type_name(&self) -> &'static str {
+        forward(self).type_name()
+    }
+    fn query_state(&self, state: State) -> bool {
+        forward(self).query_state(state)
+    }
+
+    fn to_kstr(&self) -> KStringCow<'_> {
+        forward(self).to_kstr()
+    }
+    fn to_value(&self) -> Value {
+        forward(self).to_value()
+    }
+
+    fn as_scalar(&self) -> Option<ScalarCow<'_>> {
+        forward(self).as_scalar()
+    }
+
+    fn as_array(&self) -> Option<&dyn ArrayView> {
+        forward(self).as_array()
+    }
+
+    fn as_object(&self) -> Option<&dyn ObjectView> {
+        forward(self).as_object()
+    }
+
+    fn is_nil(&self) -> bool {
+        self.is_null()
+    }
+}
+
+fn forward(value: &JsonValue) -> &dyn ValueView {
+    match value {
+        JsonValue::Null => &NIL,
+        JsonValue::Bool(x) => x,
+        JsonValue::Number(x) => x,
+        JsonValue::String(x) => x,
+        JsonValue::Array(x) => x,
+        JsonValue::Object(x) => x,
+    }
+}
+
+impl_scalar_view!([] Number, to_scalar);
+
+fn to_scalar(number: &Number) -> Scalar {
+    match number.as_i64() {
+        Some(x) => Scalar::new(x),
+        None => Scalar::new(number.as_f64().unwrap_or(f64::NAN)),
+    }
+}
+
+impl ValueView for Map<String, JsonValue> {
+    fn as_debug(&self) -> &dyn fmt::Debug {
+        self
+    }
+
+    fn render(&self) -> DisplayCow<'_> {
+        DisplayCow::Owned(Box::new(ObjectRender::new(self)))
+    }
+    fn source(&self) -> DisplayCow<'_> {
+        DisplayCow::Owned(Box::new(ObjectSource::new(self)))
+    }
+    fn type_name(&self) -> &'static str {
+        "object"
+    }
+    fn query_state(&self, state: State) -> bool {
+        match state {
+            State::Truthy => true,
+            State::DefaultValue | State::Empty | State::Blank => self.is_empty(),
+        }
+    }
+
+    fn to_kstr(&self) -> KStringCow<'_> {
+        let s = ObjectRender::new(self).to_string();
+        KStringCow::from_string(s)
+    }
+    fn to_value(&self) -> Value {
+        Value::Object(
+            self.iter()
+                .map(|(k, v)| (KString::from_ref(k), v.to_value()))
+                .collect(),
+        )
+    }
+
+    fn as_object(&self) -> Option<&dyn ObjectView> {
+        Some(self)
+    }
+}
+
+impl ObjectView for Map<String, JsonValue> {
+    fn as_value(&self) -> &dyn ValueView {
+        self
+    }
+
+    fn size(&self) -> i64 {
+        self.len() as i64
+    }
+
+    fn keys<'k>(&'k self) -> Box<dyn Iterator<Item = KStringCow<'k>> + 'k> {
+        let keys = Map::keys(self).map(|s| s.as_str().into());
+        Box::new(keys)
+    }
+
+    fn values<'k>(&'k self) -> Box<dyn Iterator<Item = &'k dyn ValueView> + 'k> {
+        let i = Map::values(self).map(|v| v as &dyn ValueView);
+        Box::new(i)
+    }
+
+    fn iter<'k>(&'k self) -> Box<dyn Iterator<Item = (KStringCow<'k>, &'k dyn ValueView)> + 'k> {
+        let i = Map::iter(self).map(|(k, v)| (k.as_str().into(), v as &dyn ValueView));
+        Box::new(i)
+    }
+
+    fn contains_key(&self, index: &str) -> bool {
+        Map::contains_key(self, index)
+    }
+
+    fn get<'s>(&'s self, index: &str) -> Option<&'s dyn ValueView> {
+        Map::get(self, index).map(|v| v as &dyn ValueView)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::model::ValueViewCmp;
+
+    #[test]
+    fn json_scalars() {
+        let value = serde_json::json!([null, true, 1, 1.5, "one"]);
+        let array = value.as_array().unwrap();
+
+        assert!(array[0].is_nil());
+        assert_eq!(array[0].render().to_string(), "");
+        assert_eq!(ValueViewCmp::new(&array[1]), true);
+        assert_eq!(ValueViewCmp::new(&array[2]), 1);
+        assert_eq!(array[2].type_name(), "whole number");
+        assert_eq!(ValueViewCmp::new(&array[3]), 1.5);
+        assert_eq!(array[3].render().to_string(), "1.5");
+        assert_eq!(ValueViewCmp::new(&array[4]), "one");
+        assert_eq!(array[4].source().to_string(), r#""one""#);
+    }
+
+    #[test]
+    fn json_containers() {
+        let value = serde_json::json!({"list": [1, 2], "empty": {}});
+
+        let object = ValueView::as_object(&value).unwrap();
+        assert_eq!(object.size(), 2);
+        let list = object.get("list").unwrap().as_array().unwrap();
+        assert_eq!(list.size(), 2);
+        assert_eq!(ValueViewCmp::new(list.last().unwrap()), 2);
+        assert!(object.get("empty").unwrap().query_state(State::Empty));
+        assert!(object.get("missing").is_none());
+
+        let mut expected = crate::model::Object::new();
+        expected.insert("empty".into(), Value::Object(Default::default()));
+        expected.insert(
+            "list".into(),
+            Value::Array(vec![Value::scalar(1), Value::scalar(2)]),
+        );
+        let expected = Value::Object(expected);
+        assert_eq!(value.to_value(), expected);
+    }
+}
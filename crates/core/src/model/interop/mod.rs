@@ -0,0 +1,24 @@
+//! `ValueView` for types from other crates, so they can be rendered without a `to_value`.
+
+#[cfg(any(
+    feature = "chrono",
+    all(feature = "rust_decimal", not(feature = "decimal")),
+    feature = "serde_json",
+    feature = "uuid"
+))]
+#[macro_use]
+mod scalar;
+
+#[cfg(feature = "chrono")]
+mod chrono;
+mod indexmap;
+#[cfg(feature = "serde_json")]
+mod json;
+#[cfg(all(feature = "rust_decimal", not(feature = "decimal")))]
+mod rust_decimal;
+#[cfg(feature = "toml")]
+mod toml;
+#[cfg(feature = "url")]
+mod url;
+#[cfg(feature = "uuid")]
+mod uuid;
@@ -0,0 +1,34 @@
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+
+use crate::model::Scalar;
+
+impl_scalar_view!([] Decimal, to_scalar);
+
+/// Whole numbers stay integers; anything else is approximated as a float.
+///
+/// The `decimal` feature instead makes `Decimal` a scalar of its own.
+fn to_scalar(decimal: &Decimal) -> Scalar {
+    match decimal.is_integer().then(|| decimal.to_i64()).flatten() {
+        Some(x) => Scalar::new(x),
+        None => Scalar::new(decimal.to_f64().unwrap_or(f64::NAN)),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::model::{ValueView, ValueViewCmp};
+
+    #[test]
+    fn decimal_scalars() {
+        let whole = Decimal::new(1200, 2);
+        assert_eq!(whole.type_name(), "whole number");
+        assert_eq!(ValueViewCmp::new(&whole), 12);
+
+        let fraction = Decimal::new(1250, 2);
+        assert_eq!(fraction.type_name(), "fractional number");
+        assert_eq!(fraction.render().to_string(), "12.5");
+    }
+}
@@ -0,0 +1,58 @@
+use std::fmt;
+
+use crate::model::{Scalar, ValueView};
+
+/// `ValueView` for a type with no `Scalar` to borrow, converting to one on each access.
+macro_rules! impl_scalar_view {
+    ([$($generics:tt)*] $ty:ty, $to_scalar:expr) => {
+        impl<$($generics)*> $crate::model::ValueView for $ty {
+            fn as_debug(&self) -> &dyn ::std::fmt::Debug {
+                self
+            }
+
+            fn render(&self) -> $crate::model::DisplayCow<'_> {
+                $crate::model::DisplayCow::Owned(Box::new(
+                    $crate::model::interop::scalar::ScalarRender($to_scalar(self)),
+                ))
+            }
+            fn source(&self) -> $crate::model::DisplayCow<'_> {
+                $crate::model::DisplayCow::Owned(Box::new(
+                    $crate::model::interop::scalar::ScalarSource($to_scalar(self)),
+                ))
+            }
+            fn type_name(&self) -> &'static str {
+                $crate::model::ValueView::type_name(&$to_scalar(self))
+            }
+            fn query_state(&self, state: $crate::model::State) -> bool {
+                $crate::model::ValueView::query_state(&$to_scalar(self), state)
+            }
+
+            fn to_kstr(&self) -> $crate::model::KStringCow<'_> {
+                $to_scalar(self).into_string().into()
+            }
+            fn to_value(&self) -> $crate::model::Value {
+                $crate::model::Value::Scalar($to_scalar(self))
+            }
+
+            fn as_scalar(&self) -> Option<$crate::model::ScalarCow<'_>> {
+                Some($to_scalar(self))
+            }
+        }
+    };
+}
+
+pub(crate) struct ScalarRender(pub(crate) Scalar);
+
+impl fmt::Display for ScalarRender {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.render().fmt(f)
+    }
+}
+
+pub(crate) struct ScalarSource(pub(crate) Scalar);
+
+impl fmt::Display for ScalarSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.source().fmt(f)
+    }
+}
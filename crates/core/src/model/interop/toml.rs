@@ -0,0 +1,181 @@
+use std::fmt;
+
+use toml::value::{Datetime, Table};
+use toml::Value as TomlValue;
+
+use crate::model::{
+    ArrayView, Date, DateTime, DisplayCow, KString, KStringCow, ObjectRender, ObjectSource,
+    ObjectView, Scalar, ScalarCow, State, Value, ValueView,
+};
+
+impl ValueView for TomlValue {
+    fn as_debug(&self) -> &dyn fmt::Debug {
+        self
+    }
+
+    fn render(&self) -> DisplayCow<'_> {
+        forward(self).render()
+    }
+    fn source(&self) -> DisplayCow<'_> {
+        forward(self).source()
+    }
+    fn type_name(&self) -> &'static str {
+        forward(self).type_name()
+    }
+    fn query_state(&self, state: State) -> bool {
+        forward(self).query_state(state)
+    }
+
+    fn to_kstr(&self) -> KStringCow<'_> {
+        forward(self).to_kstr()
+    }
+    fn to_value(&self) -> Value {
+        forward(self).to_value()
+    }
+
+    fn as_scalar(&self) -> Option<ScalarCow<'_>> {
+        forward(self).as_scalar()
+    }
+
+    fn as_array(&self) -> Option<&dyn ArrayView> {
+        forward(self).as_array()
+    }
+
+    fn as_object(&self) -> Option<&dyn ObjectView> {
+        forward(self).as_object()
+    }
+}
+
+fn forward(value: &TomlValue) -> &dyn ValueView {
+    match value {
+        TomlValue::String(x) => x,
+        TomlValue::Integer(x) => x,
+        TomlValue::Float(x) => x,
+        TomlValue::Boolean(x) => x,
+        TomlValue::Datetime(x) => x,
+        TomlValue::Array(x) => x,
+        TomlValue::Table(x) => x,
+    }
+}
+
+impl_scalar_view!([] Datetime, to_scalar);
+
+/// Local times, having no date, stay strings.
+fn to_scalar(datetime: &Datetime) -> Scalar {
+    let s = datetime.to_string();
+    if let Some(x) = DateTime::from_str(&s) {
+        Scalar::new(x)
+    } else if let Some(x) = Date::from_str(&s) {
+        Scalar::new(x)
+    } else {
+        Scalar::new(s)
+    }
+}
+
+impl ValueView for Table {
+    fn as_debug(&self) -> &dyn fmt::Debug {
+        self
+    }
+
+    fn render(&self) -> DisplayCow<'_> {
+        DisplayCow::Owned(Box::new(ObjectRender::new(self)))
+    }
+    fn source(&self) -> DisplayCow<'_> {
+        DisplayCow::Owned(Box::new(ObjectSource::new(self)))
+    }
+    fn type_name(&self) -> &'static str {
+        "object"
+    }
+    fn query_state(&self, state: State) -> bool {
+        match state {
+            State::Truthy => true,
+            State::DefaultValue | State::Empty | State::Blank => self.is_empty(),
+        }
+    }
+
+    fn to_kstr(&self) -> KStringCow<'_> {
+        let s = ObjectRender::new(self).to_string();
+        KStringCow::from_string(s)
+    }
+    fn to_value(&self) -> Value {
+        Value::Object(
+            self.iter()
+                .map(|(k, v)| (KString::from_ref(k), v.to_value()))
+                .collect(),
+        )
+    }
+
+    fn as_object(&self) -> Option<&dyn ObjectView> {
+        Some(self)
+    }
+}
+
+impl ObjectView for Table {
+    fn as_value(&self) -> &dyn ValueView {
+        self
+    }
+
+    fn size(&self) -> i64 {
+        self.len() as i64
+    }
+
+    fn keys<'k>(&'k self) -> Box<dyn Iterator<Item = KStringCow<'k>> + 'k> {
+        let keys = Table::keys(self).map(|s| s.as_str().into());
+        Box::new(keys)
+    }
+
+    fn values<'k>(&'k self) -> Box<dyn Iterator<Item = &'k dyn ValueView> + 'k> {
+        let i = Table::values(self).map(|v| v as &dyn ValueView);
+        Box::new(i)
+    }
+
+    fn iter<'k>(&'k self) -> Box<dyn Iterator<Item = (KStringCow<'k>, &'k dyn ValueView)> + 'k> {
+        let i = Table::iter(self).map(|(k, v)| (k.as_str().into(), v as &dyn ValueView));
+        Box::new(i)
+    }
+
+    fn contains_key(&self, index: &str) -> bool {
+        Table::contains_key(self, index)
+    }
+
+    fn get<'s>(&'s self, index: &str) -> Option<&'s dyn ValueView> {
+        Table::get(self, index).map(|v| v as &dyn ValueView)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::model::ValueViewCmp;
+
+    #[test]
+    fn toml_table() {
+        let value: TomlValue = toml::from_str(
+            r#"
+title = "TOML"
+released = 1979-05-27T07:32:00Z
+birthday = 1979-05-27
+alarm = 07:32:00
+
+[owner]
+name = "Tom"
+"#,
+        )
+        .unwrap();
+
+        let object = ValueView::as_object(&value).unwrap();
+        assert_eq!(ValueViewCmp::new(object.get("title").unwrap()), "TOML");
+        assert_eq!(
+            object.get("released").unwrap().render().to_string(),
+            "1979-05-27 07:32:00 +0000"
+        );
+        assert_eq!(
+            ValueViewCmp::new(object.get("birthday").unwrap()),
+            Date::from_ymd(1979, 5, 27)
+        );
+        assert_eq!(object.get("alarm").unwrap().type_name(), "string");
+        let owner = object.get("owner").unwrap().as_object().unwrap();
+        assert_eq!(owner.get("name").unwrap().to_kstr(), "Tom");
+    }
+}
@@ -0,0 +1,53 @@
+use std::fmt;
+
+use url::Url;
+
+use crate::model::scalar::StrSource;
+use crate::model::{DisplayCow, KStringCow, ScalarCow, State, Value, ValueView};
+
+impl ValueView for Url {
+    fn as_debug(&self) -> &dyn fmt::Debug {
+        self
+    }
+
+    fn render(&self) -> DisplayCow<'_> {
+        DisplayCow::Borrowed(self)
+    }
+    fn source(&self) -> DisplayCow<'_> {
+        DisplayCow::Owned(Box::new(StrSource { s: self.as_str() }))
+    }
+    fn type_name(&self) -> &'static str {
+        ValueView::type_name(&self.as_str())
+    }
+    fn query_state(&self, state: State) -> bool {
+        ValueView::query_state(&self.as_str(), state)
+    }
+
+    fn to_kstr(&self) -> KStringCow<'_> {
+        self.as_str().into()
+    }
+    fn to_value(&self) -> Value {
+        ValueView::to_value(&self.as_str())
+    }
+
+    fn as_scalar(&self) -> Option<ScalarCow<'_>> {
+        Some(ScalarCow::new(self.as_str()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::model::ValueViewCmp;
+
+    #[test]
+    fn url_is_borrowed_string() {
+        let url = Url::parse("https://example.com/a?b=c").unwrap();
+
+        assert_eq!(url.type_name(), "string");
+        assert_eq!(url.render().to_string(), "https://example.com/a?b=c");
+        assert_eq!(url.source().to_string(), r#""https://example.com/a?b=c""#);
+        assert_eq!(ValueViewCmp::new(&url), "https://example.com/a?b=c");
+    }
+}
@@ -0,0 +1,31 @@
+use uuid::Uuid;
+
+use crate::model::Scalar;
+
+impl_scalar_view!([] Uuid, to_scalar);
+
+fn to_scalar(uuid: &Uuid) -> Scalar {
+    Scalar::new(uuid.hyphenated().to_string())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::model::ValueView;
+
+    #[test]
+    fn uuid_is_hyphenated_string() {
+        let uuid = Uuid::from_u128(0x936d_a01f_9abd_4d9d_80c7_02af_85c8_22a8);
+
+        assert_eq!(uuid.type_name(), "string");
+        assert_eq!(
+            uuid.render().to_string(),
+            "936da01f-9abd-4d9d-80c7-02af85c822a8"
+        );
+        assert_eq!(
+            uuid.source().to_string(),
+            r#""936da01f-9abd-4d9d-80c7-02af85c822a8""#
+        );
+    }
+}
@@ -6,8 +6,10 @@
 
 mod array;
 mod find;
+mod interop;
 mod object;
 mod scalar;
+mod schema;
 mod value;
 
 mod ser;
@@ -16,6 +18,7 @@ pub use array::*;
 pub use find::*;
 pub use object::*;
 pub use scalar::*;
+pub use schema::*;
 pub use value::*;
 
 pub use kstring::KString;
@@ -27,6 +30,9 @@ pub use kstring::KStringRef;
 pub use liquid_derive::CoreObjectView as ObjectView;
 #[cfg(feature = "derive")]
 #[doc(hidden)]
+pub use liquid_derive::CoreToSchema as ToSchema;
+#[cfg(feature = "derive")]
+#[doc(hidden)]
 pub use liquid_derive::CoreValueView as ValueView;
 #[doc(hidden)]
 pub use object::ObjectView as _ObjectView;
@@ -0,0 +1,202 @@
+//! Support for drops: objects whose properties are computed on demand.
+
+use std::cell::Cell;
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, PoisonError};
+
+use once_cell::sync::OnceCell;
+
+use crate::model::Value;
+
+/// Properties of an object that are computed when a template reads them.
+///
+/// Usually implemented with `#[liquid::object_methods]` on an `impl` block and exposed by
+/// deriving `ObjectView` with `#[liquid(methods)]`.
+pub trait ObjectMethods {
+    /// Names of the computed properties.
+    fn method_names(&self) -> &'static [&'static str];
+
+    /// Compute the property `name`, if it exists.
+    fn call_method(&self, name: &str) -> Option<Value>;
+
+    /// Compute a property that isn't among `method_names`, like Ruby's `liquid_method_missing`.
+    ///
+    /// Unlike methods, these are not memoized nor listed as keys.
+    fn method_missing(&self, _name: &str) -> Option<Value> {
+        None
+    }
+}
+
+/// Memoizes the computed properties of a drop, so each is evaluated at most once per render.
+///
+/// Computed values are borrowed from the cache, so they can be used anywhere a field can:
+/// `assign`, `for` and filters like `map` or `where`.  Each render starts with fresh values,
+/// so a drop shared between renders, like in an `Arc`, picks up changes.
+///
+/// Since templates may still borrow them, values computed by earlier renders are only freed with
+/// the drop or by `clear`.
+#[derive(Default)]
+pub struct DropCache {
+    generations: Mutex<Vec<Generation>>,
+}
+
+/// The values computed during one render.
+struct Generation {
+    render: u64,
+    slots: Box<[OnceCell<Option<Value>>]>,
+}
+
+impl DropCache {
+    /// Create an empty cache.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The property `name` of `object`, computing it on first access.
+    ///
+    /// For hand-written `ObjectView`s; derived ones lay out the cache themselves.
+    pub fn get<'s, O>(&'s self, object: &O, name: &str) -> Option<&'s Value>
+    where
+        O: ObjectMethods + ?Sized,
+    {
+        let names = object.method_names();
+        let index = names.iter().position(|n| *n == name)?;
+        self.get_or_init(index, names.len(), || object.call_method(name))
+    }
+
+    /// The value in `slot`, out of `len` slots, computing it with `init` on first access.
+    ///
+    /// `len` must be the same on every call.
+    #[doc(hidden)]
+    pub fn get_or_init<F>(&self, slot: usize, len: usize, init: F) -> Option<&Value>
+    where
+        F: FnOnce() -> Option<Value>,
+    {
+        let render = current_render();
+        let slots: *const [OnceCell<Option<Value>>] = {
+            let mut generations = self
+                .generations
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner);
+            match generations.iter().rev().find(|g| g.render == render) {
+                Some(generation) => &*generation.slots,
+                None => {
+                    generations.push(Generation {
+                        render,
+                        slots: (0..len).map(|_| OnceCell::new()).collect(),
+                    });
+                    &*generations.last().expect("just pushed").slots
+                }
+            }
+        };
+        // SAFETY: slots are boxed, so they stay put when the `Vec` grows, and are only dropped
+        // with `&mut self`, which no borrow of `self` outlives.
+        let slots = unsafe { &*slots };
+        // Don't hold the lock while computing, as `init` may read other properties.
+        slots.get(slot)?.get_or_init(init).as_ref()
+    }
+
+    /// Forget all computed properties.
+    pub fn clear(&mut self) {
+        self.generations
+            .get_mut()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clear();
+    }
+}
+
+impl Clone for DropCache {
+    /// An empty cache, as clones compute their own values.
+    fn clone(&self) -> Self {
+        Self::default()
+    }
+}
+
+impl fmt::Debug for DropCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DropCache").finish_non_exhaustive()
+    }
+}
+
+static NEXT_RENDER: AtomicU64 = AtomicU64::new(1);
+
+thread_local! {
+    static CURRENT_RENDER: Cell<u64> = const { Cell::new(0) };
+}
+
+/// Starts a new render on this thread, so drops compute their properties again.
+pub(crate) fn begin_render() {
+    let render = NEXT_RENDER.fetch_add(1, Ordering::Relaxed);
+    CURRENT_RENDER.with(|current| current.set(render));
+}
+
+fn current_render() -> u64 {
+    CURRENT_RENDER.with(Cell::get)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use std::cell::Cell;
+
+    struct Counter {
+        calls: Cell<i64>,
+    }
+
+    impl ObjectMethods for Counter {
+        fn method_names(&self) -> &'static [&'static str] {
+            &["answer", "missing"]
+        }
+
+        fn call_method(&self, name: &str) -> Option<Value> {
+            self.calls.set(self.calls.get() + 1);
+            match name {
+                "answer" => Some(Value::scalar(42)),
+                _ => None,
+            }
+        }
+    }
+
+    #[test]
+    fn memoizes() {
+        let counter = Counter {
+            calls: Cell::new(0),
+        };
+        let mut cache = DropCache::new();
+        for _ in 0..2 {
+            assert_eq!(cache.get(&counter, "answer"), Some(&Value::scalar(42)));
+            assert_eq!(cache.get(&counter, "missing"), None);
+        }
+        assert_eq!(counter.calls.get(), 2);
+
+        assert_eq!(cache.get(&counter, "unknown"), None);
+        assert_eq!(counter.calls.get(), 2);
+
+        cache.clear();
+        cache.get(&counter, "answer");
+        assert_eq!(counter.calls.get(), 3);
+    }
+
+    #[test]
+    fn recomputes_per_render() {
+        let counter = Counter {
+            calls: Cell::new(0),
+        };
+        let cache = DropCache::new();
+        let first = cache.get(&counter, "answer");
+        begin_render();
+        let second = cache.get(&counter, "answer");
+        cache.get(&counter, "answer");
+        assert_eq!(counter.calls.get(), 2);
+        // Values from earlier renders stay borrowed.
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn is_sync() {
+        fn assert_sync<T: Send + Sync>() {}
+        assert_sync::<DropCache>();
+    }
+}
@@ -1,5 +1,6 @@
 //! Type representing a Liquid object, payload of the `Value::Object` variant
 
+mod drop;
 pub mod map;
 mod ser;
 
@@ -11,8 +12,9 @@ use crate::model::KStringCow;
 
 use crate::model::value::DisplayCow;
 use crate::model::State;
-use crate::model::{Value, ValueView};
+use crate::model::{Value, ValueCow, ValueView};
 
+pub use drop::*;
 pub use map::Object;
 pub use ser::to_object;
 
@@ -35,6 +37,21 @@ pub trait ObjectView: ValueView {
     fn contains_key(&self, index: &str) -> bool;
     /// Access a contained `Value`.
     fn get<'s>(&'s self, index: &str) -> Option<&'s dyn ValueView>;
+    /// Access a contained `Value`, computing it if needed.
+    ///
+    /// Objects that compute a property without keeping it around can override this to return
+    /// it owned; such properties are only reachable through this method, not `get`, `values` or
+    /// `iter`.  Variable lookup and property-based filters, like `map`, use this.
+    fn get_cow<'s>(&'s self, index: &str) -> Option<ValueCow<'s>> {
+        self.get(index).map(ValueCow::Borrowed)
+    }
+    /// Look up a key that isn't among `keys()`, like Ruby's `liquid_method_missing`.
+    ///
+    /// Lets an object answer any key without listing them up front, e.g. settings backed by a
+    /// service.  Only variable lookup falls back to this.
+    fn get_missing<'s>(&'s self, _index: &str) -> Option<ValueCow<'s>> {
+        None
+    }
 }
 
 impl ValueView for Object {
@@ -104,36 +121,56 @@ impl ObjectView for Object {
     }
 }
 
-impl<'o, O: ObjectView + ?Sized> ObjectView for &'o O {
-    fn as_value(&self) -> &dyn ValueView {
-        <O as ObjectView>::as_value(self)
-    }
-
-    fn size(&self) -> i64 {
-        <O as ObjectView>::size(self)
-    }
-
-    fn keys<'k>(&'k self) -> Box<dyn Iterator<Item = KStringCow<'k>> + 'k> {
-        <O as ObjectView>::keys(self)
-    }
-
-    fn values<'k>(&'k self) -> Box<dyn Iterator<Item = &'k dyn ValueView> + 'k> {
-        <O as ObjectView>::values(self)
-    }
-
-    fn iter<'k>(&'k self) -> Box<dyn Iterator<Item = (KStringCow<'k>, &'k dyn ValueView)> + 'k> {
-        <O as ObjectView>::iter(self)
-    }
-
-    fn contains_key(&self, index: &str) -> bool {
-        <O as ObjectView>::contains_key(self, index)
-    }
-
-    fn get<'s>(&'s self, index: &str) -> Option<&'s dyn ValueView> {
-        <O as ObjectView>::get(self, index)
-    }
+macro_rules! forward_object_view {
+    ([$($generics:tt)*] $ty:ty $(where $($bounds:tt)*)?) => {
+        impl<$($generics)*> ObjectView for $ty $(where $($bounds)*)? {
+            fn as_value(&self) -> &dyn ValueView {
+                <O as ObjectView>::as_value(self)
+            }
+
+            fn size(&self) -> i64 {
+                <O as ObjectView>::size(self)
+            }
+
+            fn keys<'k>(&'k self) -> Box<dyn Iterator<Item = KStringCow<'k>> + 'k> {
+                <O as ObjectView>::keys(self)
+            }
+
+            fn values<'k>(&'k self) -> Box<dyn Iterator<Item = &'k dyn ValueView> + 'k> {
+                <O as ObjectView>::values(self)
+            }
+
+            fn iter<'k>(
+                &'k self,
+            ) -> Box<dyn Iterator<Item = (KStringCow<'k>, &'k dyn ValueView)> + 'k> {
+                <O as ObjectView>::iter(self)
+            }
+
+            fn contains_key(&self, index: &str) -> bool {
+                <O as ObjectView>::contains_key(self, index)
+            }
+
+            fn get<'s>(&'s self, index: &str) -> Option<&'s dyn ValueView> {
+                <O as ObjectView>::get(self, index)
+            }
+
+            fn get_cow<'s>(&'s self, index: &str) -> Option<ValueCow<'s>> {
+                <O as ObjectView>::get_cow(self, index)
+            }
+
+            fn get_missing<'s>(&'s self, index: &str) -> Option<ValueCow<'s>> {
+                <O as ObjectView>::get_missing(self, index)
+            }
+        }
+    };
 }
 
+forward_object_view!(['o, O: ObjectView + ?Sized] &'o O);
+forward_object_view!([O: ObjectView + ?Sized] Box<O>);
+forward_object_view!([O: ObjectView + ?Sized] std::rc::Rc<O>);
+forward_object_view!([O: ObjectView + ?Sized] std::sync::Arc<O>);
+forward_object_view!(['o, O: ObjectView + ToOwned + ?Sized] std::borrow::Cow<'o, O> where O::Owned: fmt::Debug);
+
 /// Owned object index
 pub trait ObjectIndex:
     fmt::Debug + fmt::Display + Ord + std::hash::Hash + Eq + std::borrow::Borrow<str>
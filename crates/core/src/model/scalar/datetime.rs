@@ -1,8 +1,13 @@
-use std::convert::TryInto;
+use std::convert::{TryFrom, TryInto};
 use std::fmt;
 use std::ops;
 
+mod locale;
 mod strftime;
+mod zone;
+
+pub use locale::*;
+pub use zone::*;
 
 use super::Date;
 
@@ -50,6 +55,18 @@ impl DateTime {
         parse_date_time(other).map(|d| Self { inner: d })
     }
 
+    /// Create a `DateTime` from the number of seconds since the Unix epoch.
+    pub fn from_unix_timestamp(seconds: i64) -> Option<Self> {
+        DateTimeImpl::from_unix_timestamp(seconds)
+            .ok()
+            .map(|d| Self { inner: d })
+    }
+
+    /// Create a `DateTime` from the number of (fractional) seconds since the Unix epoch.
+    pub fn from_fractional_unix_timestamp(seconds: f64) -> Option<Self> {
+        from_fractional_seconds(seconds).map(|d| Self { inner: d })
+    }
+
     /// Replace date with `other`.
     pub fn with_date(self, other: Date) -> Self {
         Self {
@@ -64,6 +81,53 @@ impl DateTime {
         }
     }
 
+    /// Converts to the local time of `zone`. This does not change the actual DateTime (but will change the string representation).
+    pub fn to_time_zone(self, zone: &TimeZone) -> Self {
+        self.with_offset(zone.offset_at(self))
+    }
+
+    /// Adds `duration`, returning `None` on overflow.
+    pub fn checked_add(self, duration: time::Duration) -> Option<Self> {
+        self.inner.checked_add(duration).map(|inner| Self { inner })
+    }
+
+    /// Adds calendar months, clamping the day to the end of the resulting month (Jan 31 + 1
+    /// month is Feb 28 or 29).  Returns `None` on overflow.
+    pub fn checked_add_months(self, months: i64) -> Option<Self> {
+        let month = i64::from(self.inner.year()) * 12 + (self.inner.month() as i64 - 1);
+        let month = month.checked_add(months)?;
+        let year = i32::try_from(month.div_euclid(12)).ok()?;
+        let month = time::Month::try_from(month.rem_euclid(12) as u8 + 1).ok()?;
+        let date = (1..=self.inner.day())
+            .rev()
+            .find_map(|day| time::Date::from_calendar_date(year, month, day).ok())?;
+        Some(Self {
+            inner: self.inner.replace_date(date),
+        })
+    }
+
+    /// The start of the day, keeping the offset.
+    pub fn beginning_of_day(self) -> Self {
+        Self {
+            inner: self.inner.replace_time(time::Time::MIDNIGHT),
+        }
+    }
+
+    /// The start of the month, keeping the offset.
+    pub fn beginning_of_month(self) -> Self {
+        let date = self
+            .inner
+            .date()
+            .replace_day(1)
+            .expect("every month has a first day");
+        Self {
+            inner: self
+                .inner
+                .replace_date(date)
+                .replace_time(time::Time::MIDNIGHT),
+        }
+    }
+
     /// Retrieves a date component.
     pub fn date(self) -> Date {
         Date {
@@ -77,7 +141,32 @@ impl DateTime {
     /// module on the supported escape sequences.
     #[inline]
     pub fn format(&self, fmt: &str) -> Result<String, strftime::DateFormatError> {
-        strftime::strftime(self.inner, fmt)
+        strftime::strftime(self.inner, fmt, &DateLocaleTable::ENGLISH, None)
+    }
+
+    /// Formats the combined date and time with the specified format string, using `locale` for
+    /// month names, weekday names and the like.
+    #[inline]
+    pub fn format_with_locale(
+        &self,
+        fmt: &str,
+        locale: &dyn DateLocale,
+    ) -> Result<String, strftime::DateFormatError> {
+        strftime::strftime(self.inner, fmt, locale, None)
+    }
+
+    /// Formats the date and time as seen in `zone`, using `locale` for month names, weekday
+    /// names and the like.
+    ///
+    /// Unlike the other `format` functions, `%Z` prints the zone's abbreviation, e.g. `CEST`.
+    pub fn format_in_time_zone(
+        &self,
+        fmt: &str,
+        zone: &TimeZone,
+        locale: &dyn DateLocale,
+    ) -> Result<String, strftime::DateFormatError> {
+        let local = self.to_time_zone(zone);
+        strftime::strftime(local.inner, fmt, locale, zone.abbreviation_at(*self))
     }
 
     /// Returns an RFC 2822 date and time string such as `Tue, 1 Jul 2003 10:52:37 +0200`.
@@ -194,12 +283,18 @@ mod friendly_date_time {
 /// * `day_mon` - `DD Mon YYYY HH:MM:SS`
 /// * `mdy` -  `MM/DD/YYYY HH:MM:SS`
 /// * `dow_mon` - `Dow Mon DD HH:MM:SS YYYY`
+/// * `iso8601` - `YYYY-MM-DDTHH:MM[:SS[.fff]]`, with `T` or a space as separator
+/// * `rfc2822` - `Dow, DD Mon YYYY HH:MM:SS +HHMM`
+/// * `date` - any format accepted by `Date`, at midnight
+/// * `unix` - (fractional) seconds since the Unix epoch
 ///
 /// Offsets in one of the following forms, and are catenated with any of
 /// the above formats.
 ///
 /// * `+HHMM`
 /// * `-HHMM`
+/// * `+HH:MM`
+/// * `Z` or `UTC`
 ///
 /// Example:
 ///
@@ -209,27 +304,74 @@ fn parse_date_time(s: &str) -> Option<DateTimeImpl> {
     use time::macros::format_description;
 
     const USER_FORMATS: &[&[time::format_description::FormatItem<'_>]] = &[
-        DATE_TIME_FORMAT,
-        format_description!("[day] [month repr:long] [year] [hour]:[minute]:[second] [offset_hour sign:mandatory][offset_minute]"),
-        format_description!("[day] [month repr:short] [year] [hour]:[minute]:[second] [offset_hour sign:mandatory][offset_minute]"),
-        format_description!("[month]/[day]/[year] [hour]:[minute]:[second] [offset_hour sign:mandatory][offset_minute]"),
-        format_description!("[weekday repr:short] [month repr:short] [day padding:none] [hour]:[minute]:[second] [year] [offset_hour sign:mandatory][offset_minute]"),
+        format_description!("[year]-[month]-[day][first [T] [t] [ ]][hour]:[minute][optional [:[second][optional [.[subsecond]]]]]"),
+        format_description!("[day] [month repr:long] [year] [hour]:[minute]:[second]"),
+        format_description!("[day] [month repr:short] [year] [hour]:[minute]:[second]"),
+        format_description!("[month]/[day]/[year] [hour]:[minute]:[second]"),
+        format_description!("[weekday repr:short] [month repr:short] [day padding:none] [hour]:[minute]:[second] [year]"),
     ];
 
+    let s = s.trim();
     if let "" = s {
         None
-    } else if let "now" | "today" = s.to_lowercase().trim() {
+    } else if let "now" | "today" = s.to_lowercase().as_str() {
         Some(DateTimeImpl::now_utc())
+    } else if let Some(timestamp) = parse_unix_timestamp(s) {
+        Some(timestamp)
+    } else if let Ok(date_time) =
+        DateTimeImpl::parse(s, &time::format_description::well_known::Rfc2822)
+    {
+        Some(date_time)
     } else {
-        let offset_re = Regex::new(r"[+-][01][0-9]{3}$").unwrap();
-
-        let offset = if offset_re.is_match(s) { "" } else { " +0000" };
-        let s = s.to_owned() + offset;
+        let offset_re = Regex::new(
+            r"(?i)\s*(?:(?P<utc>z|utc|gmt)|(?P<hour>[+-][01][0-9]):?(?P<minute>[0-5][0-9]))$",
+        )
+        .unwrap();
+
+        let (s, offset) = match offset_re.captures(s) {
+            Some(captures) => {
+                let offset = if captures.name("utc").is_some() {
+                    time::UtcOffset::UTC
+                } else {
+                    let hours: i8 = captures["hour"].parse().ok()?;
+                    let mut minutes: i8 = captures["minute"].parse().ok()?;
+                    if captures["hour"].starts_with('-') {
+                        minutes = -minutes;
+                    }
+                    time::UtcOffset::from_hms(hours, minutes, 0).ok()?
+                };
+                (&s[..captures.get(0)?.start()], offset)
+            }
+            None => (s, time::UtcOffset::UTC),
+        };
 
         USER_FORMATS
             .iter()
-            .find_map(|f| DateTimeImpl::parse(s.as_str(), f).ok())
+            .find_map(|f| time::PrimitiveDateTime::parse(s, f).ok())
+            .or_else(|| Date::from_str(s).map(|d| d.inner.midnight()))
+            .map(|d| d.assume_offset(offset))
+    }
+}
+
+fn parse_unix_timestamp(s: &str) -> Option<DateTimeImpl> {
+    let digits = s.strip_prefix('-').unwrap_or(s);
+    let (whole, fraction) = digits.split_once('.').unwrap_or((digits, "0"));
+    let is_number = |part: &str| !part.is_empty() && part.bytes().all(|b| b.is_ascii_digit());
+    if !is_number(whole) || !is_number(fraction) {
+        return None;
+    }
+    match s.parse::<i64>() {
+        Ok(seconds) => DateTimeImpl::from_unix_timestamp(seconds).ok(),
+        Err(_) => from_fractional_seconds(s.parse().ok()?),
+    }
+}
+
+fn from_fractional_seconds(seconds: f64) -> Option<DateTimeImpl> {
+    let nanos = seconds * 1_000_000_000.0;
+    if !nanos.is_finite() {
+        return None;
     }
+    DateTimeImpl::from_unix_timestamp_nanos(nanos.round() as i128).ok()
 }
 
 #[cfg(test)]
@@ -343,6 +485,84 @@ mod test {
         assert!(actual.unwrap().unix_timestamp() == 1455616800);
     }
 
+    #[test]
+    fn parse_date_time_iso8601_format() {
+        let input = "2016-02-16T10:00:00+01:00"; // iso8601 format with offset
+        let actual = parse_date_time(input);
+        assert!(actual.unwrap().unix_timestamp() == 1455613200);
+
+        let input = "2016-02-16T09:00:00Z"; // iso8601 format UTC
+        let actual = parse_date_time(input);
+        assert!(actual.unwrap().unix_timestamp() == 1455613200);
+
+        let input = "2016-02-16T10:00:00.250-00:30"; // iso8601 format with fraction
+        let actual = parse_date_time(input).unwrap();
+        assert!(actual.unix_timestamp() == 1455618600);
+        assert!(actual.millisecond() == 250);
+
+        let input = "2016-02-16 10:00"; // iso8601 format without seconds
+        let actual = parse_date_time(input);
+        assert!(actual.unwrap().unix_timestamp() == 1455616800);
+
+        let input = "2016-02-16"; // date only
+        let actual = parse_date_time(input);
+        assert!(actual.unwrap().unix_timestamp() == 1455580800);
+    }
+
+    #[test]
+    fn parse_date_time_rfc2822_format() {
+        let input = "Tue, 16 Feb 2016 10:00:00 +0100";
+        let actual = parse_date_time(input);
+        assert!(actual.unwrap().unix_timestamp() == 1455613200);
+    }
+
+    #[test]
+    fn parse_date_time_unix_timestamp() {
+        let input = "1455613200";
+        let actual = parse_date_time(input);
+        assert!(actual.unwrap().unix_timestamp() == 1455613200);
+
+        let input = "1455613200.5";
+        let actual = parse_date_time(input).unwrap();
+        assert!(actual.unix_timestamp() == 1455613200);
+        assert!(actual.millisecond() == 500);
+
+        let input = "1455613200.";
+        let actual = parse_date_time(input);
+        assert!(actual.is_none());
+    }
+
+    #[test]
+    fn add_months_clamps_day() {
+        let date = DateTime::from_str("2016-01-31 10:00:00 +0100").unwrap();
+        assert_eq!(
+            date.checked_add_months(1).unwrap().to_string(),
+            "2016-02-29 10:00:00 +0100"
+        );
+        assert_eq!(
+            date.checked_add_months(-2).unwrap().to_string(),
+            "2015-11-30 10:00:00 +0100"
+        );
+        assert_eq!(
+            date.checked_add_months(13).unwrap().to_string(),
+            "2017-02-28 10:00:00 +0100"
+        );
+        assert!(date.checked_add_months(i64::MAX).is_none());
+    }
+
+    #[test]
+    fn beginning_of() {
+        let date = DateTime::from_str("2016-02-16 10:30:00 -0500").unwrap();
+        assert_eq!(
+            date.beginning_of_day().to_string(),
+            "2016-02-16 00:00:00 -0500"
+        );
+        assert_eq!(
+            date.beginning_of_month().to_string(),
+            "2016-02-01 00:00:00 -0500"
+        );
+    }
+
     #[test]
     fn parse_date_time_to_string() {
         let date = DateTime::now();
@@ -0,0 +1,389 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+use crate::model::KString;
+
+/// Locale-specific names used when formatting dates.
+///
+/// Covers `%a`, `%A`, `%b`, `%B`, `%p`, `%P` and the `%o` ordinal day.
+pub trait DateLocale: fmt::Debug + Send + Sync {
+    /// Full month name, e.g. `November`.
+    fn month_name(&self, month: time::Month) -> &str;
+    /// Abbreviated month name, e.g. `Nov`.
+    fn month_abbreviation(&self, month: time::Month) -> &str;
+    /// Full weekday name, e.g. `Thursday`.
+    fn weekday_name(&self, weekday: time::Weekday) -> &str;
+    /// Abbreviated weekday name, e.g. `Thu`.
+    fn weekday_abbreviation(&self, weekday: time::Weekday) -> &str;
+    /// Uppercase meridiem indicator, e.g. `AM` or `PM`.
+    fn meridiem(&self, is_am: bool) -> &str;
+    /// Suffix for the day of the month, e.g. `st` in `1st`.
+    fn ordinal_suffix(&self, day: u8) -> &str;
+}
+
+/// A `DateLocale` backed by static tables.
+#[derive(Copy, Clone, Debug)]
+pub struct DateLocaleTable {
+    /// Month names, starting with January.
+    pub months: [&'static str; 12],
+    /// Abbreviated month names, starting with January.
+    pub month_abbreviations: [&'static str; 12],
+    /// Weekday names, starting with Monday.
+    pub weekdays: [&'static str; 7],
+    /// Abbreviated weekday names, starting with Monday.
+    pub weekday_abbreviations: [&'static str; 7],
+    /// Uppercase `[AM, PM]` indicators.
+    pub meridiem: [&'static str; 2],
+    /// Suffix for the day of the month.
+    pub ordinal_suffix: fn(u8) -> &'static str,
+}
+
+impl DateLocale for DateLocaleTable {
+    fn month_name(&self, month: time::Month) -> &str {
+        self.months[month as usize - 1]
+    }
+
+    fn month_abbreviation(&self, month: time::Month) -> &str {
+        self.month_abbreviations[month as usize - 1]
+    }
+
+    fn weekday_name(&self, weekday: time::Weekday) -> &str {
+        self.weekdays[weekday.number_days_from_monday() as usize]
+    }
+
+    fn weekday_abbreviation(&self, weekday: time::Weekday) -> &str {
+        self.weekday_abbreviations[weekday.number_days_from_monday() as usize]
+    }
+
+    fn meridiem(&self, is_am: bool) -> &str {
+        if is_am {
+            self.meridiem[0]
+        } else {
+            self.meridiem[1]
+        }
+    }
+
+    fn ordinal_suffix(&self, day: u8) -> &str {
+        (self.ordinal_suffix)(day)
+    }
+}
+
+fn english_ordinal(day: u8) -> &'static str {
+    match (day % 10, day % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    }
+}
+
+impl DateLocaleTable {
+    /// English (`en`).
+    pub const ENGLISH: Self = Self {
+        months: [
+            "January",
+            "February",
+            "March",
+            "April",
+            "May",
+            "June",
+            "July",
+            "August",
+            "September",
+            "October",
+            "November",
+            "December",
+        ],
+        month_abbreviations: [
+            "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+        ],
+        weekdays: [
+            "Monday",
+            "Tuesday",
+            "Wednesday",
+            "Thursday",
+            "Friday",
+            "Saturday",
+            "Sunday",
+        ],
+        weekday_abbreviations: ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"],
+        meridiem: ["AM", "PM"],
+        ordinal_suffix: english_ordinal,
+    };
+
+    /// French (`fr`).
+    pub const FRENCH: Self = Self {
+        months: [
+            "janvier",
+            "février",
+            "mars",
+            "avril",
+            "mai",
+            "juin",
+            "juillet",
+            "août",
+            "septembre",
+            "octobre",
+            "novembre",
+            "décembre",
+        ],
+        month_abbreviations: [
+            "jan.", "fév.", "mar.", "avr.", "mai", "juin", "juil.", "août", "sept.", "oct.",
+            "nov.", "déc.",
+        ],
+        weekdays: [
+            "lundi", "mardi", "mercredi", "jeudi", "vendredi", "samedi", "dimanche",
+        ],
+        weekday_abbreviations: ["lun", "mar", "mer", "jeu", "ven", "sam", "dim"],
+        meridiem: ["AM", "PM"],
+        ordinal_suffix: |day| if day == 1 { "er" } else { "" },
+    };
+
+    /// German (`de`).
+    pub const GERMAN: Self = Self {
+        months: [
+            "Januar",
+            "Februar",
+            "März",
+            "April",
+            "Mai",
+            "Juni",
+            "Juli",
+            "August",
+            "September",
+            "Oktober",
+            "November",
+            "Dezember",
+        ],
+        month_abbreviations: [
+            "Jan", "Feb", "Mär", "Apr", "Mai", "Jun", "Jul", "Aug", "Sep", "Okt", "Nov", "Dez",
+        ],
+        weekdays: [
+            "Montag",
+            "Dienstag",
+            "Mittwoch",
+            "Donnerstag",
+            "Freitag",
+            "Samstag",
+            "Sonntag",
+        ],
+        weekday_abbreviations: ["Mo", "Di", "Mi", "Do", "Fr", "Sa", "So"],
+        meridiem: ["AM", "PM"],
+        ordinal_suffix: |_| ".",
+    };
+
+    /// Spanish (`es`).
+    pub const SPANISH: Self = Self {
+        months: [
+            "enero",
+            "febrero",
+            "marzo",
+            "abril",
+            "mayo",
+            "junio",
+            "julio",
+            "agosto",
+            "septiembre",
+            "octubre",
+            "noviembre",
+            "diciembre",
+        ],
+        month_abbreviations: [
+            "ene", "feb", "mar", "abr", "may", "jun", "jul", "ago", "sep", "oct", "nov", "dic",
+        ],
+        weekdays: [
+            "lunes",
+            "martes",
+            "miércoles",
+            "jueves",
+            "viernes",
+            "sábado",
+            "domingo",
+        ],
+        weekday_abbreviations: ["lun", "mar", "mié", "jue", "vie", "sáb", "dom"],
+        meridiem: ["AM", "PM"],
+        ordinal_suffix: |_| "º",
+    };
+
+    /// Italian (`it`).
+    pub const ITALIAN: Self = Self {
+        months: [
+            "gennaio",
+            "febbraio",
+            "marzo",
+            "aprile",
+            "maggio",
+            "giugno",
+            "luglio",
+            "agosto",
+            "settembre",
+            "ottobre",
+            "novembre",
+            "dicembre",
+        ],
+        month_abbreviations: [
+            "gen", "feb", "mar", "apr", "mag", "giu", "lug", "ago", "set", "ott", "nov", "dic",
+        ],
+        weekdays: [
+            "lunedì",
+            "martedì",
+            "mercoledì",
+            "giovedì",
+            "venerdì",
+            "sabato",
+            "domenica",
+        ],
+        weekday_abbreviations: ["lun", "mar", "mer", "gio", "ven", "sab", "dom"],
+        meridiem: ["AM", "PM"],
+        ordinal_suffix: |_| "º",
+    };
+
+    /// Portuguese (`pt`).
+    pub const PORTUGUESE: Self = Self {
+        months: [
+            "janeiro",
+            "fevereiro",
+            "março",
+            "abril",
+            "maio",
+            "junho",
+            "julho",
+            "agosto",
+            "setembro",
+            "outubro",
+            "novembro",
+            "dezembro",
+        ],
+        month_abbreviations: [
+            "jan", "fev", "mar", "abr", "mai", "jun", "jul", "ago", "set", "out", "nov", "dez",
+        ],
+        weekdays: [
+            "segunda-feira",
+            "terça-feira",
+            "quarta-feira",
+            "quinta-feira",
+            "sexta-feira",
+            "sábado",
+            "domingo",
+        ],
+        weekday_abbreviations: ["seg", "ter", "qua", "qui", "sex", "sáb", "dom"],
+        meridiem: ["AM", "PM"],
+        ordinal_suffix: |_| "º",
+    };
+
+    /// Dutch (`nl`).
+    pub const DUTCH: Self = Self {
+        months: [
+            "januari",
+            "februari",
+            "maart",
+            "april",
+            "mei",
+            "juni",
+            "juli",
+            "augustus",
+            "september",
+            "oktober",
+            "november",
+            "december",
+        ],
+        month_abbreviations: [
+            "jan", "feb", "mrt", "apr", "mei", "jun", "jul", "aug", "sep", "okt", "nov", "dec",
+        ],
+        weekdays: [
+            "maandag",
+            "dinsdag",
+            "woensdag",
+            "donderdag",
+            "vrijdag",
+            "zaterdag",
+            "zondag",
+        ],
+        weekday_abbreviations: ["ma", "di", "wo", "do", "vr", "za", "zo"],
+        meridiem: ["AM", "PM"],
+        ordinal_suffix: |_| "e",
+    };
+}
+
+/// Named `DateLocale`s available for formatting.
+///
+/// Defaults to the bundled locales: `en`, `fr`, `de`, `es`, `it`, `pt` and `nl`.
+#[derive(Clone, Debug)]
+pub struct DateLocales {
+    locales: HashMap<KString, Arc<dyn DateLocale>>,
+}
+
+impl DateLocales {
+    /// Create a registry with the bundled locales.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a registry without any locales.
+    pub fn empty() -> Self {
+        Self {
+            locales: HashMap::new(),
+        }
+    }
+
+    /// Register (or replace) the locale called `name`.
+    pub fn insert<S, L>(&mut self, name: S, locale: L) -> Option<Arc<dyn DateLocale>>
+    where
+        S: Into<KString>,
+        L: DateLocale + 'static,
+    {
+        self.locales.insert(name.into(), Arc::new(locale))
+    }
+
+    /// Look up a locale, falling back to parent locales (`fr-CA` falls back to `fr`).
+    pub fn get(&self, name: &str) -> Option<&dyn DateLocale> {
+        let mut name = name;
+        loop {
+            if let Some(locale) = self.locales.get(name) {
+                return Some(locale.as_ref());
+            }
+            name = name.rsplit_once(['-', '_'])?.0;
+        }
+    }
+}
+
+impl Default for DateLocales {
+    fn default() -> Self {
+        let mut locales = Self::empty();
+        locales.insert("en", DateLocaleTable::ENGLISH);
+        locales.insert("fr", DateLocaleTable::FRENCH);
+        locales.insert("de", DateLocaleTable::GERMAN);
+        locales.insert("es", DateLocaleTable::SPANISH);
+        locales.insert("it", DateLocaleTable::ITALIAN);
+        locales.insert("pt", DateLocaleTable::PORTUGUESE);
+        locales.insert("nl", DateLocaleTable::DUTCH);
+        locales
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn english_ordinals() {
+        let suffixes: Vec<_> = [1, 2, 3, 4, 11, 12, 13, 21, 22, 23, 31]
+            .iter()
+            .map(|d| DateLocaleTable::ENGLISH.ordinal_suffix(*d))
+            .collect();
+        assert_eq!(
+            suffixes,
+            ["st", "nd", "rd", "th", "th", "th", "th", "st", "nd", "rd", "st"]
+        );
+    }
+
+    #[test]
+    fn region_fallback() {
+        let locales = DateLocales::new();
+        let locale = locales.get("fr-CA").unwrap();
+        assert_eq!(locale.month_name(time::Month::November), "novembre");
+        assert!(locales.get("ja").is_none());
+    }
+}
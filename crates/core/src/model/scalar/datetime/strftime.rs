@@ -1,5 +1,8 @@
+use std::borrow::Cow;
 use std::fmt::{self, Write};
 
+use super::DateLocale;
+
 // std::fmt::Write is infallible for String https://doc.rust-lang.org/src/alloc/string.rs.html#2726
 // and would only ever fail if we were OOM which Rust won't handle regardless
 // so we simplify writes code since we know it can't fail
@@ -56,7 +59,18 @@ impl fmt::Display for DateFormatError {
 /// colon, ie +/-HH:MM, and Ruby itself recommends _not_ using `%Z` as it is
 /// OS-dependent on what the string will be, in addition to the abbreviated time
 /// zone names being ambiguous. `Z` is also not supported at all by liquidjs.
-pub fn strftime(ts: time::OffsetDateTime, fmt: &str) -> Result<String, DateFormatError> {
+///   We print `zone` when one is given, e.g. from the time zone database, and
+///   otherwise fall back to the UTC offset.
+/// - `%o` is a liquid-rust extension printing the day of the month with its ordinal suffix,
+///   eg. `3rd`.
+///
+/// Month names, weekday names, meridiem indicators and ordinal suffixes are taken from `locale`.
+pub fn strftime(
+    ts: time::OffsetDateTime,
+    fmt: &str,
+    locale: &dyn DateLocale,
+    zone: Option<&str>,
+) -> Result<String, DateFormatError> {
     let mut output = String::new();
     let mut fmt_iter = fmt.char_indices().peekable();
 
@@ -153,9 +167,9 @@ pub fn strftime(ts: time::OffsetDateTime, fmt: &str) -> Result<String, DateForma
             }
         };
 
-        enum Formats {
+        enum Formats<'a> {
             Numeric(i64, usize),
-            Alphabetical(&'static str),
+            Alphabetical(Cow<'a, str>),
             Formatted,
             Literal(char),
             Unknown,
@@ -254,14 +268,14 @@ pub fn strftime(ts: time::OffsetDateTime, fmt: &str) -> Result<String, DateForma
             'S' => Formats::Numeric(ts.second() as _, 2),
             // Number of seconds since UNIX_EPOCH
             's' => Formats::Numeric(ts.unix_timestamp(), 0),
-            // Abbreviated month name. Always 3 letters.
-            'b' | 'h' => Formats::Alphabetical(&(MONTH_NAMES[ts.month() as usize - 1])[..3]),
+            // Abbreviated month name, e.g. `Nov`.
+            'b' | 'h' => Formats::Alphabetical(locale.month_abbreviation(ts.month()).into()),
             // Full month name
-            'B' => Formats::Alphabetical(MONTH_NAMES[ts.month() as usize - 1]),
-            // Abbreviated weekday name. Always 3 letters.
-            'a' => Formats::Alphabetical(&(WEEKDAY_NAMES[ts.weekday() as usize])[..3]),
+            'B' => Formats::Alphabetical(locale.month_name(ts.month()).into()),
+            // Abbreviated weekday name, e.g. `Thu`.
+            'a' => Formats::Alphabetical(locale.weekday_abbreviation(ts.weekday()).into()),
             // Full weekday name.
-            'A' => Formats::Alphabetical(WEEKDAY_NAMES[ts.weekday() as usize]),
+            'A' => Formats::Alphabetical(locale.weekday_name(ts.weekday()).into()),
             // `am` or `pm` in 12-hour clocks.
             // OR
             // `AM` or `PM` in 12-hour clocks.
@@ -270,19 +284,14 @@ pub fn strftime(ts: time::OffsetDateTime, fmt: &str) -> Result<String, DateForma
             // format specifier :bleedingeyes:
             'P' | 'p' => {
                 let is_am = ts.hour() < 12;
+                let meridiem = locale.meridiem(is_am);
 
                 let s = if (fmt_char == 'p' && casing != Casing::Change)
                     || (fmt_char == 'P' && casing != Casing::Default)
                 {
-                    if is_am {
-                        "AM"
-                    } else {
-                        "PM"
-                    }
-                } else if is_am {
-                    "am"
+                    Cow::Borrowed(meridiem)
                 } else {
-                    "pm"
+                    Cow::Owned(meridiem.to_lowercase())
                 };
 
                 casing = Casing::Default;
@@ -309,7 +318,7 @@ pub fn strftime(ts: time::OffsetDateTime, fmt: &str) -> Result<String, DateForma
                     output,
                     "{:>2}-{}-{:04}",
                     ts.day(),
-                    &(MONTH_NAMES[ts.month() as usize - 1])[..3],
+                    locale.month_abbreviation(ts.month()),
                     ts.year(),
                 );
                 Formats::Formatted
@@ -361,7 +370,7 @@ pub fn strftime(ts: time::OffsetDateTime, fmt: &str) -> Result<String, DateForma
                     hour,
                     ts.minute(),
                     ts.second(),
-                    if is_am { "AM" } else { "PM" },
+                    locale.meridiem(is_am),
                 );
                 Formats::Formatted
             }
@@ -371,8 +380,8 @@ pub fn strftime(ts: time::OffsetDateTime, fmt: &str) -> Result<String, DateForma
                 w!(
                     output,
                     "{} {} {:>2} {:02}:{:02}:{:02} {:04}",
-                    &(WEEKDAY_NAMES[ts.weekday() as usize])[..3],
-                    &(MONTH_NAMES[ts.month() as usize - 1])[..3],
+                    locale.weekday_abbreviation(ts.weekday()),
+                    locale.month_abbreviation(ts.month()),
                     ts.day(),
                     ts.hour(),
                     ts.minute(),
@@ -381,6 +390,15 @@ pub fn strftime(ts: time::OffsetDateTime, fmt: &str) -> Result<String, DateForma
                 );
                 Formats::Formatted
             }
+            // Day of the month with its ordinal suffix, eg. 3rd (liquid-rust extension).
+            'o' => {
+                let day = ts.day();
+                let suffix = locale.ordinal_suffix(day);
+                let digits = if day < 10 { 1 } else { 2 };
+                write_padding!(comp digits + suffix.chars().count());
+                w!(output, "{}{}", day, suffix);
+                Formats::Formatted
+            }
             // Literals
             '%' => Formats::Literal('%'),
             'n' => Formats::Literal('\n'),
@@ -415,96 +433,100 @@ pub fn strftime(ts: time::OffsetDateTime, fmt: &str) -> Result<String, DateForma
 
                 continue;
             }
+            // %Z - Time zone abbreviation (e.g. CEST), when known
             // %z - Time zone as hour and minute offset from UTC (e.g. +0900)
             // %:z - hour and minute offset from UTC with a colon (e.g. +09:00)
             // %::z - hour, minute and second offset from UTC (e.g. +09:00:00)
-            'z' | 'Z' | ':' => {
-                // So Ruby _supposedly_ outputs the (OS dependent) time zone name/abbreviation
-                // however in my testing Z was instead completely ignored. In this
-                // case we preserve the previous chrono behavior of just output +/-HH:MM
-                let hm_sep = matches!(fmt_char, 'Z' | ':');
-                let mut ms_sep = false;
-
-                let mut handle_colons = || {
-                    if fmt_char == ':' {
-                        match next!() {
-                            Some((_, 'z')) => {
-                                return true;
-                            }
-                            Some((_, ':')) => {
-                                if let Some((_, 'z')) = next!() {
-                                    ms_sep = true;
+            'z' | 'Z' | ':' => match (fmt_char, zone) {
+                ('Z', Some(zone)) => Formats::Alphabetical(zone.into()),
+                _ => {
+                    // So Ruby _supposedly_ outputs the (OS dependent) time zone name/abbreviation
+                    // however in my testing Z was instead completely ignored. In this
+                    // case we preserve the previous chrono behavior of just output +/-HH:MM
+                    let hm_sep = matches!(fmt_char, 'Z' | ':');
+                    let mut ms_sep = false;
+
+                    let mut handle_colons = || {
+                        if fmt_char == ':' {
+                            match next!() {
+                                Some((_, 'z')) => {
                                     return true;
-                                } else {
-                                    return false;
                                 }
+                                Some((_, ':')) => {
+                                    if let Some((_, 'z')) = next!() {
+                                        ms_sep = true;
+                                        return true;
+                                    } else {
+                                        return false;
+                                    }
+                                }
+                                _ => return false,
                             }
-                            _ => return false,
                         }
-                    }
-
-                    true
-                };
 
-                if handle_colons() {
-                    let offset = ts.offset();
-
-                    // The timezone padding is calculated by the total size of the
-                    // output, but for rust fmt strings it only applies to the hour
-                    // component
-                    let output_size = 1 // +/-
-                        + 2 // HH
-                        + if hm_sep { 1 } else { 0 } // :
-                        + 2 // MM
-                        + if ms_sep {
-                            1 + 2 // :ss
-                        } else {
-                            0
-                        };
+                        true
+                    };
 
-                    // Note that z doesn't respect `-` even if it is numeric, mostly
-                    let pad_width = std::cmp::max(
-                        padding.unwrap_or_default().saturating_sub(output_size) + 2,
-                        2,
-                    );
+                    if handle_colons() {
+                        let offset = ts.offset();
+
+                        // The timezone padding is calculated by the total size of the
+                        // output, but for rust fmt strings it only applies to the hour
+                        // component
+                        let output_size = 1 // +/-
+                            + 2 // HH
+                            + if hm_sep { 1 } else { 0 } // :
+                            + 2 // MM
+                            + if ms_sep {
+                                1 + 2 // :ss
+                            } else {
+                                0
+                            };
+
+                        // Note that z doesn't respect `-` even if it is numeric, mostly
+                        let pad_width = std::cmp::max(
+                            padding.unwrap_or_default().saturating_sub(output_size) + 2,
+                            2,
+                        );
 
-                    if padding_style != PaddingStyle::Space {
-                        // So 0 filling to the left with a sign doesn't do at all
-                        // what you would expect, eg +0600 becomes 0+600, so we
-                        // do it manually
+                        if padding_style != PaddingStyle::Space {
+                            // So 0 filling to the left with a sign doesn't do at all
+                            // what you would expect, eg +0600 becomes 0+600, so we
+                            // do it manually
+
+                            w!(
+                                output,
+                                "{}{:0>width$}",
+                                if offset.is_negative() { '-' } else { '+' },
+                                offset.whole_hours().abs(),
+                                width = pad_width,
+                            );
+                        } else {
+                            w!(
+                                output,
+                                "{: >+width$}",
+                                offset.whole_hours(),
+                                width = pad_width
+                            );
+                        }
 
                         w!(
                             output,
-                            "{}{:0>width$}",
-                            if offset.is_negative() { '-' } else { '+' },
-                            offset.whole_hours().abs(),
-                            width = pad_width,
+                            "{}{:02}",
+                            if hm_sep { ":" } else { "" },
+                            offset.minutes_past_hour().abs()
                         );
-                    } else {
-                        w!(
-                            output,
-                            "{: >+width$}",
-                            offset.whole_hours(),
-                            width = pad_width
-                        );
-                    }
 
-                    w!(
-                        output,
-                        "{}{:02}",
-                        if hm_sep { ":" } else { "" },
-                        offset.minutes_past_hour().abs()
-                    );
+                        if ms_sep {
+                            w!(output, ":{:02}", offset.seconds_past_minute().abs());
+                        }
 
-                    if ms_sep {
-                        w!(output, ":{:02}", offset.seconds_past_minute().abs());
+                        continue;
                     }
 
-                    continue;
+                    Formats::Unknown
                 }
-
-                Formats::Unknown
-            }
+            },
             // Unknown format specifier
             _ => Formats::Unknown,
         };
@@ -541,16 +563,18 @@ pub fn strftime(ts: time::OffsetDateTime, fmt: &str) -> Result<String, DateForma
             }
             Formats::Alphabetical(s) => {
                 if use_padding && padding.is_some() {
-                    write_padding!(padding.unwrap_or_default().saturating_sub(s.len()));
+                    write_padding!(padding
+                        .unwrap_or_default()
+                        .saturating_sub(s.chars().count()));
                 }
-                output.push_str(s);
+                output.push_str(&s);
                 if casing != Casing::Default {
-                    output[out_cur..].make_ascii_uppercase();
+                    uppercase_from(&mut output, out_cur);
                 }
             }
             Formats::Formatted => {
                 if casing != Casing::Default {
-                    output[out_cur..].make_ascii_uppercase();
+                    uppercase_from(&mut output, out_cur);
                 }
             }
             Formats::Literal(lit) => {
@@ -589,45 +613,31 @@ enum Casing {
     Change,
 }
 
-// time unfortunately only implements `Display` for Month and hides
-// its more sophisticated formatting internally, so we just have our own table
-const MONTH_NAMES: &[&str] = &[
-    "January",
-    "February",
-    "March",
-    "April",
-    "May",
-    "June",
-    "July",
-    "August",
-    "September",
-    "October",
-    "November",
-    "December",
-];
-
-// Ditto
-const WEEKDAY_NAMES: &[&str] = &[
-    "Monday",
-    "Tuesday",
-    "Wednesday",
-    "Thursday",
-    "Friday",
-    "Saturday",
-    "Sunday",
-];
+/// Uppercases `output[start..]`, including non-ASCII month and weekday names.
+fn uppercase_from(output: &mut String, start: usize) {
+    if output[start..].is_ascii() {
+        output[start..].make_ascii_uppercase();
+    } else {
+        let upper = output[start..].to_uppercase();
+        output.truncate(start);
+        output.push_str(&upper);
+    }
+}
 
 #[cfg(test)]
 mod test {
+    use super::super::DateLocaleTable;
     use super::*;
 
+    const EN: &DateLocaleTable = &DateLocaleTable::ENGLISH;
+
     const SIMPLE: time::OffsetDateTime =
         time::macros::datetime!(2022-11-03 07:56:37.666_777_888 +06:00);
 
     macro_rules! eq {
         ($ts:expr => [$($fmt:expr => $exp:expr),+$(,)?]) => {
             $(
-                match strftime($ts, $fmt) {
+                match strftime($ts, $fmt, EN, None) {
                     Ok(formatted) => {
                         assert_eq!(formatted, $exp, "format string '{}' gave unexpected results", stringify!($fmt));
                     }
@@ -930,26 +940,56 @@ mod test {
         ]);
     }
 
+    #[test]
+    fn ordinal() {
+        eq!(SIMPLE => [
+            "%o" => "3rd",
+            "%5o" => "  3rd",
+        ]);
+    }
+
+    #[test]
+    fn localized() {
+        let fr = &DateLocaleTable::FRENCH;
+        let ts = time::macros::datetime!(2022-02-01 13:00:00 +00:00);
+        assert_eq!(
+            strftime(ts, "%A %o %B %Y, %a %b", fr, None).unwrap(),
+            "mardi 1er février 2022, mar fév."
+        );
+        assert_eq!(strftime(ts, "%^B", fr, None).unwrap(), "FÉVRIER");
+        assert_eq!(strftime(ts, "%10B|%p", fr, None).unwrap(), "   février|PM");
+    }
+
+    #[test]
+    fn zone_abbreviation() {
+        let ts = time::macros::datetime!(2022-07-01 13:00:00 +02:00);
+        assert_eq!(
+            strftime(ts, "%H:%M %Z|%^6Z|%z", EN, Some("cest")).unwrap(),
+            "13:00 cest|  CEST|+0200"
+        );
+        assert_eq!(strftime(ts, "%Z", EN, None).unwrap(), "+02:00");
+    }
+
     #[test]
     fn errors() {
         assert_eq!(
-            strftime(SIMPLE, "%9").unwrap_err(),
+            strftime(SIMPLE, "%9", EN, None).unwrap_err(),
             DateFormatError::NoFormatSpecifier
         );
         assert_eq!(
-            strftime(SIMPLE, "%9E").unwrap_err(),
+            strftime(SIMPLE, "%9E", EN, None).unwrap_err(),
             DateFormatError::NoFormatSpecifierAfterModifier
         );
         assert_eq!(
-            strftime(SIMPLE, "%010").unwrap_err(),
+            strftime(SIMPLE, "%010", EN, None).unwrap_err(),
             DateFormatError::NoFormatSpecifier
         );
         assert_eq!(
-            strftime(SIMPLE, "X%").unwrap_err(),
+            strftime(SIMPLE, "X%", EN, None).unwrap_err(),
             DateFormatError::NoFormatSpecifier
         );
         assert!(matches!(
-            strftime(SIMPLE, "%18446744073709551616d").unwrap_err(),
+            strftime(SIMPLE, "%18446744073709551616d", EN, None).unwrap_err(),
             DateFormatError::InvalidWidth(_)
         ));
     }
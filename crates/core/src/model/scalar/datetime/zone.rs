@@ -0,0 +1,196 @@
+use std::fmt;
+
+use super::DateTime;
+
+/// A time zone dates can be displayed in.
+///
+/// Either a fixed offset from UTC or, with the `tzdb` feature, a named zone from the embedded
+/// IANA time zone database (e.g. `Europe/Berlin`), which follows daylight saving time.
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub struct TimeZone {
+    inner: TimeZoneImpl,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum TimeZoneImpl {
+    Fixed(time::UtcOffset),
+    /// Zone data is looked up by name on use, which is cheap for the embedded database.
+    #[cfg(feature = "tzdb")]
+    Named(&'static str),
+}
+
+impl TimeZone {
+    /// Coordinated Universal Time.
+    pub const UTC: Self = Self {
+        inner: TimeZoneImpl::Fixed(time::UtcOffset::UTC),
+    };
+
+    /// A zone that is always `offset` from UTC.
+    pub fn fixed(offset: time::UtcOffset) -> Self {
+        Self {
+            inner: TimeZoneImpl::Fixed(offset),
+        }
+    }
+
+    /// Look up an IANA time zone by name (case-insensitive), e.g. `America/New_York`.
+    #[cfg(feature = "tzdb")]
+    pub fn named(name: &str) -> Option<Self> {
+        let name = tzdb::TZ_NAMES
+            .iter()
+            .find(|n| n.eq_ignore_ascii_case(name))
+            .copied()?;
+        tzdb::tz_by_name(name)?;
+        Some(Self {
+            inner: TimeZoneImpl::Named(name),
+        })
+    }
+
+    /// Parse a time zone.
+    ///
+    /// Accepts `UTC`, offsets like `+0530` or `-05:00` and, with the `tzdb` feature, IANA zone
+    /// names.
+    pub fn parse(s: &str) -> Option<Self> {
+        let s = s.trim();
+        if s.eq_ignore_ascii_case("utc") || s == "Z" {
+            return Some(Self::UTC);
+        }
+        if let Some(offset) = parse_offset(s) {
+            return Some(Self::fixed(offset));
+        }
+        #[cfg(feature = "tzdb")]
+        {
+            Self::named(s)
+        }
+        #[cfg(not(feature = "tzdb"))]
+        {
+            None
+        }
+    }
+
+    /// The IANA name of the zone, if it is a named zone.
+    pub fn name(&self) -> Option<&'static str> {
+        match self.inner {
+            TimeZoneImpl::Fixed(_) => None,
+            #[cfg(feature = "tzdb")]
+            TimeZoneImpl::Named(name) => Some(name),
+        }
+    }
+
+    /// The offset from UTC in effect at `date`.
+    #[cfg_attr(not(feature = "tzdb"), allow(unused_variables))]
+    pub fn offset_at(&self, date: DateTime) -> time::UtcOffset {
+        match self.inner {
+            TimeZoneImpl::Fixed(offset) => offset,
+            #[cfg(feature = "tzdb")]
+            TimeZoneImpl::Named(name) => tzdb::tz_by_name(name)
+                .and_then(|zone| zone.find_local_time_type(date.unix_timestamp()).ok())
+                .and_then(|t| time::UtcOffset::from_whole_seconds(t.ut_offset()).ok())
+                .unwrap_or(time::UtcOffset::UTC),
+        }
+    }
+
+    /// The abbreviation of the zone in effect at `date`, e.g. `CEST`.
+    ///
+    /// Fixed offsets other than UTC have no abbreviation.
+    #[cfg_attr(not(feature = "tzdb"), allow(unused_variables))]
+    pub fn abbreviation_at(&self, date: DateTime) -> Option<&str> {
+        match self.inner {
+            TimeZoneImpl::Fixed(offset) if offset.is_utc() => Some("UTC"),
+            TimeZoneImpl::Fixed(_) => None,
+            #[cfg(feature = "tzdb")]
+            TimeZoneImpl::Named(name) => tzdb::tz_by_name(name)
+                .and_then(|zone| zone.find_local_time_type(date.unix_timestamp()).ok())
+                .map(|t| t.time_zone_designation())
+                .filter(|a| !a.is_empty()),
+        }
+    }
+}
+
+impl Default for TimeZone {
+    fn default() -> Self {
+        Self::UTC
+    }
+}
+
+impl fmt::Debug for TimeZone {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "TimeZone({})", self)
+    }
+}
+
+impl fmt::Display for TimeZone {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.inner {
+            TimeZoneImpl::Fixed(offset) if offset.is_utc() => write!(f, "UTC"),
+            TimeZoneImpl::Fixed(offset) => write!(
+                f,
+                "{}{:02}:{:02}",
+                if offset.is_negative() { '-' } else { '+' },
+                offset.whole_hours().abs(),
+                offset.minutes_past_hour().abs()
+            ),
+            #[cfg(feature = "tzdb")]
+            TimeZoneImpl::Named(name) => write!(f, "{}", name),
+        }
+    }
+}
+
+fn parse_offset(s: &str) -> Option<time::UtcOffset> {
+    let (sign, rest) = match s.as_bytes().first()? {
+        b'+' => (1, &s[1..]),
+        b'-' => (-1, &s[1..]),
+        _ => return None,
+    };
+    let (hours, minutes) = match rest.split_once(':') {
+        Some(parts) => parts,
+        None if rest.len() == 4 => rest.split_at(2),
+        None => return None,
+    };
+    if hours.len() != 2 || minutes.len() != 2 {
+        return None;
+    }
+    let hours: i8 = hours.parse().ok()?;
+    let minutes: i8 = minutes.parse().ok()?;
+    time::UtcOffset::from_hms(sign * hours, sign * minutes, 0).ok()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_fixed() {
+        assert_eq!(TimeZone::parse("utc"), Some(TimeZone::UTC));
+        assert_eq!(
+            TimeZone::parse("-05:30").unwrap().to_string(),
+            "-05:30".to_owned()
+        );
+        assert_eq!(
+            TimeZone::parse("+0900").unwrap().offset_at(DateTime::now()),
+            time::macros::offset!(+9)
+        );
+        assert!(TimeZone::parse("0900").is_none());
+        assert!(TimeZone::parse("+9").is_none());
+    }
+
+    #[test]
+    #[cfg(feature = "tzdb")]
+    fn named_follows_dst() {
+        let zone = TimeZone::parse("europe/berlin").unwrap();
+        assert_eq!(zone.name(), Some("Europe/Berlin"));
+
+        let winter = DateTime::from_ymd(2016, 1, 13);
+        assert_eq!(zone.offset_at(winter), time::macros::offset!(+1));
+        assert_eq!(zone.abbreviation_at(winter), Some("CET"));
+
+        let summer = DateTime::from_ymd(2016, 6, 13);
+        assert_eq!(zone.offset_at(summer), time::macros::offset!(+2));
+        assert_eq!(zone.abbreviation_at(summer), Some("CEST"));
+    }
+
+    #[test]
+    #[cfg(not(feature = "tzdb"))]
+    fn named_requires_tzdb() {
+        assert!(TimeZone::parse("Europe/Berlin").is_none());
+    }
+}
@@ -18,6 +18,10 @@ use crate::model::{Value, ValueView};
 
 pub use date::*;
 pub use datetime::*;
+#[cfg(feature = "bigint")]
+pub use num_bigint::BigInt;
+#[cfg(feature = "decimal")]
+pub use rust_decimal::Decimal;
 pub use ser::to_scalar;
 
 /// A Liquid scalar value
@@ -39,6 +43,38 @@ enum ScalarCowEnum<'s> {
     DateTime(DateTime),
     Date(Date),
     Str(KStringCow<'s>),
+    #[serde(skip_deserializing)]
+    Safe(KStringCow<'s>),
+    #[cfg(feature = "decimal")]
+    #[serde(skip_deserializing, serialize_with = "serialize_decimal")]
+    Decimal(Decimal),
+    #[cfg(feature = "bigint")]
+    #[serde(skip_deserializing, serialize_with = "serialize_bigint")]
+    BigInt(BigInt),
+}
+
+/// Marks a decimal for `to_value`, which would otherwise see a string; other formats see one.
+#[cfg(feature = "decimal")]
+pub(crate) const DECIMAL_TOKEN: &str = "$liquid_core::private::Decimal";
+
+#[cfg(feature = "decimal")]
+fn serialize_decimal<S: serde::Serializer>(
+    value: &Decimal,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.serialize_newtype_struct(DECIMAL_TOKEN, &value.to_string())
+}
+
+/// Marks a big integer for `to_value`, which would otherwise see a string; other formats see one.
+#[cfg(feature = "bigint")]
+pub(crate) const BIGINT_TOKEN: &str = "$liquid_core::private::BigInt";
+
+#[cfg(feature = "bigint")]
+fn serialize_bigint<S: serde::Serializer>(
+    value: &BigInt,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.serialize_newtype_struct(BIGINT_TOKEN, &value.to_string())
 }
 
 impl<'s> ScalarCow<'s> {
@@ -47,6 +83,18 @@ impl<'s> ScalarCow<'s> {
         value.into()
     }
 
+    /// A string that is already escaped, so output escaping leaves it alone.
+    ///
+    /// It is a string to filters and comparisons.
+    pub fn safe<S: Into<KStringCow<'s>>>(value: S) -> Self {
+        Self(ScalarCowEnum::Safe(value.into()))
+    }
+
+    /// Whether this is a string marked with [`ScalarCow::safe`].
+    pub fn is_safe(&self) -> bool {
+        matches!(self.0, ScalarCowEnum::Safe(_))
+    }
+
     /// Create an owned version of the value.
     pub fn into_owned(self) -> Scalar {
         match self.0 {
@@ -56,6 +104,11 @@ impl<'s> ScalarCow<'s> {
             ScalarCowEnum::DateTime(x) => Scalar::new(x),
             ScalarCowEnum::Date(x) => Scalar::new(x),
             ScalarCowEnum::Str(x) => Scalar::new(x.into_owned()),
+            ScalarCowEnum::Safe(x) => Scalar::safe(x.into_owned()),
+            #[cfg(feature = "decimal")]
+            ScalarCowEnum::Decimal(x) => Scalar::new(x),
+            #[cfg(feature = "bigint")]
+            ScalarCowEnum::BigInt(x) => Scalar::new(x),
         }
     }
 
@@ -68,6 +121,11 @@ impl<'s> ScalarCow<'s> {
             ScalarCowEnum::DateTime(x) => ScalarCow::new(x),
             ScalarCowEnum::Date(x) => ScalarCow::new(x),
             ScalarCowEnum::Str(ref x) => ScalarCow::new(x.as_ref()),
+            ScalarCowEnum::Safe(ref x) => ScalarCow::safe(x.as_ref()),
+            #[cfg(feature = "decimal")]
+            ScalarCowEnum::Decimal(x) => ScalarCow::new(x),
+            #[cfg(feature = "bigint")]
+            ScalarCowEnum::BigInt(ref x) => ScalarCow::new(x.clone()),
         }
     }
 
@@ -79,7 +137,11 @@ impl<'s> ScalarCow<'s> {
             ScalarCowEnum::Bool(ref x) => x,
             ScalarCowEnum::DateTime(ref x) => x,
             ScalarCowEnum::Date(ref x) => x,
-            ScalarCowEnum::Str(ref x) => x,
+            ScalarCowEnum::Str(ref x) | ScalarCowEnum::Safe(ref x) => x,
+            #[cfg(feature = "decimal")]
+            ScalarCowEnum::Decimal(ref x) => x,
+            #[cfg(feature = "bigint")]
+            ScalarCowEnum::BigInt(ref x) => x,
         }
     }
 
@@ -91,7 +153,11 @@ impl<'s> ScalarCow<'s> {
             ScalarCowEnum::Bool(x) => x.to_string().into(),
             ScalarCowEnum::DateTime(x) => x.to_string().into(),
             ScalarCowEnum::Date(x) => x.to_string().into(),
-            ScalarCowEnum::Str(x) => x.into_owned(),
+            ScalarCowEnum::Str(x) | ScalarCowEnum::Safe(x) => x.into_owned(),
+            #[cfg(feature = "decimal")]
+            ScalarCowEnum::Decimal(x) => x.to_string().into(),
+            #[cfg(feature = "bigint")]
+            ScalarCowEnum::BigInt(x) => x.to_string().into(),
         }
     }
 
@@ -99,7 +165,9 @@ impl<'s> ScalarCow<'s> {
     pub fn to_integer(&self) -> Option<i64> {
         match self.0 {
             ScalarCowEnum::Integer(ref x) => Some(*x),
-            ScalarCowEnum::Str(ref x) => x.parse::<i64>().ok(),
+            ScalarCowEnum::Str(ref x) | ScalarCowEnum::Safe(ref x) => x.parse::<i64>().ok(),
+            #[cfg(feature = "bigint")]
+            ScalarCowEnum::BigInt(ref x) => num_traits::ToPrimitive::to_i64(x),
             _ => None,
         }
     }
@@ -109,7 +177,54 @@ impl<'s> ScalarCow<'s> {
         match self.0 {
             ScalarCowEnum::Integer(ref x) => Some(*x as f64),
             ScalarCowEnum::Float(ref x) => Some(*x),
-            ScalarCowEnum::Str(ref x) => x.parse::<f64>().ok(),
+            ScalarCowEnum::Str(ref x) | ScalarCowEnum::Safe(ref x) => x.parse::<f64>().ok(),
+            #[cfg(feature = "decimal")]
+            ScalarCowEnum::Decimal(ref x) => rust_decimal::prelude::ToPrimitive::to_f64(x),
+            #[cfg(feature = "bigint")]
+            ScalarCowEnum::BigInt(ref x) => num_traits::ToPrimitive::to_f64(x),
+            _ => None,
+        }
+    }
+
+    /// Access the decimal, if this is one
+    #[cfg(feature = "decimal")]
+    pub fn as_decimal(&self) -> Option<Decimal> {
+        match self.0 {
+            ScalarCowEnum::Decimal(x) => Some(x),
+            _ => None,
+        }
+    }
+
+    /// Interpret as a decimal, if possible
+    #[cfg(feature = "decimal")]
+    pub fn to_decimal(&self) -> Option<Decimal> {
+        match self.0 {
+            ScalarCowEnum::Integer(x) => Some(x.into()),
+            ScalarCowEnum::Float(x) => rust_decimal::prelude::FromPrimitive::from_f64(x),
+            ScalarCowEnum::Str(ref x) | ScalarCowEnum::Safe(ref x) => x.parse::<Decimal>().ok(),
+            ScalarCowEnum::Decimal(x) => Some(x),
+            #[cfg(feature = "bigint")]
+            ScalarCowEnum::BigInt(ref x) => x.to_string().parse::<Decimal>().ok(),
+            _ => None,
+        }
+    }
+
+    /// Access the big integer, if this is one
+    #[cfg(feature = "bigint")]
+    pub fn as_bigint(&self) -> Option<&BigInt> {
+        match self.0 {
+            ScalarCowEnum::BigInt(ref x) => Some(x),
+            _ => None,
+        }
+    }
+
+    /// Interpret as a big integer, if possible
+    #[cfg(feature = "bigint")]
+    pub fn to_bigint(&self) -> Option<BigInt> {
+        match self.0 {
+            ScalarCowEnum::Integer(x) => Some(x.into()),
+            ScalarCowEnum::Str(ref x) | ScalarCowEnum::Safe(ref x) => x.parse::<BigInt>().ok(),
+            ScalarCowEnum::BigInt(ref x) => Some(x.clone()),
             _ => None,
         }
     }
@@ -126,7 +241,10 @@ impl<'s> ScalarCow<'s> {
     pub fn to_date_time(&self) -> Option<DateTime> {
         match self.0 {
             ScalarCowEnum::DateTime(ref x) => Some(*x),
-            ScalarCowEnum::Str(ref x) => DateTime::from_str(x.as_str()),
+            ScalarCowEnum::Date(x) => Some(DateTime::default().with_date(x)),
+            ScalarCowEnum::Str(ref x) | ScalarCowEnum::Safe(ref x) => {
+                DateTime::from_str(x.as_str())
+            }
             _ => None,
         }
     }
@@ -136,7 +254,7 @@ impl<'s> ScalarCow<'s> {
         match self.0 {
             ScalarCowEnum::DateTime(ref x) => Some(x.date()),
             ScalarCowEnum::Date(ref x) => Some(*x),
-            ScalarCowEnum::Str(ref x) => Date::from_str(x.as_str()),
+            ScalarCowEnum::Str(ref x) | ScalarCowEnum::Safe(ref x) => Date::from_str(x.as_str()),
             _ => None,
         }
     }
@@ -144,7 +262,7 @@ impl<'s> ScalarCow<'s> {
     /// Interpret as a Cow str, borrowing if possible
     pub fn into_cow_str(self) -> Cow<'s, str> {
         match self {
-            Self(ScalarCowEnum::Str(x)) => x.into_cow_str(),
+            Self(ScalarCowEnum::Str(x)) | Self(ScalarCowEnum::Safe(x)) => x.into_cow_str(),
             other => other.into_string().into_cow_str(),
         }
     }
@@ -178,7 +296,10 @@ impl<'s> ValueView for ScalarCow<'s> {
         self.as_view().to_kstr()
     }
     fn to_value(&self) -> Value {
-        self.as_view().to_value()
+        match self.0 {
+            ScalarCowEnum::Safe(_) => Value::Scalar(self.as_ref().into_owned()),
+            _ => self.as_view().to_value(),
+        }
     }
 
     fn as_scalar(&self) -> Option<ScalarCow<'_>> {
@@ -371,6 +492,124 @@ impl<'s> PartialOrd<f64> for ScalarCow<'s> {
 
 impl_copyable!(f32, f64);
 
+#[cfg(feature = "decimal")]
+impl ValueView for Decimal {
+    fn as_debug(&self) -> &dyn fmt::Debug {
+        self
+    }
+
+    fn render(&self) -> DisplayCow<'_> {
+        DisplayCow::Borrowed(self)
+    }
+    fn source(&self) -> DisplayCow<'_> {
+        DisplayCow::Borrowed(self)
+    }
+    fn type_name(&self) -> &'static str {
+        "decimal number"
+    }
+    fn query_state(&self, state: State) -> bool {
+        match state {
+            State::Truthy => true,
+            State::DefaultValue => false,
+            State::Empty => false,
+            State::Blank => false,
+        }
+    }
+
+    fn to_kstr(&self) -> KStringCow<'_> {
+        self.render().to_string().into()
+    }
+    fn to_value(&self) -> Value {
+        Value::scalar(*self)
+    }
+
+    fn as_scalar(&self) -> Option<ScalarCow<'_>> {
+        Some(ScalarCow::new(*self))
+    }
+}
+
+#[cfg(feature = "decimal")]
+impl<'s> From<Decimal> for ScalarCow<'s> {
+    fn from(s: Decimal) -> Self {
+        ScalarCow(ScalarCowEnum::Decimal(s))
+    }
+}
+
+#[cfg(feature = "decimal")]
+impl<'s> PartialEq<Decimal> for ScalarCow<'s> {
+    fn eq(&self, other: &Decimal) -> bool {
+        let other = (*other).into();
+        scalar_eq(self, &other)
+    }
+}
+
+#[cfg(feature = "decimal")]
+impl<'s> PartialOrd<Decimal> for ScalarCow<'s> {
+    fn partial_cmp(&self, other: &Decimal) -> Option<Ordering> {
+        let other = (*other).into();
+        scalar_cmp(self, &other)
+    }
+}
+
+#[cfg(feature = "bigint")]
+impl ValueView for BigInt {
+    fn as_debug(&self) -> &dyn fmt::Debug {
+        self
+    }
+
+    fn render(&self) -> DisplayCow<'_> {
+        DisplayCow::Borrowed(self)
+    }
+    fn source(&self) -> DisplayCow<'_> {
+        DisplayCow::Borrowed(self)
+    }
+    fn type_name(&self) -> &'static str {
+        "whole number"
+    }
+    fn query_state(&self, state: State) -> bool {
+        match state {
+            State::Truthy => true,
+            State::DefaultValue => false,
+            State::Empty => false,
+            State::Blank => false,
+        }
+    }
+
+    fn to_kstr(&self) -> KStringCow<'_> {
+        self.render().to_string().into()
+    }
+    fn to_value(&self) -> Value {
+        Value::scalar(self.clone())
+    }
+
+    fn as_scalar(&self) -> Option<ScalarCow<'_>> {
+        Some(ScalarCow::new(self.clone()))
+    }
+}
+
+#[cfg(feature = "bigint")]
+impl<'s> From<BigInt> for ScalarCow<'s> {
+    fn from(s: BigInt) -> Self {
+        ScalarCow(ScalarCowEnum::BigInt(s))
+    }
+}
+
+#[cfg(feature = "bigint")]
+impl<'s> PartialEq<BigInt> for ScalarCow<'s> {
+    fn eq(&self, other: &BigInt) -> bool {
+        let other = other.clone().into();
+        scalar_eq(self, &other)
+    }
+}
+
+#[cfg(feature = "bigint")]
+impl<'s> PartialOrd<BigInt> for ScalarCow<'s> {
+    fn partial_cmp(&self, other: &BigInt) -> Option<Ordering> {
+        let other = other.clone().into();
+        scalar_cmp(self, &other)
+    }
+}
+
 impl ValueView for bool {
     fn as_debug(&self) -> &dyn fmt::Debug {
         self
@@ -571,8 +810,8 @@ impl<'s> ValueView for &'s str {
     }
 }
 
-struct StrSource<'s> {
-    s: &'s str,
+pub(crate) struct StrSource<'s> {
+    pub(crate) s: &'s str,
 }
 
 impl<'s> fmt::Display for StrSource<'s> {
@@ -845,9 +1084,20 @@ fn scalar_eq<'s>(lhs: &ScalarCow<'s>, rhs: &ScalarCow<'s>) -> bool {
         (&ScalarCowEnum::Date(x), &ScalarCowEnum::Date(y)) => x == y,
         (&ScalarCowEnum::DateTime(x), &ScalarCowEnum::Date(y)) => x == x.with_date(y),
         (&ScalarCowEnum::Date(x), &ScalarCowEnum::DateTime(y)) => y.with_date(x) == y,
-        (&ScalarCowEnum::Str(ref x), &ScalarCowEnum::Str(ref y)) => x == y,
+        (
+            &ScalarCowEnum::Str(ref x) | &ScalarCowEnum::Safe(ref x),
+            &ScalarCowEnum::Str(ref y) | &ScalarCowEnum::Safe(ref y),
+        ) => x == y,
         // encode Ruby truthiness: all values except false and nil are true
         (_, &ScalarCowEnum::Bool(b)) | (&ScalarCowEnum::Bool(b), _) => b,
+        #[cfg(feature = "bigint")]
+        (&ScalarCowEnum::BigInt(_), _) | (_, &ScalarCowEnum::BigInt(_)) => {
+            bigint_cmp(lhs, rhs) == Some(Ordering::Equal)
+        }
+        #[cfg(feature = "decimal")]
+        (&ScalarCowEnum::Decimal(_), _) | (_, &ScalarCowEnum::Decimal(_)) => {
+            decimal_cmp(lhs, rhs) == Some(Ordering::Equal)
+        }
         _ => false,
     }
 }
@@ -863,7 +1113,51 @@ fn scalar_cmp<'s>(lhs: &ScalarCow<'s>, rhs: &ScalarCow<'s>) -> Option<Ordering>
         (&ScalarCowEnum::Date(x), &ScalarCowEnum::Date(y)) => x.partial_cmp(&y),
         (&ScalarCowEnum::DateTime(x), &ScalarCowEnum::Date(y)) => x.partial_cmp(&x.with_date(y)),
         (&ScalarCowEnum::Date(x), &ScalarCowEnum::DateTime(y)) => y.with_date(x).partial_cmp(&y),
-        (&ScalarCowEnum::Str(ref x), &ScalarCowEnum::Str(ref y)) => x.partial_cmp(y),
+        (
+            &ScalarCowEnum::Str(ref x) | &ScalarCowEnum::Safe(ref x),
+            &ScalarCowEnum::Str(ref y) | &ScalarCowEnum::Safe(ref y),
+        ) => x.partial_cmp(y),
+        #[cfg(feature = "bigint")]
+        (&ScalarCowEnum::BigInt(_), _) | (_, &ScalarCowEnum::BigInt(_)) => bigint_cmp(lhs, rhs),
+        #[cfg(feature = "decimal")]
+        (&ScalarCowEnum::Decimal(_), _) | (_, &ScalarCowEnum::Decimal(_)) => decimal_cmp(lhs, rhs),
+        _ => None,
+    }
+}
+
+/// Decimals compare exactly with whole numbers and other decimals, and as floats with floats.
+#[cfg(feature = "decimal")]
+fn decimal_cmp<'s>(lhs: &ScalarCow<'s>, rhs: &ScalarCow<'s>) -> Option<Ordering> {
+    match (&lhs.0, &rhs.0) {
+        (&ScalarCowEnum::Float(_), _) | (_, &ScalarCowEnum::Float(_)) => {
+            lhs.to_float()?.partial_cmp(&rhs.to_float()?)
+        }
+        (&ScalarCowEnum::Integer(_), _)
+        | (_, &ScalarCowEnum::Integer(_))
+        | (&ScalarCowEnum::Decimal(_), &ScalarCowEnum::Decimal(_)) => {
+            lhs.to_decimal()?.partial_cmp(&rhs.to_decimal()?)
+        }
+        _ => None,
+    }
+}
+
+/// Big integers compare exactly with whole numbers and other big integers, and as floats with
+/// other numbers.
+#[cfg(feature = "bigint")]
+fn bigint_cmp<'s>(lhs: &ScalarCow<'s>, rhs: &ScalarCow<'s>) -> Option<Ordering> {
+    match (&lhs.0, &rhs.0) {
+        (&ScalarCowEnum::Integer(_), _)
+        | (_, &ScalarCowEnum::Integer(_))
+        | (&ScalarCowEnum::BigInt(_), &ScalarCowEnum::BigInt(_)) => {
+            lhs.to_bigint()?.partial_cmp(&rhs.to_bigint()?)
+        }
+        (&ScalarCowEnum::Float(_), _) | (_, &ScalarCowEnum::Float(_)) => {
+            lhs.to_float()?.partial_cmp(&rhs.to_float()?)
+        }
+        #[cfg(feature = "decimal")]
+        (&ScalarCowEnum::Decimal(_), _) | (_, &ScalarCowEnum::Decimal(_)) => {
+            lhs.to_float()?.partial_cmp(&rhs.to_float()?)
+        }
         _ => None,
     }
 }
@@ -1110,4 +1404,62 @@ mod test {
             assert_eq!(is_borrowed(extract_cow_str(&sc)), true);
         }
     }
+
+    #[test]
+    #[cfg(feature = "decimal")]
+    fn decimal_keeps_scale() {
+        let val: ScalarCow<'_> = Decimal::new(1990, 2).into();
+        assert_eq!(val.to_kstr(), "19.90");
+        assert_eq!(val.source().to_string(), "19.90");
+        assert_eq!(val.to_integer(), None);
+        assert_eq!(val.to_float(), Some(19.9));
+        assert_eq!(val.to_decimal(), Some(Decimal::new(1990, 2)));
+    }
+
+    #[test]
+    #[cfg(feature = "decimal")]
+    fn decimal_compares_with_numbers() {
+        let val: ScalarCow<'_> = Decimal::new(300, 2).into();
+        assert_eq!(val, Decimal::new(3, 0));
+        assert_eq!(val, 3i64);
+        assert_eq!(val, 3.0f64);
+        assert!(val < 4i64);
+        assert!(val > 2.5f64);
+        assert!(val < Decimal::new(301, 2));
+        assert_eq!(val, TRUE);
+        assert_ne!(val, ScalarCow::new("3.00"));
+        assert_eq!(val.partial_cmp(&ScalarCow::new("3.00")), None);
+    }
+
+    #[test]
+    #[cfg(feature = "bigint")]
+    fn bigint_compares_with_numbers() {
+        let big = BigInt::from(i64::MAX) + 1i64;
+        let val: ScalarCow<'_> = big.clone().into();
+        assert_eq!(val.to_kstr(), "9223372036854775808");
+        assert_eq!(val.to_integer(), None);
+        assert_eq!(val.to_bigint(), Some(big.clone()));
+        assert_eq!(val, big);
+        assert!(val > i64::MAX);
+        assert!(val < 1e19f64);
+        assert_eq!(ScalarCow::new(BigInt::from(3)), 3i64);
+        assert_eq!(val, TRUE);
+        assert_ne!(val, ScalarCow::new("9223372036854775808"));
+        assert_eq!(
+            val.partial_cmp(&ScalarCow::new("9223372036854775808")),
+            None
+        );
+    }
+
+    #[test]
+    fn safe_is_a_string() {
+        let safe = ScalarCow::safe("<b>");
+        assert!(safe.is_safe());
+        assert!(!ScalarCow::new("<b>").is_safe());
+        assert_eq!(safe, ScalarCow::new("<b>"));
+        assert_eq!(safe.to_kstr(), "<b>");
+        assert_eq!(ScalarCow::safe("1").to_integer(), Some(1));
+        assert!(safe.to_value().as_scalar().unwrap().is_safe());
+        assert!(safe.into_owned().is_safe());
+    }
 }
@@ -134,7 +134,22 @@ impl serde::Serializer for ScalarSerializer {
     where
         T: Serialize,
     {
-        value.serialize(ScalarSerializer)
+        let scalar = value.serialize(ScalarSerializer)?;
+        #[cfg(feature = "decimal")]
+        if _name == super::DECIMAL_TOKEN {
+            return scalar
+                .to_decimal()
+                .map(Scalar::new)
+                .ok_or_else(|| SerError::new(crate::error::Error::with_msg("Invalid decimal.")));
+        }
+        #[cfg(feature = "bigint")]
+        if _name == super::BIGINT_TOKEN {
+            return scalar
+                .to_bigint()
+                .map(Scalar::new)
+                .ok_or_else(|| SerError::new(crate::error::Error::with_msg("Invalid integer.")));
+        }
+        Ok(scalar)
     }
 
     fn serialize_newtype_variant<T: ?Sized>(
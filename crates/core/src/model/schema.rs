@@ -0,0 +1,434 @@
+//! The expected shape of values, for checking inputs before rendering.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use crate::error::{Error, Result};
+
+use super::{KString, KStringCow, ValueView};
+
+/// The expected shape of a value, like a subset of JSON Schema.
+///
+/// Deserializes from descriptions such as
+/// `{"type": "object", "properties": {"name": {"type": "string"}}, "required": ["name"]}`.
+#[derive(Clone, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct Schema {
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    kind: Option<SchemaType>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    properties: BTreeMap<KString, Schema>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    required: Vec<KString>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    items: Option<Box<Schema>>,
+}
+
+/// The types a `Schema` can expect.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SchemaType {
+    /// `nil`.
+    Null,
+    /// `true` or `false`.
+    Boolean,
+    /// A whole number.
+    Integer,
+    /// Any number.
+    Number,
+    /// A string.
+    String,
+    /// A date.
+    Date,
+    /// A date and time.
+    DateTime,
+    /// An array.
+    Array,
+    /// An object.
+    Object,
+}
+
+impl SchemaType {
+    fn matches(self, value: &dyn ValueView) -> bool {
+        match self {
+            SchemaType::Null => value.is_nil(),
+            SchemaType::Boolean => value.type_name() == "boolean",
+            SchemaType::Integer => value.type_name() == "whole number",
+            SchemaType::Number => matches!(
+                value.type_name(),
+                "whole number" | "fractional number" | "decimal number"
+            ),
+            SchemaType::String => value.type_name() == "string",
+            SchemaType::Date => value.type_name() == "date",
+            SchemaType::DateTime => value.type_name() == "date time",
+            SchemaType::Array => value.as_array().is_some(),
+            SchemaType::Object => value.as_object().is_some(),
+        }
+    }
+}
+
+impl fmt::Display for SchemaType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            SchemaType::Null => "nil",
+            SchemaType::Boolean => "boolean",
+            SchemaType::Integer => "whole number",
+            SchemaType::Number => "number",
+            SchemaType::String => "string",
+            SchemaType::Date => "date",
+            SchemaType::DateTime => "date time",
+            SchemaType::Array => "array",
+            SchemaType::Object => "object",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl Schema {
+    /// Accepts any value.
+    pub fn any() -> Self {
+        Self::default()
+    }
+
+    /// Accepts values of type `kind`.
+    pub fn new(kind: SchemaType) -> Self {
+        Self {
+            kind: Some(kind),
+            ..Default::default()
+        }
+    }
+
+    /// Accepts objects, with the properties added by [`Schema::property`].
+    pub fn object() -> Self {
+        Self::new(SchemaType::Object)
+    }
+
+    /// Accepts arrays whose items match `items`.
+    pub fn array(items: Schema) -> Self {
+        Self {
+            items: Some(Box::new(items)),
+            ..Self::new(SchemaType::Array)
+        }
+    }
+
+    /// Expects objects to have property `name`, matching `schema`.
+    pub fn property(self, name: impl Into<KString>, schema: Schema) -> Self {
+        self.with_property(name.into(), schema, true)
+    }
+
+    /// Expects property `name` to match `schema`, when objects have it.
+    pub fn optional_property(self, name: impl Into<KString>, schema: Schema) -> Self {
+        self.with_property(name.into(), schema, false)
+    }
+
+    /// Expects property `name` to hold a `T`, optional for `Option`s.
+    pub fn field<T: ToSchema + ?Sized>(self, name: impl Into<KString>) -> Self {
+        self.with_property(name.into(), T::schema(), T::required())
+    }
+
+    /// Also expects the properties of `other`, for flattened fields.
+    pub fn flatten(mut self, other: Schema) -> Self {
+        for (name, schema) in other.properties {
+            let required = other.required.contains(&name);
+            self = self.with_property(name, schema, required);
+        }
+        self
+    }
+
+    fn with_property(mut self, name: KString, schema: Schema, required: bool) -> Self {
+        self.required.retain(|r| *r != name);
+        if required {
+            self.required.push(name.clone());
+        }
+        self.properties.insert(name, schema);
+        self
+    }
+
+    /// The expected type, `None` for any.
+    pub fn kind(&self) -> Option<SchemaType> {
+        self.kind
+    }
+
+    /// The schema of property `name`, if it is expected.
+    pub fn get_property(&self, name: &str) -> Option<&Schema> {
+        self.properties.get(name)
+    }
+
+    /// Whether objects must have property `name`.
+    pub fn is_required(&self, name: &str) -> bool {
+        self.required.iter().any(|r| r == name)
+    }
+
+    /// The schema of array items, if they are checked.
+    pub fn get_items(&self) -> Option<&Schema> {
+        self.items.as_deref()
+    }
+
+    /// Records how `value`, found at `path`, doesn't match.
+    pub fn check(&self, value: &dyn ValueView, path: &str, violations: &mut Violations) {
+        if let Some(kind) = self.kind {
+            if !kind.matches(value) {
+                violations.mistyped(path, &kind.to_string(), value.type_name());
+                return;
+            }
+        }
+
+        if let Some(object) = value.as_object() {
+            for (name, schema) in &self.properties {
+                let property_path = join_path(path, name);
+                let required = self.is_required(name);
+                match object.get(name) {
+                    // Optional properties may also be `nil`, like a `None` field.
+                    Some(property) if !required && property.is_nil() => (),
+                    Some(property) => schema.check(property, &property_path, violations),
+                    None if required => violations.missing(property_path),
+                    None => (),
+                }
+            }
+        }
+
+        if let (Some(items), Some(array)) = (self.items.as_deref(), value.as_array()) {
+            for (index, item) in array.values().enumerate() {
+                items.check(item, &format!("{}[{}]", path, index), violations);
+            }
+        }
+    }
+}
+
+fn join_path(path: &str, name: &str) -> String {
+    if path.is_empty() {
+        name.to_owned()
+    } else {
+        format!("{}.{}", path, name)
+    }
+}
+
+/// Inputs that don't match what a template expects.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Violations {
+    missing: Vec<String>,
+    mistyped: Vec<String>,
+}
+
+impl Violations {
+    /// No violations, yet.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records that nothing was found at `path`.
+    pub fn missing(&mut self, path: impl Into<String>) {
+        let path = path.into();
+        if !self.missing.contains(&path) {
+            self.missing.push(path);
+        }
+    }
+
+    /// Records that the value at `path` is a `found` rather than an `expected`.
+    pub fn mistyped(&mut self, path: &str, expected: &str, found: &str) {
+        let mistyped = format!("{} (expected {}, found {})", path, expected, found);
+        if !self.mistyped.contains(&mistyped) {
+            self.mistyped.push(mistyped);
+        }
+    }
+
+    /// Whether everything matched.
+    pub fn is_empty(&self) -> bool {
+        self.missing.is_empty() && self.mistyped.is_empty()
+    }
+
+    /// Reports the violations as an error, if there are any.
+    pub fn into_result(self) -> Result<()> {
+        if self.is_empty() {
+            return Ok(());
+        }
+
+        let mut error = Error::with_msg("Invalid globals");
+        if !self.missing.is_empty() {
+            error = error.context("missing", self.missing.join(", "));
+        }
+        if !self.mistyped.is_empty() {
+            error = error.context("mistyped", self.mistyped.join(", "));
+        }
+        Err(error)
+    }
+}
+
+/// Rust types that know the `Schema` of their `ValueView`.
+///
+/// Derive it for structs with `#[derive(ToSchema)]`, which follows the `#[liquid(...)]`
+/// attributes of `ObjectView`.
+pub trait ToSchema {
+    /// The expected shape of values of this type.
+    fn schema() -> Schema;
+
+    /// Whether a property of this type must be present, `false` for `Option`s.
+    fn required() -> bool {
+        true
+    }
+}
+
+macro_rules! impl_to_schema {
+    ($kind:ident, $($ty:ty),+) => {
+        $(
+            impl ToSchema for $ty {
+                fn schema() -> Schema {
+                    Schema::new(SchemaType::$kind)
+                }
+            }
+        )+
+    };
+}
+
+impl_to_schema!(Boolean, bool);
+impl_to_schema!(Integer, u8, i8, u16, i16, u32, i32, i64);
+impl_to_schema!(Number, f32, f64);
+impl_to_schema!(
+    String,
+    str,
+    String,
+    KString,
+    KStringCow<'_>,
+    super::KStringRef<'_>
+);
+impl_to_schema!(Date, super::Date);
+impl_to_schema!(DateTime, super::DateTime);
+impl_to_schema!(Object, super::Object);
+#[cfg(feature = "decimal")]
+impl_to_schema!(Number, super::Decimal);
+#[cfg(feature = "bigint")]
+impl_to_schema!(Integer, super::BigInt);
+
+impl ToSchema for super::Value {
+    fn schema() -> Schema {
+        Schema::any()
+    }
+}
+
+impl<T: ToSchema> ToSchema for Option<T> {
+    fn schema() -> Schema {
+        T::schema()
+    }
+
+    fn required() -> bool {
+        false
+    }
+}
+
+impl<T: ToSchema> ToSchema for [T] {
+    fn schema() -> Schema {
+        Schema::array(T::schema())
+    }
+}
+
+impl<T: ToSchema> ToSchema for Vec<T> {
+    fn schema() -> Schema {
+        Schema::array(T::schema())
+    }
+}
+
+impl<T: ToSchema> ToSchema for std::collections::VecDeque<T> {
+    fn schema() -> Schema {
+        Schema::array(T::schema())
+    }
+}
+
+impl<K, V, S> ToSchema for std::collections::HashMap<K, V, S> {
+    fn schema() -> Schema {
+        Schema::object()
+    }
+}
+
+impl<K, V> ToSchema for std::collections::BTreeMap<K, V> {
+    fn schema() -> Schema {
+        Schema::object()
+    }
+}
+
+macro_rules! forward_to_schema {
+    ($($ty:ty),+) => {
+        $(
+            impl<T: ToSchema + ?Sized> ToSchema for $ty {
+                fn schema() -> Schema {
+                    T::schema()
+                }
+
+                fn required() -> bool {
+                    T::required()
+                }
+            }
+        )+
+    };
+}
+
+forward_to_schema!(&T, Box<T>, std::rc::Rc<T>, std::sync::Arc<T>);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::model::Value;
+
+    fn violations(schema: &Schema, value: &Value) -> Violations {
+        let mut violations = Violations::new();
+        schema.check(value, "", &mut violations);
+        violations
+    }
+
+    #[test]
+    fn test_check() {
+        let schema = Schema::object()
+            .property("name", Schema::new(SchemaType::String))
+            .optional_property("age", Schema::new(SchemaType::Integer))
+            .property("tags", Schema::array(Schema::new(SchemaType::String)));
+
+        let value = crate::value!({"name": "Ann", "age": nil, "tags": ["a", "b"]});
+        assert!(violations(&schema, &value).is_empty());
+
+        let value = crate::value!({"age": "ten", "tags": ["a", 1]});
+        let err = violations(&schema, &value).into_result().unwrap_err();
+        let err = err.to_string();
+        assert!(err.contains("missing=name"), "{}", err);
+        assert!(
+            err.contains("age (expected whole number, found string)"),
+            "{}",
+            err
+        );
+        assert!(
+            err.contains("tags[1] (expected string, found whole number)"),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_deserialize() {
+        let schema: Schema = serde_yaml::from_str(
+            r#"
+type: object
+properties:
+  price:
+    type: number
+  published:
+    type: date-time
+required: [price]
+"#,
+        )
+        .unwrap();
+        let expected = Schema::object()
+            .property("price", Schema::new(SchemaType::Number))
+            .optional_property("published", Schema::new(SchemaType::DateTime));
+        assert_eq!(schema, expected);
+    }
+
+    #[test]
+    fn test_to_schema() {
+        let schema = Schema::object()
+            .field::<Vec<i32>>("ids")
+            .field::<Option<String>>("nickname");
+        let expected = Schema::object()
+            .property("ids", Schema::array(Schema::new(SchemaType::Integer)))
+            .optional_property("nickname", Schema::new(SchemaType::String));
+        assert_eq!(schema, expected);
+    }
+}
@@ -17,6 +17,8 @@ pub enum ValueCow<'s> {
     Owned(Value),
     /// A borrowed `Value`
     Borrowed(&'s dyn ValueView),
+    /// An array of borrowed values, like the items a filter selected from a borrowed array
+    Array(Vec<ValueCow<'s>>),
 }
 
 impl<'s> ValueCow<'s> {
@@ -27,6 +29,7 @@ impl<'s> ValueCow<'s> {
         match self {
             ValueCow::Owned(x) => x,
             ValueCow::Borrowed(x) => x.to_value(),
+            ValueCow::Array(x) => x.to_value(),
         }
     }
 
@@ -35,6 +38,7 @@ impl<'s> ValueCow<'s> {
         match self {
             ValueCow::Owned(o) => o.as_view(),
             ValueCow::Borrowed(b) => *b,
+            ValueCow::Array(a) => a,
         }
     }
 }
@@ -146,6 +146,18 @@ impl serde::Serializer for ValueSerializer {
     where
         T: Serialize,
     {
+        #[cfg(feature = "decimal")]
+        if _name == crate::model::scalar::DECIMAL_TOKEN {
+            return ScalarSerializer
+                .serialize_newtype_struct(_name, value)
+                .map(Value::Scalar);
+        }
+        #[cfg(feature = "bigint")]
+        if _name == crate::model::scalar::BIGINT_TOKEN {
+            return ScalarSerializer
+                .serialize_newtype_struct(_name, value)
+                .map(Value::Scalar);
+        }
         value.serialize(ValueSerializer)
     }
 
@@ -685,6 +697,20 @@ mod test {
         difference::assert_diff!(&actual.trim(), "---\n.inf", "", 0);
     }
 
+    #[test]
+    #[cfg(feature = "decimal")]
+    pub fn serialize_decimal() {
+        let decimal = crate::model::Decimal::new(1990, 2);
+        let value = crate::model::Value::scalar(decimal);
+
+        let actual = serde_yaml::to_string(&value).unwrap();
+        difference::assert_diff!(&actual.trim(), "---\n\"19.90\"", "", 0);
+
+        let actual = crate::model::to_value(&value).unwrap();
+        let actual = actual.into_scalar().unwrap().as_decimal();
+        assert_eq!(actual, Some(decimal));
+    }
+
     #[test]
     pub fn deserialize_num() {
         let actual: crate::model::Value = serde_yaml::from_str("---\n1").unwrap();
@@ -792,7 +818,22 @@ mod test {
 
     #[test]
     pub fn serialize_object() {
-        // Skipping due to HashMap ordering issues
+        let actual: crate::model::Object = [
+            ("Num".into(), crate::model::Value::scalar(1f64)),
+            ("Bool".into(), crate::model::Value::scalar(true)),
+            ("Str".into(), crate::model::Value::scalar("true")),
+        ]
+        .iter()
+        .cloned()
+        .collect();
+        let actual = crate::model::Value::Object(actual);
+        let actual = serde_yaml::to_string(&actual).unwrap();
+        difference::assert_diff!(
+            &actual.trim(),
+            "---\nNum: 1.0\nBool: true\nStr: \"true\"",
+            "",
+            0
+        );
     }
 
     #[test]
@@ -809,5 +850,11 @@ mod test {
         .collect();
         let expected = crate::model::Value::Object(expected);
         assert_eq!(actual, expected);
+
+        let keys: Vec<_> = match &actual {
+            crate::model::Value::Object(object) => object.keys().map(|k| k.as_str()).collect(),
+            _ => unreachable!(),
+        };
+        assert_eq!(keys, ["Num", "Bool", "Str"]);
     }
 }
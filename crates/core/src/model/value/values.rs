@@ -34,6 +34,14 @@ impl Value {
         Value::Scalar(ScalarCow::new(value))
     }
 
+    /// Create from rendered template output, like a `capture`.
+    ///
+    /// Output is escaped as it's written when autoescaping is on, so it's marked
+    /// [safe](ScalarCow::safe) to not be escaped a second time when output again.
+    pub fn safe_output(output: String) -> Self {
+        Value::Scalar(ScalarCow::safe(output))
+    }
+
     /// Create as an `Array`.
     pub fn array<I: IntoIterator<Item = Value>>(iter: I) -> Value {
         let v: Array = iter.into_iter().collect();
@@ -348,6 +356,13 @@ mod test {
         assert_eq!(&Value::Nil.to_kstr(), "");
     }
 
+    #[test]
+    fn test_safe_output() {
+        let val = Value::safe_output("<b>".to_owned());
+        assert!(val.as_scalar().unwrap().is_safe());
+        assert_eq!(val, Value::scalar("<b>"));
+    }
+
     #[test]
     fn scalar_equality() {
         assert_eq!(Value::scalar("alpha"), Value::scalar("alpha"));
@@ -73,52 +73,62 @@ pub trait ValueView: fmt::Debug {
     }
 }
 
-impl<'v, V: ValueView + ?Sized> ValueView for &'v V {
-    fn as_debug(&self) -> &dyn fmt::Debug {
-        <V as ValueView>::as_debug(self)
-    }
+macro_rules! forward_value_view {
+    ([$($generics:tt)*] $ty:ty $(where $($bounds:tt)*)?) => {
+        impl<$($generics)*> ValueView for $ty $(where $($bounds)*)? {
+            fn as_debug(&self) -> &dyn fmt::Debug {
+                <V as ValueView>::as_debug(self)
+            }
 
-    fn render(&self) -> DisplayCow<'_> {
-        <V as ValueView>::render(self)
-    }
-    fn source(&self) -> DisplayCow<'_> {
-        <V as ValueView>::source(self)
-    }
-    fn type_name(&self) -> &'static str {
-        <V as ValueView>::type_name(self)
-    }
-    fn query_state(&self, state: State) -> bool {
-        <V as ValueView>::query_state(self, state)
-    }
+            fn render(&self) -> DisplayCow<'_> {
+                <V as ValueView>::render(self)
+            }
+            fn source(&self) -> DisplayCow<'_> {
+                <V as ValueView>::source(self)
+            }
+            fn type_name(&self) -> &'static str {
+                <V as ValueView>::type_name(self)
+            }
+            fn query_state(&self, state: State) -> bool {
+                <V as ValueView>::query_state(self, state)
+            }
 
-    fn to_kstr(&self) -> KStringCow<'_> {
-        <V as ValueView>::to_kstr(self)
-    }
-    fn to_value(&self) -> Value {
-        <V as ValueView>::to_value(self)
-    }
+            fn to_kstr(&self) -> KStringCow<'_> {
+                <V as ValueView>::to_kstr(self)
+            }
+            fn to_value(&self) -> Value {
+                <V as ValueView>::to_value(self)
+            }
 
-    fn as_scalar(&self) -> Option<ScalarCow<'_>> {
-        <V as ValueView>::as_scalar(self)
-    }
+            fn as_scalar(&self) -> Option<ScalarCow<'_>> {
+                <V as ValueView>::as_scalar(self)
+            }
 
-    fn as_array(&self) -> Option<&dyn ArrayView> {
-        <V as ValueView>::as_array(self)
-    }
+            fn as_array(&self) -> Option<&dyn ArrayView> {
+                <V as ValueView>::as_array(self)
+            }
 
-    fn as_object(&self) -> Option<&dyn ObjectView> {
-        <V as ValueView>::as_object(self)
-    }
+            fn as_object(&self) -> Option<&dyn ObjectView> {
+                <V as ValueView>::as_object(self)
+            }
 
-    fn as_state(&self) -> Option<State> {
-        <V as ValueView>::as_state(self)
-    }
+            fn as_state(&self) -> Option<State> {
+                <V as ValueView>::as_state(self)
+            }
 
-    fn is_nil(&self) -> bool {
-        <V as ValueView>::is_nil(self)
-    }
+            fn is_nil(&self) -> bool {
+                <V as ValueView>::is_nil(self)
+            }
+        }
+    };
 }
 
+forward_value_view!(['v, V: ValueView + ?Sized] &'v V);
+forward_value_view!([V: ValueView + ?Sized] Box<V>);
+forward_value_view!([V: ValueView + ?Sized] std::rc::Rc<V>);
+forward_value_view!([V: ValueView + ?Sized] std::sync::Arc<V>);
+forward_value_view!(['v, V: ValueView + ToOwned + ?Sized] std::borrow::Cow<'v, V> where V::Owned: fmt::Debug);
+
 static NIL: Value = Value::Nil;
 
 impl<T: ValueView> ValueView for Option<T> {
@@ -214,6 +224,20 @@ impl<'v> PartialEq<crate::model::scalar::DateTime> for ValueViewCmp<'v> {
     }
 }
 
+#[cfg(feature = "decimal")]
+impl<'v> PartialEq<crate::model::scalar::Decimal> for ValueViewCmp<'v> {
+    fn eq(&self, other: &crate::model::scalar::Decimal) -> bool {
+        super::value_eq(self.0, other)
+    }
+}
+
+#[cfg(feature = "bigint")]
+impl<'v> PartialEq<crate::model::scalar::BigInt> for ValueViewCmp<'v> {
+    fn eq(&self, other: &crate::model::scalar::BigInt) -> bool {
+        super::value_eq(self.0, other)
+    }
+}
+
 impl<'v> PartialEq<crate::model::scalar::Date> for ValueViewCmp<'v> {
     fn eq(&self, other: &crate::model::scalar::Date) -> bool {
         super::value_eq(self.0, other)
@@ -275,9 +299,10 @@ pub(crate) fn value_eq(lhs: &dyn ValueView, rhs: &dyn ValueView) -> bool {
         if x.size() != y.size() {
             return false;
         }
-        return x
-            .iter()
-            .all(|(key, value)| y.get(key.as_str()).map_or(false, |v| value_eq(v, value)));
+        return x.iter().all(|(key, value)| {
+            y.get_cow(key.as_str())
+                .map_or(false, |v| value_eq(v.as_view(), value))
+        });
     }
 
     if lhs.is_nil() && rhs.is_nil() {
@@ -348,4 +373,19 @@ mod test {
         let debug: &dyn fmt::Debug = view.as_debug();
         println!("{:?}", debug);
     }
+
+    #[test]
+    fn test_smart_pointers() {
+        let boxed: Box<dyn ValueView> = Box::new(5);
+        assert_eq!(ValueViewCmp::new(&boxed), 5);
+
+        let shared = std::sync::Arc::new(vec![1, 2]);
+        assert_eq!(shared.as_array().unwrap().size(), 2);
+
+        let object = crate::model::Object::new();
+        let cow = std::borrow::Cow::Borrowed(&object);
+        assert_eq!(cow.type_name(), "object");
+        let cow: std::borrow::Cow<'_, crate::model::Object> = std::borrow::Cow::Owned(object);
+        assert!(cow.as_object().unwrap().query_state(State::Empty));
+    }
 }
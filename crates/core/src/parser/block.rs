@@ -8,6 +8,9 @@ use super::TagTokenIter;
 pub trait BlockReflection {
     fn start_tag(&self) -> &str;
 
+    /// The tag closing the block.
+    ///
+    /// When empty, the block runs to the end of the template, like `{% extends %}`.
     fn end_tag(&self) -> &str;
 
     fn description(&self) -> &str;
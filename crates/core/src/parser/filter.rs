@@ -1,7 +1,7 @@
 use std::fmt::{Debug, Display};
 
 use crate::error::Result;
-use crate::model::{Value, ValueView};
+use crate::model::{Value, ValueCow, ValueView};
 use crate::runtime::{Expression, Runtime};
 
 /// A structure that holds the information of a single parameter in a filter.
@@ -42,6 +42,14 @@ pub trait FilterReflection {
 
     fn positional_parameters(&self) -> &'static [ParameterReflection];
     fn keyword_parameters(&self) -> &'static [ParameterReflection];
+
+    /// Whether the filter's output only depends on its input and arguments, so filtering
+    /// literals can be done once, when parsing.
+    ///
+    /// Defaults to `false`.
+    fn is_pure(&self) -> bool {
+        false
+    }
 }
 
 /// A trait that declares and holds the parameters of a filter.
@@ -100,6 +108,25 @@ pub struct FilterArguments<'a> {
 /// }
 /// ```
 ///
+/// Filter selecting part of its input, borrowing it when possible:
+/// ```ignore
+/// #[derive(Debug, Default, Display_filter)]
+/// #[name = "first"]
+/// #[filter(borrows)] // Implements `Filter` with the inherent `evaluate_cow`.
+/// struct FirstFilter;
+///
+/// impl FirstFilter {
+///     fn evaluate_cow<'v>(
+///         &'v self,
+///         input: &'v dyn ValueView,
+///         _runtime: &'v dyn Runtime,
+///     ) -> Result<ValueCow<'v>> {
+///         // Implementation of the filter here, returning `ValueCow::Borrowed` for parts
+///         // of `input`
+///     }
+/// }
+/// ```
+///
 /// Filter for filter with arguments:
 /// ```ignore
 /// #[derive(Debug, FromFilterParameters, Display_filter)]
@@ -143,6 +170,18 @@ pub struct FilterArguments<'a> {
 pub trait Filter: Send + Sync + Debug + Display {
     // This will evaluate the expressions and evaluate the filter.
     fn evaluate(&self, input: &dyn ValueView, runtime: &dyn Runtime) -> Result<Value>;
+
+    /// Like `evaluate`, but the result may borrow from `input`, the evaluated arguments or
+    /// the runtime, so selecting part of the input doesn't clone it.
+    ///
+    /// Defaults to the result of `evaluate`.
+    fn evaluate_cow<'v>(
+        &'v self,
+        input: &'v dyn ValueView,
+        runtime: &'v dyn Runtime,
+    ) -> Result<ValueCow<'v>> {
+        self.evaluate(input, runtime).map(ValueCow::Owned)
+    }
 }
 
 /// A trait to register a new filter in the `liquid::Parser`.
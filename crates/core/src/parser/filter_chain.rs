@@ -2,23 +2,58 @@ use std::fmt;
 use std::io::Write;
 
 use super::Filter;
-use crate::error::{Result, ResultLiquidExt, ResultLiquidReplaceExt};
+use crate::error::{Result, ResultLiquidExt};
 use crate::model::{ValueCow, ValueView};
+use crate::runtime::write_output;
 use crate::runtime::Expression;
+use crate::runtime::GlobalReferences;
+use crate::runtime::Program;
 use crate::runtime::Renderable;
 use crate::runtime::Runtime;
+use crate::runtime::RuntimeBuilder;
 
 /// A `Value` expression.
 #[derive(Debug)]
 pub struct FilterChain {
     entry: Expression,
     filters: Vec<Box<dyn Filter>>,
+    arguments: Vec<Expression>,
 }
 
 impl FilterChain {
     /// Create a new expression.
     pub fn new(entry: Expression, filters: Vec<Box<dyn Filter>>) -> Self {
-        Self { entry, filters }
+        Self {
+            entry,
+            filters,
+            arguments: Vec::new(),
+        }
+    }
+
+    /// Remembers the filters' argument expressions, for `collect_globals`.
+    pub(crate) fn with_arguments(mut self, arguments: Vec<Expression>) -> Self {
+        self.arguments = arguments;
+        self
+    }
+
+    /// Evaluates the chain when parsing, if it only filters literals, to render the result.
+    ///
+    /// Only for chains of pure filters, see `FilterReflection::is_pure`.  Chains failing to
+    /// evaluate are left as is, to fail when rendering.
+    pub(crate) fn fold(self) -> Self {
+        if self.filters.is_empty()
+            || !self.entry.is_literal()
+            || !self.arguments.iter().all(Expression::is_literal)
+        {
+            return self;
+        }
+
+        let runtime = RuntimeBuilder::new().build();
+        let value = match self.evaluate(&runtime) {
+            Ok(value) => value.into_owned(),
+            Err(_) => return self,
+        };
+        Self::new(Expression::Literal(value), Vec::new())
     }
 
     /// Process `Value` expression within `runtime`'s stack.
@@ -28,21 +63,34 @@ impl FilterChain {
 
         // apply all specified filters
         for filter in &self.filters {
-            entry = ValueCow::Owned(
-                filter
-                    .evaluate(entry.as_view(), runtime)
-                    .trace("Filter error")
-                    .context_key("filter")
-                    .value_with(|| format!("{}", filter).into())
-                    .context_key("input")
-                    .value_with(|| format!("{}", entry.source()).into())?,
-            );
+            entry = apply_filter(filter.as_ref(), entry, runtime)?;
         }
 
         Ok(entry)
     }
 }
 
+/// Applies `filter` to `input`, as part of a chain.
+pub(crate) fn apply_filter<'s>(
+    filter: &'s dyn Filter,
+    input: ValueCow<'s>,
+    runtime: &'s dyn Runtime,
+) -> Result<ValueCow<'s>> {
+    // Results can only borrow from inputs that outlive the chain.
+    let result = match input {
+        ValueCow::Borrowed(input) => filter.evaluate_cow(input, runtime),
+        ref input => filter
+            .evaluate_cow(input.as_view(), runtime)
+            .map(|result| ValueCow::Owned(result.into_owned())),
+    };
+    result
+        .trace("Filter error")
+        .context_key("filter")
+        .value_with(|| format!("{}", filter).into())
+        .context_key("input")
+        .value_with(|| format!("{}", input.source()).into())
+}
+
 impl fmt::Display for FilterChain {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(
@@ -57,7 +105,22 @@ impl fmt::Display for FilterChain {
 impl Renderable for FilterChain {
     fn render_to(&self, writer: &mut dyn Write, runtime: &dyn Runtime) -> Result<()> {
         let entry = self.evaluate(runtime)?;
-        write!(writer, "{}", entry.render()).replace("Failed to render")?;
-        Ok(())
+        write_output(writer, entry.as_view(), runtime)
+    }
+
+    fn lower<'t>(&'t self, program: &mut Program<'t>) -> bool {
+        program.evaluate(&self.entry);
+        for filter in &self.filters {
+            program.filter(filter.as_ref());
+        }
+        program.output();
+        true
+    }
+
+    fn collect_globals(&self, globals: &mut GlobalReferences<'_>) {
+        globals.read(&self.entry);
+        for argument in &self.arguments {
+            globals.read(argument);
+        }
     }
 }
@@ -0,0 +1,115 @@
+use crate::error::{Error, Result};
+use crate::model::Object;
+use crate::runtime::Renderable;
+
+use super::{parse, Language};
+
+/// Parses a template that may start with YAML front matter, like Jekyll pages do:
+///
+/// ```text
+/// ---
+/// title: Home
+/// ---
+/// <h1>{{ page.title }}</h1>
+/// ```
+///
+/// Returns the front matter, empty if there is none, and the rest of the template.
+pub fn parse_document(
+    text: &str,
+    options: &Language,
+) -> Result<(Object, Vec<Box<dyn Renderable>>)> {
+    let (front_matter, body) = match split_front_matter(text) {
+        Some((front_matter, body)) => (parse_front_matter(front_matter)?, body),
+        None => (Object::new(), text),
+    };
+    let elements = parse(body, options)?;
+    Ok((front_matter, elements))
+}
+
+/// Splits `---` delimited front matter from the rest of `text`, if any.
+///
+/// The front matter may also end with `...`.
+fn split_front_matter(text: &str) -> Option<(&str, &str)> {
+    let rest = text.strip_prefix("---")?;
+    let rest = rest.trim_start_matches([' ', '\t']);
+    let rest = rest
+        .strip_prefix("\r\n")
+        .or_else(|| rest.strip_prefix('\n'))?;
+
+    let mut start = 0;
+    for line in rest.split_inclusive('\n') {
+        let end = start + line.len();
+        let delimiter = line.trim_end();
+        if delimiter == "---" || delimiter == "..." {
+            return Some((&rest[..start], &rest[end..]));
+        }
+        start = end;
+    }
+    None
+}
+
+fn parse_front_matter(front_matter: &str) -> Result<Object> {
+    if front_matter.trim().is_empty() {
+        return Ok(Object::new());
+    }
+    serde_yaml::from_str(front_matter).map_err(|err| {
+        Error::with_msg("Invalid front matter")
+            .context("expected", "a YAML mapping")
+            .context("cause", err.to_string())
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::runtime::{RuntimeBuilder, Template};
+
+    #[test]
+    fn split() {
+        assert_eq!(
+            split_front_matter("---\ntitle: Home\n---\nbody"),
+            Some(("title: Home\n", "body"))
+        );
+        assert_eq!(
+            split_front_matter("--- \r\na: 1\r\nb: 2\r\n...\r\n\r\nbody"),
+            Some(("a: 1\r\nb: 2\r\n", "\r\nbody"))
+        );
+        assert_eq!(split_front_matter("---\n---"), Some(("", "")));
+        assert_eq!(split_front_matter("body\n---\n---\n"), None);
+        assert_eq!(split_front_matter("---\nunclosed"), None);
+        assert_eq!(split_front_matter("----\n---\n"), None);
+    }
+
+    #[test]
+    fn document() {
+        let (front_matter, elements) = parse_document(
+            "---\ntitle: Home\ntags: [a, b]\n---\n{{ 'body' }}",
+            &Language::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            front_matter,
+            crate::object!({"title": "Home", "tags": ["a", "b"]})
+        );
+        let runtime = RuntimeBuilder::new().build();
+        assert_eq!(Template::new(elements).render(&runtime).unwrap(), "body");
+    }
+
+    #[test]
+    fn document_without_front_matter() {
+        let (front_matter, elements) = parse_document("---- text", &Language::default()).unwrap();
+        assert!(front_matter.is_empty());
+        let runtime = RuntimeBuilder::new().build();
+        assert_eq!(
+            Template::new(elements).render(&runtime).unwrap(),
+            "---- text"
+        );
+    }
+
+    #[test]
+    fn invalid_front_matter() {
+        parse_document("---\n- a list\n---\n", &Language::default()).unwrap_err();
+        parse_document("---\ntitle: [\n---\n", &Language::default()).unwrap_err();
+    }
+}
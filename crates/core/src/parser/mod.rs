@@ -1,6 +1,8 @@
 mod block;
 mod filter;
 mod filter_chain;
+#[cfg(feature = "front-matter")]
+mod front_matter;
 mod lang;
 mod parser;
 mod registry;
@@ -10,9 +12,11 @@ mod text;
 pub use block::*;
 pub use filter::*;
 pub use filter_chain::*;
+#[cfg(feature = "front-matter")]
+pub use front_matter::*;
 pub use lang::*;
 pub use parser::*;
 pub use registry::*;
 pub use tag::*;
 
-use text::Text;
+pub(crate) use text::Text;
@@ -100,18 +100,8 @@ fn parse_literal(literal: Pair) -> Value {
 
             Value::scalar(trim_quotes.to_owned())
         }
-        Rule::IntegerLiteral => Value::scalar(
-            literal
-                .as_str()
-                .parse::<i64>()
-                .expect("Grammar ensures matches are parseable as integers."),
-        ),
-        Rule::FloatLiteral => Value::scalar(
-            literal
-                .as_str()
-                .parse::<f64>()
-                .expect("Grammar ensures matches are parseable as floats."),
-        ),
+        Rule::IntegerLiteral => parse_integer_literal(literal.as_str()),
+        Rule::FloatLiteral => parse_float_literal(literal.as_str()),
         Rule::BooleanLiteral => Value::scalar(
             literal
                 .as_str()
@@ -122,6 +112,38 @@ fn parse_literal(literal: Pair) -> Value {
     }
 }
 
+/// Integer literals too large for an `i64` become big integers with the `bigint` feature, or floats.
+fn parse_integer_literal(literal: &str) -> Value {
+    if let Ok(integer) = literal.parse::<i64>() {
+        return Value::scalar(integer);
+    }
+
+    #[cfg(feature = "bigint")]
+    if let Ok(integer) = literal.parse::<crate::model::BigInt>() {
+        return Value::scalar(integer);
+    }
+
+    Value::scalar(
+        literal
+            .parse::<f64>()
+            .expect("Grammar ensures matches are parseable as integers."),
+    )
+}
+
+/// Decimal literals are exact when the `decimal` feature is on, unless they have too many digits.
+fn parse_float_literal(literal: &str) -> Value {
+    #[cfg(feature = "decimal")]
+    if let Ok(decimal) = literal.parse::<crate::model::Decimal>() {
+        return Value::scalar(decimal);
+    }
+
+    Value::scalar(
+        literal
+            .parse::<f64>()
+            .expect("Grammar ensures matches are parseable as floats."),
+    )
+}
+
 /// Parses a `Variable` from a `Pair` with a variable.
 /// This `Pair` must be `Rule::Variable`.
 fn parse_variable(variable: Pair) -> Variable {
@@ -170,7 +192,14 @@ fn parse_value(value: Pair) -> Expression {
 
 /// Parses a `FilterCall` from a `Pair` with a filter.
 /// This `Pair` must be `Rule::Filter`.
-fn parse_filter(filter: Pair, options: &Language) -> Result<Box<dyn Filter>> {
+///
+/// The argument expressions are also added to `arguments`.
+fn parse_filter(
+    filter: Pair,
+    options: &Language,
+    arguments: &mut Vec<Expression>,
+    pure: &mut bool,
+) -> Result<Box<dyn Filter>> {
     if filter.as_rule() != Rule::Filter {
         panic!("Expected a filter.");
     }
@@ -187,6 +216,7 @@ fn parse_filter(filter: Pair, options: &Language) -> Result<Box<dyn Filter>> {
             Rule::PositionalFilterArgument => {
                 let value = arg.into_inner().next().expect("Rule ensures value.");
                 let value = parse_value(value);
+                arguments.push(value.clone());
                 positional_args.push(value);
             }
             Rule::KeywordFilterArgument => {
@@ -194,6 +224,7 @@ fn parse_filter(filter: Pair, options: &Language) -> Result<Box<dyn Filter>> {
                 let key = arg.next().expect("Rule ensures identifier.").as_str();
                 let value = arg.next().expect("Rule ensures value.");
                 let value = parse_value(value);
+                arguments.push(value.clone());
                 keyword_args.push((key, value));
             }
             _ => unreachable!(),
@@ -214,6 +245,7 @@ fn parse_filter(filter: Pair, options: &Language) -> Result<Box<dyn Filter>> {
             .context("available filters", available)
     })?;
 
+    *pure &= f.reflection().is_pure();
     let f = f
         .parse(args)
         .trace("Filter parsing error")
@@ -236,11 +268,19 @@ fn parse_filter_chain(chain: Pair, options: &Language) -> Result<FilterChain> {
             .next()
             .expect("A filterchain always has starts by a value."),
     );
-    let filters: Result<Vec<_>> = chain.map(|f| parse_filter(f, options)).collect();
+    let mut arguments = Vec::new();
+    let mut pure = true;
+    let filters: Result<Vec<_>> = chain
+        .map(|f| parse_filter(f, options, &mut arguments, &mut pure))
+        .collect();
     let filters = filters?;
 
-    let filters = FilterChain::new(entry, filters);
-    Ok(filters)
+    let filters = FilterChain::new(entry, filters).with_arguments(arguments);
+    if pure {
+        Ok(filters.fold())
+    } else {
+        Ok(filters)
+    }
 }
 
 /// An interface to access elements inside a block.
@@ -268,14 +308,29 @@ impl<'a, 'b> TagBlock<'a, 'b> {
     /// Returns the next element of the block, if any, similarly to an iterator.
     ///
     /// However, if the input text reaches its end and the block is not closed,
-    /// an error is returned instead.
+    /// an error is returned instead, unless the block has no end tag.
     #[allow(clippy::should_implement_trait)]
     pub fn next(&mut self) -> Result<Option<BlockElement<'a>>> {
         if self.closed {
             return Ok(None);
         }
 
-        let element = self.iter.next().expect("File shouldn't end before EOI.");
+        // A nested block without an end tag may have run to the end of the text.
+        let element = match self.iter.next() {
+            Some(element) if element.as_rule() != Rule::EOI => element,
+            _ if self.end_tag.is_empty() => {
+                self.closed = true;
+                return Ok(None);
+            }
+            Some(element) => element,
+            None => {
+                return Error::with_msg(format!(
+                    "Unclosed block. {{% {} %}} tag expected.",
+                    self.end_tag
+                ))
+                .into_err()
+            }
+        };
 
         if element.as_rule() == Rule::EOI {
             return error_from_pair(
@@ -1098,6 +1153,33 @@ mod test {
             .unwrap();
         assert_eq!(parse_literal(negative_float), Value::scalar(-4321.032));
 
+        #[cfg(feature = "decimal")]
+        {
+            let decimal = LiquidParser::parse(Rule::Literal, "19.90")
+                .unwrap()
+                .next()
+                .unwrap();
+            assert_eq!(
+                parse_literal(decimal),
+                Value::scalar(crate::model::Decimal::new(1990, 2))
+            );
+        }
+
+        let huge_integer = LiquidParser::parse(Rule::Literal, "9223372036854775808")
+            .unwrap()
+            .next()
+            .unwrap();
+        #[cfg(feature = "bigint")]
+        assert_eq!(
+            parse_literal(huge_integer),
+            Value::scalar(crate::model::BigInt::from(i64::MAX) + 1)
+        );
+        #[cfg(not(feature = "bigint"))]
+        assert_eq!(
+            parse_literal(huge_integer),
+            Value::scalar(9223372036854775808.0)
+        );
+
         let boolean = LiquidParser::parse(Rule::Literal, "true")
             .unwrap()
             .next()
@@ -1172,9 +1254,40 @@ mod test {
         assert_eq!(output, "5");
     }
 
+    #[test]
+    fn test_merge_static_text() {
+        let runtime = RuntimeBuilder::new().build();
+        runtime.set_global("exp".into(), Value::scalar(5));
+
+        let template = Template::new(vec![
+            Box::new(Text::new("a")),
+            Box::new(Text::new("")),
+            Box::new(Text::new("b")),
+            Box::new(FilterChain::new(
+                Expression::Variable(Variable::with_literal("exp")),
+                Vec::new(),
+            )),
+            Box::new(Text::new("")),
+        ]);
+        assert_eq!(template.elements().len(), 2);
+        assert_eq!(template.render(&runtime).unwrap(), "ab5");
+
+        let template = Template::new(vec![Box::new(Text::new(""))]);
+        assert!(template.elements().is_empty());
+        assert_eq!(template.static_output(), Some(""));
+    }
+
     /// Macro implementation of custom block test.
     macro_rules! test_custom_block_tags_impl {
-        ($start_tag:expr, $end_tag:expr) => {{
+        ($start_tag:expr, $end_tag:expr) => {
+            test_custom_block_tags_impl!(
+                $start_tag,
+                $end_tag,
+                concat!("{% ", $start_tag, " %}Hello Liquid!{% ", $end_tag, " %}"),
+                "<pre>Hello Liquid!</pre>"
+            )
+        };
+        ($start_tag:expr, $end_tag:expr, $text:expr, $expected:expr) => {{
             use crate::error::ResultLiquidReplaceExt;
             use crate::{BlockReflection, ParseBlock};
             use std::io::Write;
@@ -1236,11 +1349,10 @@ mod test {
 
             let runtime = RuntimeBuilder::new().build();
 
-            let text = concat!("{% ", $start_tag, " %}Hello Liquid!{% ", $end_tag, " %}");
-            let template = parse(text, &options).map(Template::new).unwrap();
+            let template = parse($text, &options).map(Template::new).unwrap();
             let output = template.render(&runtime).unwrap();
 
-            assert_eq!(output, "<pre>Hello Liquid!</pre>");
+            assert_eq!(output, $expected);
         }};
     }
 
@@ -1253,4 +1365,21 @@ mod test {
         // Test that tags not of the form `<name>`-`end<name>` also work.
         test_custom_block_tags_impl!("startcustom", "stopcustom");
     }
+
+    /// Test blocks running to the end of the template.
+    #[test]
+    fn test_custom_block_tags_without_end_tag() {
+        test_custom_block_tags_impl!(
+            "custom",
+            "",
+            "a{% custom %}Hello {{ 'Liquid' }}!",
+            "a<pre>Hello Liquid!</pre>"
+        );
+        test_custom_block_tags_impl!(
+            "custom",
+            "",
+            "{% custom %}a{% custom %}b",
+            "<pre>a<pre>b</pre></pre>"
+        );
+    }
 }
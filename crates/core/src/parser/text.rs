@@ -1,6 +1,7 @@
 use std::io::Write;
 
 use crate::error::{Result, ResultLiquidReplaceExt};
+use crate::runtime::Program;
 use crate::runtime::Renderable;
 use crate::runtime::Runtime;
 
@@ -22,4 +23,13 @@ impl Renderable for Text {
         write!(writer, "{}", &self.text).replace("Failed to render")?;
         Ok(())
     }
+
+    fn static_output(&self) -> Option<&str> {
+        Some(&self.text)
+    }
+
+    fn lower<'t>(&'t self, program: &mut Program<'t>) -> bool {
+        program.text(&self.text);
+        true
+    }
 }
@@ -0,0 +1,68 @@
+use std::borrow;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync;
+
+use futures_core::future::BoxFuture;
+
+use crate::error::Result;
+use crate::model::KString;
+use crate::parser;
+use crate::parser::Language;
+use crate::runtime;
+use crate::runtime::GlobalReferences;
+use crate::runtime::PartialStore;
+use crate::runtime::Renderable;
+
+use super::EagerStore;
+
+/// Partial-template source repository that needs IO to load them, like a database.
+pub trait AsyncPartialSource: fmt::Debug + Send + Sync {
+    /// Access a partial-template, `None` if there is no such partial.
+    fn try_get<'a>(&'a self, name: &'a str) -> BoxFuture<'a, Result<Option<borrow::Cow<'a, str>>>>;
+}
+
+/// Loads and compiles the partials `template` includes by name from `source`, and those they
+/// include in turn, so it can be rendered without blocking on IO.
+///
+/// Partials included by a name only known while rendering, like `{% include page.sidebar %}`,
+/// can't be loaded ahead of time and are left out.  So are partials `source` doesn't have, and
+/// partials that fail to parse are kept as errors, both reported when rendering.
+pub async fn load_partials(
+    source: &dyn AsyncPartialSource,
+    template: &dyn Renderable,
+    language: &Language,
+) -> Result<Box<dyn PartialStore + Send + Sync>> {
+    let mut store = HashMap::new();
+    let mut requested: Vec<KString> = Vec::new();
+    let mut pending = included(template);
+    while let Some(name) = pending.pop() {
+        if requested.contains(&name) {
+            continue;
+        }
+        requested.push(name.clone());
+
+        let text = match source.try_get(name.as_str()).await {
+            Ok(Some(text)) => text,
+            Ok(None) => continue,
+            Err(err) => return Err(err.context("requested partial", name.to_string())),
+        };
+        let partial = parser::parse(&text, language).map(|partial| {
+            let partial = runtime::Template::new(partial);
+            pending.extend(included(&partial));
+            let partial: sync::Arc<dyn Renderable> = sync::Arc::new(partial);
+            partial
+        });
+        store.insert(name.to_string(), partial);
+    }
+    Ok(Box::new(EagerStore { store }))
+}
+
+fn included(template: &dyn Renderable) -> Vec<KString> {
+    let mut globals = GlobalReferences::new();
+    template.collect_globals(&mut globals);
+    let mut included = globals.included().to_vec();
+    // Load in the order they're included.
+    included.reverse();
+    included
+}
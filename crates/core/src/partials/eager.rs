@@ -107,8 +107,8 @@ where
     }
 }
 
-struct EagerStore {
-    store: HashMap<String, Result<sync::Arc<dyn runtime::Renderable>>>,
+pub(super) struct EagerStore {
+    pub(super) store: HashMap<String, Result<sync::Arc<dyn runtime::Renderable>>>,
 }
 
 impl PartialStore for EagerStore {
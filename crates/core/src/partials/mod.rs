@@ -7,11 +7,15 @@ use crate::error::Result;
 use crate::parser::Language;
 use crate::runtime::PartialStore;
 
+#[cfg(feature = "async")]
+mod async_source;
 mod eager;
 mod inmemory;
 mod lazy;
 mod ondemand;
 
+#[cfg(feature = "async")]
+pub use self::async_source::*;
 pub use self::eager::*;
 pub use self::inmemory::*;
 pub use self::lazy::*;
@@ -0,0 +1,121 @@
+use std::fmt;
+
+use futures_core::future::BoxFuture;
+
+use crate::error::Result;
+use crate::model::{KString, Object, Value};
+
+/// A global that needs IO to load, like the result of a database query.
+pub trait AsyncValue: fmt::Debug + Send + Sync {
+    /// Loads the value.
+    fn load(&self) -> BoxFuture<'_, Result<Value>>;
+}
+
+/// Globals, some of them loaded asynchronously.
+///
+/// Only the globals a template reads are loaded, see [`AsyncGlobals::load`].
+#[derive(Debug, Default)]
+pub struct AsyncGlobals {
+    values: Object,
+    pending: Vec<(KString, Box<dyn AsyncValue>)>,
+}
+
+impl AsyncGlobals {
+    /// Globals, with none to load.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets global `name` to `value`.
+    pub fn insert(&mut self, name: impl Into<KString>, value: Value) {
+        let name = name.into();
+        self.pending.retain(|(pending, _)| *pending != name);
+        self.values.insert(name, value);
+    }
+
+    /// Sets global `name` to what `value` loads.
+    pub fn insert_async<V: AsyncValue + 'static>(&mut self, name: impl Into<KString>, value: V) {
+        let name = name.into();
+        self.values.remove(&name);
+        self.pending.retain(|(pending, _)| *pending != name);
+        self.pending.push((name, Box::new(value)));
+    }
+
+    /// The globals that don't need loading.
+    pub fn values(&self) -> &Object {
+        &self.values
+    }
+
+    /// Loads the globals named by `names` that need it, one after the other.
+    ///
+    /// Render with the loaded globals on top of [`AsyncGlobals::values`], like with a
+    /// [`StackFrame`](super::StackFrame).
+    pub async fn load<'n, I>(&self, names: I) -> Result<Object>
+    where
+        I: IntoIterator<Item = &'n str>,
+    {
+        let mut loaded = Object::new();
+        for name in names {
+            if loaded.contains_key(name) {
+                continue;
+            }
+            if let Some((name, value)) = self.pending.iter().find(|(pending, _)| pending == name) {
+                let value = value
+                    .load()
+                    .await
+                    .map_err(|err| err.context("global", name.clone()))?;
+                loaded.insert(name.clone(), value);
+            }
+        }
+        Ok(loaded)
+    }
+}
+
+impl From<Object> for AsyncGlobals {
+    fn from(values: Object) -> Self {
+        Self {
+            values,
+            pending: Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::model::ValueView;
+
+    #[derive(Debug)]
+    struct Load(&'static str);
+
+    impl AsyncValue for Load {
+        fn load(&self) -> BoxFuture<'_, Result<Value>> {
+            Box::pin(async move { Ok(Value::scalar(self.0)) })
+        }
+    }
+
+    #[derive(Debug)]
+    struct Fail;
+
+    impl AsyncValue for Fail {
+        fn load(&self) -> BoxFuture<'_, Result<Value>> {
+            Box::pin(async { Err(crate::Error::with_msg("Connection refused")) })
+        }
+    }
+
+    #[test]
+    fn load_read_globals() {
+        let mut globals = AsyncGlobals::from(crate::object!({"title": "Home"}));
+        globals.insert_async("user", Load("Jane"));
+        globals.insert_async("orders", Fail);
+        let loaded = futures_executor::block_on(globals.load(vec!["title", "user"])).unwrap();
+        assert_eq!(globals.values().get("title").unwrap().to_kstr(), "Home");
+        assert!(loaded.get("title").is_none());
+        assert_eq!(loaded.get("user").unwrap().to_kstr(), "Jane");
+        assert!(loaded.get("orders").is_none());
+
+        let err = futures_executor::block_on(globals.load(vec!["orders"])).unwrap_err();
+        assert!(err.to_string().contains("Connection refused"), "{}", err);
+    }
+}
@@ -0,0 +1,363 @@
+use std::borrow::Cow;
+use std::fmt::{self, Write as _};
+use std::io::Write;
+use std::sync::Arc;
+
+use crate::error::{Error, Result, ResultLiquidReplaceExt};
+use crate::model::ValueView;
+
+use super::Runtime;
+
+/// Post-processes every `{{ }}` output value before it is written, like escaping it for the
+/// format of the template.
+///
+/// Strings marked with [`ScalarCow::safe`](crate::model::ScalarCow::safe) are written as is.
+pub trait Escaper: fmt::Debug + Send + Sync {
+    /// Transforms rendered output, borrowing it when there's nothing to change.
+    fn escape<'s>(&self, text: &'s str) -> Cow<'s, str>;
+
+    /// The built-in strategy this escapes like, if any.
+    ///
+    /// Filters only mark strings they escaped with the same strategy as safe.
+    fn strategy(&self) -> Option<Escape> {
+        None
+    }
+}
+
+/// The built-in escapers, for the context output is written into.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Escape {
+    /// HTML text, e.g. `<p>{{ x }}</p>`.
+    Html,
+    /// HTML attribute values, including unquoted ones.
+    Attribute,
+    /// JavaScript string literals, e.g. `var x = '{{ x }}';`.
+    JavaScript,
+    /// CSS strings and identifiers.
+    Css,
+    /// A URL component, e.g. a query parameter.
+    Url,
+    /// The contents of a JSON string, e.g. `"{{ x }}"`.
+    Json,
+    /// A CSV field, quoted when needed.
+    Csv,
+    /// A shell word, single-quoted when needed.
+    Shell,
+}
+
+impl Escape {
+    /// The strategy's name, as accepted by [`Escape::from_name`].
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Html => "html",
+            Self::Attribute => "attribute",
+            Self::JavaScript => "js",
+            Self::Css => "css",
+            Self::Url => "url",
+            Self::Json => "json",
+            Self::Csv => "csv",
+            Self::Shell => "shell",
+        }
+    }
+
+    /// Looks up a strategy by name, e.g. for a filter argument.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "html" => Some(Self::Html),
+            "attribute" | "attr" => Some(Self::Attribute),
+            "js" | "javascript" => Some(Self::JavaScript),
+            "css" => Some(Self::Css),
+            "url" => Some(Self::Url),
+            "json" => Some(Self::Json),
+            "csv" => Some(Self::Csv),
+            "shell" | "sh" => Some(Self::Shell),
+            _ => None,
+        }
+    }
+
+    /// Escapes `text`, borrowing it when nothing needs escaping.
+    pub fn escape(self, text: &str) -> Cow<'_, str> {
+        match self {
+            Self::Csv => csv_field(text),
+            Self::Shell => shell_word(text),
+            _ => self.escape_chars(text),
+        }
+    }
+
+    fn escape_chars(self, text: &str) -> Cow<'_, str> {
+        let needs_escape = |c: char| match self {
+            Self::Html => matches!(c, '<' | '>' | '&' | '"' | '\''),
+            Self::Attribute => !(c.is_alphanumeric() || matches!(c, ',' | '.' | '-' | '_')),
+            Self::JavaScript => {
+                c.is_ascii() && !(c.is_ascii_alphanumeric() || matches!(c, ',' | '.' | '_' | ' '))
+                    || c == '\u{2028}'
+                    || c == '\u{2029}'
+            }
+            Self::Css => c.is_ascii() && !c.is_ascii_alphanumeric(),
+            Self::Url => !(c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | '~')),
+            Self::Json => matches!(c, '"' | '\\') || c.is_control(),
+            Self::Csv | Self::Shell => unreachable!("Escaped as a whole."),
+        };
+        let start = match text.find(needs_escape) {
+            Some(start) => start,
+            None => return Cow::Borrowed(text),
+        };
+
+        let mut escaped = String::with_capacity(text.len() + 16);
+        escaped.push_str(&text[..start]);
+        for c in text[start..].chars() {
+            if !needs_escape(c) {
+                escaped.push(c);
+                continue;
+            }
+            // Writing to a `String` never fails.
+            let _ = match self {
+                Self::Html => escaped.write_str(match c {
+                    '<' => "&lt;",
+                    '>' => "&gt;",
+                    '&' => "&amp;",
+                    '"' => "&quot;",
+                    _ => "&#39;",
+                }),
+                Self::Attribute => write!(escaped, "&#x{:X};", c as u32),
+                Self::JavaScript if c.is_ascii() => write!(escaped, "\\x{:02X}", c as u32),
+                Self::JavaScript => write!(escaped, "\\u{:04X}", c as u32),
+                Self::Css => write!(escaped, "\\{:X} ", c as u32),
+                Self::Url => {
+                    let mut buf = [0; 4];
+                    for byte in c.encode_utf8(&mut buf).bytes() {
+                        let _ = write!(escaped, "%{:02X}", byte);
+                    }
+                    Ok(())
+                }
+                Self::Json => match c {
+                    '"' => escaped.write_str("\\\""),
+                    '\\' => escaped.write_str("\\\\"),
+                    '\n' => escaped.write_str("\\n"),
+                    '\r' => escaped.write_str("\\r"),
+                    '\t' => escaped.write_str("\\t"),
+                    _ => write!(escaped, "\\u{:04x}", c as u32),
+                },
+                Self::Csv | Self::Shell => unreachable!("Escaped as a whole."),
+            };
+        }
+        Cow::Owned(escaped)
+    }
+}
+
+impl Escaper for Escape {
+    fn escape<'s>(&self, text: &'s str) -> Cow<'s, str> {
+        Escape::escape(*self, text)
+    }
+
+    fn strategy(&self) -> Option<Escape> {
+        Some(*self)
+    }
+}
+
+/// RFC 4180: quote fields with separators, quotes or line breaks, doubling the quotes.
+fn csv_field(text: &str) -> Cow<'_, str> {
+    if !text.contains(&[',', '"', '\n', '\r'][..]) {
+        return Cow::Borrowed(text);
+    }
+    Cow::Owned(format!("\"{}\"", text.replace('"', "\"\"")))
+}
+
+/// POSIX shells: single-quote anything but plain words, closing the quote around `'`.
+fn shell_word(text: &str) -> Cow<'_, str> {
+    let is_plain = |c: char| {
+        c.is_ascii_alphanumeric()
+            || matches!(c, '_' | '-' | '.' | '/' | ':' | ',' | '+' | '=' | '@' | '%')
+    };
+    if !text.is_empty() && text.chars().all(is_plain) {
+        return Cow::Borrowed(text);
+    }
+    Cow::Owned(format!("'{}'", text.replace('\'', "'\\''")))
+}
+
+impl std::str::FromStr for Escape {
+    type Err = Error;
+
+    fn from_str(name: &str) -> Result<Self> {
+        Self::from_name(name).ok_or_else(|| {
+            Error::with_msg("Unknown escaping strategy")
+                .context("strategy", name.to_owned())
+                .context(
+                    "expected",
+                    "html, attribute, js, css, url, json, csv, shell",
+                )
+        })
+    }
+}
+
+/// The escaper applied to output for the current render, if any.
+///
+/// Without one, output is written as is.
+#[derive(Debug, Clone, Default)]
+pub struct AutoEscapeRegister {
+    escaper: Option<Arc<dyn Escaper>>,
+}
+
+impl AutoEscapeRegister {
+    /// The active escaper, if autoescaping is on.
+    pub fn get(&self) -> Option<Arc<dyn Escaper>> {
+        self.escaper.clone()
+    }
+
+    /// Whether output is autoescaped with `strategy`, so text escaped with it is safe to output.
+    pub fn escapes_with(&self, strategy: Escape) -> bool {
+        self.escaper
+            .as_ref()
+            .map_or(false, |escaper| escaper.strategy() == Some(strategy))
+    }
+
+    /// Turns on autoescaping, returning the previous escaper.
+    pub fn set(&mut self, escaper: Arc<dyn Escaper>) -> Option<Arc<dyn Escaper>> {
+        self.escaper.replace(escaper)
+    }
+
+    /// Turns off autoescaping.
+    pub fn reset(&mut self) -> Option<Arc<dyn Escaper>> {
+        self.escaper.take()
+    }
+}
+
+/// Writes a value as template output, escaping it if autoescaping is on.
+pub fn write_output(
+    writer: &mut dyn Write,
+    value: &dyn ValueView,
+    runtime: &dyn Runtime,
+) -> Result<()> {
+    let escaper = runtime.registers().get_mut::<AutoEscapeRegister>().get();
+    let is_safe = value.as_scalar().map_or(false, |s| s.is_safe());
+    match escaper {
+        Some(escaper) if !is_safe => {
+            let rendered = value.render().to_string();
+            write!(writer, "{}", escaper.escape(&rendered)).replace("Failed to render")?;
+        }
+        _ => {
+            write!(writer, "{}", value.render()).replace("Failed to render")?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::model::{Scalar, Value};
+    use crate::runtime::{Registers, RuntimeBuilder};
+
+    #[test]
+    fn escape_html() {
+        let escape = Escape::Html;
+        assert_eq!(escape.escape("plain"), Cow::Borrowed("plain"));
+        assert_eq!(
+            escape.escape("<a href=\"x\">Tom & Jerry's</a>"),
+            "&lt;a href=&quot;x&quot;&gt;Tom &amp; Jerry&#39;s&lt;/a&gt;"
+        );
+    }
+
+    #[test]
+    fn escape_attribute() {
+        assert_eq!(
+            Escape::Attribute.escape("a b=c`d"),
+            "a&#x20;b&#x3D;c&#x60;d"
+        );
+        assert_eq!(Escape::Attribute.escape("café-1.0"), "café-1.0");
+    }
+
+    #[test]
+    fn escape_javascript() {
+        assert_eq!(
+            Escape::JavaScript.escape("it's </script>\u{2028}"),
+            "it\\x27s \\x3C\\x2Fscript\\x3E\\u2028"
+        );
+    }
+
+    #[test]
+    fn escape_css() {
+        assert_eq!(Escape::Css.escape("red;}"), "red\\3B \\7D ");
+    }
+
+    #[test]
+    fn escape_url() {
+        assert_eq!(Escape::Url.escape("a b/ü~"), "a%20b%2F%C3%BC~");
+    }
+
+    #[test]
+    fn escape_json() {
+        assert_eq!(
+            Escape::Json.escape("say \"hi\"\\\n\u{1}"),
+            "say \\\"hi\\\"\\\\\\n\\u0001"
+        );
+        assert_eq!(Escape::Json.escape("<é>"), Cow::Borrowed("<é>"));
+    }
+
+    #[test]
+    fn escape_csv() {
+        assert_eq!(
+            Escape::Csv.escape("plain text"),
+            Cow::Borrowed("plain text")
+        );
+        assert_eq!(Escape::Csv.escape("a,b"), "\"a,b\"");
+        assert_eq!(Escape::Csv.escape("say \"hi\""), "\"say \"\"hi\"\"\"");
+    }
+
+    #[test]
+    fn escape_shell() {
+        assert_eq!(
+            Escape::Shell.escape("./a-b.txt"),
+            Cow::Borrowed("./a-b.txt")
+        );
+        assert_eq!(Escape::Shell.escape(""), "''");
+        assert_eq!(Escape::Shell.escape("it's $HOME"), "'it'\\''s $HOME'");
+    }
+
+    #[derive(Debug)]
+    struct Upcase;
+
+    impl Escaper for Upcase {
+        fn escape<'s>(&self, text: &'s str) -> Cow<'s, str> {
+            Cow::Owned(text.to_uppercase())
+        }
+    }
+
+    #[test]
+    fn custom_escaper() {
+        let registers = Registers::default();
+        registers
+            .get_mut::<AutoEscapeRegister>()
+            .set(Arc::new(Upcase));
+        let runtime = RuntimeBuilder::new().set_registers(&registers).build();
+
+        let mut output = Vec::new();
+        write_output(&mut output, &Value::scalar("a"), &runtime).unwrap();
+        assert_eq!(String::from_utf8(output).unwrap(), "A");
+    }
+
+    #[test]
+    fn escape_names() {
+        assert_eq!("js".parse::<Escape>().unwrap(), Escape::JavaScript);
+        assert_eq!(
+            Escape::from_name(Escape::Attribute.name()),
+            Some(Escape::Attribute)
+        );
+        assert!("xml".parse::<Escape>().is_err());
+    }
+
+    #[test]
+    fn output_is_escaped_unless_safe() {
+        let registers = Registers::default();
+        registers
+            .get_mut::<AutoEscapeRegister>()
+            .set(Arc::new(Escape::Html));
+        let runtime = RuntimeBuilder::new().set_registers(&registers).build();
+
+        let mut output = Vec::new();
+        write_output(&mut output, &Value::scalar("<b>"), &runtime).unwrap();
+        write_output(&mut output, &Value::scalar(Scalar::safe("<i>")), &runtime).unwrap();
+        assert_eq!(String::from_utf8(output).unwrap(), "&lt;b&gt;<i>");
+    }
+}
@@ -24,6 +24,11 @@ impl Expression {
         Expression::Literal(Value::scalar(literal))
     }
 
+    /// Whether the value is known without a runtime.
+    pub fn is_literal(&self) -> bool {
+        matches!(*self, Expression::Literal(_))
+    }
+
     /// Convert into a literal if possible.
     pub fn into_literal(self) -> Option<Value> {
         match self {
@@ -0,0 +1,298 @@
+use std::fmt;
+
+use crate::model::{KString, Scalar, Value, ValueView};
+
+use super::Expression;
+use super::PartialStore;
+use super::Variable;
+
+/// A global variable a template reads, as far as its path is known before rendering.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GlobalReference {
+    path: Vec<Scalar>,
+    optional: bool,
+}
+
+impl GlobalReference {
+    /// The path up to the first index only known while rendering, like `[key]` in `a[key].b`.
+    pub fn path(&self) -> &[Scalar] {
+        &self.path
+    }
+
+    /// Whether rendering works without it, like `user` in `{% if user %}`.
+    pub fn is_optional(&self) -> bool {
+        self.optional
+    }
+}
+
+impl fmt::Display for GlobalReference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut path = self.path.iter();
+        if let Some(root) = path.next() {
+            write!(f, "{}", root.render())?;
+        }
+        for index in path {
+            if index.type_name() == "whole number" {
+                write!(f, "[{}]", index.render())?;
+            } else {
+                write!(f, ".{}", index.render())?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Names defined by the template rather than its globals, and paths tested for existence.
+#[derive(Clone, Debug, Default)]
+struct Scope {
+    locals: Vec<KString>,
+    guards: Vec<Vec<Scalar>>,
+}
+
+/// Collects the globals a template reads, see `Renderable::collect_globals`.
+///
+/// Variables count as globals unless the template defined them first, e.g. with `assign` or
+/// as a `for` loop's item.
+#[derive(Debug)]
+pub struct GlobalReferences<'p> {
+    partials: Option<&'p dyn PartialStore>,
+    scopes: Vec<Scope>,
+    including: Vec<KString>,
+    included: Vec<KString>,
+    references: Vec<GlobalReference>,
+}
+
+impl<'p> GlobalReferences<'p> {
+    /// Collect globals, without following `include`s.
+    pub fn new() -> Self {
+        Self {
+            partials: None,
+            scopes: vec![Scope::default()],
+            including: Vec::new(),
+            included: Vec::new(),
+            references: Vec::new(),
+        }
+    }
+
+    /// Collect globals, including those of the partials from `partials`.
+    pub fn with_partials(partials: &'p dyn PartialStore) -> Self {
+        Self {
+            partials: Some(partials),
+            ..Self::new()
+        }
+    }
+
+    /// Records an expression that must resolve while rendering.
+    pub fn read(&mut self, expression: &Expression) {
+        self.record(expression, false);
+    }
+
+    /// Records an expression that rendering only tests for existence, like `{% if user %}`.
+    pub fn test(&mut self, expression: &Expression) {
+        self.record(expression, true);
+    }
+
+    /// Records an existence test, whose paths are also optional until the matching
+    /// [`pop_scope`](Self::pop_scope), like in the body of `{% if user %}`.
+    pub fn guard(&mut self, expression: &Expression) {
+        self.test(expression);
+        if let Expression::Variable(variable) = expression {
+            if let Some(path) = self.global_path(variable) {
+                self.current_scope().guards.push(path);
+            }
+        }
+    }
+
+    /// Defines `name` for the rest of the template, like `assign` does.
+    pub fn assign(&mut self, name: &str) {
+        self.scopes[0].locals.push(KString::from_ref(name));
+    }
+
+    /// Defines `locals` until the matching [`pop_scope`](Self::pop_scope), like a `for` loop
+    /// does its item.
+    pub fn push_scope(&mut self, locals: &[&str]) {
+        self.scopes.push(Scope {
+            locals: locals
+                .iter()
+                .map(|&local| KString::from_ref(local))
+                .collect(),
+            guards: Vec::new(),
+        });
+    }
+
+    /// Ends the scope started by [`push_scope`](Self::push_scope).
+    pub fn pop_scope(&mut self) {
+        if self.scopes.len() > 1 {
+            self.scopes.pop();
+        }
+    }
+
+    /// Records the globals read by partial `name`, with `locals` defined.
+    ///
+    /// Does nothing without partials, or when `name` is already being included.
+    pub fn include(&mut self, name: &str, locals: &[&str]) {
+        if !self.included.iter().any(|included| included == name) {
+            self.included.push(KString::from_ref(name));
+        }
+        let partials = match self.partials {
+            Some(partials) => partials,
+            None => return,
+        };
+        if self.including.iter().any(|including| including == name) {
+            return;
+        }
+        let partial = match partials.try_get(name) {
+            Some(partial) => partial,
+            None => return,
+        };
+
+        self.including.push(KString::from_ref(name));
+        self.push_scope(locals);
+        partial.collect_globals(self);
+        self.pop_scope();
+        self.including.pop();
+    }
+
+    /// The partials included by name so far, whether there are partials to follow or not.
+    pub fn included(&self) -> &[KString] {
+        &self.included
+    }
+
+    /// The globals read so far.
+    pub fn iter(&self) -> std::slice::Iter<'_, GlobalReference> {
+        self.references.iter()
+    }
+
+    /// Stops collecting.
+    pub fn into_vec(self) -> Vec<GlobalReference> {
+        self.references
+    }
+
+    fn current_scope(&mut self) -> &mut Scope {
+        self.scopes.last_mut().expect("There is always a scope.")
+    }
+
+    fn is_local(&self, name: &str) -> bool {
+        self.scopes
+            .iter()
+            .any(|scope| scope.locals.iter().any(|local| local == name))
+    }
+
+    fn is_guarded(&self, path: &[Scalar]) -> bool {
+        self.scopes
+            .iter()
+            .flat_map(|scope| scope.guards.iter())
+            .any(|guard| path.starts_with(guard))
+    }
+
+    fn global_path(&self, variable: &Variable) -> Option<Vec<Scalar>> {
+        if self.is_local(variable.root().to_kstr().as_str()) {
+            return None;
+        }
+
+        let mut path = vec![variable.root().clone()];
+        for index in variable.indexes() {
+            match index {
+                Expression::Literal(Value::Scalar(index)) => path.push(index.clone()),
+                _ => break,
+            }
+        }
+        Some(path)
+    }
+
+    fn record(&mut self, expression: &Expression, optional: bool) {
+        let variable = match expression {
+            Expression::Variable(variable) => variable,
+            Expression::Literal(_) => return,
+        };
+
+        for index in variable.indexes() {
+            self.record(index, optional);
+        }
+
+        let path = match self.global_path(variable) {
+            Some(path) => path,
+            None => return,
+        };
+        let optional = optional || self.is_guarded(&path);
+        match self.references.iter_mut().find(|r| r.path == path) {
+            Some(reference) => reference.optional &= optional,
+            None => self.references.push(GlobalReference { path, optional }),
+        }
+    }
+}
+
+impl<'p> Default for GlobalReferences<'p> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn variable(path: &str) -> Expression {
+        let mut parts = path.split('.');
+        let mut variable = Variable::with_literal(parts.next().unwrap().to_owned());
+        for part in parts {
+            variable = variable.push_literal(part.to_owned());
+        }
+        Expression::Variable(variable)
+    }
+
+    fn collected(globals: GlobalReferences<'_>) -> Vec<(String, bool)> {
+        globals
+            .iter()
+            .map(|r| (r.to_string(), r.is_optional()))
+            .collect()
+    }
+
+    #[test]
+    fn test_locals() {
+        let mut globals = GlobalReferences::new();
+        globals.read(&variable("title"));
+        globals.assign("title");
+        globals.read(&variable("title"));
+        globals.push_scope(&["item"]);
+        globals.read(&variable("item.name"));
+        globals.pop_scope();
+        globals.read(&variable("item.name"));
+        assert_eq!(
+            collected(globals),
+            vec![("title".to_owned(), false), ("item.name".to_owned(), false)]
+        );
+    }
+
+    #[test]
+    fn test_guards() {
+        let mut globals = GlobalReferences::new();
+        globals.push_scope(&[]);
+        globals.guard(&variable("user"));
+        globals.read(&variable("user.name"));
+        globals.pop_scope();
+        globals.read(&variable("site.name"));
+        globals.read(&variable("user.email"));
+        assert_eq!(
+            collected(globals),
+            vec![
+                ("user".to_owned(), true),
+                ("user.name".to_owned(), true),
+                ("site.name".to_owned(), false),
+                ("user.email".to_owned(), false),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_dynamic_index() {
+        let mut globals = GlobalReferences::new();
+        let mut products = Variable::with_literal("products");
+        products.extend(vec![variable("handle"), Expression::with_literal("price")]);
+        globals.read(&Expression::Variable(products));
+        assert_eq!(
+            collected(globals),
+            vec![("handle".to_owned(), false), ("products".to_owned(), false)]
+        );
+    }
+}
@@ -3,16 +3,26 @@
 #![warn(missing_docs)]
 #![warn(unused_extern_crates)]
 
+#[cfg(feature = "async")]
+mod async_globals;
+mod escape;
 mod expression;
+mod globals;
 mod partials;
+mod program;
 mod renderable;
 mod runtime;
 mod stack;
 mod template;
 mod variable;
 
+#[cfg(feature = "async")]
+pub use self::async_globals::*;
+pub use self::escape::*;
 pub use self::expression::*;
+pub use self::globals::*;
 pub use self::partials::*;
+pub use self::program::*;
 pub use self::renderable::*;
 pub use self::runtime::*;
 pub use self::stack::*;
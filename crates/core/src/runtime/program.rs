@@ -0,0 +1,309 @@
+use std::fmt;
+use std::io::Write;
+
+use crate::error::{Error, Result, ResultLiquidReplaceExt};
+use crate::model::ValueCow;
+use crate::parser::Filter;
+
+use super::write_output;
+use super::Expression;
+use super::InterruptRegister;
+use super::Renderable;
+use super::Runtime;
+use super::Template;
+
+/// A test deciding which branch a [`Program`] takes, like the condition of `{% if %}`.
+pub trait Predicate: Send + Sync + fmt::Debug {
+    /// Whether the branch is taken.
+    fn test(&self, runtime: &dyn Runtime) -> Result<bool>;
+}
+
+/// A block that renders its bodies itself, like `{% for %}` its loop body, so they can be
+/// compiled anyway.
+pub trait BlockRenderer: Send + Sync + fmt::Debug {
+    /// Renders the block, with `bodies` in the order the block lowered them.
+    fn render_bodies_to(
+        &self,
+        bodies: &[&dyn Renderable],
+        writer: &mut dyn Write,
+        runtime: &dyn Runtime,
+    ) -> Result<()>;
+}
+
+/// A step of a [`Program`].
+#[derive(Debug)]
+pub enum Instruction<'t> {
+    /// Writes text as is.
+    Text(&'t str),
+    /// Evaluates an expression, for the instructions after it to filter or output.
+    Evaluate(&'t Expression),
+    /// Applies a filter to the value evaluated before, like `upcase` in `{{ name | upcase }}`.
+    Filter(&'t dyn Filter),
+    /// Writes the value evaluated before.
+    Output,
+    /// Continues at `target` unless `predicate` holds.
+    Branch {
+        /// The test.
+        predicate: &'t dyn Predicate,
+        /// Where to continue unless the test passes.
+        target: usize,
+    },
+    /// Continues at the target.
+    Jump(usize),
+    /// Renders a block with compiled bodies.
+    Block {
+        /// The block.
+        block: &'t dyn BlockRenderer,
+        /// Its bodies.
+        bodies: Vec<Program<'t>>,
+    },
+    /// Renders something that doesn't compile, like a custom tag.
+    Render(&'t dyn Renderable),
+}
+
+/// A template compiled to a flat list of instructions, to render it without walking its tree.
+///
+/// Elements compile themselves with [`Renderable::lower`], or are rendered as is.
+#[derive(Debug, Default)]
+pub struct Program<'t> {
+    instructions: Vec<Instruction<'t>>,
+    traces: Vec<Trace>,
+}
+
+/// Instructions whose errors are traced, see [`Program::begin_trace`].
+#[derive(Debug)]
+struct Trace {
+    start: usize,
+    end: usize,
+    trace: String,
+}
+
+impl<'t> Program<'t> {
+    /// Compiles `template`.
+    pub fn compile(template: &'t Template) -> Self {
+        let mut program = Self::default();
+        program.append(template);
+        program
+    }
+
+    /// The instructions, in order.
+    pub fn instructions(&self) -> &[Instruction<'t>] {
+        &self.instructions
+    }
+
+    /// Appends the instructions for `template`.
+    pub fn append(&mut self, template: &'t Template) {
+        for element in template.elements() {
+            if !element.lower(self) {
+                self.render(element.as_ref());
+            }
+        }
+    }
+
+    /// Appends an instruction writing `text`.
+    pub fn text(&mut self, text: &'t str) {
+        self.instructions.push(Instruction::Text(text));
+    }
+
+    /// Appends an instruction evaluating `expression`, to filter or output.
+    pub fn evaluate(&mut self, expression: &'t Expression) {
+        self.instructions.push(Instruction::Evaluate(expression));
+    }
+
+    /// Appends an instruction applying `filter` to the value evaluated before.
+    pub fn filter(&mut self, filter: &'t dyn Filter) {
+        self.instructions.push(Instruction::Filter(filter));
+    }
+
+    /// Appends an instruction writing the value evaluated before.
+    pub fn output(&mut self) {
+        self.instructions.push(Instruction::Output);
+    }
+
+    /// Appends an instruction rendering `element` as is.
+    pub fn render(&mut self, element: &'t dyn Renderable) {
+        self.instructions.push(Instruction::Render(element));
+    }
+
+    /// Appends an instruction rendering `block`, with `bodies` compiled.
+    pub fn block(&mut self, block: &'t dyn BlockRenderer, bodies: &[&'t Template]) {
+        let bodies = bodies.iter().map(|body| Self::compile(body)).collect();
+        self.instructions.push(Instruction::Block { block, bodies });
+    }
+
+    /// Appends a branch skipping ahead unless `predicate` holds, to the [`Label`] set with
+    /// [`Program::target`].
+    pub fn branch(&mut self, predicate: &'t dyn Predicate) -> Label {
+        self.instructions.push(Instruction::Branch {
+            predicate,
+            target: usize::MAX,
+        });
+        Label(self.instructions.len() - 1)
+    }
+
+    /// Appends a jump ahead, to the [`Label`] set with [`Program::target`].
+    pub fn jump(&mut self) -> Label {
+        self.instructions.push(Instruction::Jump(usize::MAX));
+        Label(self.instructions.len() - 1)
+    }
+
+    /// Makes `label` continue at the next instruction appended.
+    pub fn target(&mut self, label: Label) {
+        let next = self.instructions.len();
+        match self.instructions[label.0] {
+            Instruction::Branch { ref mut target, .. } => *target = next,
+            Instruction::Jump(ref mut target) => *target = next,
+            _ => unreachable!("labels are only made for branches and jumps"),
+        }
+    }
+
+    /// Traces errors of the instructions appended until [`Program::end_trace`] with `trace`,
+    /// like a block does for its body.
+    pub fn begin_trace(&mut self, trace: String) -> Traced {
+        self.traces.push(Trace {
+            start: self.instructions.len(),
+            end: usize::MAX,
+            trace,
+        });
+        Traced(self.traces.len() - 1)
+    }
+
+    /// Stops tracing errors of the instructions appended next.
+    pub fn end_trace(&mut self, traced: Traced) {
+        self.traces[traced.0].end = self.instructions.len();
+    }
+
+    /// Adds the traces of the instruction at `index` to `error`, innermost first.
+    fn trace(&self, index: usize, error: Error) -> Error {
+        self.traces
+            .iter()
+            .rev()
+            .filter(|trace| trace.start <= index && index < trace.end)
+            .fold(error, |error, trace| error.trace(trace.trace.clone()))
+    }
+}
+
+/// A branch or jump whose target is set later, see [`Program::target`].
+#[derive(Debug)]
+#[must_use]
+pub struct Label(usize);
+
+/// Instructions being traced, until [`Program::end_trace`].
+#[derive(Debug)]
+#[must_use]
+pub struct Traced(usize);
+
+impl<'t> Renderable for Program<'t> {
+    fn render_to(&self, writer: &mut dyn Write, runtime: &dyn Runtime) -> Result<()> {
+        let mut value: Option<ValueCow<'_>> = None;
+        let mut next = 0;
+        while let Some(instruction) = self.instructions.get(next) {
+            let index = next;
+            next += 1;
+            let executed = match *instruction {
+                Instruction::Text(text) => writer
+                    .write_all(text.as_bytes())
+                    .replace("Failed to render"),
+                Instruction::Evaluate(expression) => expression
+                    .evaluate(runtime)
+                    .map(|evaluated| value = Some(evaluated)),
+                Instruction::Filter(filter) => {
+                    let input = value.take().expect("filters follow a value");
+                    crate::parser::apply_filter(filter, input, runtime)
+                        .map(|filtered| value = Some(filtered))
+                }
+                Instruction::Output => {
+                    let output = value.take().expect("outputs follow a value");
+                    write_output(writer, output.as_view(), runtime)
+                }
+                Instruction::Branch { predicate, target } => predicate.test(runtime).map(|holds| {
+                    if !holds {
+                        next = target;
+                    }
+                }),
+                Instruction::Jump(target) => {
+                    next = target;
+                    Ok(())
+                }
+                Instruction::Block { block, ref bodies } => {
+                    let bodies: Vec<&dyn Renderable> =
+                        bodies.iter().map(|body| body as &dyn Renderable).collect();
+                    block.render_bodies_to(&bodies, writer, runtime)
+                }
+                Instruction::Render(element) => element.render_to(writer, runtime),
+            };
+            if let Err(error) = executed {
+                return Err(self.trace(index, error));
+            }
+
+            // Like `Template`, stop at a `break` or `continue`, which only blocks and elements
+            // rendered as is can set.
+            if let Instruction::Block { .. } | Instruction::Render(_) = *instruction {
+                if runtime
+                    .registers()
+                    .get_mut::<InterruptRegister>()
+                    .interrupted()
+                {
+                    break;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::parser;
+    use crate::runtime::RuntimeBuilder;
+
+    #[test]
+    fn compile_text_and_output() {
+        let template = parser::parse("a{{ 'b' }}c", &Default::default())
+            .map(Template::new)
+            .unwrap();
+        let program = Program::compile(&template);
+        assert!(matches!(
+            program.instructions(),
+            [
+                Instruction::Text("a"),
+                Instruction::Evaluate(_),
+                Instruction::Output,
+                Instruction::Text("c")
+            ]
+        ));
+
+        let runtime = RuntimeBuilder::new().build();
+        assert_eq!(program.render(&runtime).unwrap(), "abc");
+    }
+
+    #[derive(Debug)]
+    struct Holds(bool);
+
+    impl Predicate for Holds {
+        fn test(&self, _runtime: &dyn Runtime) -> Result<bool> {
+            Ok(self.0)
+        }
+    }
+
+    #[test]
+    fn branch() {
+        let compile = |predicate| {
+            let mut program = Program::default();
+            let otherwise = program.branch(predicate);
+            program.text("yes");
+            let end = program.jump();
+            program.target(otherwise);
+            program.text("no");
+            program.target(end);
+            program.text("!");
+            program
+        };
+
+        let runtime = RuntimeBuilder::new().build();
+        assert_eq!(compile(&Holds(true)).render(&runtime).unwrap(), "yes!");
+        assert_eq!(compile(&Holds(false)).render(&runtime).unwrap(), "no!");
+    }
+}
@@ -3,6 +3,8 @@ use std::io::Write;
 
 use crate::error::Result;
 
+use super::GlobalReferences;
+use super::Program;
 use super::Runtime;
 
 /// Any object (tag/block) that can be rendered by liquid must implement this trait.
@@ -16,4 +18,24 @@ pub trait Renderable: Send + Sync + Debug {
 
     /// Renders the Renderable instance given a Liquid runtime.
     fn render_to(&self, writer: &mut dyn Write, runtime: &dyn Runtime) -> Result<()>;
+
+    /// Records the globals rendering reads, for validating inputs ahead of time.
+    ///
+    /// Defaults to recording nothing.
+    fn collect_globals(&self, _globals: &mut GlobalReferences<'_>) {}
+
+    /// The output, when it is known without rendering, like for plain text.
+    ///
+    /// Defaults to `None`.  Elements with static output must not have side effects, as
+    /// [`Template`](super::Template) merges them.
+    fn static_output(&self) -> Option<&str> {
+        None
+    }
+
+    /// Appends the instructions rendering this to `program`, see [`Program`].
+    ///
+    /// Defaults to `false`, to be rendered as is.
+    fn lower<'t>(&'t self, _program: &mut Program<'t>) -> bool {
+        false
+    }
 }
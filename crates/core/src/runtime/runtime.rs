@@ -29,6 +29,29 @@ pub trait Runtime {
         val: crate::model::Value,
     ) -> Option<crate::model::Value>;
 
+    /// Sets a value nested in the global runtime, e.g. `obj.key` or `arr[0]`.
+    ///
+    /// The global at the start of `path` is copied, changed with `model::set_path` and stored
+    /// with `set_global`, so values provided by the caller are never modified.
+    fn set_global_path(&self, path: &[ScalarCow<'_>], val: Value) -> Result<Option<Value>> {
+        let (root, rest) = match path.split_first() {
+            Some(split) => split,
+            None => return Error::with_msg("Expected a variable").into_err(),
+        };
+        let name = root.to_kstr().into_owned();
+        if rest.is_empty() {
+            return Ok(self.set_global(name, val));
+        }
+
+        let mut global = self
+            .try_get(&path[..1])
+            .map(ValueCow::into_owned)
+            .unwrap_or_default();
+        let old = crate::model::set_path(&mut global, rest, val)?;
+        self.set_global(name, global);
+        Ok(old)
+    }
+
     /// Used by increment and decrement tags
     fn set_index(&self, name: crate::model::KString, val: Value) -> Option<Value>;
     /// Used by increment and decrement tags
@@ -67,6 +90,10 @@ impl<'r, R: Runtime + ?Sized> Runtime for &'r R {
         <R as Runtime>::set_global(self, name, val)
     }
 
+    fn set_global_path(&self, path: &[ScalarCow<'_>], val: Value) -> Result<Option<Value>> {
+        <R as Runtime>::set_global_path(self, path, val)
+    }
+
     fn set_index(&self, name: crate::model::KString, val: Value) -> Option<Value> {
         <R as Runtime>::set_index(self, name, val)
     }
@@ -84,6 +111,7 @@ impl<'r, R: Runtime + ?Sized> Runtime for &'r R {
 pub struct RuntimeBuilder<'g, 'p> {
     globals: Option<&'g dyn ObjectView>,
     partials: Option<&'p dyn PartialStore>,
+    registers: Option<&'g Registers>,
 }
 
 impl<'c, 'g: 'c, 'p: 'c> RuntimeBuilder<'g, 'p> {
@@ -92,14 +120,19 @@ impl<'c, 'g: 'c, 'p: 'c> RuntimeBuilder<'g, 'p> {
         Self {
             globals: None,
             partials: None,
+            registers: None,
         }
     }
 
     /// Initialize the stack with the given globals.
-    pub fn set_globals<'n>(self, values: &'n dyn ObjectView) -> RuntimeBuilder<'n, 'p> {
+    pub fn set_globals<'n>(self, values: &'n dyn ObjectView) -> RuntimeBuilder<'n, 'p>
+    where
+        'g: 'n,
+    {
         RuntimeBuilder {
             globals: Some(values),
             partials: self.partials,
+            registers: self.registers,
         }
     }
 
@@ -108,15 +141,33 @@ impl<'c, 'g: 'c, 'p: 'c> RuntimeBuilder<'g, 'p> {
         RuntimeBuilder {
             globals: self.globals,
             partials: Some(values),
+            registers: self.registers,
+        }
+    }
+
+    /// Share plugin state with the caller.
+    ///
+    /// This allows seeding registers (like the active locale) before rendering and inspecting
+    /// them afterwards.
+    pub fn set_registers(self, registers: &'g Registers) -> Self {
+        RuntimeBuilder {
+            globals: self.globals,
+            partials: self.partials,
+            registers: Some(registers),
         }
     }
 
     /// Create the `Runtime`.
     pub fn build(self) -> impl Runtime + 'c {
+        crate::model::begin_render();
         let partials = self.partials.unwrap_or(&NullPartials);
+        let registers = match self.registers {
+            Some(registers) => RegistersRef::Borrowed(registers),
+            None => RegistersRef::Owned(Default::default()),
+        };
         let runtime = RuntimeCore {
             partials,
-            ..Default::default()
+            registers,
         };
         let runtime = super::IndexFrame::new(runtime);
         let runtime = super::StackFrame::new(runtime, self.globals.unwrap_or(&NullObject));
@@ -207,7 +258,23 @@ impl Default for RuntimeBuilder<'static, 'static> {
 pub struct RuntimeCore<'g> {
     partials: &'g dyn PartialStore,
 
-    registers: Registers,
+    registers: RegistersRef<'g>,
+}
+
+enum RegistersRef<'g> {
+    Owned(Registers),
+    Borrowed(&'g Registers),
+}
+
+impl<'g> std::ops::Deref for RegistersRef<'g> {
+    type Target = Registers;
+
+    fn deref(&self) -> &Registers {
+        match self {
+            RegistersRef::Owned(registers) => registers,
+            RegistersRef::Borrowed(registers) => registers,
+        }
+    }
 }
 
 impl<'g> RuntimeCore<'g> {
@@ -274,22 +341,22 @@ impl<'g> Default for RuntimeCore<'g> {
     fn default() -> Self {
         Self {
             partials: &NullPartials,
-            registers: Default::default(),
+            registers: RegistersRef::Owned(Default::default()),
         }
     }
 }
 
 /// Unnamed state for plugins during rendering
 pub struct Registers {
-    registers: std::cell::RefCell<anymap2::AnyMap>,
+    registers: std::cell::RefCell<anymap2::Map<dyn anymap2::any::Any + Send>>,
 }
 
 impl Registers {
     /// Data store for stateful tags/blocks.
     ///
-    /// If a plugin needs state, it creates a `struct Register : Default` and accesses it via
-    /// `get_mut`.
-    pub fn get_mut<T: std::any::Any + Default>(&self) -> std::cell::RefMut<'_, T> {
+    /// If a plugin needs state, it creates a `struct Register : Default + Send` and accesses it
+    /// via `get_mut`.
+    pub fn get_mut<T: std::any::Any + Default + Send>(&self) -> std::cell::RefMut<'_, T> {
         std::cell::RefMut::map(self.registers.borrow_mut(), |registers| {
             registers.entry::<T>().or_insert_with(Default::default)
         })
@@ -299,11 +366,129 @@ impl Registers {
 impl Default for Registers {
     fn default() -> Self {
         Self {
-            registers: std::cell::RefCell::new(anymap2::AnyMap::new()),
+            registers: std::cell::RefCell::new(anymap2::Map::new()),
         }
     }
 }
 
+/// The locale requested for the current render.
+///
+/// Used by locale-aware plugins, like translation filters.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct LocaleRegister {
+    locale: Option<crate::model::KString>,
+}
+
+impl LocaleRegister {
+    /// The active locale, if one was requested.
+    pub fn get(&self) -> Option<&str> {
+        self.locale.as_deref()
+    }
+
+    /// Sets the active locale, returning the previous one.
+    pub fn set<S: Into<crate::model::KString>>(
+        &mut self,
+        locale: S,
+    ) -> Option<crate::model::KString> {
+        self.locale.replace(locale.into())
+    }
+
+    /// Clears the active locale.
+    pub fn reset(&mut self) -> Option<crate::model::KString> {
+        self.locale.take()
+    }
+}
+
+/// The time zone requested for the current render.
+///
+/// Used by date filters to display dates in local time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct TimeZoneRegister {
+    zone: Option<crate::model::TimeZone>,
+}
+
+impl TimeZoneRegister {
+    /// The active time zone, if one was requested.
+    pub fn get(&self) -> Option<crate::model::TimeZone> {
+        self.zone
+    }
+
+    /// Sets the active time zone, returning the previous one.
+    pub fn set(&mut self, zone: crate::model::TimeZone) -> Option<crate::model::TimeZone> {
+        self.zone.replace(zone)
+    }
+
+    /// Clears the active time zone.
+    pub fn reset(&mut self) -> Option<crate::model::TimeZone> {
+        self.zone.take()
+    }
+}
+
+/// Content captured while rendering a template, for the layout it's rendered in.
+///
+/// Layout plugins store the template's output under `layout`; apps can add their own content,
+/// like scripts under `header`, before rendering.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ContentForRegister {
+    content: std::collections::BTreeMap<crate::model::KString, String>,
+}
+
+impl ContentForRegister {
+    /// The content captured under `name`, if any.
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.content.get(name).map(|content| content.as_str())
+    }
+
+    /// Replaces the content captured under `name`, returning the previous one.
+    pub fn set<S: Into<crate::model::KString>>(
+        &mut self,
+        name: S,
+        content: String,
+    ) -> Option<String> {
+        self.content.insert(name.into(), content)
+    }
+
+    /// Adds to the content captured under `name`.
+    pub fn append<S: Into<crate::model::KString>>(&mut self, name: S, content: &str) {
+        self.content
+            .entry(name.into())
+            .or_default()
+            .push_str(content);
+    }
+
+    /// Clears the content captured under `name`.
+    pub fn reset(&mut self, name: &str) -> Option<String> {
+        self.content.remove(name)
+    }
+
+    /// The captured content, by name.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.content
+            .iter()
+            .map(|(name, content)| (name.as_str(), content.as_str()))
+    }
+}
+
+/// What math filters do when a whole-number result doesn't fit in an `i64`.
+///
+/// Set by the parser's `integer_overflow` option; plugins read it from the registers.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum IntegerOverflow {
+    /// Fail the render with an error.
+    Error,
+    /// Redo the calculation with floats.
+    Float,
+    /// Redo the calculation with an arbitrary-precision integer.
+    #[cfg(feature = "bigint")]
+    BigInt,
+}
+
+impl Default for IntegerOverflow {
+    fn default() -> Self {
+        Self::Error
+    }
+}
+
 /// The current interrupt state. The interrupt state is used by
 /// the `break` and `continue` tags to halt template rendering
 /// at a given point and unwind the `render` call stack until
@@ -389,6 +574,35 @@ mod test {
         assert_eq!(&rt.get(&test_path).unwrap(), &ValueViewCmp::new(&42));
     }
 
+    #[test]
+    fn shared_registers() {
+        let registers = Registers::default();
+        registers.get_mut::<LocaleRegister>().set("fr");
+
+        {
+            let rt = RuntimeBuilder::new().set_registers(&registers).build();
+            assert_eq!(rt.registers().get_mut::<LocaleRegister>().get(), Some("fr"));
+            rt.registers().get_mut::<LocaleRegister>().set("de");
+        }
+
+        assert_eq!(registers.get_mut::<LocaleRegister>().get(), Some("de"));
+    }
+
+    #[test]
+    fn content_for_register() {
+        let mut content = ContentForRegister::default();
+        content.append("header", "<script>");
+        content.append("header", "</script>");
+        assert_eq!(content.set("layout", "page".into()), None);
+        assert_eq!(content.get("header"), Some("<script></script>"));
+        assert_eq!(
+            content.iter().collect::<Vec<_>>(),
+            vec![("header", "<script></script>"), ("layout", "page")]
+        );
+        assert_eq!(content.reset("layout"), Some("page".into()));
+        assert_eq!(content.get("layout"), None);
+    }
+
     #[test]
     fn global_variables() {
         let global_path = [Scalar::new("global")];
@@ -407,4 +621,28 @@ mod test {
             &ValueViewCmp::new(&"some value")
         );
     }
+
+    #[test]
+    fn global_paths() {
+        let globals = crate::object!({"site": {"title": "Blog", "tags": ["a"]}});
+        let rt = RuntimeBuilder::new().set_globals(&globals).build();
+
+        let path = [Scalar::new("site"), Scalar::new("title")];
+        let old = rt.set_global_path(&path, Value::scalar("News")).unwrap();
+        assert_eq!(old, Some(Value::scalar("Blog")));
+        assert_eq!(&rt.get(&path).unwrap(), &ValueViewCmp::new(&"News"));
+
+        let path = [Scalar::new("site"), Scalar::new("tags"), Scalar::new(1)];
+        rt.set_global_path(&path, Value::scalar("b")).unwrap();
+        assert_eq!(&rt.get(&path).unwrap(), &ValueViewCmp::new(&"b"));
+
+        let path = [Scalar::new("site"), Scalar::new("title"), Scalar::new("x")];
+        assert!(rt.set_global_path(&path, Value::Nil).is_err());
+
+        // The caller's globals are left alone.
+        assert_eq!(
+            globals,
+            crate::object!({"site": {"title": "Blog", "tags": ["a"]}})
+        );
+    }
 }
@@ -2,6 +2,7 @@ use std::io::Write;
 
 use crate::error::Result;
 
+use super::GlobalReferences;
 use super::Renderable;
 use super::Runtime;
 
@@ -13,8 +14,75 @@ pub struct Template {
 
 impl Template {
     /// Create an executable template block.
+    ///
+    /// Consecutive elements with static output, see [`Renderable::static_output`], are merged
+    /// into one text element, and those with no output are dropped.
     pub fn new(elements: Vec<Box<dyn Renderable>>) -> Template {
-        Template { elements }
+        let mut merged: Vec<Box<dyn Renderable>> = Vec::with_capacity(elements.len());
+        let mut text: Option<String> = None;
+        let mut run: Vec<Box<dyn Renderable>> = Vec::new();
+        for element in elements {
+            match element.static_output() {
+                Some(output) => {
+                    text.get_or_insert_with(String::new).push_str(output);
+                    run.push(element);
+                }
+                None => {
+                    Self::end_run(&mut merged, &mut run, text.take());
+                    merged.push(element);
+                }
+            }
+        }
+        Self::end_run(&mut merged, &mut run, text.take());
+        Template { elements: merged }
+    }
+
+    /// Adds a run of elements with static output, merged unless it's a single element.
+    fn end_run(
+        merged: &mut Vec<Box<dyn Renderable>>,
+        run: &mut Vec<Box<dyn Renderable>>,
+        text: Option<String>,
+    ) {
+        let text = match text {
+            Some(text) if !text.is_empty() => text,
+            _ => {
+                run.clear();
+                return;
+            }
+        };
+        if run.len() == 1 {
+            merged.append(run);
+        } else {
+            run.clear();
+            merged.push(Box::new(crate::parser::Text::new(text)));
+        }
+    }
+
+    /// The elements, in order.
+    pub fn elements(&self) -> &[Box<dyn Renderable>] {
+        &self.elements
+    }
+
+    /// Renders the element at `index`, for rendering a template one element at a time.
+    ///
+    /// Returns whether there is more to render, which stops at the last element or when an
+    /// element interrupts rendering, like `break`.
+    pub fn render_element_to(
+        &self,
+        index: usize,
+        writer: &mut dyn Write,
+        runtime: &dyn Runtime,
+    ) -> Result<bool> {
+        let el = match self.elements.get(index) {
+            Some(el) => el,
+            None => return Ok(false),
+        };
+        el.render_to(writer, runtime)?;
+        let interrupted = runtime
+            .registers()
+            .get_mut::<super::InterruptRegister>()
+            .interrupted();
+        Ok(!interrupted && index + 1 < self.elements.len())
     }
 }
 
@@ -37,4 +105,18 @@ impl Renderable for Template {
         }
         Ok(())
     }
+
+    fn static_output(&self) -> Option<&str> {
+        match *self.elements.as_slice() {
+            [] => Some(""),
+            [ref element] => element.static_output(),
+            _ => None,
+        }
+    }
+
+    fn collect_globals(&self, globals: &mut GlobalReferences<'_>) {
+        for el in &self.elements {
+            el.collect_globals(globals);
+        }
+    }
 }
@@ -30,6 +30,16 @@ impl Variable {
         self
     }
 
+    /// The variable name.
+    pub fn root(&self) -> &Scalar {
+        &self.variable
+    }
+
+    /// Indexes into the variable, e.g. `key` and `0` in `var.key[0]`.
+    pub fn indexes(&self) -> &[Expression] {
+        &self.indexes
+    }
+
     /// Convert to a `Path`.
     pub fn try_evaluate<'c>(&'c self, runtime: &'c dyn Runtime) -> Option<Path<'c>> {
         let mut path = Path::with_index(self.variable.as_ref());
@@ -39,6 +49,7 @@ impl Variable {
             let s = match v {
                 ValueCow::Owned(v) => v.into_scalar(),
                 ValueCow::Borrowed(v) => v.as_scalar(),
+                ValueCow::Array(_) => None,
             }?;
             path.push(s);
         }
@@ -54,6 +65,7 @@ impl Variable {
             let s = match v {
                 ValueCow::Owned(v) => v.into_scalar(),
                 ValueCow::Borrowed(v) => v.as_scalar(),
+                ValueCow::Array(_) => None,
             }
             .ok_or_else(|| {
                 let v = expr.evaluate(runtime).expect("lookup already verified");
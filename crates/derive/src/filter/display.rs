@@ -4,11 +4,12 @@ use proc_quote::*;
 use syn::*;
 
 /// Struct that contains information about the `Filter` struct to generate the
-/// necessary code for `Display`.
+/// necessary code for `Display` (and `Filter`, for borrowing filters).
 struct FilterStruct<'a> {
     name: &'a Ident,
     filter_name: String,
     parameters: Option<Parameters<'a>>,
+    borrows: bool,
     generics: &'a Generics,
 }
 
@@ -92,6 +93,43 @@ impl<'a> FilterStruct<'a> {
         }
     }
 
+    /// Searches for `#[filter(borrows)]`.
+    fn parse_borrows(attrs: &[Attribute]) -> Result<bool> {
+        let mut borrows = false;
+        for attr in attrs.iter().filter(|attr| attr.path.is_ident("filter")) {
+            let meta = attr.parse_meta()?;
+            let list = match meta {
+                Meta::List(list) => list,
+                meta => {
+                    return Err(Error::new_spanned(
+                        meta,
+                        "Couldn't parse filter attribute. Have you tried `#[filter(borrows)]`?",
+                    ))
+                }
+            };
+            for nested in list.nested {
+                match nested {
+                    NestedMeta::Meta(Meta::Path(ref path)) if path.is_ident("borrows") => {
+                        if borrows {
+                            return Err(Error::new_spanned(
+                                path,
+                                "Multiple definitions for `borrows`.",
+                            ));
+                        }
+                        borrows = true;
+                    }
+                    nested => {
+                        return Err(Error::new_spanned(
+                            nested,
+                            "Unknown element in filter attribute. Expected `borrows`.",
+                        ))
+                    }
+                }
+            }
+        }
+        Ok(borrows)
+    }
+
     /// Tries to create a new `FilterStruct` from the given `DeriveInput`
     fn from_input(input: &'a DeriveInput) -> Result<Self> {
         let DeriveInput {
@@ -137,11 +175,13 @@ impl<'a> FilterStruct<'a> {
         let name = ident;
         let filter_name = Self::parse_attrs(attrs)?;
         let parameters = parameters.into_option();
+        let borrows = Self::parse_borrows(attrs)?;
 
         Ok(Self {
             name,
             filter_name,
             parameters,
+            borrows,
             generics,
         })
     }
@@ -176,11 +216,45 @@ fn generate_impl_display(filter: &FilterStruct<'_>) -> TokenStream {
     }
 }
 
+/// Generates implementation of `Filter` on top of the inherent `evaluate_cow`.
+fn generate_impl_filter(filter: &FilterStruct<'_>) -> TokenStream {
+    let impl_filter = filter.generate_impl(quote! { ::liquid_core::parser::Filter });
+
+    quote! {
+        #impl_filter {
+            fn evaluate(
+                &self,
+                input: &dyn ::liquid_core::model::ValueView,
+                runtime: &dyn ::liquid_core::runtime::Runtime,
+            ) -> ::liquid_core::error::Result<::liquid_core::model::Value> {
+                Self::evaluate_cow(self, input, runtime).map(::liquid_core::model::ValueCow::into_owned)
+            }
+
+            fn evaluate_cow<'v>(
+                &'v self,
+                input: &'v dyn ::liquid_core::model::ValueView,
+                runtime: &'v dyn ::liquid_core::runtime::Runtime,
+            ) -> ::liquid_core::error::Result<::liquid_core::model::ValueCow<'v>> {
+                Self::evaluate_cow(self, input, runtime)
+            }
+        }
+    }
+}
+
 pub fn derive(input: &DeriveInput) -> TokenStream {
     let filter = match FilterStruct::from_input(input) {
         Ok(filter) => filter,
         Err(err) => return err.to_compile_error(),
     };
 
-    generate_impl_display(&filter)
+    let impl_display = generate_impl_display(&filter);
+    if filter.borrows {
+        let impl_filter = generate_impl_filter(&filter);
+        quote! {
+            #impl_display
+            #impl_filter
+        }
+    } else {
+        impl_display
+    }
 }
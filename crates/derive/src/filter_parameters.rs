@@ -491,6 +491,7 @@ fn generate_evaluate_field(field: &FilterParameter<'_>) -> TokenStream {
                 ::liquid_core::ValueCow::Borrowed(v) => {
                     v.as_scalar()
                 },
+                ::liquid_core::ValueCow::Array(_) => ::std::option::Option::None,
             }.ok_or_else(||
                 ::liquid_core::error::Error::with_msg("Invalid argument")
                     .context("argument", #liquid_name)
@@ -550,6 +551,9 @@ fn generate_evaluate_field(field: &FilterParameter<'_>) -> TokenStream {
                 ::liquid_core::ValueCow::Borrowed(v) => {
                     ::std::result::Result::Ok(v.to_kstr())
                 },
+                ::liquid_core::ValueCow::Array(v) => {
+                    ::std::result::Result::Ok(v.to_kstr().into_owned().into())
+                },
             }
         },
     };
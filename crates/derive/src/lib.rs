@@ -5,8 +5,10 @@ extern crate proc_macro;
 mod filter;
 mod filter_parameters;
 pub(crate) mod helpers;
+mod object_methods;
 mod object_view;
 mod parse_filter;
+mod to_schema;
 mod value_view;
 
 use proc_macro::TokenStream;
@@ -177,6 +179,8 @@ pub fn derive_parse_filter(item: TokenStream) -> TokenStream {
 ///     - `description` -> the description of the filter
 ///     - `parameters` -> (OPTIONAL) only required if the filter has parameters,
 /// the `FilterParameters` struct
+///     - `pure` -> (OPTIONAL) the filter only depends on its input and arguments,
+/// so it can be evaluated when parsing literals
 ///
 /// # Example
 ///
@@ -186,7 +190,8 @@ pub fn derive_parse_filter(item: TokenStream) -> TokenStream {
 ///     name = "slice",
 ///     description = "Takes a slice of a given string or array.",
 ///     parameters(SliceArgs), // The filter has parameters
-///     parsed(SliceFilter) // Required by `ParseFilter`, not `FilterReflection`
+///     parsed(SliceFilter), // Required by `ParseFilter`, not `FilterReflection`
+///     pure // The filter has no side effects
 /// )]
 /// pub struct Slice;
 /// ```
@@ -230,6 +235,9 @@ pub fn derive_from_filter_parameters(item: TokenStream) -> TokenStream {
 /// If the filter has parameters, the field that holds them must be marked
 /// with `#[parameters]`.
 ///
+/// For filters returning parts of their input, `#[filter(borrows)]` also implements
+/// `Filter` on top of an inherent `evaluate_cow` with the same signature as the trait's.
+///
 /// # Example
 ///
 /// ```ignore
@@ -244,32 +252,129 @@ pub fn derive_from_filter_parameters(item: TokenStream) -> TokenStream {
 ///     // ...
 /// }
 /// ```
-#[proc_macro_derive(Display_filter, attributes(name, parameters))]
+///
+/// Borrowing filter:
+/// ```ignore
+/// #[derive(Debug, Default, Display_filter)]
+/// #[name = "first"]
+/// #[filter(borrows)] // Implements `Filter` with `FirstFilter::evaluate_cow`
+/// struct FirstFilter;
+///
+/// impl FirstFilter {
+///     fn evaluate_cow<'v>(
+///         &'v self,
+///         input: &'v dyn ValueView,
+///         runtime: &'v dyn Runtime,
+///     ) -> Result<ValueCow<'v>> {
+///         // ...
+///     }
+/// }
+/// ```
+#[proc_macro_derive(Display_filter, attributes(name, parameters, filter))]
 pub fn derive_display_filter(item: TokenStream) -> TokenStream {
     let input = syn::parse_macro_input!(item as syn::DeriveInput);
     filter::display::derive(&input).into()
 }
 
-#[proc_macro_derive(CoreValueView)]
+#[proc_macro_derive(CoreValueView, attributes(liquid))]
 pub fn derive_core_value_view(item: TokenStream) -> TokenStream {
     let input = syn::parse_macro_input!(item as syn::DeriveInput);
     value_view::core_derive(&input).into()
 }
 
-#[proc_macro_derive(CoreObjectView)]
+#[proc_macro_derive(CoreObjectView, attributes(liquid))]
 pub fn derive_core_object_view(item: TokenStream) -> TokenStream {
     let input = syn::parse_macro_input!(item as syn::DeriveInput);
     object_view::core_derive(&input).into()
 }
 
-#[proc_macro_derive(ValueView)]
+#[proc_macro_derive(CoreToSchema, attributes(liquid))]
+pub fn derive_core_to_schema(item: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(item as syn::DeriveInput);
+    to_schema::core_derive(&input).into()
+}
+
+/// Implements `ValueView`, depending on the shape of the type:
+/// - Structs with named fields are objects, see [`macro@ObjectView`], which they must also
+///   derive.
+/// - Tuple structs with one field are that field, e.g. `struct Money(i64)` is a number.
+/// - Other tuple structs are arrays of their fields.
+/// - `enum`s render unit variants as their name.  Variants with named fields are objects,
+///   so the `enum` must also derive `ObjectView`.
+///
+/// Variant names take `#[liquid(rename = "...")]` and `#[liquid(rename_all = "...")]` like
+/// fields do.
+#[proc_macro_derive(ValueView, attributes(liquid))]
 pub fn derive_value_view(item: TokenStream) -> TokenStream {
     let input = syn::parse_macro_input!(item as syn::DeriveInput);
     value_view::derive(&input).into()
 }
 
-#[proc_macro_derive(ObjectView)]
+/// Implements `ObjectView`, exposing each field as a property.
+///
+/// Tuple structs with one field forward to it.  Each variant of an `enum` is an object with its
+/// name under a tag key and its fields.
+///
+/// Attributes:
+/// - `#[liquid(methods)]` on the struct also exposes the properties computed by its
+///   `ObjectMethods`, see [`macro@object_methods`].
+/// - `#[liquid(rename_all = "...")]` on the struct changes the case of the property names, e.g.
+///   `camelCase` or `kebab-case` (the same cases as serde).
+/// - `#[liquid(cache)]` on a `DropCache` field memoizes those computed properties.  The field
+///   itself is not exposed.
+/// - `#[liquid(rename = "...")]` on a field exposes it under another name.
+/// - `#[liquid(skip)]` on a field hides it from templates.
+/// - `#[liquid(flatten)]` on a field exposes the properties of that `ObjectView` in place of it.
+/// - `#[liquid(with = "path")]` on a field exposes `path(&field)` instead, computed on first
+///   access.  Requires a `#[liquid(cache)]` field.
+/// - `#[liquid(tag = "...")]` on an `enum` sets the tag key, `type` by default.  Fields of its
+///   variants take `rename` and `skip`.
+#[proc_macro_derive(ObjectView, attributes(liquid))]
 pub fn derive_object_view(item: TokenStream) -> TokenStream {
     let input = syn::parse_macro_input!(item as syn::DeriveInput);
     object_view::derive(&input).into()
 }
+
+/// Implements `ToSchema`, describing the properties `ObjectView` exposes.
+///
+/// Follows the same `#[liquid(...)]` attributes: fields are required properties of their type
+/// (optional for `Option`s), `with` fields accept any value and `flatten`ed fields add their
+/// own properties.  Tuple structs with one field forward to it, other tuple structs are arrays
+/// and `enum`s accept any value.
+#[proc_macro_derive(ToSchema, attributes(liquid))]
+pub fn derive_to_schema(item: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(item as syn::DeriveInput);
+    to_schema::derive(&input).into()
+}
+
+/// Implements `ObjectMethods` for the `#[liquid(method)]` methods of an `impl` block, so
+/// templates can read them as properties computed on demand ("drops").
+///
+/// Methods must take only `&self` and return a `ValueView`.
+///
+/// One method may instead be marked `#[liquid(method_missing)]`: it takes `&self` and the
+/// name of any other property, and returns an `Option` of a `ValueView`.  Variable lookups fall
+/// back to it for keys the object doesn't have.
+///
+/// ```ignore
+/// #[derive(Debug, liquid::ObjectView, liquid::ValueView)]
+/// #[liquid(methods)]
+/// struct ProductDrop {
+///     id: i64,
+///     #[liquid(cache)]
+///     cache: liquid::model::DropCache,
+/// }
+///
+/// #[liquid::object_methods]
+/// impl ProductDrop {
+///     #[liquid(method)]
+///     fn related_products(&self) -> Vec<String> {
+///         expensive_lookup(self.id)
+///     }
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn object_methods(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let item = syn::parse_macro_input!(item as syn::ItemImpl);
+    object_methods::generate(attr.into(), item).into()
+}
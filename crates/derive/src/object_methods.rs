@@ -0,0 +1,152 @@
+use proc_macro2::*;
+use proc_quote::*;
+use syn::*;
+
+pub fn generate(attr: TokenStream, mut item: ItemImpl) -> TokenStream {
+    if !attr.is_empty() {
+        return Error::new_spanned(attr, "`object_methods` takes no arguments.").to_compile_error();
+    }
+    if let Some((_, path, _)) = &item.trait_ {
+        return Error::new_spanned(
+            path,
+            "`object_methods` only supports inherent `impl` blocks.",
+        )
+        .to_compile_error();
+    }
+
+    let mut methods = Vec::new();
+    let mut method_missing = None;
+    for impl_item in &mut item.items {
+        if let ImplItem::Method(method) = impl_item {
+            match take_method_attr(method) {
+                Ok(Some(MethodKind::Method)) => methods.push(method.sig.ident.clone()),
+                Ok(Some(MethodKind::MethodMissing)) => {
+                    if method_missing.is_some() {
+                        return Error::new_spanned(
+                            &method.sig,
+                            "Only one method can be marked `#[liquid(method_missing)]`.",
+                        )
+                        .to_compile_error();
+                    }
+                    method_missing = Some(method.sig.ident.clone());
+                }
+                Ok(None) => (),
+                Err(err) => return err.to_compile_error(),
+            }
+        }
+    }
+
+    let ItemImpl {
+        generics, self_ty, ..
+    } = &item;
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+
+    let method_missing = method_missing.map(|method_missing| {
+        quote! {
+            fn method_missing(&self, name: &str) -> Option<::liquid::model::Value> {
+                self.#method_missing(name)
+                    .map(|value| ::liquid::ValueView::to_value(&value))
+            }
+        }
+    });
+
+    quote! {
+        #item
+
+        impl #impl_generics ::liquid::model::ObjectMethods for #self_ty #where_clause {
+            fn method_names(&self) -> &'static [&'static str] {
+                &[#(stringify!(#methods)),*]
+            }
+
+            fn call_method(&self, name: &str) -> Option<::liquid::model::Value> {
+                match name {
+                    #(
+                        stringify!(#methods) => Some(::liquid::ValueView::to_value(&self.#methods())),
+                    )*
+                    _ => None,
+                }
+            }
+
+            #method_missing
+        }
+    }
+}
+
+/// How a method of an `object_methods` block is exposed.
+enum MethodKind {
+    /// `#[liquid(method)]`: a property named after the method.
+    Method,
+    /// `#[liquid(method_missing)]`: the fallback for any other property.
+    MethodMissing,
+}
+
+/// Removes `#[liquid(method)]` or `#[liquid(method_missing)]` from `method`, returning which
+/// was present.
+fn take_method_attr(method: &mut ImplItemMethod) -> Result<Option<MethodKind>> {
+    let mut found = None;
+    let mut attrs = Vec::with_capacity(method.attrs.len());
+    for attr in method.attrs.drain(..) {
+        if !attr.path.is_ident("liquid") {
+            attrs.push(attr);
+            continue;
+        }
+        let kind = match attr.parse_meta()? {
+            Meta::List(list) if list.nested.len() == 1 => match list.nested.first() {
+                Some(NestedMeta::Meta(Meta::Path(path))) if path.is_ident("method") => {
+                    Some(MethodKind::Method)
+                }
+                Some(NestedMeta::Meta(Meta::Path(path))) if path.is_ident("method_missing") => {
+                    Some(MethodKind::MethodMissing)
+                }
+                _ => None,
+            },
+            _ => None,
+        };
+        match kind {
+            Some(kind) => found = Some((attr, kind)),
+            None => {
+                return Err(Error::new_spanned(
+                    attr,
+                    "Expected `#[liquid(method)]` or `#[liquid(method_missing)]`.",
+                ))
+            }
+        }
+    }
+    method.attrs = attrs;
+
+    let (attr, kind) = match found {
+        Some(found) => found,
+        None => return Ok(None),
+    };
+    let sig = &method.sig;
+    let takes_ref_self = matches!(
+        sig.inputs.first(),
+        Some(FnArg::Receiver(Receiver {
+            reference: Some(_),
+            mutability: None,
+            ..
+        }))
+    );
+    match kind {
+        MethodKind::Method if !takes_ref_self || sig.inputs.len() != 1 => {
+            return Err(Error::new_spanned(
+                &sig.inputs,
+                "`#[liquid(method)]` methods must only take `&self`.",
+            ));
+        }
+        MethodKind::MethodMissing if !takes_ref_self || sig.inputs.len() != 2 => {
+            return Err(Error::new_spanned(
+                &sig.inputs,
+                "`#[liquid(method_missing)]` methods must take `&self` and the property name.",
+            ));
+        }
+        _ => (),
+    }
+    if !sig.generics.params.is_empty() || sig.asyncness.is_some() {
+        return Err(Error::new_spanned(
+            attr,
+            "`#[liquid(method)]` methods cannot be generic or `async`.",
+        ));
+    }
+    Ok(Some(kind))
+}
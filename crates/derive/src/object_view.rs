@@ -1,191 +1,798 @@
 use proc_macro2::*;
 use proc_quote::*;
+use syn::ext::IdentExt;
 use syn::*;
 
+use crate::helpers::*;
+
 pub fn derive(input: &DeriveInput) -> TokenStream {
+    generate(input, quote! { ::liquid })
+}
+
+pub fn core_derive(input: &DeriveInput) -> TokenStream {
+    generate(input, quote! { ::liquid_core })
+}
+
+fn generate(input: &DeriveInput, krate: TokenStream) -> TokenStream {
     let DeriveInput {
         ident,
         data,
         generics,
+        attrs,
         ..
     } = input;
 
     let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
+    let container = match ContainerAttrs::from_attrs(attrs) {
+        Ok(container) => container,
+        Err(err) => return err.to_compile_error(),
+    };
+    let shape = match Shape::of(data) {
+        Ok(shape) => shape,
+        Err(err) => return err.to_compile_error(),
+    };
+    if let Err(err) = container.check(ident, shape) {
+        return err.to_compile_error();
+    }
+    match shape {
+        Shape::Object => (),
+        Shape::Newtype => return generate_newtype(input, &krate),
+        Shape::Array => {
+            return Error::new_spanned(
+                ident,
+                "Tuple structs are arrays, so they only implement `ValueView`.",
+            )
+            .to_compile_error();
+        }
+        Shape::Enum(data) => return generate_enum(input, &krate, &container, data),
+    }
+
     let fields = match get_fields(data) {
         Ok(fields) => fields,
         Err(err) => return err.to_compile_error(),
     };
-    let num_fields = fields.len();
+    let cache = match get_cache_field(data) {
+        Ok(cache) => cache,
+        Err(err) => return err.to_compile_error(),
+    };
+
+    let num_with = fields
+        .iter()
+        .filter(|field| matches!(field.kind, FieldKind::With(_)))
+        .count();
+    let cache = match cache {
+        Some(cache) => Some(cache),
+        None if container.methods => {
+            return Error::new_spanned(
+                ident,
+                "`#[liquid(methods)]` requires a `#[liquid(cache)]` field of type `DropCache`.",
+            )
+            .to_compile_error();
+        }
+        None if num_with != 0 => {
+            return Error::new_spanned(
+                ident,
+                "`#[liquid(with = \"...\")]` requires a `#[liquid(cache)]` field of type `DropCache`.",
+            )
+            .to_compile_error();
+        }
+        None => None,
+    };
+    // Computed fields come first in the cache, then methods.
+    let num_slots = if container.methods {
+        quote! { (#num_with + #krate::model::ObjectMethods::method_names(self).len()) }
+    } else {
+        quote! { #num_with }
+    };
+
+    let mut num_fields = 0_usize;
+    let mut flattened = Vec::new();
+    let mut keys = Vec::new();
+    let mut values = Vec::new();
+    let mut get_arms = Vec::new();
+    let mut slot = 0_usize;
+    for field in &fields {
+        let field_ident = field.ident;
+        let name = field.name(container.rename_all);
+        let value = match &field.kind {
+            FieldKind::Flatten => {
+                flattened.push(field_ident);
+                continue;
+            }
+            FieldKind::Field => quote! { &self.#field_ident },
+            FieldKind::With(with) => {
+                let cache = cache.expect("Checked above.");
+                let value = quote! {
+                    self.#cache
+                        .get_or_init(#slot, #num_slots, || {
+                            Some(#krate::ValueView::to_value(&#with(&self.#field_ident)))
+                        })
+                        .expect("Computed fields always have a value.")
+                };
+                slot += 1;
+                value
+            }
+        };
+        if keys.contains(&name) {
+            return Error::new_spanned(field_ident, format!("Duplicate property `{}`.", name))
+                .to_compile_error();
+        }
+        num_fields += 1;
+        keys.push(name.clone());
+        values.push(value.clone());
+        get_arms.push(quote! { #name => Some(#value), });
+    }
+
+    let (num_methods, method_keys, method_values, method_iter, has_method, get_method, get_missing) =
+        if container.methods {
+            let cache = cache.expect("Checked above.");
+            // Methods are computed as they are iterated.
+            let method = quote! {
+                move |(index, name): (usize, &&'static str)| {
+                    self.#cache
+                        .get_or_init(#num_with + index, #num_slots, || {
+                            #krate::model::ObjectMethods::call_method(self, name)
+                        })
+                        .map(|value| (*name, value))
+                }
+            };
+            (
+                quote! { #krate::model::ObjectMethods::method_names(self).len() },
+                quote! {
+                    keys.extend(
+                        #krate::model::ObjectMethods::method_names(self)
+                            .iter()
+                            .map(|name| #krate::model::KStringCow::from_static(name)),
+                    );
+                },
+                quote! {
+                    let values = values.chain(
+                        #krate::model::ObjectMethods::method_names(self)
+                            .iter()
+                            .enumerate()
+                            .filter_map(#method)
+                            .map(|(_, value)| value as &dyn #krate::ValueView),
+                    );
+                },
+                quote! {
+                    let values = values.chain(
+                        #krate::model::ObjectMethods::method_names(self)
+                            .iter()
+                            .enumerate()
+                            .filter_map(#method)
+                            .map(|(name, value)| {
+                                (#krate::model::KStringCow::from_static(name), value as &dyn #krate::ValueView)
+                            }),
+                    );
+                },
+                quote! { #krate::model::ObjectMethods::method_names(self).contains(&index) },
+                quote! {
+                    #krate::model::ObjectMethods::method_names(self)
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, name)| **name == index)
+                        .find_map(#method)
+                        .map(|(_, value)| value as &dyn #krate::ValueView)
+                },
+                quote! {
+                    fn get_missing<'liquid_derive_s>(&'liquid_derive_s self, index: &str) -> Option<#krate::model::ValueCow<'liquid_derive_s>> {
+                        #krate::model::ObjectMethods::method_missing(self, index)
+                            .map(#krate::model::ValueCow::Owned)
+                    }
+                },
+            )
+        } else {
+            (
+                quote! { 0 },
+                quote! {},
+                quote! {},
+                quote! {},
+                quote! { false },
+                quote! { None },
+                quote! {},
+            )
+        };
 
     quote! {
-        impl #impl_generics ::liquid::ObjectView for #ident #ty_generics #where_clause {
-            fn as_value(&self) -> &dyn ::liquid::ValueView {
+        impl #impl_generics #krate::ObjectView for #ident #ty_generics #where_clause {
+            fn as_value(&self) -> &dyn #krate::ValueView {
                 self
             }
 
             fn size(&self) -> i64 {
-                #num_fields as i64
+                let size = #num_fields + #num_methods;
+                #(
+                    let size = size + #krate::ObjectView::size(&self.#flattened) as usize;
+                )*
+                size as i64
             }
 
-            fn keys<'liquid_derive_k>(&'liquid_derive_k self) -> Box<dyn Iterator<Item = ::liquid::model::KStringCow<'liquid_derive_k>> + 'liquid_derive_k> {
+            fn keys<'liquid_derive_k>(&'liquid_derive_k self) -> Box<dyn Iterator<Item = #krate::model::KStringCow<'liquid_derive_k>> + 'liquid_derive_k> {
                 let mut keys = Vec::with_capacity(#num_fields);
                 #(
-                    keys.push(::liquid::model::KStringCow::from_static(stringify!(#fields)));
+                    keys.push(#krate::model::KStringCow::from_static(#keys));
+                )*
+                #(
+                    keys.extend(#krate::ObjectView::keys(&self.#flattened));
                 )*
+                #method_keys
                 Box::new(keys.into_iter())
             }
 
-            fn values<'liquid_derive_k>(&'liquid_derive_k self) -> Box<dyn Iterator<Item = &'liquid_derive_k dyn ::liquid::ValueView> + 'liquid_derive_k> {
-                let mut values = Vec::<&dyn ::liquid::ValueView>::with_capacity(#num_fields);
+            fn values<'liquid_derive_k>(&'liquid_derive_k self) -> Box<dyn Iterator<Item = &'liquid_derive_k dyn #krate::ValueView> + 'liquid_derive_k> {
+                let mut values = Vec::<&dyn #krate::ValueView>::with_capacity(#num_fields);
                 #(
-                    values.push(&self.#fields);
+                    values.push(#values);
                 )*
-                Box::new(values.into_iter())
+                #(
+                    values.extend(#krate::ObjectView::values(&self.#flattened));
+                )*
+                let values = values.into_iter();
+                #method_values
+                Box::new(values)
             }
 
-            fn iter<'liquid_derive_k>(&'liquid_derive_k self) -> Box<dyn Iterator<Item = (::liquid::model::KStringCow<'liquid_derive_k>, &'liquid_derive_k dyn ::liquid::ValueView)> + 'liquid_derive_k> {
-                let mut values = Vec::<(::liquid::model::KStringCow<'liquid_derive_k>, &'liquid_derive_k dyn ::liquid::ValueView)>::with_capacity(#num_fields);
+            fn iter<'liquid_derive_k>(&'liquid_derive_k self) -> Box<dyn Iterator<Item = (#krate::model::KStringCow<'liquid_derive_k>, &'liquid_derive_k dyn #krate::ValueView)> + 'liquid_derive_k> {
+                let mut values = Vec::<(#krate::model::KStringCow<'liquid_derive_k>, &'liquid_derive_k dyn #krate::ValueView)>::with_capacity(#num_fields);
                 #(
                     values.push((
-                        ::liquid::model::KStringCow::from_static(stringify!(#fields)),
-                        &self.#fields,
+                        #krate::model::KStringCow::from_static(#keys),
+                        #values,
                     ));
                 )*
-                Box::new(values.into_iter())
+                #(
+                    values.extend(#krate::ObjectView::iter(&self.#flattened));
+                )*
+                let values = values.into_iter();
+                #method_iter
+                Box::new(values)
             }
 
             fn contains_key(&self, index: &str) -> bool {
                 match index {
                     #(
-                        stringify!(#fields) => true,
+                        #keys => true,
                     )*
-                    _ => false,
+                    _ => {
+                        #(
+                            if #krate::ObjectView::contains_key(&self.#flattened, index) {
+                                return true;
+                            }
+                        )*
+                        #has_method
+                    }
                 }
             }
 
-            fn get<'liquid_derive_s>(&'liquid_derive_s self, index: &str) -> Option<&'liquid_derive_s dyn ::liquid::ValueView> {
+            fn get<'liquid_derive_s>(&'liquid_derive_s self, index: &str) -> Option<&'liquid_derive_s dyn #krate::ValueView> {
                 match index {
                     #(
-                        stringify!(#fields) => Some(&self.#fields),
+                        #get_arms
                     )*
-                    _ => None,
+                    _ => {
+                        #(
+                            if let Some(value) = #krate::ObjectView::get(&self.#flattened, index) {
+                                return Some(value);
+                            }
+                        )*
+                        #get_method
+                    }
                 }
             }
+
+            #get_missing
         }
     }
 }
 
-pub fn core_derive(input: &DeriveInput) -> TokenStream {
+/// Forwards to the only field.
+fn generate_newtype(input: &DeriveInput, krate: &TokenStream) -> TokenStream {
     let DeriveInput {
-        ident,
-        data,
-        generics,
-        ..
+        ident, generics, ..
     } = input;
-
     let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
-    let fields = match get_fields(data) {
-        Ok(fields) => fields,
-        Err(err) => return err.to_compile_error(),
-    };
-    let num_fields = fields.len();
-
     quote! {
-        impl #impl_generics ::liquid_core::ObjectView for #ident #ty_generics #where_clause {
-            fn as_value(&self) -> &dyn ::liquid_core::ValueView {
+        impl #impl_generics #krate::ObjectView for #ident #ty_generics #where_clause {
+            fn as_value(&self) -> &dyn #krate::ValueView {
                 self
             }
 
             fn size(&self) -> i64 {
-                #num_fields as i64
+                #krate::ObjectView::size(&self.0)
             }
 
-            fn keys<'liquid_derive_k>(&'liquid_derive_k self) -> Box<dyn Iterator<Item = ::liquid_core::model::KStringCow<'liquid_derive_k>> + 'liquid_derive_k> {
-                let mut keys = Vec::with_capacity(#num_fields);
-                #(
-                    keys.push(::liquid_core::model::KStringCow::from_static(stringify!(#fields)));
-                )*
-                Box::new(keys.into_iter())
+            fn keys<'liquid_derive_k>(&'liquid_derive_k self) -> Box<dyn Iterator<Item = #krate::model::KStringCow<'liquid_derive_k>> + 'liquid_derive_k> {
+                #krate::ObjectView::keys(&self.0)
             }
 
-            fn values<'liquid_derive_k>(&'liquid_derive_k self) -> Box<dyn Iterator<Item = &'liquid_derive_k dyn ::liquid_core::ValueView> + 'liquid_derive_k> {
-                let mut values = Vec::<&dyn ::liquid_core::ValueView>::with_capacity(#num_fields);
-                #(
-                    values.push(&self.#fields);
-                )*
-                Box::new(values.into_iter())
+            fn values<'liquid_derive_k>(&'liquid_derive_k self) -> Box<dyn Iterator<Item = &'liquid_derive_k dyn #krate::ValueView> + 'liquid_derive_k> {
+                #krate::ObjectView::values(&self.0)
             }
 
-            fn iter<'liquid_derive_k>(&'liquid_derive_k self) -> Box<dyn Iterator<Item = (::liquid_core::model::KStringCow<'liquid_derive_k>, &'liquid_derive_k dyn ::liquid_core::ValueView)> + 'liquid_derive_k> {
-                let mut values = Vec::<(::liquid_core::model::KStringCow<'liquid_derive_k>, &'liquid_derive_k dyn ::liquid_core::ValueView)>::with_capacity(#num_fields);
-                #(
-                    values.push((
-                        ::liquid_core::model::KStringCow::from_static(stringify!(#fields)),
-                        &self.#fields,
-                    ));
-                )*
-                Box::new(values.into_iter())
+            fn iter<'liquid_derive_k>(&'liquid_derive_k self) -> Box<dyn Iterator<Item = (#krate::model::KStringCow<'liquid_derive_k>, &'liquid_derive_k dyn #krate::ValueView)> + 'liquid_derive_k> {
+                #krate::ObjectView::iter(&self.0)
             }
 
             fn contains_key(&self, index: &str) -> bool {
-                match index {
+                #krate::ObjectView::contains_key(&self.0, index)
+            }
+
+            fn get<'liquid_derive_s>(&'liquid_derive_s self, index: &str) -> Option<&'liquid_derive_s dyn #krate::ValueView> {
+                #krate::ObjectView::get(&self.0, index)
+            }
+
+            fn get_cow<'liquid_derive_s>(&'liquid_derive_s self, index: &str) -> Option<#krate::model::ValueCow<'liquid_derive_s>> {
+                #krate::ObjectView::get_cow(&self.0, index)
+            }
+
+            fn get_missing<'liquid_derive_s>(&'liquid_derive_s self, index: &str) -> Option<#krate::model::ValueCow<'liquid_derive_s>> {
+                #krate::ObjectView::get_missing(&self.0, index)
+            }
+        }
+    }
+}
+
+/// Exposes each variant as an object with the variant name under the tag key and its fields.
+fn generate_enum(
+    input: &DeriveInput,
+    krate: &TokenStream,
+    container: &ContainerAttrs,
+    data: &DataEnum,
+) -> TokenStream {
+    let DeriveInput {
+        ident, generics, ..
+    } = input;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let variants = match get_variants(data, container) {
+        Ok(variants) => variants,
+        Err(err) => return err.to_compile_error(),
+    };
+    let tag = container.tag();
+
+    let mut patterns = Vec::new();
+    let mut bindings = Vec::new();
+    let mut sizes = Vec::new();
+    let mut keys = Vec::new();
+    let mut values = Vec::new();
+    for variant in &variants {
+        let variant_ident = variant.ident;
+        let field_idents: Vec<_> = variant
+            .fields
+            .iter()
+            .flatten()
+            .map(|(_, ident)| *ident)
+            .collect();
+        let field_names: Vec<_> = variant
+            .fields
+            .iter()
+            .flatten()
+            .map(|(name, _)| name)
+            .collect();
+        if field_names.iter().any(|name| *name == tag) {
+            return Error::new_spanned(
+                variant_ident,
+                format!("Duplicate property `{}`, the tag of the `enum`.", tag),
+            )
+            .to_compile_error();
+        }
+        match variant.fields {
+            Some(_) => {
+                patterns.push(quote! { Self::#variant_ident { .. } });
+                bindings.push(quote! { Self::#variant_ident { #(#field_idents,)* .. } });
+            }
+            None => {
+                patterns.push(quote! { Self::#variant_ident });
+                bindings.push(quote! { Self::#variant_ident });
+            }
+        }
+        sizes.push(1 + field_idents.len());
+        let name = &variant.name;
+        keys.push(quote! { #tag #(, #field_names)* });
+        values.push(quote! {
+            &#name as &dyn #krate::ValueView
+            #(, #field_idents as &dyn #krate::ValueView)*
+        });
+    }
+
+    quote! {
+        impl #impl_generics #krate::ObjectView for #ident #ty_generics #where_clause {
+            fn as_value(&self) -> &dyn #krate::ValueView {
+                self
+            }
+
+            fn size(&self) -> i64 {
+                match self {
                     #(
-                        stringify!(#fields) => true,
+                        #patterns => #sizes as i64,
                     )*
-                    _ => false,
                 }
             }
 
-            fn get<'liquid_derive_s>(&'liquid_derive_s self, index: &str) -> Option<&'liquid_derive_s dyn ::liquid_core::ValueView> {
-                match index {
+            fn keys<'liquid_derive_k>(&'liquid_derive_k self) -> Box<dyn Iterator<Item = #krate::model::KStringCow<'liquid_derive_k>> + 'liquid_derive_k> {
+                let keys: &'static [&'static str] = match self {
                     #(
-                        stringify!(#fields) => Some(&self.#fields),
+                        #patterns => &[#keys],
                     )*
-                    _ => None,
-                }
+                };
+                Box::new(keys.iter().map(|key| #krate::model::KStringCow::from_static(key)))
+            }
+
+            fn values<'liquid_derive_k>(&'liquid_derive_k self) -> Box<dyn Iterator<Item = &'liquid_derive_k dyn #krate::ValueView> + 'liquid_derive_k> {
+                let values = match self {
+                    #(
+                        #bindings => vec![#values],
+                    )*
+                };
+                Box::new(values.into_iter())
+            }
+
+            fn iter<'liquid_derive_k>(&'liquid_derive_k self) -> Box<dyn Iterator<Item = (#krate::model::KStringCow<'liquid_derive_k>, &'liquid_derive_k dyn #krate::ValueView)> + 'liquid_derive_k> {
+                let iter = #krate::ObjectView::keys(self).zip(#krate::ObjectView::values(self));
+                Box::new(iter)
+            }
+
+            fn contains_key(&self, index: &str) -> bool {
+                #krate::ObjectView::keys(self).any(|key| key.as_str() == index)
             }
+
+            fn get<'liquid_derive_s>(&'liquid_derive_s self, index: &str) -> Option<&'liquid_derive_s dyn #krate::ValueView> {
+                #krate::ObjectView::iter(self)
+                    .find(|(key, _)| key.as_str() == index)
+                    .map(|(_, value)| value)
+            }
+        }
+    }
+}
+
+/// How a type is exposed to templates.
+#[derive(Clone, Copy)]
+pub(crate) enum Shape<'d> {
+    /// A struct with named fields, exposed as an object.
+    Object,
+    /// A tuple struct with one field, exposed as that field.
+    Newtype,
+    /// A tuple struct, exposed as an array.
+    Array,
+    /// An enum: unit variants are exposed as their name, others as tagged objects.
+    Enum(&'d DataEnum),
+}
+
+impl<'d> Shape<'d> {
+    pub(crate) fn of(data: &'d Data) -> Result<Self> {
+        match data {
+            Data::Struct(data) => match &data.fields {
+                Fields::Named(_) => Ok(Shape::Object),
+                Fields::Unnamed(fields) if fields.unnamed.len() == 1 => Ok(Shape::Newtype),
+                Fields::Unnamed(_) => Ok(Shape::Array),
+                Fields::Unit => Err(Error::new_spanned(
+                    &data.fields,
+                    "`ObjectView` support for unit-structs is unimplemented.",
+                )),
+            },
+            Data::Enum(data) => Ok(Shape::Enum(data)),
+            Data::Union(data) => Err(Error::new_spanned(
+                data.union_token,
+                "Unions cannot impl ObjectView.",
+            )),
         }
     }
 }
 
-pub(crate) fn get_fields(data: &Data) -> Result<Vec<&Ident>> {
-    let fields = match data {
-        Data::Struct(data) => &data.fields,
-        Data::Enum(data) => {
+/// Options from `#[liquid(...)]` on the type.
+#[derive(Default)]
+pub(crate) struct ContainerAttrs {
+    /// Expose the `ObjectMethods` of the type as properties.
+    methods: bool,
+    /// Case of the property names of fields, or of variant names for `enum`s, without
+    /// `#[liquid(rename = "...")]`.
+    pub(crate) rename_all: Option<RenameRule>,
+    /// Key holding the variant name of `enum`s exposed as objects.
+    tag: Option<String>,
+}
+
+impl ContainerAttrs {
+    pub(crate) fn from_attrs(attrs: &[Attribute]) -> Result<Self> {
+        let mut methods = false;
+        let mut rename_all = AssignOnce::Unset;
+        let mut tag = AssignOnce::Unset;
+        for meta in liquid_attrs(attrs)? {
+            match meta {
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("methods") => {
+                    methods = true;
+                }
+                NestedMeta::Meta(Meta::NameValue(MetaNameValue { path, lit, .. }))
+                    if path.is_ident("rename_all") =>
+                {
+                    let key = path.get_ident().expect("Checked above.");
+                    parse_str_value(&mut rename_all, key, &lit)?;
+                }
+                NestedMeta::Meta(Meta::NameValue(MetaNameValue { path, lit, .. }))
+                    if path.is_ident("tag") =>
+                {
+                    let key = path.get_ident().expect("Checked above.");
+                    assign_str_value(&mut tag, key, &lit)?;
+                }
+                meta => return Err(Error::new_spanned(meta, "Unknown `liquid` attribute.")),
+            }
+        }
+        Ok(Self {
+            methods,
+            rename_all: rename_all.into_option(),
+            tag: tag.into_option(),
+        })
+    }
+
+    /// Checks the options apply to `shape`.
+    pub(crate) fn check(&self, ident: &Ident, shape: Shape<'_>) -> Result<()> {
+        let is_object = matches!(shape, Shape::Object);
+        let is_enum = matches!(shape, Shape::Enum(_));
+        if self.methods && !is_object {
             return Err(Error::new_spanned(
-                data.enum_token,
-                "`ObjectView` support for `enum` is unimplemented.",
+                ident,
+                "`#[liquid(methods)]` is only supported on structs with named fields.",
             ));
         }
-        Data::Union(data) => {
+        if self.rename_all.is_some() && !is_object && !is_enum {
             return Err(Error::new_spanned(
-                data.union_token,
-                "Unions cannot impl ObjectView.",
+                ident,
+                "`#[liquid(rename_all = \"...\")]` is only supported on structs with named fields and `enum`s.",
             ));
         }
-    };
+        if self.tag.is_some() && !is_enum {
+            return Err(Error::new_spanned(
+                ident,
+                "`#[liquid(tag = \"...\")]` is only supported on `enum`s.",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Key holding the variant name of `enum`s exposed as objects.
+    pub(crate) fn tag(&self) -> &str {
+        self.tag.as_deref().unwrap_or("type")
+    }
+}
 
-    let fields = match fields {
-        Fields::Named(fields) => fields,
-        Fields::Unnamed(fields) => {
+/// How a field is exposed.
+pub(crate) enum FieldKind {
+    /// As a property holding the field.
+    Field,
+    /// As a property holding the result of the conversion function, computed on first access.
+    With(ExprPath),
+    /// The field's own properties are exposed in place of the field.
+    Flatten,
+}
+
+/// A field exposed to templates.
+pub(crate) struct ExposedField<'f> {
+    pub(crate) ident: &'f Ident,
+    pub(crate) ty: &'f Type,
+    rename: Option<String>,
+    pub(crate) kind: FieldKind,
+}
+
+impl<'f> ExposedField<'f> {
+    /// The property name.
+    pub(crate) fn name(&self, rename_all: Option<RenameRule>) -> String {
+        let name = self.ident.unraw().to_string();
+        match (&self.rename, rename_all) {
+            (Some(rename), _) => rename.clone(),
+            (None, Some(rule)) => rule.apply(&name),
+            (None, None) => name,
+        }
+    }
+}
+
+/// Options from `#[liquid(...)]` on a field.
+#[derive(Default)]
+struct FieldAttrs {
+    cache: bool,
+    skip: bool,
+    flatten: bool,
+    rename: AssignOnce<String>,
+    with: AssignOnce<ExprPath>,
+}
+
+impl FieldAttrs {
+    fn from_field(field: &Field) -> Result<Self> {
+        let mut attrs = Self::default();
+        for meta in liquid_attrs(&field.attrs)? {
+            match meta {
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("cache") => attrs.cache = true,
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("skip") => attrs.skip = true,
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("flatten") => {
+                    attrs.flatten = true
+                }
+                NestedMeta::Meta(Meta::NameValue(MetaNameValue { path, lit, .. }))
+                    if path.is_ident("rename") =>
+                {
+                    let key = path.get_ident().expect("Checked above.");
+                    assign_str_value(&mut attrs.rename, key, &lit)?;
+                }
+                NestedMeta::Meta(Meta::NameValue(MetaNameValue { path, lit, .. }))
+                    if path.is_ident("with") =>
+                {
+                    let key = path.get_ident().expect("Checked above.");
+                    let with = match &lit {
+                        Lit::Str(with) => with.parse()?,
+                        _ => return Err(Error::new_spanned(lit, "Expected string literal.")),
+                    };
+                    attrs.with.set(with, || {
+                        Error::new_spanned(key, format!("Element `{}` was already defined.", key))
+                    })?;
+                }
+                meta => return Err(Error::new_spanned(meta, "Unknown `liquid` attribute.")),
+            }
+        }
+
+        let renamed = matches!(attrs.rename, AssignOnce::Set(_));
+        let with = matches!(attrs.with, AssignOnce::Set(_));
+        if attrs.cache && (attrs.skip || attrs.flatten || renamed || with) {
             return Err(Error::new_spanned(
-                fields,
-                "`ObjectView` support for tuple-structs is unimplemented.",
-            ))
+                field,
+                "`#[liquid(cache)]` fields are never exposed, so they take no other attributes.",
+            ));
         }
-        Fields::Unit => {
+        if attrs.skip && (attrs.flatten || renamed || with) {
             return Err(Error::new_spanned(
-                fields,
-                "`ObjectView` support for unit-structs is unimplemented.",
-            ))
+                field,
+                "`#[liquid(skip)]` fields take no other attributes.",
+            ));
         }
-    };
+        if attrs.flatten && (renamed || with) {
+            return Err(Error::new_spanned(
+                field,
+                "`#[liquid(flatten)]` cannot be combined with `rename` or `with`.",
+            ));
+        }
+        Ok(attrs)
+    }
+}
 
-    Ok(fields
-        .named
-        .iter()
-        .map(|field| field.ident.as_ref().expect("Fields are named."))
-        .collect())
+/// The contents of all `#[liquid(...)]` attributes.
+fn liquid_attrs(attrs: &[Attribute]) -> Result<Vec<NestedMeta>> {
+    let mut metas = Vec::new();
+    for attr in attrs.iter().filter(|attr| attr.path.is_ident("liquid")) {
+        match attr.parse_meta()? {
+            Meta::List(list) => metas.extend(list.nested),
+            meta => return Err(Error::new_spanned(meta, "Expected `#[liquid(...)]`.")),
+        }
+    }
+    Ok(metas)
+}
+
+fn get_named_fields(data: &Data) -> Result<&FieldsNamed> {
+    match data {
+        Data::Struct(DataStruct {
+            fields: Fields::Named(fields),
+            ..
+        }) => Ok(fields),
+        _ => unreachable!("Only called for `Shape::Object`."),
+    }
+}
+
+/// Fields exposed as properties.
+pub(crate) fn get_fields(data: &Data) -> Result<Vec<ExposedField<'_>>> {
+    let mut fields = Vec::new();
+    for field in &get_named_fields(data)?.named {
+        let attrs = FieldAttrs::from_field(field)?;
+        if attrs.cache || attrs.skip {
+            continue;
+        }
+        let kind = if attrs.flatten {
+            FieldKind::Flatten
+        } else if let Some(with) = attrs.with.into_option() {
+            FieldKind::With(with)
+        } else {
+            FieldKind::Field
+        };
+        fields.push(ExposedField {
+            ident: field.ident.as_ref().expect("Fields are named."),
+            ty: &field.ty,
+            rename: attrs.rename.into_option(),
+            kind,
+        });
+    }
+    Ok(fields)
+}
+
+/// The `#[liquid(cache)]` field memoizing computed properties, if any.
+fn get_cache_field(data: &Data) -> Result<Option<&Ident>> {
+    let mut cache = None;
+    for field in &get_named_fields(data)?.named {
+        if FieldAttrs::from_field(field)?.cache {
+            if cache.is_some() {
+                return Err(Error::new_spanned(
+                    field,
+                    "Only one field can be marked `#[liquid(cache)]`.",
+                ));
+            }
+            cache = field.ident.as_ref();
+        }
+    }
+    Ok(cache)
+}
+
+/// A variant of an `enum`.
+pub(crate) struct ExposedVariant<'v> {
+    pub(crate) ident: &'v Ident,
+    /// The variant name, as seen by templates.
+    pub(crate) name: String,
+    /// Property names and fields of struct variants, `None` for unit variants.
+    pub(crate) fields: Option<Vec<(String, &'v Ident)>>,
+}
+
+/// Variants of an `enum`, with the options from their `#[liquid(...)]` attributes.
+pub(crate) fn get_variants<'v>(
+    data: &'v DataEnum,
+    container: &ContainerAttrs,
+) -> Result<Vec<ExposedVariant<'v>>> {
+    let mut variants = Vec::new();
+    for variant in &data.variants {
+        let mut rename = AssignOnce::Unset;
+        for meta in liquid_attrs(&variant.attrs)? {
+            match meta {
+                NestedMeta::Meta(Meta::NameValue(MetaNameValue { path, lit, .. }))
+                    if path.is_ident("rename") =>
+                {
+                    let key = path.get_ident().expect("Checked above.");
+                    assign_str_value(&mut rename, key, &lit)?;
+                }
+                meta => return Err(Error::new_spanned(meta, "Unknown `liquid` attribute.")),
+            }
+        }
+        let name = match (rename.into_option(), container.rename_all) {
+            (Some(rename), _) => rename,
+            (None, Some(rule)) => rule.apply(&variant.ident.unraw().to_string()),
+            (None, None) => variant.ident.unraw().to_string(),
+        };
+
+        let fields = match &variant.fields {
+            Fields::Named(named) => {
+                let mut fields = Vec::new();
+                for field in &named.named {
+                    let attrs = FieldAttrs::from_field(field)?;
+                    if attrs.cache || attrs.flatten || matches!(attrs.with, AssignOnce::Set(_)) {
+                        return Err(Error::new_spanned(
+                            field,
+                            "Fields of `enum` variants only support `rename` and `skip`.",
+                        ));
+                    }
+                    if attrs.skip {
+                        continue;
+                    }
+                    let field_ident = field.ident.as_ref().expect("Fields are named.");
+                    let field_name = attrs
+                        .rename
+                        .into_option()
+                        .unwrap_or_else(|| field_ident.unraw().to_string());
+                    if fields.iter().any(|(name, _)| *name == field_name) {
+                        return Err(Error::new_spanned(
+                            field_ident,
+                            format!("Duplicate property `{}`.", field_name),
+                        ));
+                    }
+                    fields.push((field_name, field_ident));
+                }
+                Some(fields)
+            }
+            Fields::Unnamed(fields) => {
+                return Err(Error::new_spanned(
+                    fields,
+                    "Tuple variants are unsupported, use named fields.",
+                ));
+            }
+            Fields::Unit => None,
+        };
+
+        variants.push(ExposedVariant {
+            ident: &variant.ident,
+            name,
+            fields,
+        });
+    }
+    Ok(variants)
 }
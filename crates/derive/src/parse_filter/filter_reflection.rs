@@ -6,6 +6,7 @@ fn generate_reflection(filter_parser: &ParseFilter<'_>) -> Result<TokenStream> {
         filter_name,
         filter_description,
         parameters_struct_name,
+        pure,
         ..
     } = &filter_parser.meta;
 
@@ -42,6 +43,10 @@ fn generate_reflection(filter_parser: &ParseFilter<'_>) -> Result<TokenStream> {
             fn keyword_parameters(&self) -> &'static [::liquid_core::parser::ParameterReflection] {
                 #keyword_parameters
             }
+
+            fn is_pure(&self) -> bool {
+                #pure
+            }
         }
     })
 }
@@ -85,6 +85,7 @@ struct ParseFilterMeta {
     filter_description: Result<String>,
     parameters_struct_name: Option<Ident>,
     filter_struct_name: Result<Ident>,
+    pure: bool,
 }
 
 impl ParseFilterMeta {
@@ -113,6 +114,7 @@ impl ParseFilterMeta {
         let mut description = AssignOnce::Unset;
         let mut parameters = AssignOnce::Unset;
         let mut parsed = AssignOnce::Unset;
+        let mut pure = false;
 
         for meta in meta.nested.into_iter() {
             match meta {
@@ -185,6 +187,13 @@ impl ParseFilterMeta {
                     }
                 }
 
+                NestedMeta::Meta(Meta::Path(ref path)) if path.is_ident("pure") => {
+                    if pure {
+                        return Err(Error::new_spanned(path, "Multiple definitions for `pure`."));
+                    }
+                    pure = true;
+                }
+
                 _ => {
                     return Err(Error::new_spanned(
                         meta,
@@ -213,6 +222,7 @@ impl ParseFilterMeta {
             filter_description,
             parameters_struct_name,
             filter_struct_name,
+            pure,
         })
     }
 }
@@ -0,0 +1,90 @@
+use proc_macro2::*;
+use proc_quote::*;
+use syn::*;
+
+use crate::object_view::{get_fields, ContainerAttrs, FieldKind, Shape};
+
+pub fn derive(input: &DeriveInput) -> TokenStream {
+    generate(input, quote! { ::liquid })
+}
+
+pub fn core_derive(input: &DeriveInput) -> TokenStream {
+    generate(input, quote! { ::liquid_core })
+}
+
+fn generate(input: &DeriveInput, krate: TokenStream) -> TokenStream {
+    let DeriveInput {
+        ident,
+        data,
+        generics,
+        attrs,
+        ..
+    } = input;
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let container = match ContainerAttrs::from_attrs(attrs) {
+        Ok(container) => container,
+        Err(err) => return err.to_compile_error(),
+    };
+    let shape = match Shape::of(data) {
+        Ok(shape) => shape,
+        Err(err) => return err.to_compile_error(),
+    };
+    if let Err(err) = container.check(ident, shape) {
+        return err.to_compile_error();
+    }
+
+    let schema = match shape {
+        Shape::Object => {
+            let fields = match get_fields(data) {
+                Ok(fields) => fields,
+                Err(err) => return err.to_compile_error(),
+            };
+            let properties = fields.iter().map(|field| {
+                let ty = field.ty;
+                let name = field.name(container.rename_all);
+                match &field.kind {
+                    FieldKind::Field => quote! { .field::<#ty>(#name) },
+                    FieldKind::With(_) => {
+                        quote! { .property(#name, #krate::model::Schema::any()) }
+                    }
+                    FieldKind::Flatten => quote! {
+                        .flatten(<#ty as #krate::model::ToSchema>::schema())
+                    },
+                }
+            });
+            quote! { #krate::model::Schema::object() #(#properties)* }
+        }
+        Shape::Newtype => {
+            let ty = match data {
+                Data::Struct(data) => &data.fields.iter().next().expect("Checked above.").ty,
+                _ => unreachable!("Checked above."),
+            };
+            return quote! {
+                impl #impl_generics #krate::model::ToSchema for #ident #ty_generics #where_clause {
+                    fn schema() -> #krate::model::Schema {
+                        <#ty as #krate::model::ToSchema>::schema()
+                    }
+
+                    fn required() -> bool {
+                        <#ty as #krate::model::ToSchema>::required()
+                    }
+                }
+            };
+        }
+        Shape::Array => quote! {
+            #krate::model::Schema::new(#krate::model::SchemaType::Array)
+        },
+        // Unit variants are strings while the others are objects.
+        Shape::Enum(_) => quote! { #krate::model::Schema::any() },
+    };
+
+    quote! {
+        impl #impl_generics #krate::model::ToSchema for #ident #ty_generics #where_clause {
+            fn schema() -> #krate::model::Schema {
+                #schema
+            }
+        }
+    }
+}
@@ -2,117 +2,372 @@ use proc_macro2::*;
 use proc_quote::*;
 use syn::*;
 
+use crate::object_view::{ContainerAttrs, Shape};
+
 pub fn derive(input: &DeriveInput) -> TokenStream {
+    generate(input, quote! { ::liquid })
+}
+
+pub fn core_derive(input: &DeriveInput) -> TokenStream {
+    generate(input, quote! { ::liquid_core })
+}
+
+fn generate(input: &DeriveInput, krate: TokenStream) -> TokenStream {
     let DeriveInput {
-        ident,
-        data,
-        generics,
-        ..
+        ident, data, attrs, ..
     } = input;
 
-    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
-
-    let fields = match crate::object_view::get_fields(data) {
-        Ok(fields) => fields,
+    let container = match ContainerAttrs::from_attrs(attrs) {
+        Ok(container) => container,
+        Err(err) => return err.to_compile_error(),
+    };
+    let shape = match Shape::of(data) {
+        Ok(shape) => shape,
         Err(err) => return err.to_compile_error(),
     };
+    if let Err(err) = container.check(ident, shape) {
+        return err.to_compile_error();
+    }
+
+    match shape {
+        Shape::Object => {
+            if let Err(err) = crate::object_view::get_fields(data) {
+                return err.to_compile_error();
+            }
+            generate_object(input, &krate)
+        }
+        Shape::Newtype => generate_newtype(input, &krate),
+        Shape::Array => generate_array(input, &krate),
+        Shape::Enum(data) => generate_enum(input, &krate, &container, data),
+    }
+}
+
+/// An object, through its `ObjectView`.
+fn generate_object(input: &DeriveInput, krate: &TokenStream) -> TokenStream {
+    let DeriveInput {
+        ident, generics, ..
+    } = input;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
     quote! {
-        impl #impl_generics ::liquid::ValueView for #ident #ty_generics #where_clause {
+        impl #impl_generics #krate::ValueView for #ident #ty_generics #where_clause {
             fn as_debug(&self) -> &dyn ::std::fmt::Debug {
                 self
             }
 
-            fn render(&self) -> ::liquid::model::DisplayCow<'_> {
-                ::liquid::model::DisplayCow::Owned(Box::new(::liquid::model::ObjectRender::new(self)))
+            fn render(&self) -> #krate::model::DisplayCow<'_> {
+                #krate::model::DisplayCow::Owned(Box::new(#krate::model::ObjectRender::new(self)))
             }
-            fn source(&self) -> ::liquid::model::DisplayCow<'_> {
-                ::liquid::model::DisplayCow::Owned(Box::new(::liquid::model::ObjectSource::new(self)))
+            fn source(&self) -> #krate::model::DisplayCow<'_> {
+                #krate::model::DisplayCow::Owned(Box::new(#krate::model::ObjectSource::new(self)))
             }
             fn type_name(&self) -> &'static str {
                 "object"
             }
-            fn query_state(&self, state: ::liquid::model::State) -> bool {
+            fn query_state(&self, state: #krate::model::State) -> bool {
                 match state {
-                    ::liquid::model::State::Truthy => true,
-                    ::liquid::model::State::DefaultValue |
-                    ::liquid::model::State::Empty |
-                    ::liquid::model::State::Blank => self.size() == 0,
+                    #krate::model::State::Truthy => true,
+                    #krate::model::State::DefaultValue |
+                    #krate::model::State::Empty |
+                    #krate::model::State::Blank => #krate::ObjectView::size(self) == 0,
                 }
             }
 
-            fn to_kstr(&self) -> ::liquid::model::KStringCow<'_> {
-                let s = ::liquid::model::ObjectRender::new(self).to_string();
-                ::liquid::model::KStringCow::from_string(s)
+            fn to_kstr(&self) -> #krate::model::KStringCow<'_> {
+                let s = #krate::model::ObjectRender::new(self).to_string();
+                #krate::model::KStringCow::from_string(s)
             }
-            fn to_value(&self) -> ::liquid::model::Value {
-                let mut object = ::liquid::model::Object::new();
-                #(
-                    object.insert(stringify!(#fields).into(), ::liquid::model::ValueView::to_value(&self.#fields));
-                )*
-                ::liquid::model::Value::Object(object)
+            fn to_value(&self) -> #krate::model::Value {
+                let mut object = #krate::model::Object::new();
+                for (key, value) in #krate::ObjectView::iter(self) {
+                    object.insert(key.into_owned(), value.to_value());
+                }
+                #krate::model::Value::Object(object)
             }
 
-            fn as_object(&self) -> Option<&dyn ::liquid::ObjectView> {
+            fn as_object(&self) -> Option<&dyn #krate::ObjectView> {
                 Some(self)
             }
         }
     }
 }
 
-pub fn core_derive(input: &DeriveInput) -> TokenStream {
+/// The only field, as is.
+fn generate_newtype(input: &DeriveInput, krate: &TokenStream) -> TokenStream {
+    let DeriveInput {
+        ident, generics, ..
+    } = input;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    quote! {
+        impl #impl_generics #krate::ValueView for #ident #ty_generics #where_clause {
+            fn as_debug(&self) -> &dyn ::std::fmt::Debug {
+                self
+            }
+
+            fn render(&self) -> #krate::model::DisplayCow<'_> {
+                #krate::ValueView::render(&self.0)
+            }
+            fn source(&self) -> #krate::model::DisplayCow<'_> {
+                #krate::ValueView::source(&self.0)
+            }
+            fn type_name(&self) -> &'static str {
+                #krate::ValueView::type_name(&self.0)
+            }
+            fn query_state(&self, state: #krate::model::State) -> bool {
+                #krate::ValueView::query_state(&self.0, state)
+            }
+
+            fn to_kstr(&self) -> #krate::model::KStringCow<'_> {
+                #krate::ValueView::to_kstr(&self.0)
+            }
+            fn to_value(&self) -> #krate::model::Value {
+                #krate::ValueView::to_value(&self.0)
+            }
+
+            fn as_scalar(&self) -> Option<#krate::model::ScalarCow<'_>> {
+                #krate::ValueView::as_scalar(&self.0)
+            }
+            fn as_array(&self) -> Option<&dyn #krate::model::ArrayView> {
+                #krate::ValueView::as_array(&self.0)
+            }
+            fn as_object(&self) -> Option<&dyn #krate::ObjectView> {
+                #krate::ValueView::as_object(&self.0)
+            }
+            fn as_state(&self) -> Option<#krate::model::State> {
+                #krate::ValueView::as_state(&self.0)
+            }
+            fn is_nil(&self) -> bool {
+                #krate::ValueView::is_nil(&self.0)
+            }
+        }
+    }
+}
+
+/// An array of the fields, in order.
+fn generate_array(input: &DeriveInput, krate: &TokenStream) -> TokenStream {
     let DeriveInput {
         ident,
         data,
         generics,
         ..
     } = input;
-
     let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
-    let fields = match crate::object_view::get_fields(data) {
-        Ok(fields) => fields,
-        Err(err) => return err.to_compile_error(),
+    let fields = match data {
+        Data::Struct(DataStruct {
+            fields: Fields::Unnamed(fields),
+            ..
+        }) => &fields.unnamed,
+        _ => unreachable!("Only called for `Shape::Array`."),
     };
+    if let Some(attr) = fields
+        .iter()
+        .flat_map(|field| &field.attrs)
+        .find(|attr| attr.path.is_ident("liquid"))
+    {
+        return Error::new_spanned(attr, "Fields of tuple structs take no `liquid` attributes.")
+            .to_compile_error();
+    }
+    let indexes: Vec<_> = (0..fields.len()).map(Index::from).collect();
+    let positions: Vec<_> = (0..fields.len() as i64).collect();
+    let len = fields.len() as i64;
 
     quote! {
-        impl #impl_generics ::liquid_core::ValueView for #ident #ty_generics #where_clause {
+        impl #impl_generics #krate::ValueView for #ident #ty_generics #where_clause {
             fn as_debug(&self) -> &dyn ::std::fmt::Debug {
                 self
             }
 
-            fn render(&self) -> ::liquid_core::model::DisplayCow<'_> {
-                ::liquid_core::model::DisplayCow::Owned(Box::new(::liquid_core::model::ObjectRender::new(self)))
+            fn render(&self) -> #krate::model::DisplayCow<'_> {
+                #krate::model::DisplayCow::Owned(Box::new(#krate::model::ArrayRender::new(self)))
             }
-            fn source(&self) -> ::liquid_core::model::DisplayCow<'_> {
-                ::liquid_core::model::DisplayCow::Owned(Box::new(::liquid_core::model::ObjectSource::new(self)))
+            fn source(&self) -> #krate::model::DisplayCow<'_> {
+                #krate::model::DisplayCow::Owned(Box::new(#krate::model::ArraySource::new(self)))
             }
             fn type_name(&self) -> &'static str {
-                "object"
+                "array"
             }
-            fn query_state(&self, state: ::liquid_core::model::State) -> bool {
+            fn query_state(&self, state: #krate::model::State) -> bool {
                 match state {
-                    ::liquid_core::model::State::Truthy => true,
-                    ::liquid_core::model::State::DefaultValue |
-                    ::liquid_core::model::State::Empty |
-                    ::liquid_core::model::State::Blank => self.size() == 0,
+                    #krate::model::State::Truthy => true,
+                    #krate::model::State::DefaultValue |
+                    #krate::model::State::Empty |
+                    #krate::model::State::Blank => #len == 0,
                 }
             }
 
-            fn to_kstr(&self) -> ::liquid_core::model::KStringCow<'_> {
-                let s = ::liquid_core::model::ObjectRender::new(self).to_string();
-                ::liquid_core::model::KStringCow::from_string(s)
+            fn to_kstr(&self) -> #krate::model::KStringCow<'_> {
+                let s = #krate::model::ArrayRender::new(self).to_string();
+                #krate::model::KStringCow::from_string(s)
+            }
+            fn to_value(&self) -> #krate::model::Value {
+                #krate::model::Value::Array(vec![
+                    #(
+                        #krate::ValueView::to_value(&self.#indexes),
+                    )*
+                ])
+            }
+
+            fn as_array(&self) -> Option<&dyn #krate::model::ArrayView> {
+                Some(self)
             }
-            fn to_value(&self) -> ::liquid_core::model::Value {
-                let mut object = ::liquid_core::model::Object::new();
+        }
+
+        impl #impl_generics #krate::model::ArrayView for #ident #ty_generics #where_clause {
+            fn as_value(&self) -> &dyn #krate::ValueView {
+                self
+            }
+
+            fn size(&self) -> i64 {
+                #len
+            }
+
+            fn values<'liquid_derive_k>(&'liquid_derive_k self) -> Box<dyn Iterator<Item = &'liquid_derive_k dyn #krate::ValueView> + 'liquid_derive_k> {
+                let values: Vec<&dyn #krate::ValueView> = vec![
+                    #(
+                        &self.#indexes,
+                    )*
+                ];
+                Box::new(values.into_iter())
+            }
+
+            fn contains_key(&self, index: i64) -> bool {
+                let index = if index < 0 { #len + index } else { index };
+                (0..#len).contains(&index)
+            }
+
+            fn get(&self, index: i64) -> Option<&dyn #krate::ValueView> {
+                let index = if index < 0 { #len + index } else { index };
+                match index {
+                    #(
+                        #positions => Some(&self.#indexes),
+                    )*
+                    _ => None,
+                }
+            }
+        }
+    }
+}
+
+/// Unit variants as their name, others as objects, through their `ObjectView`.
+fn generate_enum(
+    input: &DeriveInput,
+    krate: &TokenStream,
+    container: &ContainerAttrs,
+    data: &DataEnum,
+) -> TokenStream {
+    let DeriveInput {
+        ident, generics, ..
+    } = input;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let variants = match crate::object_view::get_variants(data, container) {
+        Ok(variants) => variants,
+        Err(err) => return err.to_compile_error(),
+    };
+    let units: Vec<_> = variants
+        .iter()
+        .filter(|variant| variant.fields.is_none())
+        .collect();
+    let unit_idents: Vec<_> = units.iter().map(|variant| variant.ident).collect();
+    let unit_names: Vec<_> = units.iter().map(|variant| &variant.name).collect();
+    let has_objects = units.len() != variants.len();
+
+    // Forwards `method(args)` to the variant name for unit variants, else evaluates `object`.
+    let dispatch = |method: TokenStream, args: TokenStream, object: TokenStream| {
+        let object = if has_objects {
+            object
+        } else {
+            quote! { unreachable!() }
+        };
+        quote! {
+            match self {
                 #(
-                    object.insert(stringify!(#fields).into(), ::liquid_core::model::ValueView::to_value(&self.#fields));
+                    Self::#unit_idents => #krate::ValueView::#method(&#unit_names #args),
                 )*
-                ::liquid_core::model::Value::Object(object)
+                #[allow(unreachable_patterns)]
+                _ => #object,
+            }
+        }
+    };
+    let render = dispatch(
+        quote! { render },
+        quote! {},
+        quote! { #krate::model::DisplayCow::Owned(Box::new(#krate::model::ObjectRender::new(self))) },
+    );
+    let source = dispatch(
+        quote! { source },
+        quote! {},
+        quote! { #krate::model::DisplayCow::Owned(Box::new(#krate::model::ObjectSource::new(self))) },
+    );
+    let type_name = dispatch(quote! { type_name }, quote! {}, quote! { "object" });
+    let query_state = dispatch(
+        quote! { query_state },
+        quote! { , state },
+        quote! {
+            match state {
+                #krate::model::State::Truthy => true,
+                #krate::model::State::DefaultValue |
+                #krate::model::State::Empty |
+                #krate::model::State::Blank => #krate::ObjectView::size(self) == 0,
+            }
+        },
+    );
+    let to_kstr = dispatch(
+        quote! { to_kstr },
+        quote! {},
+        quote! {
+            #krate::model::KStringCow::from_string(#krate::model::ObjectRender::new(self).to_string())
+        },
+    );
+    let to_value = dispatch(
+        quote! { to_value },
+        quote! {},
+        quote! {
+            {
+                let mut object = #krate::model::Object::new();
+                for (key, value) in #krate::ObjectView::iter(self) {
+                    object.insert(key.into_owned(), value.to_value());
+                }
+                #krate::model::Value::Object(object)
             }
+        },
+    );
+    let as_scalar = dispatch(quote! { as_scalar }, quote! {}, quote! { None });
+    let as_object = dispatch(quote! { as_object }, quote! {}, quote! { Some(self) });
 
-            fn as_object(&self) -> Option<&dyn ::liquid_core::ObjectView> {
-                Some(self)
+    quote! {
+        impl #impl_generics #krate::ValueView for #ident #ty_generics #where_clause {
+            fn as_debug(&self) -> &dyn ::std::fmt::Debug {
+                self
+            }
+
+            fn render(&self) -> #krate::model::DisplayCow<'_> {
+                #render
+            }
+            fn source(&self) -> #krate::model::DisplayCow<'_> {
+                #source
+            }
+            fn type_name(&self) -> &'static str {
+                #type_name
+            }
+            fn query_state(&self, state: #krate::model::State) -> bool {
+                #query_state
+            }
+
+            fn to_kstr(&self) -> #krate::model::KStringCow<'_> {
+                #to_kstr
+            }
+            fn to_value(&self) -> #krate::model::Value {
+                #to_value
+            }
+
+            fn as_scalar(&self) -> Option<#krate::model::ScalarCow<'_>> {
+                #as_scalar
+            }
+            fn as_object(&self) -> Option<&dyn #krate::ObjectView> {
+                #as_object
             }
         }
     }
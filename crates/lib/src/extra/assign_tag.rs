@@ -0,0 +1,177 @@
+use std::io::Write;
+
+use liquid_core::error::ResultLiquidExt;
+use liquid_core::parser::FilterChain;
+use liquid_core::runtime::{GlobalReferences, Variable};
+use liquid_core::Language;
+use liquid_core::Renderable;
+use liquid_core::Result;
+use liquid_core::Runtime;
+use liquid_core::ValueView;
+use liquid_core::{ParseTag, TagReflection, TagTokenIter};
+
+// liquid-rust proprietary
+
+/// `assign` that can also set a key or index of a variable, e.g. `{% assign page.title = "Home" %}`
+/// or `{% assign tags[0] = "news" %}`.
+///
+/// Register it in place of the standard `assign` to opt in.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct PathAssignTag;
+
+impl PathAssignTag {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl TagReflection for PathAssignTag {
+    fn tag(&self) -> &'static str {
+        "assign"
+    }
+
+    fn description(&self) -> &'static str {
+        "Assigns a value to a variable, or to a key or index of one."
+    }
+}
+
+impl ParseTag for PathAssignTag {
+    fn parse(
+        &self,
+        mut arguments: TagTokenIter<'_>,
+        options: &Language,
+    ) -> Result<Box<dyn Renderable>> {
+        let dst = arguments
+            .expect_next("Variable expected.")?
+            .expect_variable()
+            .into_result()?;
+
+        arguments
+            .expect_next("Assignment operator \"=\" expected.")?
+            .expect_str("=")
+            .into_result_custom_msg("Assignment operator \"=\" expected.")?;
+
+        let src = arguments
+            .expect_next("FilterChain expected.")?
+            .expect_filter_chain(options)
+            .into_result()?;
+
+        // no more arguments should be supplied, trying to supply them is an error
+        arguments.expect_nothing()?;
+
+        Ok(Box::new(PathAssign { dst, src }))
+    }
+
+    fn reflection(&self) -> &dyn TagReflection {
+        self
+    }
+}
+
+#[derive(Debug)]
+struct PathAssign {
+    dst: Variable,
+    src: FilterChain,
+}
+
+impl PathAssign {
+    fn trace(&self) -> String {
+        format!("{{% assign {} = {}%}}", self.dst, self.src)
+    }
+}
+
+impl Renderable for PathAssign {
+    fn render_to(&self, _writer: &mut dyn Write, runtime: &dyn Runtime) -> Result<()> {
+        let value = self
+            .src
+            .evaluate(runtime)
+            .trace_with(|| self.trace().into())?
+            .into_owned();
+        let path = self
+            .dst
+            .evaluate(runtime)
+            .trace_with(|| self.trace().into())?;
+        runtime
+            .set_global_path(&path, value)
+            .trace_with(|| self.trace().into())?;
+        Ok(())
+    }
+
+    fn collect_globals(&self, globals: &mut GlobalReferences<'_>) {
+        self.src.collect_globals(globals);
+        for index in self.dst.indexes() {
+            globals.read(index);
+        }
+        globals.assign(&self.dst.root().to_kstr());
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use liquid_core::parser;
+    use liquid_core::runtime;
+    use liquid_core::runtime::RuntimeBuilder;
+
+    use crate::stdlib;
+
+    fn options() -> Language {
+        let mut options = Language::default();
+        options
+            .tags
+            .register("assign".to_string(), PathAssignTag.into());
+        options
+            .blocks
+            .register("for".to_string(), stdlib::ForBlock.into());
+        options
+    }
+
+    fn render(text: &str, globals: &liquid_core::Object) -> Result<String> {
+        let template = parser::parse(text, &options()).map(runtime::Template::new)?;
+        let runtime = RuntimeBuilder::new().set_globals(globals).build();
+        template.render(&runtime)
+    }
+
+    #[test]
+    fn assign_variable() {
+        let output = render("{% assign x = 5 %}{{ x }}", &liquid_core::Object::new()).unwrap();
+        assert_eq!(output, "5");
+    }
+
+    #[test]
+    fn assign_key() {
+        let globals = liquid_core::object!({"page": {"title": "Home"}});
+        let text = concat!(
+            "{% assign page.title = 'About' %}",
+            "{% assign page.meta.author = 'Ann' %}",
+            "{% assign key = 'lang' %}{% assign page[key] = 'en' %}",
+            "{{ page.title }} {{ page.meta.author }} {{ page.lang }}",
+        );
+        assert_eq!(render(text, &globals).unwrap(), "About Ann en");
+    }
+
+    #[test]
+    fn assign_index() {
+        let globals = liquid_core::object!({"tags": ["a", "b"]});
+        let text = concat!(
+            "{% assign tags[0] = 'x' %}{% assign tags[-1] = 'y' %}{% assign tags[2] = 'z' %}",
+            "{% for tag in tags %}{{ tag }}{% endfor %}",
+        );
+        assert_eq!(render(text, &globals).unwrap(), "xyz");
+    }
+
+    #[test]
+    fn assign_builds_hash() {
+        let text = concat!(
+            "{% for i in (1..3) %}{% assign squares[i] = i %}{% endfor %}",
+            "{{ squares['2'] }}{{ squares.size }}",
+        );
+        assert_eq!(render(text, &liquid_core::Object::new()).unwrap(), "23");
+    }
+
+    #[test]
+    fn assign_into_scalar_fails() {
+        let globals = liquid_core::object!({"title": "Home"});
+        assert!(render("{% assign title.x = 1 %}", &globals).is_err());
+    }
+}
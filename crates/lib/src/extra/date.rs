@@ -1,12 +1,15 @@
+use std::convert::TryFrom;
+
+use liquid_core::model::{DateTime, TimeZone};
 use liquid_core::Expression;
 use liquid_core::Result;
 use liquid_core::Runtime;
 use liquid_core::{
     Display_filter, Filter, FilterParameters, FilterReflection, FromFilterParameters, ParseFilter,
 };
-use liquid_core::{Value, ValueView};
+use liquid_core::{Error, Value, ValueView};
 
-use crate::invalid_input;
+use crate::{date_locale, invalid_input, BUNDLED_DATE_LOCALES};
 
 // liquid-rust proprietary
 
@@ -15,10 +18,15 @@ struct DateInTzArgs {
     #[parameter(description = "The format to return the date in.", arg_type = "str")]
     format: Expression,
     #[parameter(
-        description = "The timezone to convert the date to.",
-        arg_type = "integer"
+        description = "The timezone to convert the date to, as hours from UTC, an offset like `-05:30` or, with the `tzdb` feature, a name like `Europe/Berlin`."
     )]
     timezone: Expression,
+    #[parameter(
+        description = "The locale for month and weekday names. Defaults to the active locale.",
+        arg_type = "str",
+        mode = "keyword"
+    )]
+    locale: Option<Expression>,
 }
 
 #[derive(Clone, ParseFilter, FilterReflection)]
@@ -41,22 +49,336 @@ impl Filter for DateInTzFilter {
     fn evaluate(&self, input: &dyn ValueView, runtime: &dyn Runtime) -> Result<Value> {
         let args = self.args.evaluate(runtime)?;
 
-        let date = input
-            .as_scalar()
-            .and_then(|s| s.to_date_time())
-            .ok_or_else(|| invalid_input("Invalid date format"))?;
+        let date = to_date_time(input)?;
 
-        let offset = time::UtcOffset::from_whole_seconds(args.timezone as i32 * 3600)
-            .map_err(|_err| invalid_input("Timezone was too large"))?;
+        let zone = args
+            .timezone
+            .as_scalar()
+            .ok_or_else(|| invalid_input("Invalid timezone"))?;
+        let zone = match zone.to_integer() {
+            Some(hours) => {
+                let offset = i32::try_from(hours)
+                    .ok()
+                    .and_then(|hours| hours.checked_mul(3600))
+                    .and_then(|seconds| time::UtcOffset::from_whole_seconds(seconds).ok())
+                    .ok_or_else(|| invalid_input("Timezone was too large"))?;
+                TimeZone::fixed(offset)
+            }
+            None => TimeZone::parse(zone.to_kstr().as_str())
+                .ok_or_else(|| invalid_input("Unknown timezone"))?,
+        };
 
+        let locale = date_locale(
+            &BUNDLED_DATE_LOCALES,
+            args.locale.as_ref().map(|l| l.as_str()),
+            runtime,
+        )?;
         let date_str = date
-            .with_offset(offset)
-            .format(args.format.as_str())
+            .format_in_time_zone(args.format.as_str(), &zone, locale)
             .map_err(|_err| invalid_input("Invalid format string"))?;
         Ok(Value::scalar(date_str))
     }
 }
 
+fn to_date_time(input: &dyn ValueView) -> Result<DateTime> {
+    input
+        .as_scalar()
+        .and_then(|s| s.to_date_time())
+        .ok_or_else(|| invalid_input("Invalid date format"))
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum DateUnit {
+    Seconds,
+    Minutes,
+    Hours,
+    Days,
+    Weeks,
+    Months,
+    Years,
+}
+
+impl DateUnit {
+    fn parse(unit: &str) -> Result<Self> {
+        match unit.to_ascii_lowercase().as_str() {
+            "second" | "seconds" => Ok(Self::Seconds),
+            "minute" | "minutes" => Ok(Self::Minutes),
+            "hour" | "hours" => Ok(Self::Hours),
+            "day" | "days" => Ok(Self::Days),
+            "week" | "weeks" => Ok(Self::Weeks),
+            "month" | "months" => Ok(Self::Months),
+            "year" | "years" => Ok(Self::Years),
+            _ => Error::with_msg("Unknown date unit")
+                .context("unit", unit.to_owned())
+                .into_err(),
+        }
+    }
+
+    fn seconds(self) -> Option<i64> {
+        match self {
+            Self::Seconds => Some(1),
+            Self::Minutes => Some(60),
+            Self::Hours => Some(60 * 60),
+            Self::Days => Some(24 * 60 * 60),
+            Self::Weeks => Some(7 * 24 * 60 * 60),
+            Self::Months | Self::Years => None,
+        }
+    }
+
+    fn months(self) -> i64 {
+        match self {
+            Self::Years => 12,
+            _ => 1,
+        }
+    }
+}
+
+fn shift(date: DateTime, amount: i64, unit: DateUnit) -> Result<Value> {
+    let shifted = match unit.seconds() {
+        Some(seconds) => amount
+            .checked_mul(seconds)
+            .and_then(|seconds| date.checked_add(time::Duration::seconds(seconds))),
+        None => amount
+            .checked_mul(unit.months())
+            .and_then(|months| date.checked_add_months(months)),
+    };
+    shifted
+        .map(Value::scalar)
+        .ok_or_else(|| invalid_input("Date out of range"))
+}
+
+#[derive(Debug, FilterParameters)]
+struct DateShiftArgs {
+    #[parameter(description = "The number of units to shift by.", arg_type = "integer")]
+    amount: Expression,
+    #[parameter(
+        description = "The unit: `seconds`, `minutes`, `hours`, `days`, `weeks`, `months` or `years`.",
+        arg_type = "str"
+    )]
+    unit: Expression,
+}
+
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+    name = "date_add",
+    description = "Adds an amount of time to a date, e.g. `{{ date | date_add: 3, 'days' }}`.",
+    parameters(DateShiftArgs),
+    parsed(DateAddFilter)
+)]
+pub struct DateAdd;
+
+#[derive(Debug, FromFilterParameters, Display_filter)]
+#[name = "date_add"]
+struct DateAddFilter {
+    #[parameters]
+    args: DateShiftArgs,
+}
+
+impl Filter for DateAddFilter {
+    fn evaluate(&self, input: &dyn ValueView, runtime: &dyn Runtime) -> Result<Value> {
+        let args = self.args.evaluate(runtime)?;
+        let unit = DateUnit::parse(&args.unit)?;
+        shift(to_date_time(input)?, args.amount, unit)
+    }
+}
+
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+    name = "date_subtract",
+    description = "Subtracts an amount of time from a date, e.g. `{{ date | date_subtract: 1, 'months' }}`.",
+    parameters(DateShiftArgs),
+    parsed(DateSubtractFilter)
+)]
+pub struct DateSubtract;
+
+#[derive(Debug, FromFilterParameters, Display_filter)]
+#[name = "date_subtract"]
+struct DateSubtractFilter {
+    #[parameters]
+    args: DateShiftArgs,
+}
+
+impl Filter for DateSubtractFilter {
+    fn evaluate(&self, input: &dyn ValueView, runtime: &dyn Runtime) -> Result<Value> {
+        let args = self.args.evaluate(runtime)?;
+        let unit = DateUnit::parse(&args.unit)?;
+        let amount = args
+            .amount
+            .checked_neg()
+            .ok_or_else(|| invalid_input("Date out of range"))?;
+        shift(to_date_time(input)?, amount, unit)
+    }
+}
+
+#[derive(Debug, FilterParameters)]
+struct DateDiffArgs {
+    #[parameter(description = "The date to subtract.", arg_type = "date_time")]
+    other: Expression,
+    #[parameter(
+        description = "The unit: `seconds`, `minutes`, `hours`, `days` (default), `weeks`, `months` or `years`.",
+        arg_type = "str"
+    )]
+    unit: Option<Expression>,
+}
+
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+    name = "date_diff",
+    description = "Counts the whole units of time from a date to the input, e.g. `{{ deadline | date_diff: 'now', 'days' }}`.",
+    parameters(DateDiffArgs),
+    parsed(DateDiffFilter)
+)]
+pub struct DateDiff;
+
+#[derive(Debug, FromFilterParameters, Display_filter)]
+#[name = "date_diff"]
+struct DateDiffFilter {
+    #[parameters]
+    args: DateDiffArgs,
+}
+
+impl Filter for DateDiffFilter {
+    fn evaluate(&self, input: &dyn ValueView, runtime: &dyn Runtime) -> Result<Value> {
+        let args = self.args.evaluate(runtime)?;
+        let unit = match args.unit {
+            Some(unit) => DateUnit::parse(&unit)?,
+            None => DateUnit::Days,
+        };
+        let date = to_date_time(input)?;
+        let other = args.other;
+
+        let diff = match unit.seconds() {
+            Some(seconds) => (date.unix_timestamp() - other.unix_timestamp()) / seconds,
+            None => {
+                let month_index = |d: &DateTime| i64::from(d.year()) * 12 + i64::from(d.month());
+                let mut months = month_index(&date) - month_index(&other);
+                // Only count months that have fully elapsed.
+                let shifted = other.checked_add_months(months);
+                if months > 0 && shifted.map_or(false, |s| s > date) {
+                    months -= 1;
+                } else if months < 0 && shifted.map_or(false, |s| s < date) {
+                    months += 1;
+                }
+                months / unit.months()
+            }
+        };
+        Ok(Value::scalar(diff))
+    }
+}
+
+/// Rails' `distance_of_time_in_words`.
+fn distance_in_words(seconds: i64) -> String {
+    let plural = |count: i64, unit: &str| {
+        if count == 1 {
+            format!("1 {}", unit)
+        } else {
+            format!("{} {}s", count, unit)
+        }
+    };
+
+    const MINUTES_IN_DAY: i64 = 24 * 60;
+    const MINUTES_IN_MONTH: i64 = 30 * MINUTES_IN_DAY;
+    const MINUTES_IN_YEAR: i64 = 365 * MINUTES_IN_DAY;
+
+    let minutes = (seconds.abs() + 30) / 60;
+    match minutes {
+        0 => "less than a minute".to_owned(),
+        1..=44 => plural(minutes, "minute"),
+        45..=89 => "about 1 hour".to_owned(),
+        90..=1439 => format!("about {}", plural((minutes + 30) / 60, "hour")),
+        1440..=2519 => "1 day".to_owned(),
+        2520..=43199 => plural((minutes + MINUTES_IN_DAY / 2) / MINUTES_IN_DAY, "day"),
+        43200..=86399 => format!(
+            "about {}",
+            plural((minutes + MINUTES_IN_MONTH / 2) / MINUTES_IN_MONTH, "month")
+        ),
+        86400..=525599 => plural((minutes + MINUTES_IN_MONTH / 2) / MINUTES_IN_MONTH, "month"),
+        _ => {
+            let years = minutes / MINUTES_IN_YEAR;
+            let remainder = minutes % MINUTES_IN_YEAR;
+            if remainder < MINUTES_IN_YEAR / 4 {
+                format!("about {}", plural(years, "year"))
+            } else if remainder < MINUTES_IN_YEAR * 3 / 4 {
+                format!("over {}", plural(years, "year"))
+            } else {
+                format!("almost {}", plural(years + 1, "year"))
+            }
+        }
+    }
+}
+
+#[derive(Debug, FilterParameters)]
+struct TimeAgoInWordsArgs {
+    #[parameter(
+        description = "The time to measure from. Defaults to now.",
+        arg_type = "date_time"
+    )]
+    now: Option<Expression>,
+}
+
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+    name = "time_ago_in_words",
+    description = "Describes the time between a date and now, e.g. `about 3 hours`.",
+    parameters(TimeAgoInWordsArgs),
+    parsed(TimeAgoInWordsFilter)
+)]
+pub struct TimeAgoInWords;
+
+#[derive(Debug, FromFilterParameters, Display_filter)]
+#[name = "time_ago_in_words"]
+struct TimeAgoInWordsFilter {
+    #[parameters]
+    args: TimeAgoInWordsArgs,
+}
+
+impl Filter for TimeAgoInWordsFilter {
+    fn evaluate(&self, input: &dyn ValueView, runtime: &dyn Runtime) -> Result<Value> {
+        let args = self.args.evaluate(runtime)?;
+        let date = to_date_time(input)?;
+        let now = args.now.unwrap_or_else(DateTime::now);
+        Ok(Value::scalar(distance_in_words(
+            now.unix_timestamp() - date.unix_timestamp(),
+        )))
+    }
+}
+
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+    name = "beginning_of_day",
+    description = "Moves a date to midnight of the same day.",
+    parsed(BeginningOfDayFilter)
+)]
+pub struct BeginningOfDay;
+
+#[derive(Debug, Default, Display_filter)]
+#[name = "beginning_of_day"]
+struct BeginningOfDayFilter;
+
+impl Filter for BeginningOfDayFilter {
+    fn evaluate(&self, input: &dyn ValueView, _runtime: &dyn Runtime) -> Result<Value> {
+        Ok(Value::scalar(to_date_time(input)?.beginning_of_day()))
+    }
+}
+
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+    name = "beginning_of_month",
+    description = "Moves a date to midnight of the first day of its month.",
+    parsed(BeginningOfMonthFilter)
+)]
+pub struct BeginningOfMonth;
+
+#[derive(Debug, Default, Display_filter)]
+#[name = "beginning_of_month"]
+struct BeginningOfMonthFilter;
+
+impl Filter for BeginningOfMonthFilter {
+    fn evaluate(&self, input: &dyn ValueView, _runtime: &dyn Runtime) -> Result<Value> {
+        Ok(Value::scalar(to_date_time(input)?.beginning_of_month()))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -100,6 +422,82 @@ mod tests {
         assert_eq!(unit_result, desired_result);
     }
 
+    #[test]
+    fn unit_date_in_tz_offset_string() {
+        let unit_result = liquid_core::call_filter!(
+            DateInTz,
+            "13 Jun 2016 12:00:00 +0000",
+            "%Y-%m-%d %H:%M:%S %z",
+            "+05:30"
+        )
+        .unwrap();
+        let desired_result = liquid_core::value!("2016-06-13 17:30:00 +0530");
+        assert_eq!(unit_result, desired_result);
+    }
+
+    #[test]
+    fn unit_date_in_tz_locale() {
+        use liquid_core::runtime::{LocaleRegister, Registers};
+        use liquid_core::Renderable;
+
+        let mut options = liquid_core::Language::default();
+        options
+            .filters
+            .register("date_in_tz".to_string(), DateInTz.into());
+        let render = |text: &str, registers: &Registers| {
+            let template = liquid_core::parser::parse(text, &options)
+                .map(liquid_core::runtime::Template::new)?;
+            let runtime = liquid_core::runtime::RuntimeBuilder::new()
+                .set_registers(registers)
+                .build();
+            template.render(&runtime)
+        };
+
+        let registers = Registers::default();
+        assert_eq!(
+            render(
+                r#"{{ "13 Jun 2016 12:00:00 +0000" | date_in_tz: "%A %B", 2, locale: "es" }}"#,
+                &registers
+            )
+            .unwrap(),
+            "lunes junio"
+        );
+        registers.get_mut::<LocaleRegister>().set("fr-CA");
+        assert_eq!(
+            render(
+                r#"{{ "13 Jun 2016 12:00:00 +0000" | date_in_tz: "%A %B", 2 }}"#,
+                &registers
+            )
+            .unwrap(),
+            "lundi juin"
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "tzdb")]
+    fn unit_date_in_tz_named() {
+        let format = "%Y-%m-%d %H:%M:%S %z %Z";
+        let summer = liquid_core::call_filter!(
+            DateInTz,
+            "13 Jun 2016 12:00:00 +0000",
+            format,
+            "Europe/Berlin"
+        )
+        .unwrap();
+        assert_eq!(
+            summer,
+            liquid_core::value!("2016-06-13 14:00:00 +0200 CEST")
+        );
+        let winter = liquid_core::call_filter!(
+            DateInTz,
+            "13 Jan 2016 12:00:00 +0000",
+            format,
+            "Europe/Berlin"
+        )
+        .unwrap();
+        assert_eq!(winter, liquid_core::value!("2016-01-13 13:00:00 +0100 CET"));
+    }
+
     #[test]
     fn unit_date_in_tz_input_not_a_string() {
         liquid_core::call_filter!(DateInTz, 0f64, "%Y-%m-%d %H:%M:%S %z", 0i64).unwrap_err();
@@ -148,4 +546,115 @@ mod tests {
         )
         .unwrap_err();
     }
+
+    fn date(s: &str) -> Value {
+        Value::scalar(DateTime::from_str(s).unwrap())
+    }
+
+    #[test]
+    fn unit_date_add() {
+        assert_eq!(
+            liquid_core::call_filter!(DateAdd, "2016-01-31 10:00:00 +0100", 3i64, "days").unwrap(),
+            date("2016-02-03 10:00:00 +0100")
+        );
+        assert_eq!(
+            liquid_core::call_filter!(DateAdd, "2016-01-31 10:00:00 +0100", 1i64, "month").unwrap(),
+            date("2016-02-29 10:00:00 +0100")
+        );
+        assert_eq!(
+            liquid_core::call_filter!(DateAdd, "2016-01-31 10:00:00 +0100", -90i64, "minutes")
+                .unwrap(),
+            date("2016-01-31 08:30:00 +0100")
+        );
+        assert_eq!(
+            liquid_core::call_filter!(DateAdd, "2016-01-31 10:00:00 +0100", 2i64, "Hours").unwrap(),
+            date("2016-01-31 12:00:00 +0100")
+        );
+        liquid_core::call_filter!(DateAdd, "2016-01-31 10:00:00 +0100", 1i64, "fortnight")
+            .unwrap_err();
+        liquid_core::call_filter!(DateAdd, "2016-01-31 10:00:00 +0100", 1i64, "dayss").unwrap_err();
+        liquid_core::call_filter!(DateAdd, "2016-01-31 10:00:00 +0100", i64::MAX, "hours")
+            .unwrap_err();
+    }
+
+    #[test]
+    fn unit_date_subtract() {
+        assert_eq!(
+            liquid_core::call_filter!(DateSubtract, "2016-03-31 10:00:00 +0000", 1i64, "months")
+                .unwrap(),
+            date("2016-02-29 10:00:00 +0000")
+        );
+        assert_eq!(
+            liquid_core::call_filter!(DateSubtract, "2016-03-31 10:00:00 +0000", 2i64, "years")
+                .unwrap(),
+            date("2014-03-31 10:00:00 +0000")
+        );
+        assert_eq!(
+            liquid_core::call_filter!(DateSubtract, "2016-03-31 10:00:00 +0000", 36i64, "hours")
+                .unwrap(),
+            date("2016-03-29 22:00:00 +0000")
+        );
+    }
+
+    #[test]
+    fn unit_date_diff() {
+        let end = "2016-03-31 10:00:00 +0000";
+        assert_eq!(
+            liquid_core::call_filter!(DateDiff, end, "2016-03-01 12:00:00 +0000").unwrap(),
+            Value::scalar(29i64)
+        );
+        assert_eq!(
+            liquid_core::call_filter!(DateDiff, end, "2016-03-31 12:00:00 +0000", "hours").unwrap(),
+            Value::scalar(-2i64)
+        );
+        assert_eq!(
+            liquid_core::call_filter!(DateDiff, end, "2016-01-31 10:00:01 +0000", "months")
+                .unwrap(),
+            Value::scalar(1i64)
+        );
+        assert_eq!(
+            liquid_core::call_filter!(DateDiff, end, "2014-04-01 10:00:00 +0000", "years").unwrap(),
+            Value::scalar(1i64)
+        );
+        liquid_core::call_filter!(DateDiff, end, "blah").unwrap_err();
+    }
+
+    #[test]
+    fn unit_time_ago_in_words() {
+        let now = "2016-03-31 10:00:00 +0000";
+        let cases = [
+            ("2016-03-31 09:59:45 +0000", "less than a minute"),
+            ("2016-03-31 09:59:00 +0000", "1 minute"),
+            ("2016-03-31 09:15:00 +0000", "about 1 hour"),
+            ("2016-03-31 05:00:00 +0000", "about 5 hours"),
+            ("2016-03-30 08:00:00 +0000", "1 day"),
+            ("2016-03-21 10:00:00 +0000", "10 days"),
+            ("2016-02-21 10:00:00 +0000", "about 1 month"),
+            ("2015-12-01 10:00:00 +0000", "4 months"),
+            ("2014-12-01 10:00:00 +0000", "over 1 year"),
+            ("2013-05-01 10:00:00 +0000", "almost 3 years"),
+            ("2016-04-01 10:00:00 +0000", "1 day"),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(
+                liquid_core::call_filter!(TimeAgoInWords, input, now).unwrap(),
+                Value::scalar(expected),
+                "{}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn unit_beginning_of() {
+        let input = "2016-03-31 10:30:00 -0500";
+        assert_eq!(
+            liquid_core::call_filter!(BeginningOfDay, input).unwrap(),
+            date("2016-03-31 00:00:00 -0500")
+        );
+        assert_eq!(
+            liquid_core::call_filter!(BeginningOfMonth, input).unwrap(),
+            date("2016-03-01 00:00:00 -0500")
+        );
+    }
 }
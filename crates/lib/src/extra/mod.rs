@@ -1,3 +1,7 @@
+mod assign_tag;
 mod date;
+mod object;
 
+pub use self::assign_tag::*;
 pub use self::date::*;
+pub use self::object::*;
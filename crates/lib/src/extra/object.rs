@@ -0,0 +1,227 @@
+use std::fmt;
+
+use liquid_core::model::{set_path, KString};
+use liquid_core::parser::FilterArguments;
+use liquid_core::Expression;
+use liquid_core::Result;
+use liquid_core::Runtime;
+use liquid_core::{
+    Display_filter, Filter, FilterParameters, FilterReflection, FromFilterParameters, ParseFilter,
+};
+use liquid_core::{Error, Object, Value, ValueView};
+
+use crate::{invalid_argument, invalid_input};
+
+// liquid-rust proprietary
+
+#[derive(Debug, FilterParameters)]
+struct SetArgs {
+    #[parameter(description = "The key of an object or index of an array to set.")]
+    key: Expression,
+    #[parameter(description = "The value to set.")]
+    value: Expression,
+}
+
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+    name = "set",
+    description = "Sets a key of an object (or `nil`) or an index of an array, e.g. `{{ page | set: 'title', 'Home' }}`.",
+    parameters(SetArgs),
+    parsed(SetFilter)
+)]
+pub struct Set;
+
+#[derive(Debug, FromFilterParameters, Display_filter)]
+#[name = "set"]
+struct SetFilter {
+    #[parameters]
+    args: SetArgs,
+}
+
+impl Filter for SetFilter {
+    fn evaluate(&self, input: &dyn ValueView, runtime: &dyn Runtime) -> Result<Value> {
+        let args = self.args.evaluate(runtime)?;
+
+        if !input.is_nil() && input.as_object().is_none() && input.as_array().is_none() {
+            return Err(invalid_input("Object or array expected"));
+        }
+        let key = args
+            .key
+            .as_scalar()
+            .ok_or_else(|| invalid_argument("key", "Whole number or string expected"))?;
+
+        let mut value = input.to_value();
+        set_path(&mut value, &[key], args.value.to_value())?;
+        Ok(value)
+    }
+}
+
+#[derive(Debug, FilterParameters)]
+struct MergeArgs {
+    #[parameter(description = "The object whose entries are added, replacing existing keys.")]
+    other: Expression,
+}
+
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+    name = "merge",
+    description = "Combines two objects, with the argument's entries taking precedence, e.g. `{{ defaults | merge: options }}`.",
+    parameters(MergeArgs),
+    parsed(MergeFilter)
+)]
+pub struct Merge;
+
+#[derive(Debug, FromFilterParameters, Display_filter)]
+#[name = "merge"]
+struct MergeFilter {
+    #[parameters]
+    args: MergeArgs,
+}
+
+impl Filter for MergeFilter {
+    fn evaluate(&self, input: &dyn ValueView, runtime: &dyn Runtime) -> Result<Value> {
+        let args = self.args.evaluate(runtime)?;
+
+        let mut object = as_object(input).ok_or_else(|| invalid_input("Object expected"))?;
+        let other =
+            as_object(&args.other).ok_or_else(|| invalid_argument("other", "Object expected"))?;
+        for (key, value) in other {
+            object.insert(key, value);
+        }
+        Ok(Value::Object(object))
+    }
+}
+
+/// Objects, with `nil` as an empty one.
+fn as_object(value: &dyn ValueView) -> Option<Object> {
+    if value.is_nil() {
+        Some(Object::new())
+    } else {
+        value.to_value().into_object()
+    }
+}
+
+/// The `dict` filter, building an object from its keyword arguments.
+#[derive(Clone, FilterReflection)]
+#[filter(
+    name = "dict",
+    description = "Creates an object from keyword arguments, added to the input object (or `nil`), e.g. `{{ nil | dict: title: 'Home', lang: 'en' }}`."
+)]
+pub struct Dict;
+
+impl ParseFilter for Dict {
+    fn parse(&self, mut arguments: FilterArguments) -> Result<Box<dyn Filter>> {
+        if arguments.positional.next().is_some() {
+            return Err(Error::with_msg("Invalid number of positional arguments")
+                .context("cause", "expected at most 0 positional arguments"));
+        }
+
+        let entries = arguments
+            .keyword
+            .map(|(name, value)| (KString::from_ref(name), value))
+            .collect();
+        Ok(Box::new(DictFilter { entries }))
+    }
+
+    fn reflection(&self) -> &dyn FilterReflection {
+        self
+    }
+}
+
+#[derive(Debug)]
+struct DictFilter {
+    entries: Vec<(KString, Expression)>,
+}
+
+impl Filter for DictFilter {
+    fn evaluate(&self, input: &dyn ValueView, runtime: &dyn Runtime) -> Result<Value> {
+        let mut object = as_object(input).ok_or_else(|| invalid_input("Object expected"))?;
+        for (name, value) in &self.entries {
+            object.insert(name.clone(), value.evaluate(runtime)?.into_owned());
+        }
+        Ok(Value::Object(object))
+    }
+}
+
+impl fmt::Display for DictFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let entries = self
+            .entries
+            .iter()
+            .map(|(name, value)| format!("{}: {}", name, value))
+            .collect::<Vec<_>>()
+            .join(", ");
+        if entries.is_empty() {
+            write!(f, "dict")
+        } else {
+            write!(f, "dict : {}", entries)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use liquid_core::parser;
+    use liquid_core::runtime;
+    use liquid_core::runtime::RuntimeBuilder;
+    use liquid_core::Language;
+    use liquid_core::Renderable;
+
+    #[test]
+    fn unit_set() {
+        assert_eq!(
+            liquid_core::call_filter!(Set, liquid_core::value!({"a": 1}), "b", 2).unwrap(),
+            liquid_core::value!({"a": 1, "b": 2})
+        );
+        assert_eq!(
+            liquid_core::call_filter!(Set, liquid_core::value!([1, 2]), -1, 3).unwrap(),
+            liquid_core::value!([1, 3])
+        );
+        assert_eq!(
+            liquid_core::call_filter!(Set, Value::Nil, "a", 1).unwrap(),
+            liquid_core::value!({"a": 1})
+        );
+        liquid_core::call_filter!(Set, "text", "a", 1).unwrap_err();
+        liquid_core::call_filter!(Set, liquid_core::value!([1]), "a", 1).unwrap_err();
+    }
+
+    #[test]
+    fn unit_merge() {
+        assert_eq!(
+            liquid_core::call_filter!(
+                Merge,
+                liquid_core::value!({"a": 1, "b": 2}),
+                liquid_core::value!({"b": 3, "c": 4})
+            )
+            .unwrap(),
+            liquid_core::value!({"a": 1, "b": 3, "c": 4})
+        );
+        assert_eq!(
+            liquid_core::call_filter!(Merge, Value::Nil, liquid_core::value!({"a": 1})).unwrap(),
+            liquid_core::value!({"a": 1})
+        );
+        liquid_core::call_filter!(Merge, liquid_core::value!({}), 1).unwrap_err();
+        liquid_core::call_filter!(Merge, liquid_core::value!([]), liquid_core::value!({}))
+            .unwrap_err();
+    }
+
+    #[test]
+    fn unit_dict() {
+        let mut options = Language::default();
+        options.filters.register("dict".to_string(), Dict.into());
+
+        let template = parser::parse("{{ base | dict: title: 'Home', lang: lang }}", &options)
+            .map(runtime::Template::new)
+            .unwrap();
+        let globals = liquid_core::object!({"base": {"id": 1}, "lang": "en"});
+        let runtime = RuntimeBuilder::new().set_globals(&globals).build();
+        assert_eq!(
+            template.render(&runtime).unwrap(),
+            liquid_core::value!({"id": 1, "title": "Home", "lang": "en"})
+                .render()
+                .to_string()
+        );
+    }
+}
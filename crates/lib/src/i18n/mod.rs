@@ -0,0 +1,7 @@
+mod plural;
+mod translate;
+mod translations;
+
+pub use self::plural::*;
+pub use self::translate::*;
+pub use self::translations::*;
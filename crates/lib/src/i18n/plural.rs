@@ -0,0 +1,173 @@
+/// CLDR plural category, used as the key of a plural form in a locale tree.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum PluralCategory {
+    Zero,
+    One,
+    Two,
+    Few,
+    Many,
+    Other,
+}
+
+impl PluralCategory {
+    /// The key of the form in a locale tree, e.g. `few`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            PluralCategory::Zero => "zero",
+            PluralCategory::One => "one",
+            PluralCategory::Two => "two",
+            PluralCategory::Few => "few",
+            PluralCategory::Many => "many",
+            PluralCategory::Other => "other",
+        }
+    }
+}
+
+/// Selects the plural category for a count.
+pub type PluralRule = fn(f64) -> PluralCategory;
+
+/// The CLDR cardinal plural rule for a language, e.g. `ru` or `pt-BR`.
+///
+/// Unknown languages use the English rule.
+pub fn plural_rule(locale: &str) -> PluralRule {
+    let locale = locale.to_ascii_lowercase().replace('_', "-");
+    let language = locale.split('-').next().unwrap_or_default();
+    match language {
+        "pt" if locale.starts_with("pt-pt") => english,
+        "fr" | "pt" => french,
+        "ru" | "uk" | "be" => east_slavic,
+        "pl" => polish,
+        "cs" | "sk" => czech,
+        "ar" => arabic,
+        "he" | "iw" => hebrew,
+        "ja" | "zh" | "ko" | "vi" | "th" | "id" | "ms" => no_plural,
+        _ => english,
+    }
+}
+
+/// Integer value of `n`, if it has no fractional digits.
+fn integer(n: f64) -> Option<u64> {
+    if n.fract() == 0.0 {
+        Some(n.abs() as u64)
+    } else {
+        None
+    }
+}
+
+/// `one` for 1, `other` otherwise: `en`, `de`, `nl`, `es`, `it`, `sv`, ...
+fn english(n: f64) -> PluralCategory {
+    match integer(n) {
+        Some(1) => PluralCategory::One,
+        _ => PluralCategory::Other,
+    }
+}
+
+/// `one` for 0 and 1 (including fractions in between).
+fn french(n: f64) -> PluralCategory {
+    if n.abs() < 2.0 {
+        PluralCategory::One
+    } else {
+        PluralCategory::Other
+    }
+}
+
+fn east_slavic(n: f64) -> PluralCategory {
+    match integer(n) {
+        Some(i) if i % 10 == 1 && i % 100 != 11 => PluralCategory::One,
+        Some(i) if (2..=4).contains(&(i % 10)) && !(12..=14).contains(&(i % 100)) => {
+            PluralCategory::Few
+        }
+        Some(_) => PluralCategory::Many,
+        None => PluralCategory::Other,
+    }
+}
+
+fn polish(n: f64) -> PluralCategory {
+    match integer(n) {
+        Some(1) => PluralCategory::One,
+        Some(i) if (2..=4).contains(&(i % 10)) && !(12..=14).contains(&(i % 100)) => {
+            PluralCategory::Few
+        }
+        Some(_) => PluralCategory::Many,
+        None => PluralCategory::Other,
+    }
+}
+
+fn czech(n: f64) -> PluralCategory {
+    match integer(n) {
+        Some(1) => PluralCategory::One,
+        Some(2..=4) => PluralCategory::Few,
+        Some(_) => PluralCategory::Other,
+        None => PluralCategory::Many,
+    }
+}
+
+fn arabic(n: f64) -> PluralCategory {
+    match integer(n) {
+        Some(0) => PluralCategory::Zero,
+        Some(1) => PluralCategory::One,
+        Some(2) => PluralCategory::Two,
+        Some(i) if (3..=10).contains(&(i % 100)) => PluralCategory::Few,
+        Some(i) if (11..=99).contains(&(i % 100)) => PluralCategory::Many,
+        _ => PluralCategory::Other,
+    }
+}
+
+fn hebrew(n: f64) -> PluralCategory {
+    match integer(n) {
+        Some(1) => PluralCategory::One,
+        Some(2) => PluralCategory::Two,
+        _ => PluralCategory::Other,
+    }
+}
+
+fn no_plural(_n: f64) -> PluralCategory {
+    PluralCategory::Other
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn categories(locale: &str, counts: &[f64]) -> Vec<&'static str> {
+        let rule = plural_rule(locale);
+        counts.iter().map(|n| rule(*n).as_str()).collect()
+    }
+
+    #[test]
+    fn french_zero_is_singular() {
+        assert_eq!(
+            categories("fr-CA", &[0.0, 1.0, 1.5, 2.0]),
+            ["one", "one", "one", "other"]
+        );
+    }
+
+    #[test]
+    fn slavic_forms() {
+        assert_eq!(
+            categories("ru", &[1.0, 2.0, 5.0, 11.0, 21.0, 22.0, 112.0, 1.5]),
+            ["one", "few", "many", "many", "one", "few", "many", "other"]
+        );
+        assert_eq!(
+            categories("pl", &[1.0, 2.0, 5.0, 21.0, 22.0]),
+            ["one", "few", "many", "many", "few"]
+        );
+    }
+
+    #[test]
+    fn arabic_forms() {
+        assert_eq!(
+            categories("ar", &[0.0, 1.0, 2.0, 3.0, 11.0, 100.0]),
+            ["zero", "one", "two", "few", "many", "other"]
+        );
+    }
+
+    #[test]
+    fn unknown_is_english() {
+        assert_eq!(
+            categories("xx", &[0.0, 1.0, 2.0]),
+            ["other", "one", "other"]
+        );
+        assert_eq!(categories("ja", &[1.0]), ["other"]);
+    }
+}
@@ -0,0 +1,274 @@
+use std::fmt;
+use std::sync::Arc;
+
+use liquid_core::model::KString;
+use liquid_core::parser::FilterArguments;
+use liquid_core::runtime::LocaleRegister;
+use liquid_core::Error;
+use liquid_core::Expression;
+use liquid_core::Object;
+use liquid_core::Result;
+use liquid_core::Runtime;
+use liquid_core::{Filter, FilterReflection, ParseFilter};
+use liquid_core::{Value, ValueView};
+
+use super::Translations;
+
+/// Translations that were requested but not found during a render.
+///
+/// Only recorded when `Translations` is not strict.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct MissingTranslations {
+    missing: Vec<(KString, KString)>,
+}
+
+impl MissingTranslations {
+    /// `(locale, key)` pairs, in the order they were requested.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.missing.iter().map(|(l, k)| (l.as_str(), k.as_str()))
+    }
+
+    /// Whether any translation was missing.
+    pub fn is_empty(&self) -> bool {
+        self.missing.is_empty()
+    }
+
+    /// Fetches and clears the missing translations.
+    pub fn reset(&mut self) -> Vec<(KString, KString)> {
+        std::mem::take(&mut self.missing)
+    }
+
+    fn record(&mut self, locale: &str, key: &str) {
+        let entry = (KString::from_ref(locale), KString::from_ref(key));
+        if !self.missing.contains(&entry) {
+            self.missing.push(entry);
+        }
+    }
+}
+
+/// The `t` filter, looking up the input as a key in `Translations`.
+///
+/// The locale is taken from, in order, the `locale` argument, the `LocaleRegister` and the
+/// default locale of the `Translations`.  All other keyword arguments are available for
+/// interpolation, with `count` also selecting the plural form.
+#[derive(Clone, FilterReflection)]
+#[filter(
+    name = "t",
+    description = "Translates a key into the active locale, e.g. `{{ 'cart.items' | t: count: 3 }}`."
+)]
+pub struct Translate {
+    translations: Arc<Translations>,
+}
+
+impl Translate {
+    /// Create a `t` filter backed by `translations`.
+    pub fn new<T: Into<Arc<Translations>>>(translations: T) -> Self {
+        Self {
+            translations: translations.into(),
+        }
+    }
+}
+
+impl ParseFilter for Translate {
+    fn parse(&self, mut arguments: FilterArguments) -> Result<Box<dyn Filter>> {
+        if arguments.positional.next().is_some() {
+            return Err(Error::with_msg("Invalid number of positional arguments")
+                .context("cause", "expected at most 0 positional arguments"));
+        }
+
+        let mut locale = None;
+        let mut args = Vec::new();
+        for (name, value) in arguments.keyword {
+            if name == "locale" {
+                locale = Some(value);
+            } else {
+                args.push((KString::from_ref(name), value));
+            }
+        }
+
+        Ok(Box::new(TranslateFilter {
+            translations: self.translations.clone(),
+            locale,
+            args,
+        }))
+    }
+
+    fn reflection(&self) -> &dyn FilterReflection {
+        self
+    }
+}
+
+#[derive(Debug)]
+struct TranslateFilter {
+    translations: Arc<Translations>,
+    locale: Option<Expression>,
+    args: Vec<(KString, Expression)>,
+}
+
+impl TranslateFilter {
+    fn locale(&self, runtime: &dyn Runtime) -> Result<KString> {
+        if let Some(locale) = &self.locale {
+            return Ok(locale.evaluate(runtime)?.to_kstr().into());
+        }
+        if let Some(locale) = runtime.registers().get_mut::<LocaleRegister>().get() {
+            return Ok(KString::from_ref(locale));
+        }
+        self.translations
+            .get_default_locale()
+            .map(KString::from_ref)
+            .ok_or_else(|| Error::with_msg("No active locale"))
+    }
+}
+
+impl Filter for TranslateFilter {
+    fn evaluate(&self, input: &dyn ValueView, runtime: &dyn Runtime) -> Result<Value> {
+        let key = input.to_kstr();
+        let locale = self.locale(runtime)?;
+
+        let mut args = Object::new();
+        for (name, value) in &self.args {
+            args.insert(name.clone(), value.evaluate(runtime)?.into_owned());
+        }
+
+        match self.translations.translate(&locale, &key, &args)? {
+            Some(translation) => Ok(translation),
+            None if self.translations.is_strict() => Error::with_msg("Missing translation")
+                .context("locale", locale)
+                .context("key", key.into_owned())
+                .into_err(),
+            None => {
+                runtime
+                    .registers()
+                    .get_mut::<MissingTranslations>()
+                    .record(&locale, &key);
+                Ok(Value::scalar(format!(
+                    "translation missing: {}.{}",
+                    locale, key
+                )))
+            }
+        }
+    }
+}
+
+impl fmt::Display for TranslateFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let locale = self
+            .locale
+            .iter()
+            .map(|locale| format!("locale: {}", locale));
+        let args = self
+            .args
+            .iter()
+            .map(|(name, value)| format!("{}: {}", name, value));
+        let parameters = locale.chain(args).collect::<Vec<_>>().join(", ");
+        if parameters.is_empty() {
+            write!(f, "t")
+        } else {
+            write!(f, "t : {}", parameters)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use liquid_core::parser;
+    use liquid_core::runtime;
+    use liquid_core::runtime::Registers;
+    use liquid_core::runtime::RuntimeBuilder;
+    use liquid_core::Language;
+    use liquid_core::Renderable;
+
+    fn options(translations: Translations) -> Language {
+        let mut options = Language::default();
+        options
+            .filters
+            .register("t".to_string(), Translate::new(translations).into());
+        options
+    }
+
+    fn translations() -> Translations {
+        Translations::new()
+            .locale(
+                "en",
+                liquid_core::object!({
+                    "greeting": "Hello {{ name }}",
+                    "cart": {"items": {"one": "{{ count }} item", "other": "{{ count }} items"}},
+                }),
+            )
+            .locale(
+                "fr",
+                liquid_core::object!({"greeting": "Bonjour {{ name }}"}),
+            )
+            .default_locale("en")
+    }
+
+    fn render(text: &str, translations: Translations, registers: &Registers) -> Result<String> {
+        let options = options(translations);
+        let template = parser::parse(text, &options).map(runtime::Template::new)?;
+        let runtime = RuntimeBuilder::new().set_registers(registers).build();
+        runtime.set_global("user".into(), liquid_core::value!("Ana"));
+        template.render(&runtime)
+    }
+
+    #[test]
+    fn translate_default_locale() {
+        let output = render(
+            "{{ 'cart.items' | t: count: 3 }}",
+            translations(),
+            &Registers::default(),
+        )
+        .unwrap();
+        assert_eq!(output, "3 items");
+    }
+
+    #[test]
+    fn translate_active_locale() {
+        let registers = Registers::default();
+        registers.get_mut::<LocaleRegister>().set("fr");
+        let output = render(
+            "{{ 'greeting' | t: name: user }}, {{ 'cart.items' | t: count: 1 }}",
+            translations(),
+            &registers,
+        )
+        .unwrap();
+        assert_eq!(output, "Bonjour Ana, 1 item");
+    }
+
+    #[test]
+    fn translate_locale_argument() {
+        let output = render(
+            "{{ 'greeting' | t: name: user, locale: 'fr' }}",
+            translations(),
+            &Registers::default(),
+        )
+        .unwrap();
+        assert_eq!(output, "Bonjour Ana");
+    }
+
+    #[test]
+    fn translate_missing() {
+        let registers = Registers::default();
+        registers.get_mut::<LocaleRegister>().set("fr");
+        let output = render("{{ 'cart.title' | t }}", translations(), &registers).unwrap();
+        assert_eq!(output, "translation missing: fr.cart.title");
+        assert_eq!(
+            registers
+                .get_mut::<MissingTranslations>()
+                .iter()
+                .collect::<Vec<_>>(),
+            [("fr", "cart.title")]
+        );
+    }
+
+    #[test]
+    fn translate_missing_strict() {
+        render(
+            "{{ 'cart.title' | t }}",
+            translations().strict(true),
+            &Registers::default(),
+        )
+        .unwrap_err();
+    }
+}
@@ -0,0 +1,342 @@
+use std::collections::HashMap;
+
+use liquid_core::model::KString;
+use liquid_core::Error;
+use liquid_core::Object;
+use liquid_core::Result;
+use liquid_core::{ObjectView, Value, ValueView};
+use regex::Regex;
+
+use super::{plural_rule, PluralRule};
+
+static INTERPOLATION: once_cell::sync::Lazy<Regex> =
+    once_cell::sync::Lazy::new(|| Regex::new(r"\{\{\s*([\w-]+)\s*\}\}").unwrap());
+
+/// Locale trees for the `t` filter.
+///
+/// Each locale is an `Object` of nested keys, usually deserialized from YAML or JSON, e.g.
+/// ```yaml
+/// en:
+///   cart:
+///     title: "Your cart"
+///     items:
+///       zero: "Your cart is empty"
+///       one: "{{ count }} item"
+///       other: "{{ count }} items"
+/// ```
+///
+/// When a key is missing from the requested locale, it is looked up in the locale's fallback
+/// chain: explicitly configured fallbacks, then parent locales (`fr-CA` falls back to `fr`) and
+/// finally the default locale.
+///
+/// Plural forms are selected with the CLDR rule of the locale the entry was found in (see
+/// `plural_rule`), with an explicit `zero` form taking precedence for a count of 0.
+#[derive(Clone, Debug, Default)]
+pub struct Translations {
+    locales: Object,
+    fallbacks: HashMap<KString, Vec<KString>>,
+    plural_rules: HashMap<KString, PluralRule>,
+    default_locale: Option<KString>,
+    strict: bool,
+}
+
+impl Translations {
+    /// Create an empty store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a store from a tree whose top-level keys are locales.
+    pub fn from_object(locales: Object) -> Self {
+        Self {
+            locales,
+            ..Default::default()
+        }
+    }
+
+    /// Add (or replace) the tree for `locale`.
+    pub fn locale<S: Into<KString>>(mut self, locale: S, tree: Object) -> Self {
+        self.locales.insert(locale.into(), Value::Object(tree));
+        self
+    }
+
+    /// Locales to try, in order, when a key is missing from `locale`.
+    pub fn fallbacks<S, I>(mut self, locale: S, fallbacks: I) -> Self
+    where
+        S: Into<KString>,
+        I: IntoIterator,
+        I::Item: Into<KString>,
+    {
+        self.fallbacks.insert(
+            locale.into(),
+            fallbacks.into_iter().map(|l| l.into()).collect(),
+        );
+        self
+    }
+
+    /// Override the plural rule for `locale` and its sub-locales.
+    pub fn plural_rule<S: Into<KString>>(mut self, locale: S, rule: PluralRule) -> Self {
+        self.plural_rules.insert(locale.into(), rule);
+        self
+    }
+
+    /// The plural rule used for entries found in `locale`.
+    pub fn get_plural_rule(&self, locale: &str) -> PluralRule {
+        let mut name = locale;
+        loop {
+            if let Some(rule) = self.plural_rules.get(name) {
+                return *rule;
+            }
+            match name.rsplit_once(['-', '_']) {
+                Some((parent, _)) => name = parent,
+                None => return plural_rule(locale),
+            }
+        }
+    }
+
+    /// Locale used when none is active for the render and as the last fallback.
+    pub fn default_locale<S: Into<KString>>(mut self, locale: S) -> Self {
+        self.default_locale = Some(locale.into());
+        self
+    }
+
+    /// Whether missing translations are an error, rather than rendering a placeholder.
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Locale used when none is active for the render.
+    pub fn get_default_locale(&self) -> Option<&str> {
+        self.default_locale.as_deref()
+    }
+
+    /// Whether missing translations are an error.
+    pub fn is_strict(&self) -> bool {
+        self.strict
+    }
+
+    /// Available locales.
+    pub fn locales(&self) -> impl Iterator<Item = &str> {
+        self.locales.keys().map(|k| k.as_str())
+    }
+
+    /// Locales searched, in order, for a key requested in `locale`.
+    pub fn fallback_chain<'s>(&'s self, locale: &'s str) -> Vec<&'s str> {
+        let mut chain = Vec::new();
+        self.extend_chain(locale, &mut chain);
+        if let Some(default) = self.default_locale.as_deref() {
+            self.extend_chain(default, &mut chain);
+        }
+        chain
+    }
+
+    fn extend_chain<'s>(&'s self, locale: &'s str, chain: &mut Vec<&'s str>) {
+        if chain.contains(&locale) {
+            return;
+        }
+        chain.push(locale);
+        if let Some(fallbacks) = self.fallbacks.get(locale) {
+            for fallback in fallbacks {
+                self.extend_chain(fallback.as_str(), chain);
+            }
+        }
+        if let Some((parent, _)) = locale.rsplit_once(['-', '_']) {
+            self.extend_chain(parent, chain);
+        }
+    }
+
+    /// Find the raw entry for `key` (dot-separated) in `locale` or its fallbacks.
+    ///
+    /// Returns the locale the entry was found in.
+    pub fn lookup<'s>(&'s self, locale: &'s str, key: &str) -> Option<(&'s str, &'s Value)> {
+        self.fallback_chain(locale)
+            .into_iter()
+            .find_map(|locale| self.lookup_exact(locale, key).map(|v| (locale, v)))
+    }
+
+    fn lookup_exact(&self, locale: &str, key: &str) -> Option<&Value> {
+        key.split('.')
+            .try_fold(self.locales.get(locale)?, |value, part| match value {
+                Value::Object(obj) => obj.get(part),
+                Value::Array(arr) => part.parse::<usize>().ok().and_then(|i| arr.get(i)),
+                _ => None,
+            })
+    }
+
+    /// Translate `key` in `locale`, pluralizing on `args["count"]` and interpolating `args`.
+    ///
+    /// Returns `None` if the key is missing from the whole fallback chain.
+    pub fn translate(
+        &self,
+        locale: &str,
+        key: &str,
+        args: &dyn ObjectView,
+    ) -> Result<Option<Value>> {
+        let (found, entry) = match self.lookup(locale, key) {
+            Some(found) => found,
+            None => return Ok(None),
+        };
+
+        let entry = match (entry, args.get("count")) {
+            (Value::Object(forms), Some(count)) => {
+                match select_plural(forms, count, self.get_plural_rule(found)) {
+                    Some(entry) => entry,
+                    None => {
+                        return Error::with_msg("Missing plural form")
+                            .context("locale", found.to_owned())
+                            .context("key", key.to_owned())
+                            .context("count", count.to_kstr().into_owned())
+                            .into_err();
+                    }
+                }
+            }
+            (entry, _) => entry,
+        };
+
+        let translation = match entry {
+            Value::Scalar(s) => Value::scalar(interpolate(s.to_kstr().as_str(), args)?),
+            entry => entry.clone(),
+        };
+        Ok(Some(translation))
+    }
+}
+
+fn select_plural<'o>(
+    forms: &'o Object,
+    count: &dyn ValueView,
+    rule: PluralRule,
+) -> Option<&'o Value> {
+    let count = count.as_scalar().and_then(|s| s.to_float());
+    let form = match count {
+        Some(count) if count == 0.0 && forms.contains_key("zero") => "zero",
+        Some(count) => rule(count).as_str(),
+        None => "other",
+    };
+    forms.get(form).or_else(|| forms.get("other"))
+}
+
+fn interpolate(template: &str, args: &dyn ObjectView) -> Result<String> {
+    let mut result = String::with_capacity(template.len());
+    let mut last = 0;
+    for captures in INTERPOLATION.captures_iter(template) {
+        let whole = captures.get(0).expect("always a whole match");
+        let name = &captures[1];
+        let value = args.get(name).ok_or_else(|| {
+            Error::with_msg("Unknown interpolation variable").context("variable", name.to_owned())
+        })?;
+        result.push_str(&template[last..whole.start()]);
+        result.push_str(&value.render().to_string());
+        last = whole.end();
+    }
+    result.push_str(&template[last..]);
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::PluralCategory;
+    use super::*;
+
+    fn translations() -> Translations {
+        let locales: Object = serde_yaml::from_str(
+            r#"
+en:
+  greeting: "Hello {{ name }}!"
+  cart:
+    items:
+      zero: "Your cart is empty"
+      one: "{{ count }} item"
+      other: "{{ count }} items"
+fr:
+  greeting: "Bonjour {{name}} !"
+  cart:
+    items:
+      one: "{{ count }} article"
+      other: "{{ count }} articles"
+fr-CA:
+  greeting: "Allô {{ name }}!"
+ru:
+  cart:
+    items:
+      one: "{{ count }} товар"
+      few: "{{ count }} товара"
+      many: "{{ count }} товаров"
+      other: "{{ count }} товара"
+"#,
+        )
+        .unwrap();
+        Translations::from_object(locales).default_locale("en")
+    }
+
+    #[test]
+    fn fallback_chain() {
+        let translations = translations().fallbacks("pt-BR", ["pt", "es"]);
+        assert_eq!(translations.fallback_chain("fr-CA"), ["fr-CA", "fr", "en"]);
+        assert_eq!(
+            translations.fallback_chain("pt-BR"),
+            ["pt-BR", "pt", "es", "en"]
+        );
+        assert_eq!(translations.fallback_chain("en"), ["en"]);
+    }
+
+    #[test]
+    fn lookup_falls_back() {
+        let translations = translations();
+        let (locale, _) = translations.lookup("fr-CA", "cart.items").unwrap();
+        assert_eq!(locale, "fr");
+        let (locale, _) = translations.lookup("de", "greeting").unwrap();
+        assert_eq!(locale, "en");
+        assert!(translations.lookup("fr", "cart.missing").is_none());
+    }
+
+    #[test]
+    fn translate_interpolates() {
+        let translations = translations();
+        let args = liquid_core::object!({"name": "Ana"});
+        assert_eq!(
+            translations.translate("fr", "greeting", &args).unwrap(),
+            Some(liquid_core::value!("Bonjour Ana !"))
+        );
+        translations
+            .translate("fr", "greeting", &Object::new())
+            .unwrap_err();
+    }
+
+    #[test]
+    fn translate_pluralizes() {
+        let translations = translations();
+        let translate = |locale, count: i64| {
+            let args = liquid_core::object!({ "count": count });
+            translations
+                .translate(locale, "cart.items", &args)
+                .unwrap()
+                .unwrap()
+        };
+        assert_eq!(
+            translate("en", 0),
+            liquid_core::value!("Your cart is empty")
+        );
+        assert_eq!(translate("en", 1), liquid_core::value!("1 item"));
+        assert_eq!(translate("en", 3), liquid_core::value!("3 items"));
+        assert_eq!(translate("fr", 0), liquid_core::value!("0 article"));
+        assert_eq!(translate("fr", 2), liquid_core::value!("2 articles"));
+        assert_eq!(translate("ru", 1), liquid_core::value!("1 товар"));
+        assert_eq!(translate("ru", 3), liquid_core::value!("3 товара"));
+        assert_eq!(translate("ru", 5), liquid_core::value!("5 товаров"));
+        assert_eq!(translate("ru", 21), liquid_core::value!("21 товар"));
+    }
+
+    #[test]
+    fn custom_plural_rule() {
+        let translations = translations().plural_rule("fr", |_| PluralCategory::Other);
+        let args = liquid_core::object!({ "count": 1 });
+        assert_eq!(
+            translations
+                .translate("fr-CA", "cart.items", &args)
+                .unwrap()
+                .unwrap(),
+            liquid_core::value!("1 articles")
+        );
+    }
+}
@@ -0,0 +1,197 @@
+use std::io::Write;
+use std::sync::Arc;
+
+use liquid_core::error::ResultLiquidExt;
+use liquid_core::model::KString;
+use liquid_core::runtime::{GlobalReferences, StackFrame};
+use liquid_core::Language;
+use liquid_core::Renderable;
+use liquid_core::Runtime;
+use liquid_core::Template;
+use liquid_core::{BlockReflection, ParseBlock, TagBlock, TagTokenIter};
+use liquid_core::{Error, Result};
+use liquid_core::{Object, Value};
+
+use super::{BlockBody, InheritanceRegister};
+
+/// `{% block name %}`, a part of a template that templates extending it can replace.
+///
+/// The replacement can render the original with `{{ block.super }}`.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct BlockBlock;
+
+impl BlockBlock {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl BlockReflection for BlockBlock {
+    fn start_tag(&self) -> &str {
+        "block"
+    }
+
+    fn end_tag(&self) -> &str {
+        "endblock"
+    }
+
+    fn description(&self) -> &str {
+        "Defines a part of the template that templates extending it can replace."
+    }
+}
+
+impl ParseBlock for BlockBlock {
+    fn parse(
+        &self,
+        mut arguments: TagTokenIter<'_>,
+        mut tokens: TagBlock<'_, '_>,
+        options: &Language,
+    ) -> Result<Box<dyn Renderable>> {
+        let name: KString = arguments
+            .expect_next("Identifier expected")?
+            .expect_identifier()
+            .into_result()?
+            .to_string()
+            .into();
+
+        // no more arguments should be supplied, trying to supply them is an error
+        arguments.expect_nothing()?;
+
+        let template = Template::new(
+            tokens
+                .parse_all(options)
+                .trace_with(|| format!("{{% block {} %}}", &name).into())?,
+        );
+
+        tokens.assert_empty();
+
+        let mut globals = GlobalReferences::new();
+        template.collect_globals(&mut globals);
+        let uses_super = globals
+            .iter()
+            .any(|global| global.path().first().map_or(false, |root| root == "block"));
+
+        let body = Arc::new(BlockBody {
+            template,
+            uses_super,
+        });
+        Ok(Box::new(Block { name, body }))
+    }
+
+    fn reflection(&self) -> &dyn BlockReflection {
+        self
+    }
+}
+
+#[derive(Debug)]
+struct Block {
+    name: KString,
+    body: Arc<BlockBody>,
+}
+
+impl Block {
+    fn trace(&self) -> String {
+        format!("{{% block {} %}}", self.name)
+    }
+}
+
+impl Renderable for Block {
+    fn render_to(&self, writer: &mut dyn Write, runtime: &dyn Runtime) -> Result<()> {
+        let mut bodies = {
+            let mut register = runtime.registers().get_mut::<InheritanceRegister>();
+            if register.collecting {
+                if register.collected.contains(&self.name) {
+                    return Error::with_msg("Block defined twice")
+                        .context("block", self.name.clone())
+                        .into_err();
+                }
+                register.collected.push(self.name.clone());
+                register
+                    .overrides
+                    .entry(self.name.clone())
+                    .or_default()
+                    .push(self.body.clone());
+                return Ok(());
+            }
+
+            register.rendered.insert(self.name.clone());
+            register
+                .overrides
+                .get(&self.name)
+                .cloned()
+                .unwrap_or_default()
+        };
+        bodies.push(self.body.clone());
+
+        render_body(&bodies, writer, runtime).trace_with(|| self.trace().into())
+    }
+
+    fn collect_globals(&self, globals: &mut GlobalReferences<'_>) {
+        globals.push_scope(&["block"]);
+        self.body.template.collect_globals(globals);
+        globals.pop_scope();
+    }
+}
+
+/// Renders the first of `bodies`, with the rest as its `block.super`.
+fn render_body(
+    bodies: &[Arc<BlockBody>],
+    writer: &mut dyn Write,
+    runtime: &dyn Runtime,
+) -> Result<()> {
+    let (body, parents) = bodies
+        .split_first()
+        .expect("blocks always have their own body");
+    if !body.uses_super {
+        return body.template.render_to(writer, runtime);
+    }
+
+    let parent = if parents.is_empty() {
+        Value::Nil
+    } else {
+        let mut rendered = Vec::new();
+        render_body(parents, &mut rendered, runtime)?;
+        let rendered = String::from_utf8(rendered).expect("render only writes UTF-8");
+        Value::safe_output(rendered)
+    };
+    let mut block = Object::new();
+    block.insert("super".into(), parent);
+    let mut scope = Object::new();
+    scope.insert("block".into(), Value::Object(block));
+
+    let scope = StackFrame::new(runtime, &scope);
+    body.template.render_to(writer, &scope)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use liquid_core::parser;
+    use liquid_core::runtime;
+    use liquid_core::runtime::RuntimeBuilder;
+
+    fn options() -> Language {
+        let mut options = Language::default();
+        options
+            .blocks
+            .register("block".to_string(), BlockBlock.into());
+        options
+    }
+
+    #[test]
+    fn block_renders_its_content() {
+        let text = "a{% block title %}{{ 'b' }}{{ block.super }}{% endblock %}c";
+        let template = parser::parse(text, &options())
+            .map(runtime::Template::new)
+            .unwrap();
+        let runtime = RuntimeBuilder::new().build();
+        assert_eq!(template.render(&runtime).unwrap(), "abc");
+    }
+
+    #[test]
+    fn block_needs_a_name() {
+        parser::parse("{% block %}{% endblock %}", &options()).unwrap_err();
+        parser::parse("{% block a b %}{% endblock %}", &options()).unwrap_err();
+    }
+}
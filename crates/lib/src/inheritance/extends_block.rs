@@ -0,0 +1,305 @@
+use std::io::{self, Write};
+
+use liquid_core::error::ResultLiquidExt;
+use liquid_core::model::KString;
+use liquid_core::runtime::GlobalReferences;
+use liquid_core::Expression;
+use liquid_core::Language;
+use liquid_core::Renderable;
+use liquid_core::Runtime;
+use liquid_core::Template;
+use liquid_core::ValueView;
+use liquid_core::{BlockReflection, ParseBlock, TagBlock, TagTokenIter};
+use liquid_core::{Error, Result};
+
+use super::InheritanceRegister;
+
+/// `{% extends 'base' %}`, rendering the partial `base` with its blocks replaced by the ones of
+/// this template.
+///
+/// It runs to the end of the template, so has no end tag.  Anything outside of blocks isn't
+/// output, though tags like `assign` still apply.  Replacing a block the extended templates
+/// don't render is an error.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct ExtendsBlock;
+
+impl ExtendsBlock {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl BlockReflection for ExtendsBlock {
+    fn start_tag(&self) -> &str {
+        "extends"
+    }
+
+    fn end_tag(&self) -> &str {
+        ""
+    }
+
+    fn description(&self) -> &str {
+        "Renders a parent template with its blocks replaced by the ones of this template."
+    }
+}
+
+impl ParseBlock for ExtendsBlock {
+    fn parse(
+        &self,
+        mut arguments: TagTokenIter<'_>,
+        mut tokens: TagBlock<'_, '_>,
+        options: &Language,
+    ) -> Result<Box<dyn Renderable>> {
+        let parent = arguments
+            .expect_next("Identifier or literal expected.")?
+            .expect_value()
+            .into_result()?;
+
+        // no more arguments should be supplied, trying to supply them is an error
+        arguments.expect_nothing()?;
+
+        let template = Template::new(
+            tokens
+                .parse_all(options)
+                .trace_with(|| format!("{{% extends {} %}}", parent).into())?,
+        );
+
+        tokens.assert_empty();
+        Ok(Box::new(Extends { parent, template }))
+    }
+
+    fn reflection(&self) -> &dyn BlockReflection {
+        self
+    }
+}
+
+#[derive(Debug)]
+struct Extends {
+    parent: Expression,
+    template: Template,
+}
+
+impl Extends {
+    fn trace(&self) -> String {
+        format!("{{% extends {} %}}", self.parent)
+    }
+
+    /// Renders the parent once this template's blocks are collected.
+    fn render_parent(
+        &self,
+        name: &str,
+        writer: &mut dyn Write,
+        runtime: &dyn Runtime,
+    ) -> Result<()> {
+        let parent = runtime.partials().get(name)?;
+        parent.render_to(writer, runtime)
+    }
+}
+
+impl Renderable for Extends {
+    fn render_to(&self, writer: &mut dyn Write, runtime: &dyn Runtime) -> Result<()> {
+        let name: KString = self
+            .parent
+            .evaluate(runtime)
+            .trace_with(|| self.trace().into())?
+            .to_kstr()
+            .into();
+
+        let (collecting, outer_collected, overrides) = {
+            let mut register = runtime.registers().get_mut::<InheritanceRegister>();
+            if register.extending.contains(&name) {
+                return Error::with_msg("Circular extends")
+                    .context("template", name)
+                    .trace(self.trace())
+                    .into_err();
+            }
+            register.extending.push(name.clone());
+            let collecting = std::mem::replace(&mut register.collecting, true);
+            let outer_collected = std::mem::take(&mut register.collected);
+            (collecting, outer_collected, register.overrides.clone())
+        };
+
+        // Only this template's blocks are output, by the parent.
+        let result = self.template.render_to(&mut io::sink(), runtime);
+        let (collected, outer_rendered) = {
+            let mut register = runtime.registers().get_mut::<InheritanceRegister>();
+            register.collecting = false;
+            let collected = std::mem::replace(&mut register.collected, outer_collected);
+            (collected, std::mem::take(&mut register.rendered))
+        };
+        let result = result.and_then(|()| self.render_parent(&name, writer, runtime));
+
+        let unknown = {
+            let mut register = runtime.registers().get_mut::<InheritanceRegister>();
+            let unknown = collected
+                .into_iter()
+                .find(|block| !register.rendered.contains(block));
+            register.rendered.extend(outer_rendered);
+            register.overrides = overrides;
+            register.collecting = collecting;
+            register.extending.pop();
+            unknown
+        };
+        result
+            .trace_with(|| self.trace().into())
+            .context_key("template")
+            .value_with(|| name.to_string().into())?;
+
+        match unknown {
+            Some(block) => Error::with_msg("Unknown block")
+                .context("block", block)
+                .context("cause", "not rendered by the templates it extends")
+                .context("template", name)
+                .trace(self.trace())
+                .into_err(),
+            None => Ok(()),
+        }
+    }
+
+    fn collect_globals(&self, globals: &mut GlobalReferences<'_>) {
+        globals.read(&self.parent);
+        self.template.collect_globals(globals);
+        if let Expression::Literal(ref name) = self.parent {
+            globals.include(&name.to_kstr(), &[]);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use liquid_core::parser;
+    use liquid_core::partials;
+    use liquid_core::partials::PartialCompiler;
+    use liquid_core::runtime;
+    use liquid_core::runtime::RuntimeBuilder;
+    use liquid_core::Object;
+
+    use crate::inheritance::BlockBlock;
+    use crate::stdlib;
+
+    fn options() -> Language {
+        let mut options = Language::default();
+        options
+            .blocks
+            .register("extends".to_string(), ExtendsBlock.into());
+        options
+            .blocks
+            .register("block".to_string(), BlockBlock.into());
+        options
+            .tags
+            .register("assign".to_string(), stdlib::AssignTag.into());
+        options
+    }
+
+    fn render(text: &str, partials: &[(&str, &str)], globals: &Object) -> Result<String> {
+        let mut source = partials::InMemorySource::new();
+        for (name, partial) in partials {
+            source.add(*name, *partial);
+        }
+        let options = std::sync::Arc::new(options());
+        let partials = partials::EagerCompiler::new(source)
+            .compile(options.clone())
+            .unwrap();
+        let template = parser::parse(text, &options).map(runtime::Template::new)?;
+        let runtime = RuntimeBuilder::new()
+            .set_globals(globals)
+            .set_partials(partials.as_ref())
+            .build();
+        template.render(&runtime)
+    }
+
+    const BASE: &str = concat!(
+        "<title>{% block title %}Site{% endblock %}</title>",
+        "<main>{% block content %}{% endblock %}</main>",
+    );
+
+    #[test]
+    fn extends_replaces_blocks() {
+        let text = concat!(
+            "{% extends 'base' %}ignored",
+            "{% block content %}Hello {{ name }}{% endblock %}",
+        );
+        let globals = liquid_core::object!({"name": "Ann"});
+        assert_eq!(
+            render(text, &[("base", BASE)], &globals).unwrap(),
+            "<title>Site</title><main>Hello Ann</main>"
+        );
+    }
+
+    #[test]
+    fn extends_super() {
+        let text = "{% extends 'base' %}{% block title %}Post | {{ block.super }}{% endblock %}";
+        assert_eq!(
+            render(text, &[("base", BASE)], &Object::new()).unwrap(),
+            "<title>Post | Site</title><main></main>"
+        );
+    }
+
+    #[test]
+    fn extends_multiple_levels() {
+        let section = concat!(
+            "{% extends 'base' %}",
+            "{% block title %}Blog | {{ block.super }}{% endblock %}",
+            "{% block content %}<article>{% block article %}{% endblock %}</article>{% endblock %}",
+        );
+        let text = concat!(
+            "{% extends 'section' %}{% assign title = 'Post' %}",
+            "{% block title %}{{ title }} | {{ block.super }}{% endblock %}",
+            "{% block article %}Text{% endblock %}",
+        );
+        assert_eq!(
+            render(
+                text,
+                &[("base", BASE), ("section", section)],
+                &Object::new()
+            )
+            .unwrap(),
+            "<title>Post | Blog | Site</title><main><article>Text</article></main>"
+        );
+    }
+
+    #[test]
+    fn extends_by_variable() {
+        let text = "{% extends layout %}{% block title %}Post{% endblock %}";
+        let globals = liquid_core::object!({"layout": "base"});
+        assert_eq!(
+            render(text, &[("base", BASE)], &globals).unwrap(),
+            "<title>Post</title><main></main>"
+        );
+    }
+
+    #[test]
+    fn extends_unknown_block() {
+        let text = "{% extends 'base' %}{% block sidebar %}Links{% endblock %}";
+        let err = render(text, &[("base", BASE)], &Object::new())
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("Unknown block"), "{}", err);
+        assert!(err.contains("sidebar"), "{}", err);
+    }
+
+    #[test]
+    fn extends_block_twice() {
+        let text = concat!(
+            "{% extends 'base' %}",
+            "{% block title %}a{% endblock %}{% block title %}b{% endblock %}",
+        );
+        let err = render(text, &[("base", BASE)], &Object::new())
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("Block defined twice"), "{}", err);
+    }
+
+    #[test]
+    fn extends_itself() {
+        let looping = "{% extends 'looping' %}";
+        render(looping, &[("looping", looping)], &Object::new()).unwrap_err();
+    }
+
+    #[test]
+    fn extends_unknown_template() {
+        render("{% extends 'missing' %}", &[], &Object::new()).unwrap_err();
+    }
+}
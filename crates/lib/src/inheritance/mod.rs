@@ -0,0 +1,38 @@
+//! Template inheritance, with `{% extends %}` and `{% block %}`.
+
+mod block_block;
+mod extends_block;
+
+pub use self::block_block::*;
+pub use self::extends_block::*;
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use liquid_core::model::KString;
+use liquid_core::Template;
+
+// liquid-rust proprietary
+
+/// The content of a `{% block %}`, shared with the templates overriding it.
+#[derive(Debug)]
+struct BlockBody {
+    template: Template,
+    /// Whether it reads `block.super`, rendered only then.
+    uses_super: bool,
+}
+
+/// The state of template inheritance for the current render.
+#[derive(Debug, Default)]
+struct InheritanceRegister {
+    /// Set while `extends` looks for the blocks of its template.
+    collecting: bool,
+    /// The blocks found by the innermost `extends`.
+    collected: Vec<KString>,
+    /// The overrides of each block, the most derived first.
+    overrides: HashMap<KString, Vec<Arc<BlockBody>>>,
+    /// The blocks rendered by the templates being extended.
+    rendered: HashSet<KString>,
+    /// The templates being extended, to catch cycles.
+    extending: Vec<KString>,
+}
@@ -3,6 +3,7 @@ use std::io::Write;
 use liquid_core::error::ResultLiquidExt;
 use liquid_core::model::KString;
 use liquid_core::parser::TryMatchToken;
+use liquid_core::runtime::GlobalReferences;
 use liquid_core::Expression;
 use liquid_core::Language;
 use liquid_core::Renderable;
@@ -120,6 +121,22 @@ impl Renderable for Include {
 
         Ok(())
     }
+
+    fn collect_globals(&self, globals: &mut GlobalReferences<'_>) {
+        globals.read(&self.partial);
+        for (_, val) in &self.vars {
+            globals.read(val);
+        }
+
+        if let Expression::Literal(ref name) = self.partial {
+            let locals: &[&str] = if self.vars.is_empty() {
+                &[]
+            } else {
+                &["include"]
+            };
+            globals.include(&name.render().to_string(), locals);
+        }
+    }
 }
 
 #[cfg(test)]
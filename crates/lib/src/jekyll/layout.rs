@@ -0,0 +1,336 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::io::Write;
+use std::sync;
+
+use liquid_core::error::ResultLiquidExt;
+use liquid_core::model::{KString, ScalarCow};
+use liquid_core::parser::{self, Language};
+use liquid_core::partials::{PartialCompiler, PartialSource};
+use liquid_core::runtime::{GlobalReferences, PartialStore, StackFrame};
+use liquid_core::Renderable;
+use liquid_core::Runtime;
+use liquid_core::Template;
+use liquid_core::{Error, Result};
+use liquid_core::{Object, Value, ValueView};
+
+/// Compiles partials like `EagerCompiler`, after taking off their YAML front matter.
+///
+/// This lets layouts name the layout they go in, for [`render_page`], and read their own front
+/// matter as `layout`.
+#[derive(Debug)]
+pub struct LayoutCompiler<S: PartialSource> {
+    source: S,
+}
+
+impl<S> LayoutCompiler<S>
+where
+    S: PartialSource,
+{
+    /// Create a layout compiler for `PartialSource`.
+    pub fn new(source: S) -> Self {
+        LayoutCompiler { source }
+    }
+}
+
+impl<S> LayoutCompiler<S>
+where
+    S: PartialSource + Default,
+{
+    /// Create an empty compiler for `PartialSource`.
+    pub fn empty() -> Self {
+        Default::default()
+    }
+}
+
+impl<S> Default for LayoutCompiler<S>
+where
+    S: PartialSource + Default,
+{
+    fn default() -> Self {
+        Self {
+            source: Default::default(),
+        }
+    }
+}
+
+impl<S> ::std::ops::Deref for LayoutCompiler<S>
+where
+    S: PartialSource,
+{
+    type Target = S;
+
+    fn deref(&self) -> &S {
+        &self.source
+    }
+}
+
+impl<S> ::std::ops::DerefMut for LayoutCompiler<S>
+where
+    S: PartialSource,
+{
+    fn deref_mut(&mut self) -> &mut S {
+        &mut self.source
+    }
+}
+
+impl<S> PartialCompiler for LayoutCompiler<S>
+where
+    S: PartialSource + Send + Sync + 'static,
+{
+    fn compile(self, language: sync::Arc<Language>) -> Result<Box<dyn PartialStore + Send + Sync>> {
+        let store: HashMap<_, _> = self
+            .source
+            .names()
+            .into_iter()
+            .map(|name| {
+                let layout = self.source.get(name).and_then(|s| {
+                    parser::parse_document(s.as_ref(), &language).map(|(front_matter, elements)| {
+                        let layout: sync::Arc<dyn Renderable> = sync::Arc::new(Layout {
+                            front_matter,
+                            template: Template::new(elements),
+                        });
+                        layout
+                    })
+                });
+                (name.to_owned(), layout)
+            })
+            .collect();
+        Ok(Box::new(LayoutStore { store }))
+    }
+
+    fn source(&self) -> &dyn PartialSource {
+        &self.source
+    }
+}
+
+struct LayoutStore {
+    store: HashMap<String, Result<sync::Arc<dyn Renderable>>>,
+}
+
+impl PartialStore for LayoutStore {
+    fn contains(&self, name: &str) -> bool {
+        self.store.contains_key(name)
+    }
+
+    fn names(&self) -> Vec<&str> {
+        self.store.keys().map(|s| s.as_str()).collect()
+    }
+
+    fn try_get(&self, name: &str) -> Option<sync::Arc<dyn Renderable>> {
+        self.store.get(name).and_then(|r| r.clone().ok())
+    }
+
+    fn get(&self, name: &str) -> Result<sync::Arc<dyn Renderable>> {
+        let result = self.store.get(name).ok_or_else(|| {
+            let mut available: Vec<_> = self.names();
+            available.sort_unstable();
+            let available = itertools::join(available, ", ");
+            Error::with_msg("Unknown partial-template")
+                .context("requested partial", name.to_owned())
+                .context("available partials", available)
+        })?;
+        result.clone()
+    }
+}
+
+impl fmt::Debug for LayoutStore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.names().fmt(f)
+    }
+}
+
+/// The layout named by the front matter of the last partial rendered.
+#[derive(Debug, Default)]
+struct LayoutRegister {
+    layout: Option<KString>,
+}
+
+#[derive(Debug)]
+struct Layout {
+    front_matter: Object,
+    template: Template,
+}
+
+impl Renderable for Layout {
+    fn render_to(&self, writer: &mut dyn Write, runtime: &dyn Runtime) -> Result<()> {
+        if self.front_matter.is_empty() {
+            self.template.render_to(writer, runtime)?;
+        } else {
+            let mut scope = Object::new();
+            scope.insert("layout".into(), Value::Object(self.front_matter.clone()));
+            let scope = StackFrame::new(runtime, &scope);
+            self.template.render_to(writer, &scope)?;
+        }
+
+        // Partials included by this one were rendered first, so this one's layout wins.
+        runtime.registers().get_mut::<LayoutRegister>().layout = layout_name(&self.front_matter);
+        Ok(())
+    }
+
+    fn collect_globals(&self, globals: &mut GlobalReferences<'_>) {
+        let locals: &[&str] = if self.front_matter.is_empty() {
+            &[]
+        } else {
+            &["layout"]
+        };
+        globals.push_scope(locals);
+        self.template.collect_globals(globals);
+        globals.pop_scope();
+    }
+}
+
+/// The `layout` of front matter, unless it's missing, `nil` or empty.
+fn layout_name(front_matter: &Object) -> Option<KString> {
+    front_matter
+        .get("layout")
+        .filter(|layout| !layout.is_nil())
+        .map(|layout| layout.to_kstr().into_owned())
+        .filter(|layout| !layout.is_empty())
+}
+
+/// Renders a page and the chain of layouts it goes in, like Jekyll.
+///
+/// `front_matter` is added to the `page` global.  Its `layout` names a layout in the partials,
+/// rendered with the page's output as `content`; if that layout, compiled by
+/// [`LayoutCompiler`], names a layout in turn, it is rendered with the layout's output, and so
+/// on.
+pub fn render_page(
+    front_matter: &Object,
+    page: &dyn Renderable,
+    runtime: &dyn Runtime,
+) -> Result<String> {
+    let mut page_globals = runtime
+        .try_get(&[ScalarCow::new("page")])
+        .and_then(|page| page.into_owned().into_object())
+        .unwrap_or_default();
+    for (key, value) in front_matter.iter() {
+        page_globals.insert(key.clone(), value.clone());
+    }
+    let mut scope = Object::new();
+    scope.insert("page".into(), Value::Object(page_globals));
+
+    let mut content = page.render(&StackFrame::new(runtime, &scope))?;
+    let mut layout = layout_name(front_matter);
+    let mut rendered: Vec<KString> = Vec::new();
+    while let Some(name) = layout {
+        if rendered.contains(&name) {
+            return Error::with_msg("Circular layouts")
+                .context("layout", name)
+                .into_err();
+        }
+        let partial = runtime
+            .partials()
+            .get(&name)
+            .context_key("layout")
+            .value_with(|| name.to_string().into())?;
+
+        scope.insert("content".into(), Value::safe_output(content));
+        runtime.registers().get_mut::<LayoutRegister>().layout = None;
+        content = partial
+            .render(&StackFrame::new(runtime, &scope))
+            .context_key("layout")
+            .value_with(|| name.to_string().into())?;
+
+        layout = runtime
+            .registers()
+            .get_mut::<LayoutRegister>()
+            .layout
+            .take();
+        rendered.push(name);
+    }
+    Ok(content)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use liquid_core::partials::InMemorySource;
+    use liquid_core::runtime::RuntimeBuilder;
+
+    use super::super::IncludeTag;
+
+    fn options() -> sync::Arc<Language> {
+        let mut options = Language::default();
+        options
+            .tags
+            .register("include".to_string(), IncludeTag.into());
+        sync::Arc::new(options)
+    }
+
+    fn render(text: &str, layouts: &[(&str, &str)], globals: &Object) -> Result<String> {
+        let mut compiler = LayoutCompiler::<InMemorySource>::empty();
+        for (name, layout) in layouts {
+            compiler.add(*name, *layout);
+        }
+        let options = options();
+        let partials = compiler.compile(options.clone())?;
+        let (front_matter, elements) = parser::parse_document(text, &options)?;
+        let runtime = RuntimeBuilder::new()
+            .set_globals(globals)
+            .set_partials(partials.as_ref())
+            .build();
+        render_page(&front_matter, &Template::new(elements), &runtime)
+    }
+
+    const DEFAULT: &str = "<title>{{ page.title }}</title>{{ content }}";
+    const POST: &str = "---\nlayout: default\nclass: post\n---\n<article class=\"{{ layout.class }}\">{{ content }}</article>";
+
+    #[test]
+    fn page_without_layout() {
+        let globals = liquid_core::object!({"page": {"url": "/"}});
+        let text = "---\ntitle: Home\n---\n{{ page.title }} {{ page.url }}";
+        assert_eq!(render(text, &[], &globals).unwrap(), "Home /");
+    }
+
+    #[test]
+    fn page_with_layouts() {
+        let text = "---\nlayout: post\ntitle: Hello\n---\nText";
+        assert_eq!(
+            render(
+                text,
+                &[("default", DEFAULT), ("post", POST)],
+                &Object::new()
+            )
+            .unwrap(),
+            "<title>Hello</title><article class=\"post\">Text</article>"
+        );
+    }
+
+    #[test]
+    fn layout_chain_ignores_includes() {
+        let post = "---\nlayout: default\n---\n{% include footer %}{{ content }}";
+        let footer = "---\nlayout: post\n---\n(c)";
+        let text = "---\nlayout: post\ntitle: Hello\n---\nText";
+        assert_eq!(
+            render(
+                text,
+                &[("default", DEFAULT), ("post", post), ("footer", footer)],
+                &Object::new()
+            )
+            .unwrap(),
+            "<title>Hello</title>(c)Text"
+        );
+    }
+
+    #[test]
+    fn layout_null() {
+        let text = "---\nlayout: null\n---\nText";
+        assert_eq!(render(text, &[], &Object::new()).unwrap(), "Text");
+    }
+
+    #[test]
+    fn circular_layouts() {
+        let looping = "---\nlayout: looping\n---\n{{ content }}";
+        let text = "---\nlayout: looping\n---\nText";
+        let err = render(text, &[("looping", looping)], &Object::new()).unwrap_err();
+        assert!(err.to_string().contains("Circular layouts"), "{}", err);
+    }
+
+    #[test]
+    fn unknown_layout() {
+        let text = "---\nlayout: missing\n---\nText";
+        render(text, &[], &Object::new()).unwrap_err();
+    }
+}
@@ -1,7 +1,9 @@
 mod array;
 mod include_tag;
+mod layout;
 mod slugify;
 
 pub use self::array::*;
 pub use self::include_tag::*;
+pub use self::layout::*;
 pub use self::slugify::*;
@@ -2,14 +2,24 @@
 
 #[cfg(feature = "extra")]
 pub mod extra;
+#[cfg(feature = "i18n")]
+pub mod i18n;
+#[cfg(feature = "inheritance")]
+pub mod inheritance;
 #[cfg(feature = "jekyll")]
 pub mod jekyll;
 #[cfg(feature = "shopify")]
 pub mod shopify;
-#[cfg(feature = "stdlib")]
+#[cfg(any(feature = "stdlib", feature = "extra"))]
 pub mod stdlib;
 
 use liquid_core::Error;
+#[cfg(any(feature = "stdlib", feature = "extra"))]
+use liquid_core::{
+    model::{DateLocale, DateLocaleTable, DateLocales},
+    runtime::LocaleRegister,
+    Result, Runtime,
+};
 
 pub(crate) fn invalid_input<S>(cause: S) -> Error
 where
@@ -26,3 +36,28 @@ where
         .context("argument", argument)
         .context("cause", cause)
 }
+
+/// Locales used by date filters that weren't configured with their own.
+#[cfg(any(feature = "stdlib", feature = "extra"))]
+pub(crate) static BUNDLED_DATE_LOCALES: once_cell::sync::Lazy<DateLocales> =
+    once_cell::sync::Lazy::new(DateLocales::new);
+
+/// The locale a date is formatted in: the requested one, else the active `LocaleRegister`.
+#[cfg(any(feature = "stdlib", feature = "extra"))]
+pub(crate) fn date_locale<'l>(
+    locales: &'l DateLocales,
+    requested: Option<&str>,
+    runtime: &dyn Runtime,
+) -> Result<&'l dyn DateLocale> {
+    if let Some(name) = requested {
+        return locales
+            .get(name)
+            .ok_or_else(|| Error::with_msg("Unknown locale").context("locale", name.to_owned()));
+    }
+    let active = runtime.registers().get_mut::<LocaleRegister>();
+    // Apps may activate locales without date names, so fall back to English.
+    Ok(active
+        .get()
+        .and_then(|name| locales.get(name))
+        .unwrap_or(&DateLocaleTable::ENGLISH))
+}
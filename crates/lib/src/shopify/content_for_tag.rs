@@ -0,0 +1,244 @@
+use std::io::Write;
+
+use liquid_core::error::ResultLiquidExt;
+use liquid_core::model::{KString, ScalarCow};
+use liquid_core::runtime::{GlobalReferences, StackFrame};
+use liquid_core::Expression;
+use liquid_core::Language;
+use liquid_core::Renderable;
+use liquid_core::Runtime;
+use liquid_core::{Error, Result};
+use liquid_core::{Object, Value, ValueView};
+use liquid_core::{ParseTag, TagReflection, TagTokenIter};
+
+use super::{evaluate_arguments, parse_arguments};
+
+/// `{% content_for 'blocks' %}` renders the blocks of the current section, each with the
+/// partial `blocks/<type>.liquid`.
+///
+/// `{% content_for 'block', type: 'slide', id: 'slide-1' %}` renders a single block, with its
+/// settings from the section's blocks if it has one with that id.  Other arguments are passed
+/// to the block's partial as variables.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct ContentForTag;
+
+impl ContentForTag {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl TagReflection for ContentForTag {
+    fn tag(&self) -> &'static str {
+        "content_for"
+    }
+
+    fn description(&self) -> &'static str {
+        "Renders the blocks of the current section, or a single block."
+    }
+}
+
+impl ParseTag for ContentForTag {
+    fn parse(
+        &self,
+        mut arguments: TagTokenIter<'_>,
+        _options: &Language,
+    ) -> Result<Box<dyn Renderable>> {
+        let kind = arguments
+            .expect_next("Identifier or literal expected.")?
+            .expect_value()
+            .into_result()?;
+        let (positional, vars) = parse_arguments(arguments)?;
+        if !positional.is_empty() {
+            return Error::with_msg("Unexpected argument")
+                .context("expected", "`key: value` arguments")
+                .into_err();
+        }
+
+        Ok(Box::new(ContentFor { kind, vars }))
+    }
+
+    fn reflection(&self) -> &dyn TagReflection {
+        self
+    }
+}
+
+#[derive(Debug)]
+struct ContentFor {
+    kind: Expression,
+    vars: Vec<(KString, Expression)>,
+}
+
+impl ContentFor {
+    fn trace(&self) -> String {
+        format!("{{% content_for {} %}}", self.kind)
+    }
+}
+
+impl Renderable for ContentFor {
+    fn render_to(&self, writer: &mut dyn Write, runtime: &dyn Runtime) -> Result<()> {
+        let kind = self
+            .kind
+            .evaluate(runtime)
+            .trace_with(|| self.trace().into())?
+            .to_kstr()
+            .into_owned();
+        let mut vars =
+            evaluate_arguments(&self.vars, runtime).trace_with(|| self.trace().into())?;
+        let blocks = runtime
+            .try_get(&[ScalarCow::new("section"), ScalarCow::new("blocks")])
+            .map(|blocks| blocks.into_owned());
+        let blocks = blocks.as_ref().and_then(|blocks| blocks.as_array());
+
+        match kind.as_str() {
+            "blocks" => {
+                for block in blocks.into_iter().flat_map(|blocks| blocks.values()) {
+                    render_block(block.to_value(), Object::new(), writer, runtime)
+                        .trace_with(|| self.trace().into())?;
+                }
+                Ok(())
+            }
+            "block" => {
+                let block_type = vars.remove("type");
+                let id = vars.remove("id");
+                let (block_type, id) = match (block_type, id) {
+                    (Some(block_type), Some(id)) => (block_type, id),
+                    _ => {
+                        return Error::with_msg("Block `type` and `id` expected")
+                            .trace(self.trace())
+                            .into_err();
+                    }
+                };
+                let mut block = blocks
+                    .into_iter()
+                    .flat_map(|blocks| blocks.values())
+                    .find(|block| {
+                        block
+                            .as_object()
+                            .and_then(|block| block.get("id"))
+                            .map_or(false, |block_id| block_id.to_kstr() == id.to_kstr())
+                    })
+                    .and_then(|block| block.to_value().into_object())
+                    .unwrap_or_default();
+                block.insert("type".into(), block_type);
+                block.insert("id".into(), id);
+                render_block(Value::Object(block), vars, writer, runtime)
+                    .trace_with(|| self.trace().into())
+            }
+            _ => Error::with_msg("Unknown content_for type")
+                .context("requested", kind)
+                .context("expected", "\"blocks\" or \"block\"")
+                .trace(self.trace())
+                .into_err(),
+        }
+    }
+
+    fn collect_globals(&self, globals: &mut GlobalReferences<'_>) {
+        globals.read(&self.kind);
+        for (_, val) in &self.vars {
+            globals.read(val);
+        }
+    }
+}
+
+/// Renders the partial for the type of `block`, with it as `block` alongside `vars`.
+fn render_block(
+    block: Value,
+    mut vars: Object,
+    writer: &mut dyn Write,
+    runtime: &dyn Runtime,
+) -> Result<()> {
+    let block_type = block
+        .as_object()
+        .and_then(|block| block.get("type"))
+        .map(|block_type| block_type.to_kstr().into_owned())
+        .ok_or_else(|| {
+            Error::with_msg("Block type expected").context("block", block.source().to_string())
+        })?;
+    vars.insert("block".into(), block);
+
+    let name = format!("blocks/{}.liquid", block_type);
+    let partial = runtime.partials().get(&name)?;
+    partial
+        .render_to(writer, &StackFrame::new(runtime, &vars))
+        .context_key("block")
+        .value_with(|| block_type.to_string().into())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use liquid_core::parser;
+    use liquid_core::partials;
+    use liquid_core::partials::PartialCompiler;
+    use liquid_core::runtime;
+    use liquid_core::runtime::RuntimeBuilder;
+
+    fn options() -> Language {
+        let mut options = Language::default();
+        options
+            .tags
+            .register("content_for".to_string(), ContentForTag.into());
+        options
+    }
+
+    fn render(text: &str) -> Result<String> {
+        let mut source = partials::InMemorySource::new();
+        source.add("blocks/text.liquid", "<p>{{ block.settings.text }}</p>");
+        source.add(
+            "blocks/slide.liquid",
+            "<img alt=\"{{ block.id }}\" src=\"{{ block.settings.src }}\">",
+        );
+        source.add("blocks/caption.liquid", "{{ block.id }}: {{ caption }}");
+        let options = std::sync::Arc::new(options());
+        let partials = partials::EagerCompiler::new(source)
+            .compile(options.clone())
+            .unwrap();
+        let template = parser::parse(text, &options).map(runtime::Template::new)?;
+        let globals = liquid_core::object!({
+            "section": {"blocks": [
+                {"id": "a", "type": "text", "settings": {"text": "Hi"}},
+                {"id": "b", "type": "slide", "settings": {"src": "b.png"}},
+            ]},
+        });
+        let runtime = RuntimeBuilder::new()
+            .set_globals(&globals)
+            .set_partials(partials.as_ref())
+            .build();
+        template.render(&runtime)
+    }
+
+    #[test]
+    fn content_for_blocks() {
+        assert_eq!(
+            render("{% content_for 'blocks' %}").unwrap(),
+            "<p>Hi</p><img alt=\"b\" src=\"b.png\">"
+        );
+    }
+
+    #[test]
+    fn content_for_block() {
+        assert_eq!(
+            render("{% content_for 'block', type: 'slide', id: 'b' %}").unwrap(),
+            "<img alt=\"b\" src=\"b.png\">"
+        );
+        assert_eq!(
+            render("{% content_for 'block', type: 'caption', id: 'c', caption: 'Sale' %}").unwrap(),
+            "c: Sale"
+        );
+    }
+
+    #[test]
+    fn content_for_block_needs_type_and_id() {
+        render("{% content_for 'block', type: 'slide' %}").unwrap_err();
+    }
+
+    #[test]
+    fn content_for_unknown_type() {
+        let err = render("{% content_for 'header' %}")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("Unknown content_for type"), "{}", err);
+    }
+}
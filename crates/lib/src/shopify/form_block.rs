@@ -0,0 +1,362 @@
+use std::fmt;
+use std::io::Write;
+use std::sync::Arc;
+
+use liquid_core::error::{ResultLiquidExt, ResultLiquidReplaceExt};
+use liquid_core::model::KString;
+use liquid_core::runtime::{Escape, GlobalReferences, StackFrame};
+use liquid_core::Expression;
+use liquid_core::Language;
+use liquid_core::Renderable;
+use liquid_core::Runtime;
+use liquid_core::Template;
+use liquid_core::{BlockReflection, ParseBlock, TagBlock, TagTokenIter};
+use liquid_core::{Error, Result};
+use liquid_core::{Object, Value, ValueView};
+
+use super::{evaluate_arguments, parse_arguments};
+
+/// How `{% form %}` renders, for apps with their own form types or endpoints.
+pub trait FormHandler: fmt::Debug + Send + Sync {
+    /// The `form` variable inside the block, like the errors of a failed post.
+    fn form(&self, form_type: &str, object: Option<&dyn ValueView>) -> Result<Object> {
+        let _ = (form_type, object);
+        let mut form = Object::new();
+        form.insert("errors".into(), Value::Nil);
+        form.insert("posted_successfully?".into(), Value::scalar(false));
+        Ok(form)
+    }
+
+    /// Writes the `<form>` element around the block's rendered `content`.
+    ///
+    /// `attributes` are the block's `key: value` arguments, in order.
+    fn render(
+        &self,
+        form_type: &str,
+        object: Option<&dyn ValueView>,
+        attributes: &Object,
+        content: &str,
+        writer: &mut dyn Write,
+    ) -> Result<()>;
+}
+
+/// Renders Shopify's storefront forms, posting to their usual endpoints.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct StorefrontForms;
+
+impl StorefrontForms {
+    fn action(form_type: &str, object: Option<&dyn ValueView>) -> Result<String> {
+        let action = match form_type {
+            "activate_customer_password" => "/account/activate",
+            "cart" => "/cart",
+            "contact" => "/contact",
+            "create_customer" => "/account",
+            "currency" | "localization" => "/localization",
+            "customer" => "/contact#contact_form",
+            "customer_address" => "/account/addresses",
+            "customer_login" | "guest_login" => "/account/login",
+            "new_comment" => {
+                let url = object
+                    .and_then(|article| article.as_object())
+                    .and_then(|article| article.get("url"))
+                    .map(|url| url.to_kstr().into_owned())
+                    .ok_or_else(|| {
+                        Error::with_msg("Article with a `url` expected")
+                            .context("form", form_type.to_owned())
+                    })?;
+                return Ok(format!("{}/comments", url));
+            }
+            "product" => "/cart/add",
+            "recover_customer_password" => "/account/recover",
+            "reset_customer_password" => "/account/reset",
+            "storefront_password" => "/password",
+            _ => {
+                return Error::with_msg("Unknown form type")
+                    .context("form", form_type.to_owned())
+                    .into_err();
+            }
+        };
+        Ok(action.to_owned())
+    }
+}
+
+impl FormHandler for StorefrontForms {
+    fn render(
+        &self,
+        form_type: &str,
+        object: Option<&dyn ValueView>,
+        attributes: &Object,
+        content: &str,
+        writer: &mut dyn Write,
+    ) -> Result<()> {
+        let action = Self::action(form_type, object)?;
+        let mut return_to = None;
+        let mut html = format!(
+            "<form method=\"post\" action=\"{}\"",
+            Escape::Html.escape(&action)
+        );
+        for (name, value) in attributes.iter() {
+            let value = value.to_kstr();
+            if name == "return_to" {
+                return_to = Some(value.into_owned());
+                continue;
+            }
+            html.push_str(&format!(
+                " {}=\"{}\"",
+                Escape::Attribute.escape(name),
+                Escape::Html.escape(&value)
+            ));
+        }
+        html.push_str(" accept-charset=\"UTF-8\">");
+        html.push_str(&format!(
+            "<input type=\"hidden\" name=\"form_type\" value=\"{}\" />",
+            Escape::Html.escape(form_type)
+        ));
+        html.push_str("<input type=\"hidden\" name=\"utf8\" value=\"✓\" />");
+        if let Some(return_to) = return_to {
+            html.push_str(&format!(
+                "<input type=\"hidden\" name=\"return_to\" value=\"{}\" />",
+                Escape::Html.escape(&return_to)
+            ));
+        }
+        write!(writer, "{}{}</form>", html, content).replace("Failed to render")?;
+        Ok(())
+    }
+}
+
+/// `{% form 'product', product, class: 'buy' %}`, wrapping its content in a `<form>` for the
+/// given form type and object.
+///
+/// How forms render is up to the [`FormHandler`], by default [`StorefrontForms`].
+#[derive(Clone, Debug)]
+pub struct FormBlock {
+    handler: Arc<dyn FormHandler>,
+}
+
+impl FormBlock {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Renders forms with `handler`.
+    pub fn with_handler<H: FormHandler + 'static>(handler: H) -> Self {
+        Self {
+            handler: Arc::new(handler),
+        }
+    }
+}
+
+impl Default for FormBlock {
+    fn default() -> Self {
+        Self::with_handler(StorefrontForms)
+    }
+}
+
+impl BlockReflection for FormBlock {
+    fn start_tag(&self) -> &str {
+        "form"
+    }
+
+    fn end_tag(&self) -> &str {
+        "endform"
+    }
+
+    fn description(&self) -> &str {
+        "Wraps its content in a form of the given type."
+    }
+}
+
+impl ParseBlock for FormBlock {
+    fn parse(
+        &self,
+        mut arguments: TagTokenIter<'_>,
+        mut tokens: TagBlock<'_, '_>,
+        options: &Language,
+    ) -> Result<Box<dyn Renderable>> {
+        let form_type = arguments
+            .expect_next("Identifier or literal expected.")?
+            .expect_value()
+            .into_result()?;
+        let (mut positional, attributes) = parse_arguments(arguments)?;
+        if positional.len() > 1 {
+            return Error::with_msg("Unexpected argument")
+                .context(
+                    "expected",
+                    "the form's object, then `key: value` attributes",
+                )
+                .into_err();
+        }
+        let object = positional.pop();
+
+        let template = Template::new(
+            tokens
+                .parse_all(options)
+                .trace_with(|| format!("{{% form {} %}}", form_type).into())?,
+        );
+
+        tokens.assert_empty();
+        Ok(Box::new(Form {
+            form_type,
+            object,
+            attributes,
+            template,
+            handler: self.handler.clone(),
+        }))
+    }
+
+    fn reflection(&self) -> &dyn BlockReflection {
+        self
+    }
+}
+
+#[derive(Debug)]
+struct Form {
+    form_type: Expression,
+    object: Option<Expression>,
+    attributes: Vec<(KString, Expression)>,
+    template: Template,
+    handler: Arc<dyn FormHandler>,
+}
+
+impl Form {
+    fn trace(&self) -> String {
+        format!("{{% form {} %}}", self.form_type)
+    }
+}
+
+impl Renderable for Form {
+    fn render_to(&self, writer: &mut dyn Write, runtime: &dyn Runtime) -> Result<()> {
+        let form_type = self
+            .form_type
+            .evaluate(runtime)
+            .trace_with(|| self.trace().into())?
+            .to_kstr()
+            .into_owned();
+        let object = match self.object {
+            Some(ref object) => Some(
+                object
+                    .evaluate(runtime)
+                    .trace_with(|| self.trace().into())?,
+            ),
+            None => None,
+        };
+        let object = object.as_ref().map(|object| object.as_view());
+        let attributes =
+            evaluate_arguments(&self.attributes, runtime).trace_with(|| self.trace().into())?;
+
+        let form = self
+            .handler
+            .form(&form_type, object)
+            .trace_with(|| self.trace().into())?;
+        let mut scope = Object::new();
+        scope.insert("form".into(), Value::Object(form));
+        let content = self
+            .template
+            .render(&StackFrame::new(runtime, &scope))
+            .trace_with(|| self.trace().into())?;
+
+        self.handler
+            .render(&form_type, object, &attributes, &content, writer)
+            .trace_with(|| self.trace().into())
+    }
+
+    fn collect_globals(&self, globals: &mut GlobalReferences<'_>) {
+        globals.read(&self.form_type);
+        if let Some(ref object) = self.object {
+            globals.read(object);
+        }
+        for (_, val) in &self.attributes {
+            globals.read(val);
+        }
+        globals.push_scope(&["form"]);
+        self.template.collect_globals(globals);
+        globals.pop_scope();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use liquid_core::parser;
+    use liquid_core::runtime;
+    use liquid_core::runtime::RuntimeBuilder;
+
+    fn options(form: FormBlock) -> Language {
+        let mut options = Language::default();
+        options.blocks.register("form".to_string(), form.into());
+        options
+    }
+
+    fn render(text: &str, form: FormBlock) -> Result<String> {
+        let template = parser::parse(text, &options(form)).map(runtime::Template::new)?;
+        let globals = liquid_core::object!({"article": {"url": "/blogs/news/hello"}});
+        let runtime = RuntimeBuilder::new().set_globals(&globals).build();
+        template.render(&runtime)
+    }
+
+    #[test]
+    fn storefront_form() {
+        let text = "{% form 'contact', class: 'a b', return_to: '/thanks' %}{{ form.errors }}ok{% endform %}";
+        assert_eq!(
+            render(text, FormBlock::new()).unwrap(),
+            concat!(
+                "<form method=\"post\" action=\"/contact\" class=\"a b\" accept-charset=\"UTF-8\">",
+                "<input type=\"hidden\" name=\"form_type\" value=\"contact\" />",
+                "<input type=\"hidden\" name=\"utf8\" value=\"✓\" />",
+                "<input type=\"hidden\" name=\"return_to\" value=\"/thanks\" />",
+                "ok</form>",
+            )
+        );
+    }
+
+    #[test]
+    fn storefront_form_with_object() {
+        let text = "{% form 'new_comment', article %}{% endform %}";
+        assert!(render(text, FormBlock::new())
+            .unwrap()
+            .starts_with("<form method=\"post\" action=\"/blogs/news/hello/comments\""));
+    }
+
+    #[test]
+    fn unknown_form_type() {
+        render("{% form 'unknown' %}{% endform %}", FormBlock::new()).unwrap_err();
+    }
+
+    #[derive(Debug)]
+    struct AppForms;
+
+    impl FormHandler for AppForms {
+        fn form(&self, form_type: &str, _object: Option<&dyn ValueView>) -> Result<Object> {
+            let mut form = Object::new();
+            form.insert("type".into(), Value::scalar(form_type.to_owned()));
+            Ok(form)
+        }
+
+        fn render(
+            &self,
+            form_type: &str,
+            _object: Option<&dyn ValueView>,
+            _attributes: &Object,
+            content: &str,
+            writer: &mut dyn Write,
+        ) -> Result<()> {
+            write!(
+                writer,
+                "<form action=\"/apps/{}\">{}</form>",
+                form_type, content
+            )
+            .replace("Failed to render")?;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn custom_form_handler() {
+        let text = "{% form 'wishlist' %}{{ form.type }}{% endform %}";
+        assert_eq!(
+            render(text, FormBlock::with_handler(AppForms)).unwrap(),
+            "<form action=\"/apps/wishlist\">wishlist</form>"
+        );
+    }
+}
@@ -0,0 +1,171 @@
+use std::fmt::Write;
+
+use hmac::Mac;
+use liquid_core::Expression;
+use liquid_core::Result;
+use liquid_core::Runtime;
+use liquid_core::{
+    Display_filter, Filter, FilterParameters, FilterReflection, FromFilterParameters, ParseFilter,
+};
+use liquid_core::{Value, ValueView};
+use sha2::Digest;
+
+fn to_hex(bytes: &[u8]) -> String {
+    let mut hex = String::with_capacity(bytes.len() * 2);
+    for byte in bytes {
+        write!(hex, "{:02x}", byte).expect("writing to a String never fails");
+    }
+    hex
+}
+
+fn digest<D: Digest>(input: &dyn ValueView) -> Value {
+    let s = input.to_kstr();
+    Value::scalar(to_hex(&D::digest(s.as_bytes())))
+}
+
+fn hmac<M: Mac + hmac::digest::KeyInit>(input: &dyn ValueView, secret: &str) -> Value {
+    let s = input.to_kstr();
+    let mut mac = <M as hmac::digest::KeyInit>::new_from_slice(secret.as_bytes())
+        .expect("HMAC accepts keys of any size");
+    mac.update(s.as_bytes());
+    Value::scalar(to_hex(&mac.finalize().into_bytes()))
+}
+
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+    name = "md5",
+    description = "Converts a string into an MD5 hash.",
+    parsed(Md5Filter)
+)]
+pub struct Md5;
+
+#[derive(Debug, Default, Display_filter)]
+#[name = "md5"]
+struct Md5Filter;
+
+impl Filter for Md5Filter {
+    fn evaluate(&self, input: &dyn ValueView, _runtime: &dyn Runtime) -> Result<Value> {
+        Ok(digest::<md5::Md5>(input))
+    }
+}
+
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+    name = "sha1",
+    description = "Converts a string into a SHA-1 hash.",
+    parsed(Sha1Filter)
+)]
+pub struct Sha1;
+
+#[derive(Debug, Default, Display_filter)]
+#[name = "sha1"]
+struct Sha1Filter;
+
+impl Filter for Sha1Filter {
+    fn evaluate(&self, input: &dyn ValueView, _runtime: &dyn Runtime) -> Result<Value> {
+        Ok(digest::<sha1::Sha1>(input))
+    }
+}
+
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+    name = "sha256",
+    description = "Converts a string into a SHA-256 hash.",
+    parsed(Sha256Filter)
+)]
+pub struct Sha256;
+
+#[derive(Debug, Default, Display_filter)]
+#[name = "sha256"]
+struct Sha256Filter;
+
+impl Filter for Sha256Filter {
+    fn evaluate(&self, input: &dyn ValueView, _runtime: &dyn Runtime) -> Result<Value> {
+        Ok(digest::<sha2::Sha256>(input))
+    }
+}
+
+#[derive(Debug, FilterParameters)]
+struct HmacArgs {
+    #[parameter(description = "The secret key.", arg_type = "str")]
+    secret: Expression,
+}
+
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+    name = "hmac_sha1",
+    description = "Converts a string into a SHA-1 hash using a hash message authentication code (HMAC).",
+    parameters(HmacArgs),
+    parsed(HmacSha1Filter)
+)]
+pub struct HmacSha1;
+
+#[derive(Debug, FromFilterParameters, Display_filter)]
+#[name = "hmac_sha1"]
+struct HmacSha1Filter {
+    #[parameters]
+    args: HmacArgs,
+}
+
+impl Filter for HmacSha1Filter {
+    fn evaluate(&self, input: &dyn ValueView, runtime: &dyn Runtime) -> Result<Value> {
+        let args = self.args.evaluate(runtime)?;
+        Ok(hmac::<hmac::Hmac<sha1::Sha1>>(input, args.secret.as_str()))
+    }
+}
+
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+    name = "hmac_sha256",
+    description = "Converts a string into a SHA-256 hash using a hash message authentication code (HMAC).",
+    parameters(HmacArgs),
+    parsed(HmacSha256Filter)
+)]
+pub struct HmacSha256;
+
+#[derive(Debug, FromFilterParameters, Display_filter)]
+#[name = "hmac_sha256"]
+struct HmacSha256Filter {
+    #[parameters]
+    args: HmacArgs,
+}
+
+impl Filter for HmacSha256Filter {
+    fn evaluate(&self, input: &dyn ValueView, runtime: &dyn Runtime) -> Result<Value> {
+        let args = self.args.evaluate(runtime)?;
+        Ok(hmac::<hmac::Hmac<sha2::Sha256>>(
+            input,
+            args.secret.as_str(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unit_md5_non_string() {
+        assert_eq!(
+            liquid_core::call_filter!(Md5, 1i64).unwrap(),
+            liquid_core::value!("c4ca4238a0b923820dcc509a6f75849b")
+        );
+    }
+
+    #[test]
+    fn unit_hmac_non_string_secret() {
+        assert_eq!(
+            liquid_core::call_filter!(HmacSha1, "ShopifyIsAwesome!", 42i64).unwrap(),
+            liquid_core::value!("ec1b97702bd87e5d0ae3b33807536b9f1b17ea2c")
+        );
+        assert_eq!(
+            liquid_core::call_filter!(HmacSha256, "ShopifyIsAwesome!", 42i64).unwrap(),
+            liquid_core::value!("6f3bdbc2ddc48d48c76833700516ce0c4e85fb26bc16036d12d3280c815c81df")
+        );
+    }
+
+    #[test]
+    fn unit_hmac_missing_secret() {
+        liquid_core::call_filter!(HmacSha1, "ShopifyIsAwesome!").unwrap_err();
+    }
+}
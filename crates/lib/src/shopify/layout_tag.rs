@@ -0,0 +1,218 @@
+use std::io::Write;
+
+use liquid_core::error::ResultLiquidExt;
+use liquid_core::model::KString;
+use liquid_core::runtime::{ContentForRegister, GlobalReferences, StackFrame};
+use liquid_core::Expression;
+use liquid_core::Language;
+use liquid_core::Renderable;
+use liquid_core::Runtime;
+use liquid_core::{Error, Result};
+use liquid_core::{Object, Value, ValueView};
+use liquid_core::{ParseTag, TagReflection, TagTokenIter};
+
+/// The layout templates are rendered in, unless they pick another with `{% layout %}`.
+const DEFAULT_LAYOUT: &str = "theme";
+
+/// The layout chosen for the current render, if any.
+#[derive(Debug, Default)]
+struct LayoutRegister {
+    layout: Option<KString>,
+}
+
+/// `{% layout 'name' %}` picks the layout the template is rendered in, `{% layout none %}` none.
+///
+/// Only applies to templates rendered with [`render_in_layout`].
+#[derive(Copy, Clone, Debug, Default)]
+pub struct LayoutTag;
+
+impl LayoutTag {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl TagReflection for LayoutTag {
+    fn tag(&self) -> &'static str {
+        "layout"
+    }
+
+    fn description(&self) -> &'static str {
+        "Picks the layout the template is rendered in, or `none`."
+    }
+}
+
+impl ParseTag for LayoutTag {
+    fn parse(
+        &self,
+        mut arguments: TagTokenIter<'_>,
+        _options: &Language,
+    ) -> Result<Box<dyn Renderable>> {
+        let layout = arguments.expect_next("Identifier or literal expected.")?;
+        let layout = if layout.as_str() == "none" {
+            None
+        } else {
+            Some(layout.expect_value().into_result()?)
+        };
+
+        // no more arguments should be supplied, trying to supply them is an error
+        arguments.expect_nothing()?;
+
+        Ok(Box::new(Layout { layout }))
+    }
+
+    fn reflection(&self) -> &dyn TagReflection {
+        self
+    }
+}
+
+#[derive(Debug)]
+struct Layout {
+    layout: Option<Expression>,
+}
+
+impl Renderable for Layout {
+    fn render_to(&self, _writer: &mut dyn Write, runtime: &dyn Runtime) -> Result<()> {
+        let layout = match self.layout {
+            Some(ref layout) => {
+                let layout = layout
+                    .evaluate(runtime)
+                    .trace_with(|| format!("{{% layout {} %}}", layout).into())?;
+                Some(layout.to_kstr().into_owned())
+            }
+            None => None,
+        };
+        runtime.registers().get_mut::<LayoutRegister>().layout = layout;
+        Ok(())
+    }
+
+    fn collect_globals(&self, globals: &mut GlobalReferences<'_>) {
+        if let Some(ref layout) = self.layout {
+            globals.read(layout);
+        }
+    }
+}
+
+/// Renders a template in its layout, like Shopify themes.
+///
+/// The layout is the partial `layout/theme.liquid`, or the one picked by `{% layout %}`.  It
+/// gets the template's output as `content_for_layout`, and everything else captured in the
+/// [`ContentForRegister`] as `content_for_<name>`, like scripts apps put under `header`.
+pub fn render_in_layout(template: &dyn Renderable, runtime: &dyn Runtime) -> Result<String> {
+    runtime.registers().get_mut::<LayoutRegister>().layout =
+        Some(KString::from_static(DEFAULT_LAYOUT));
+    let content = template.render(runtime)?;
+    let layout = match runtime
+        .registers()
+        .get_mut::<LayoutRegister>()
+        .layout
+        .take()
+    {
+        Some(layout) => layout,
+        None => return Ok(content),
+    };
+
+    let mut scope = Object::new();
+    scope.insert(
+        "content_for_header".into(),
+        Value::safe_output(String::new()),
+    );
+    {
+        let mut captured = runtime.registers().get_mut::<ContentForRegister>();
+        captured.set("layout", content);
+        for (name, content) in captured.iter() {
+            scope.insert(
+                format!("content_for_{}", name).into(),
+                Value::safe_output(content.to_owned()),
+            );
+        }
+    }
+
+    let name = format!("layout/{}.liquid", layout);
+    let partial = runtime.partials().get(&name).map_err(|err| {
+        Error::with_msg("Unknown layout")
+            .context("layout", layout.clone())
+            .cause(err)
+    })?;
+    partial
+        .render(&StackFrame::new(runtime, &scope))
+        .context_key("layout")
+        .value_with(|| layout.to_string().into())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use liquid_core::parser;
+    use liquid_core::partials;
+    use liquid_core::partials::PartialCompiler;
+    use liquid_core::runtime;
+    use liquid_core::runtime::{Registers, RuntimeBuilder};
+
+    fn options() -> Language {
+        let mut options = Language::default();
+        options
+            .tags
+            .register("layout".to_string(), LayoutTag.into());
+        options
+    }
+
+    fn render(text: &str, registers: &Registers) -> Result<String> {
+        let mut source = partials::InMemorySource::new();
+        source.add(
+            "layout/theme.liquid",
+            "<head>{{ content_for_header }}</head><main>{{ content_for_layout }}</main>",
+        );
+        source.add(
+            "layout/checkout.liquid",
+            "<form>{{ content_for_layout }}</form>",
+        );
+        let options = std::sync::Arc::new(options());
+        let partials = partials::EagerCompiler::new(source)
+            .compile(options.clone())
+            .unwrap();
+        let template = parser::parse(text, &options).map(runtime::Template::new)?;
+        let runtime = RuntimeBuilder::new()
+            .set_partials(partials.as_ref())
+            .set_registers(registers)
+            .build();
+        render_in_layout(&template, &runtime)
+    }
+
+    #[test]
+    fn default_layout() {
+        let registers = Registers::default();
+        registers
+            .get_mut::<ContentForRegister>()
+            .append("header", "<script></script>");
+        assert_eq!(
+            render("<p>{{ 'page' }}</p>", &registers).unwrap(),
+            "<head><script></script></head><main><p>page</p></main>"
+        );
+    }
+
+    #[test]
+    fn layout_tag() {
+        let registers = Registers::default();
+        assert_eq!(
+            render("{% layout 'checkout' %}page", &registers).unwrap(),
+            "<form>page</form>"
+        );
+        assert_eq!(render("{% layout none %}page", &registers).unwrap(), "page");
+    }
+
+    #[test]
+    fn unknown_layout() {
+        let err = render("{% layout 'missing' %}page", &Registers::default())
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("Unknown layout"), "{}", err);
+    }
+
+    #[test]
+    fn layout_needs_a_name() {
+        parser::parse("{% layout %}", &options()).unwrap_err();
+        parser::parse("{% layout 'a' 'b' %}", &options()).unwrap_err();
+    }
+}
@@ -1,3 +1,78 @@
+mod content_for_tag;
+mod encoding;
+mod form_block;
+mod hash;
+mod layout_tag;
+mod paginate_block;
 mod pluralize;
+mod section_tag;
+mod string;
+mod url;
+mod yield_tag;
 
+pub use self::content_for_tag::*;
+pub use self::encoding::*;
+pub use self::form_block::*;
+pub use self::hash::*;
+pub use self::layout_tag::*;
+pub use self::paginate_block::*;
 pub use self::pluralize::*;
+pub use self::section_tag::*;
+pub use self::string::*;
+pub use self::url::*;
+pub use self::yield_tag::*;
+
+use liquid_core::model::KString;
+use liquid_core::parser::TagTokenIter;
+use liquid_core::Expression;
+use liquid_core::Runtime;
+use liquid_core::{Error, Result};
+use liquid_core::{Object, ValueView};
+
+/// The `key: value` arguments of a tag, in order.
+type NamedArguments = Vec<(KString, Expression)>;
+
+/// Parses the `, value` and `, key: value` arguments ending Shopify tags, like
+/// `{% form 'product', product, id: 'buy' %}`.
+///
+/// Positional values must come before named ones.
+fn parse_arguments(arguments: TagTokenIter<'_>) -> Result<(Vec<Expression>, NamedArguments)> {
+    let mut positional = Vec::new();
+    let mut named: NamedArguments = Vec::new();
+    let mut arguments = arguments.peekable();
+    while let Some(comma) = arguments.next() {
+        comma
+            .expect_str(",")
+            .into_result_custom_msg("expected \",\" between arguments")?;
+        let token = arguments
+            .next()
+            .ok_or_else(|| Error::with_msg("Argument expected after \",\""))?;
+        if arguments.peek().map_or(false, |next| next.as_str() == ":") {
+            arguments.next();
+            let id = token.expect_identifier().into_result()?.to_string();
+            let value = arguments
+                .next()
+                .ok_or_else(|| Error::with_msg("Value expected").context("argument", id.clone()))?
+                .expect_value()
+                .into_result()?;
+            named.push((id.into(), value));
+        } else if named.is_empty() {
+            positional.push(token.expect_value().into_result()?);
+        } else {
+            return Err(token.raise_custom_error("positional arguments go before named ones"));
+        }
+    }
+    Ok((positional, named))
+}
+
+/// Evaluates the `key: value` arguments of a tag.
+fn evaluate_arguments(
+    arguments: &[(KString, Expression)],
+    runtime: &dyn Runtime,
+) -> Result<Object> {
+    let mut values = Object::new();
+    for (id, value) in arguments {
+        values.insert(id.clone(), value.evaluate(runtime)?.to_value());
+    }
+    Ok(values)
+}
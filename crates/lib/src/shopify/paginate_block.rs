@@ -0,0 +1,337 @@
+use std::fmt;
+use std::io::Write;
+use std::sync::Arc;
+
+use liquid_core::error::ResultLiquidExt;
+use liquid_core::model::{set_path, ScalarCow};
+use liquid_core::runtime::Variable;
+use liquid_core::runtime::{GlobalReferences, StackFrame};
+use liquid_core::Expression;
+use liquid_core::Language;
+use liquid_core::Renderable;
+use liquid_core::Runtime;
+use liquid_core::Template;
+use liquid_core::{BlockReflection, ParseBlock, TagBlock, TagTokenIter};
+use liquid_core::{Error, Result};
+use liquid_core::{Object, Value, ValueView};
+
+/// Where `{% paginate %}` gets the current page from and how it links to pages, for apps
+/// with their own URLs.
+pub trait PaginateHandler: fmt::Debug + Send + Sync {
+    /// The page to render, counting from 1.
+    ///
+    /// Defaults to the `current_page` global, or the first page.
+    fn current_page(&self, runtime: &dyn Runtime) -> usize {
+        runtime
+            .try_get(&[ScalarCow::new("current_page")])
+            .and_then(|page| page.as_scalar().and_then(|page| page.to_integer()))
+            .map_or(1, |page| page.max(1) as usize)
+    }
+
+    /// The URL of `page`.
+    fn url(&self, page: usize, runtime: &dyn Runtime) -> String {
+        let _ = runtime;
+        format!("?page={}", page)
+    }
+}
+
+/// Reads the page from the `current_page` global and links to pages with `?page=N`.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct QueryPagination;
+
+impl PaginateHandler for QueryPagination {}
+
+/// `{% paginate collection.products by 12 %}`, rendering its content with only the current
+/// page of `collection.products`, and the `paginate` variable to link to the others.
+///
+/// Which page is current and the page URLs are up to the [`PaginateHandler`], by default
+/// [`QueryPagination`].
+#[derive(Clone, Debug)]
+pub struct PaginateBlock {
+    handler: Arc<dyn PaginateHandler>,
+}
+
+impl PaginateBlock {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Gets pages with `handler`.
+    pub fn with_handler<H: PaginateHandler + 'static>(handler: H) -> Self {
+        Self {
+            handler: Arc::new(handler),
+        }
+    }
+}
+
+impl Default for PaginateBlock {
+    fn default() -> Self {
+        Self::with_handler(QueryPagination)
+    }
+}
+
+impl BlockReflection for PaginateBlock {
+    fn start_tag(&self) -> &str {
+        "paginate"
+    }
+
+    fn end_tag(&self) -> &str {
+        "endpaginate"
+    }
+
+    fn description(&self) -> &str {
+        "Splits an array into pages, rendering the current one."
+    }
+}
+
+impl ParseBlock for PaginateBlock {
+    fn parse(
+        &self,
+        mut arguments: TagTokenIter<'_>,
+        mut tokens: TagBlock<'_, '_>,
+        options: &Language,
+    ) -> Result<Box<dyn Renderable>> {
+        let items = arguments
+            .expect_next("Identifier expected.")?
+            .expect_variable()
+            .into_result()?;
+
+        arguments
+            .expect_next("\"by\" expected.")?
+            .expect_str("by")
+            .into_result_custom_msg("\"by\" expected.")?;
+
+        let page_size = arguments
+            .expect_next("Page size expected.")?
+            .expect_value()
+            .into_result()?;
+
+        // no more arguments should be supplied, trying to supply them is an error
+        arguments.expect_nothing()?;
+
+        let template = Template::new(
+            tokens
+                .parse_all(options)
+                .trace_with(|| format!("{{% paginate {} by {} %}}", items, page_size).into())?,
+        );
+
+        tokens.assert_empty();
+        Ok(Box::new(Paginate {
+            items,
+            page_size,
+            template,
+            handler: self.handler.clone(),
+        }))
+    }
+
+    fn reflection(&self) -> &dyn BlockReflection {
+        self
+    }
+}
+
+#[derive(Debug)]
+struct Paginate {
+    items: Variable,
+    page_size: Expression,
+    template: Template,
+    handler: Arc<dyn PaginateHandler>,
+}
+
+impl Paginate {
+    fn trace(&self) -> String {
+        format!("{{% paginate {} by {} %}}", self.items, self.page_size)
+    }
+
+    fn page_link(&self, title: String, page: usize, is_link: bool, runtime: &dyn Runtime) -> Value {
+        let mut link = Object::new();
+        link.insert("title".into(), Value::scalar(title));
+        link.insert("url".into(), Value::scalar(self.handler.url(page, runtime)));
+        link.insert("is_link".into(), Value::scalar(is_link));
+        Value::Object(link)
+    }
+}
+
+impl Renderable for Paginate {
+    fn render_to(&self, writer: &mut dyn Write, runtime: &dyn Runtime) -> Result<()> {
+        let page_size = self
+            .page_size
+            .evaluate(runtime)
+            .trace_with(|| self.trace().into())?;
+        let page_size = page_size
+            .as_scalar()
+            .and_then(|size| size.to_integer())
+            .filter(|size| *size > 0)
+            .ok_or_else(|| {
+                Error::with_msg("Invalid page size")
+                    .context("page size", page_size.source().to_string())
+                    .context("expected", "a positive integer")
+                    .trace(self.trace())
+            })? as usize;
+
+        let path = self
+            .items
+            .evaluate(runtime)
+            .trace_with(|| self.trace().into())?;
+        let items = runtime.get(&path).trace_with(|| self.trace().into())?;
+        let items = items.as_array().ok_or_else(|| {
+            Error::with_msg("Can only `paginate` arrays")
+                .context("items", items.source().to_string())
+                .trace(self.trace())
+        })?;
+
+        let count = items.size() as usize;
+        let pages = ((count + page_size - 1) / page_size).max(1);
+        let current = self.handler.current_page(runtime);
+        let offset = (current - 1) * page_size;
+        let page: Vec<Value> = items
+            .values()
+            .skip(offset)
+            .take(page_size)
+            .map(|item| item.to_value())
+            .collect();
+
+        let mut paginate = Object::new();
+        paginate.insert("current_offset".into(), Value::scalar(offset as i64));
+        paginate.insert("current_page".into(), Value::scalar(current as i64));
+        paginate.insert("items".into(), Value::scalar(count as i64));
+        paginate.insert("page_size".into(), Value::scalar(page_size as i64));
+        paginate.insert("pages".into(), Value::scalar(pages as i64));
+        let previous = if current > 1 {
+            self.page_link("« Previous".to_owned(), current - 1, true, runtime)
+        } else {
+            Value::Nil
+        };
+        paginate.insert("previous".into(), previous);
+        let next = if current < pages {
+            self.page_link("Next »".to_owned(), current + 1, true, runtime)
+        } else {
+            Value::Nil
+        };
+        paginate.insert("next".into(), next);
+        let parts = (1..=pages)
+            .map(|page| self.page_link(page.to_string(), page, page != current, runtime))
+            .collect();
+        paginate.insert("parts".into(), Value::Array(parts));
+
+        // Only the current page is visible inside the block.
+        let (root, indexes) = path.split_first().expect("variables have a root");
+        let mut items = runtime
+            .get(std::slice::from_ref(root))
+            .trace_with(|| self.trace().into())?
+            .into_owned();
+        set_path(&mut items, indexes, Value::Array(page)).trace_with(|| self.trace().into())?;
+
+        let mut scope = Object::new();
+        scope.insert(root.to_kstr().into_owned(), items);
+        scope.insert("paginate".into(), Value::Object(paginate));
+        self.template
+            .render_to(writer, &StackFrame::new(runtime, &scope))
+            .trace_with(|| self.trace().into())
+    }
+
+    fn collect_globals(&self, globals: &mut GlobalReferences<'_>) {
+        globals.read(&Expression::Variable(self.items.clone()));
+        globals.read(&self.page_size);
+        globals.push_scope(&["paginate"]);
+        self.template.collect_globals(globals);
+        globals.pop_scope();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use liquid_core::parser;
+    use liquid_core::runtime;
+    use liquid_core::runtime::RuntimeBuilder;
+
+    use crate::stdlib;
+
+    fn options(paginate: PaginateBlock) -> Language {
+        let mut options = Language::default();
+        options
+            .blocks
+            .register("paginate".to_string(), paginate.into());
+        options
+            .blocks
+            .register("for".to_string(), stdlib::ForBlock.into());
+        options
+            .blocks
+            .register("if".to_string(), stdlib::IfBlock.into());
+        options
+    }
+
+    fn render(text: &str, globals: &Object, paginate: PaginateBlock) -> Result<String> {
+        let template = parser::parse(text, &options(paginate)).map(runtime::Template::new)?;
+        let runtime = RuntimeBuilder::new().set_globals(globals).build();
+        template.render(&runtime)
+    }
+
+    const TEXT: &str = concat!(
+        "{% paginate collection.products by 2 %}",
+        "{% for p in collection.products %}{{ p }}{% endfor %}|",
+        "{{ paginate.current_page }}/{{ paginate.pages }}|",
+        "{% if paginate.previous %}{{ paginate.previous.url }}{% endif %}|",
+        "{% if paginate.next %}{{ paginate.next.url }}{% endif %}|",
+        "{% for part in paginate.parts %}{{ part.title }}{{ part.is_link }} {% endfor %}",
+        "{% endpaginate %}",
+        "{{ collection.products.size }}",
+    );
+
+    #[test]
+    fn paginate() {
+        let globals = liquid_core::object!({
+            "collection": {"products": ["a", "b", "c", "d", "e"]},
+            "current_page": 2,
+        });
+        assert_eq!(
+            render(TEXT, &globals, PaginateBlock::new()).unwrap(),
+            "cd|2/3|?page=1|?page=3|1true 2false 3true 5"
+        );
+    }
+
+    #[test]
+    fn paginate_first_page() {
+        let globals = liquid_core::object!({"collection": {"products": ["a", "b", "c"]}});
+        assert_eq!(
+            render(TEXT, &globals, PaginateBlock::new()).unwrap(),
+            "ab|1/2||?page=2|1false 2true 3"
+        );
+    }
+
+    #[derive(Debug)]
+    struct LastPage;
+
+    impl PaginateHandler for LastPage {
+        fn current_page(&self, _runtime: &dyn Runtime) -> usize {
+            3
+        }
+
+        fn url(&self, page: usize, _runtime: &dyn Runtime) -> String {
+            format!("/collections/all/{}", page)
+        }
+    }
+
+    #[test]
+    fn custom_paginate_handler() {
+        let globals = liquid_core::object!({
+            "collection": {"products": ["a", "b", "c", "d", "e"]},
+        });
+        assert_eq!(
+            render(TEXT, &globals, PaginateBlock::with_handler(LastPage)).unwrap(),
+            "e|3/3|/collections/all/2||1true 2true 3false 5"
+        );
+    }
+
+    #[test]
+    fn paginate_needs_an_array() {
+        let globals = liquid_core::object!({"collection": {"products": 5}});
+        render(TEXT, &globals, PaginateBlock::new()).unwrap_err();
+        parser::parse(
+            "{% paginate products %}{% endpaginate %}",
+            &options(PaginateBlock::new()),
+        )
+        .unwrap_err();
+    }
+}
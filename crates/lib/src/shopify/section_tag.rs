@@ -0,0 +1,361 @@
+use std::io::Write;
+
+use liquid_core::error::{ResultLiquidExt, ResultLiquidReplaceExt};
+use liquid_core::model::ScalarCow;
+use liquid_core::runtime::{GlobalReferences, StackFrame};
+use liquid_core::Expression;
+use liquid_core::Language;
+use liquid_core::Renderable;
+use liquid_core::Runtime;
+use liquid_core::{Error, Result};
+use liquid_core::{Object, Value, ValueView};
+use liquid_core::{ParseTag, TagReflection, TagTokenIter};
+
+/// `{% section 'header' %}` renders the partial `sections/header.liquid`, wrapped in a
+/// `<div class="shopify-section">`.
+///
+/// The section reads its settings and blocks as `section`, from the global `sections.header`
+/// if there is one.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct SectionTag;
+
+impl SectionTag {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl TagReflection for SectionTag {
+    fn tag(&self) -> &'static str {
+        "section"
+    }
+
+    fn description(&self) -> &'static str {
+        "Renders a section of the theme."
+    }
+}
+
+impl ParseTag for SectionTag {
+    fn parse(
+        &self,
+        mut arguments: TagTokenIter<'_>,
+        _options: &Language,
+    ) -> Result<Box<dyn Renderable>> {
+        let name = arguments
+            .expect_next("Identifier or literal expected.")?
+            .expect_value()
+            .into_result()?;
+
+        // no more arguments should be supplied, trying to supply them is an error
+        arguments.expect_nothing()?;
+
+        Ok(Box::new(Section { name }))
+    }
+
+    fn reflection(&self) -> &dyn TagReflection {
+        self
+    }
+}
+
+#[derive(Debug)]
+struct Section {
+    name: Expression,
+}
+
+impl Section {
+    fn trace(&self) -> String {
+        format!("{{% section {} %}}", self.name)
+    }
+}
+
+impl Renderable for Section {
+    fn render_to(&self, writer: &mut dyn Write, runtime: &dyn Runtime) -> Result<()> {
+        let name = self
+            .name
+            .evaluate(runtime)
+            .trace_with(|| self.trace().into())?
+            .to_kstr()
+            .into_owned();
+        let section = runtime
+            .try_get(&[ScalarCow::new("sections"), ScalarCow::new(name.as_str())])
+            .map(|section| section.into_owned())
+            .unwrap_or_else(|| Value::Object(Object::new()));
+        render_section(&name, &name, section, None, writer, runtime)
+            .trace_with(|| self.trace().into())
+    }
+
+    fn collect_globals(&self, globals: &mut GlobalReferences<'_>) {
+        globals.read(&self.name);
+    }
+}
+
+/// `{% sections 'header-group' %}` renders the sections of the group in the global
+/// `section_groups.header-group`, like Shopify's section group files.
+///
+/// The group lists its `sections` by id, each with the `type` of section to render, and their
+/// `order`.  Sections with `disabled: true` are skipped.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct SectionsTag;
+
+impl SectionsTag {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl TagReflection for SectionsTag {
+    fn tag(&self) -> &'static str {
+        "sections"
+    }
+
+    fn description(&self) -> &'static str {
+        "Renders the sections of a section group."
+    }
+}
+
+impl ParseTag for SectionsTag {
+    fn parse(
+        &self,
+        mut arguments: TagTokenIter<'_>,
+        _options: &Language,
+    ) -> Result<Box<dyn Renderable>> {
+        let group = arguments
+            .expect_next("Identifier or literal expected.")?
+            .expect_value()
+            .into_result()?;
+
+        // no more arguments should be supplied, trying to supply them is an error
+        arguments.expect_nothing()?;
+
+        Ok(Box::new(Sections { group }))
+    }
+
+    fn reflection(&self) -> &dyn TagReflection {
+        self
+    }
+}
+
+#[derive(Debug)]
+struct Sections {
+    group: Expression,
+}
+
+impl Sections {
+    fn trace(&self) -> String {
+        format!("{{% sections {} %}}", self.group)
+    }
+}
+
+impl Renderable for Sections {
+    fn render_to(&self, writer: &mut dyn Write, runtime: &dyn Runtime) -> Result<()> {
+        let group = self
+            .group
+            .evaluate(runtime)
+            .trace_with(|| self.trace().into())?
+            .to_kstr()
+            .into_owned();
+        let path = [
+            ScalarCow::new("section_groups"),
+            ScalarCow::new(group.as_str()),
+        ];
+        let data = runtime.try_get(&path).ok_or_else(|| {
+            Error::with_msg("Unknown section group")
+                .context("group", group.clone())
+                .trace(self.trace())
+        })?;
+        let data = data.as_object().ok_or_else(|| {
+            Error::with_msg("Invalid section group")
+                .context("group", group.clone())
+                .context("expected", "an object with `sections` and `order`")
+                .trace(self.trace())
+        })?;
+        let sections = data.get("sections").and_then(|s| s.as_object());
+        let order = data.get("order").and_then(|o| o.as_array());
+        let (sections, order) = match (sections, order) {
+            (Some(sections), Some(order)) => (sections, order),
+            _ => {
+                return Error::with_msg("Invalid section group")
+                    .context("group", group.clone())
+                    .context("expected", "an object with `sections` and `order`")
+                    .trace(self.trace())
+                    .into_err();
+            }
+        };
+
+        for id in order.values() {
+            let id = id.to_kstr();
+            let section = sections.get(id.as_str()).ok_or_else(|| {
+                Error::with_msg("Unknown section")
+                    .context("section", id.clone().into_owned())
+                    .context("group", group.clone())
+            });
+            let section = section.trace_with(|| self.trace().into())?;
+            if section
+                .as_object()
+                .and_then(|s| s.get("disabled"))
+                .map_or(false, |disabled| {
+                    disabled.query_state(liquid_core::model::State::Truthy)
+                })
+            {
+                continue;
+            }
+            let section_type = section
+                .as_object()
+                .and_then(|s| s.get("type"))
+                .map(|t| t.to_kstr().into_owned())
+                .ok_or_else(|| {
+                    Error::with_msg("Section type expected")
+                        .context("section", id.clone().into_owned())
+                        .context("group", group.clone())
+                        .trace(self.trace())
+                })?;
+            let section_id = format!("{}__{}", group, id);
+            render_section(
+                &section_type,
+                &section_id,
+                section.to_value(),
+                Some(&group),
+                writer,
+                runtime,
+            )
+            .trace_with(|| self.trace().into())?;
+        }
+        Ok(())
+    }
+
+    fn collect_globals(&self, globals: &mut GlobalReferences<'_>) {
+        globals.read(&self.group);
+    }
+}
+
+/// Renders the partial for `section_type` as `section`, with `id` set.
+fn render_section(
+    section_type: &str,
+    id: &str,
+    section: Value,
+    group: Option<&str>,
+    writer: &mut dyn Write,
+    runtime: &dyn Runtime,
+) -> Result<()> {
+    let mut section = match section {
+        Value::Object(section) => section,
+        _ => {
+            return Error::with_msg("Invalid section")
+                .context("section", id.to_owned())
+                .context("expected", "an object")
+                .into_err();
+        }
+    };
+    section.insert("id".into(), Value::scalar(id.to_owned()));
+    let mut scope = Object::new();
+    scope.insert("section".into(), Value::Object(section));
+
+    let name = format!("sections/{}.liquid", section_type);
+    let partial = runtime.partials().get(&name)?;
+    match group {
+        Some(group) => write!(
+            writer,
+            "<div id=\"shopify-section-{}\" class=\"shopify-section shopify-section-group-{}\">",
+            id, group
+        ),
+        None => write!(
+            writer,
+            "<div id=\"shopify-section-{}\" class=\"shopify-section\">",
+            id
+        ),
+    }
+    .replace("Failed to render")?;
+    partial
+        .render_to(writer, &StackFrame::new(runtime, &scope))
+        .context_key("section")
+        .value_with(|| id.to_owned().into())?;
+    write!(writer, "</div>").replace("Failed to render")?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use liquid_core::parser;
+    use liquid_core::partials;
+    use liquid_core::partials::PartialCompiler;
+    use liquid_core::runtime;
+    use liquid_core::runtime::RuntimeBuilder;
+
+    fn options() -> Language {
+        let mut options = Language::default();
+        options
+            .tags
+            .register("section".to_string(), SectionTag.into());
+        options
+            .tags
+            .register("sections".to_string(), SectionsTag.into());
+        options
+    }
+
+    fn render(text: &str, globals: &Object) -> Result<String> {
+        let mut source = partials::InMemorySource::new();
+        source.add(
+            "sections/header.liquid",
+            "<h1>{{ section.settings.title }}</h1>",
+        );
+        source.add("sections/banner.liquid", "{{ section.id }}");
+        let options = std::sync::Arc::new(options());
+        let partials = partials::EagerCompiler::new(source)
+            .compile(options.clone())
+            .unwrap();
+        let template = parser::parse(text, &options).map(runtime::Template::new)?;
+        let runtime = RuntimeBuilder::new()
+            .set_globals(globals)
+            .set_partials(partials.as_ref())
+            .build();
+        template.render(&runtime)
+    }
+
+    #[test]
+    fn section() {
+        let globals = liquid_core::object!({
+            "sections": {"header": {"settings": {"title": "Shop"}}},
+        });
+        assert_eq!(
+            render("{% section 'header' %}", &globals).unwrap(),
+            "<div id=\"shopify-section-header\" class=\"shopify-section\"><h1>Shop</h1></div>"
+        );
+    }
+
+    #[test]
+    fn section_without_settings() {
+        assert_eq!(
+            render("{% section 'banner' %}", &Object::new()).unwrap(),
+            "<div id=\"shopify-section-banner\" class=\"shopify-section\">banner</div>"
+        );
+    }
+
+    #[test]
+    fn sections() {
+        let globals = liquid_core::object!({
+            "section_groups": {"footer": {
+                "sections": {
+                    "a": {"type": "banner"},
+                    "b": {"type": "banner", "disabled": true},
+                    "c": {"type": "header", "settings": {"title": "Bye"}},
+                },
+                "order": ["c", "b", "a"],
+            }},
+        });
+        assert_eq!(
+            render("{% sections 'footer' %}", &globals).unwrap(),
+            concat!(
+                "<div id=\"shopify-section-footer__c\" class=\"shopify-section shopify-section-group-footer\"><h1>Bye</h1></div>",
+                "<div id=\"shopify-section-footer__a\" class=\"shopify-section shopify-section-group-footer\">footer__a</div>",
+            )
+        );
+    }
+
+    #[test]
+    fn unknown_section() {
+        render("{% section 'missing' %}", &Object::new()).unwrap_err();
+        render("{% sections 'missing' %}", &Object::new()).unwrap_err();
+    }
+}
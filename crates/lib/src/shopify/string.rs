@@ -0,0 +1,143 @@
+use liquid_core::Result;
+use liquid_core::Runtime;
+use liquid_core::{Display_filter, Filter, FilterReflection, ParseFilter};
+use liquid_core::{Value, ValueView};
+use regex::Regex;
+
+static HANDLE_REMOVED_CHARS: once_cell::sync::Lazy<Regex> =
+    once_cell::sync::Lazy::new(|| Regex::new(r#"['"()\[\]]"#).unwrap());
+static HANDLE_INVALID_CHARS: once_cell::sync::Lazy<Regex> =
+    once_cell::sync::Lazy::new(|| Regex::new(r"[^0-9\p{Alphabetic}_]+").unwrap());
+static CAMELCASE_SEPARATORS: once_cell::sync::Lazy<Regex> =
+    once_cell::sync::Lazy::new(|| Regex::new(r"[-_\s]+").unwrap());
+
+fn handleize(s: &str) -> String {
+    let s = s.to_lowercase();
+    let s = HANDLE_REMOVED_CHARS.replace_all(&s, "");
+    let s = HANDLE_INVALID_CHARS.replace_all(&s, "-");
+    s.trim_matches('-').to_owned()
+}
+
+fn camelcase(s: &str) -> String {
+    CAMELCASE_SEPARATORS
+        .split(s)
+        .flat_map(|word| {
+            let mut chars = word.chars();
+            let first = chars.next().into_iter().flat_map(char::to_uppercase);
+            first.chain(chars)
+        })
+        .collect()
+}
+
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+    name = "handleize",
+    description = "Converts a string into a lowercase, hyphen-separated handle.",
+    parsed(HandleizeFilter)
+)]
+pub struct Handleize;
+
+#[derive(Debug, Default, Display_filter)]
+#[name = "handleize"]
+struct HandleizeFilter;
+
+impl Filter for HandleizeFilter {
+    fn evaluate(&self, input: &dyn ValueView, _runtime: &dyn Runtime) -> Result<Value> {
+        let s = input.to_kstr();
+        Ok(Value::scalar(handleize(s.as_str())))
+    }
+}
+
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+    name = "handle",
+    description = "Converts a string into a lowercase, hyphen-separated handle. Alias of handleize.",
+    parsed(HandleFilter)
+)]
+pub struct Handle;
+
+#[derive(Debug, Default, Display_filter)]
+#[name = "handle"]
+struct HandleFilter;
+
+impl Filter for HandleFilter {
+    fn evaluate(&self, input: &dyn ValueView, _runtime: &dyn Runtime) -> Result<Value> {
+        let s = input.to_kstr();
+        Ok(Value::scalar(handleize(s.as_str())))
+    }
+}
+
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+    name = "camelcase",
+    description = "Converts a dash, underscore or space separated string into CamelCase.",
+    parsed(CamelcaseFilter)
+)]
+pub struct Camelcase;
+
+#[derive(Debug, Default, Display_filter)]
+#[name = "camelcase"]
+struct CamelcaseFilter;
+
+impl Filter for CamelcaseFilter {
+    fn evaluate(&self, input: &dyn ValueView, _runtime: &dyn Runtime) -> Result<Value> {
+        let s = input.to_kstr();
+        Ok(Value::scalar(camelcase(s.as_str())))
+    }
+}
+
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+    name = "camelize",
+    description = "Converts a dash, underscore or space separated string into CamelCase. Alias of camelcase.",
+    parsed(CamelizeFilter)
+)]
+pub struct Camelize;
+
+#[derive(Debug, Default, Display_filter)]
+#[name = "camelize"]
+struct CamelizeFilter;
+
+impl Filter for CamelizeFilter {
+    fn evaluate(&self, input: &dyn ValueView, _runtime: &dyn Runtime) -> Result<Value> {
+        let s = input.to_kstr();
+        Ok(Value::scalar(camelcase(s.as_str())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unit_handleize() {
+        assert_eq!(
+            liquid_core::call_filter!(Handleize, "Gregory's \"Best\" [Shirt]").unwrap(),
+            liquid_core::value!("gregorys-best-shirt")
+        );
+        assert_eq!(
+            liquid_core::call_filter!(Handle, "  Über ètat  ").unwrap(),
+            liquid_core::value!("über-ètat")
+        );
+        assert_eq!(
+            liquid_core::call_filter!(Handle, "Привет, мир!").unwrap(),
+            liquid_core::value!("привет-мир")
+        );
+        assert_eq!(
+            liquid_core::call_filter!(Handleize, liquid_core::Value::Nil).unwrap(),
+            liquid_core::value!("")
+        );
+    }
+
+    #[test]
+    fn unit_camelcase() {
+        assert_eq!(
+            liquid_core::call_filter!(Camelize, "snake_case and spaces").unwrap(),
+            liquid_core::value!("SnakeCaseAndSpaces")
+        );
+        assert_eq!(
+            liquid_core::call_filter!(Camelcase, liquid_core::Value::Nil).unwrap(),
+            liquid_core::value!("")
+        );
+    }
+}
@@ -0,0 +1,111 @@
+use liquid_core::Result;
+use liquid_core::Runtime;
+use liquid_core::{Display_filter, Filter, FilterReflection, ParseFilter};
+use liquid_core::{Value, ValueView};
+
+/// Characters left untouched by Ruby's `URI::DEFAULT_PARSER.escape`.
+const URL_ESCAPE: &percent_encoding::AsciiSet = &percent_encoding::NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'_')
+    .remove(b'.')
+    .remove(b'!')
+    .remove(b'~')
+    .remove(b'*')
+    .remove(b'\'')
+    .remove(b'(')
+    .remove(b')')
+    .remove(b';')
+    .remove(b'/')
+    .remove(b'?')
+    .remove(b':')
+    .remove(b'@')
+    .remove(b'&')
+    .remove(b'=')
+    .remove(b'+')
+    .remove(b'$')
+    .remove(b',')
+    .remove(b'[')
+    .remove(b']');
+
+const URL_PARAM_ESCAPE: &percent_encoding::AsciiSet = &URL_ESCAPE.add(b'&');
+
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+    name = "url_escape",
+    description = "Escapes any URL-unsafe characters in a string, leaving URL delimiters intact.",
+    parsed(UrlEscapeFilter)
+)]
+pub struct UrlEscape;
+
+#[derive(Debug, Default, Display_filter)]
+#[name = "url_escape"]
+struct UrlEscapeFilter;
+
+impl Filter for UrlEscapeFilter {
+    fn evaluate(&self, input: &dyn ValueView, _runtime: &dyn Runtime) -> Result<Value> {
+        if input.is_nil() {
+            return Ok(Value::Nil);
+        }
+
+        let s = input.to_kstr();
+
+        let result: String =
+            percent_encoding::utf8_percent_encode(s.as_str(), URL_ESCAPE).collect();
+        Ok(Value::scalar(result))
+    }
+}
+
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+    name = "url_param_escape",
+    description = "Escapes any URL-unsafe characters in a string, including ampersands.",
+    parsed(UrlParamEscapeFilter)
+)]
+pub struct UrlParamEscape;
+
+#[derive(Debug, Default, Display_filter)]
+#[name = "url_param_escape"]
+struct UrlParamEscapeFilter;
+
+impl Filter for UrlParamEscapeFilter {
+    fn evaluate(&self, input: &dyn ValueView, _runtime: &dyn Runtime) -> Result<Value> {
+        if input.is_nil() {
+            return Ok(Value::Nil);
+        }
+
+        let s = input.to_kstr();
+
+        let result: String =
+            percent_encoding::utf8_percent_encode(s.as_str(), URL_PARAM_ESCAPE).collect();
+        Ok(Value::scalar(result))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unit_url_escape() {
+        assert_eq!(
+            liquid_core::call_filter!(UrlEscape, "café ünïcode").unwrap(),
+            liquid_core::value!("caf%C3%A9%20%C3%BCn%C3%AFcode")
+        );
+        assert_eq!(
+            liquid_core::call_filter!(UrlEscape, liquid_core::Value::Nil).unwrap(),
+            liquid_core::Value::Nil
+        );
+    }
+
+    #[test]
+    fn unit_url_param_escape() {
+        assert_eq!(
+            liquid_core::call_filter!(UrlParamEscape, "a=1&b=café").unwrap(),
+            liquid_core::value!("a=1%26b=caf%C3%A9")
+        );
+        assert_eq!(
+            liquid_core::call_filter!(UrlParamEscape, liquid_core::Value::Nil).unwrap(),
+            liquid_core::Value::Nil
+        );
+    }
+}
@@ -0,0 +1,115 @@
+use std::io::Write;
+
+use liquid_core::error::{ResultLiquidExt, ResultLiquidReplaceExt};
+use liquid_core::runtime::{ContentForRegister, GlobalReferences};
+use liquid_core::Expression;
+use liquid_core::Language;
+use liquid_core::Renderable;
+use liquid_core::Result;
+use liquid_core::Runtime;
+use liquid_core::ValueView;
+use liquid_core::{ParseTag, TagReflection, TagTokenIter};
+
+/// `{% yield %}` outputs the template rendered in the layout, `{% yield 'name' %}` the content
+/// captured under `name`, like `content_for_name`.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct YieldTag;
+
+impl YieldTag {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl TagReflection for YieldTag {
+    fn tag(&self) -> &'static str {
+        "yield"
+    }
+
+    fn description(&self) -> &'static str {
+        "Outputs the template rendered in the layout, or other captured content."
+    }
+}
+
+impl ParseTag for YieldTag {
+    fn parse(
+        &self,
+        mut arguments: TagTokenIter<'_>,
+        _options: &Language,
+    ) -> Result<Box<dyn Renderable>> {
+        let name = match arguments.next() {
+            Some(name) => name.expect_value().into_result()?,
+            None => Expression::with_literal("layout"),
+        };
+
+        // no more arguments should be supplied, trying to supply them is an error
+        arguments.expect_nothing()?;
+
+        Ok(Box::new(Yield { name }))
+    }
+
+    fn reflection(&self) -> &dyn TagReflection {
+        self
+    }
+}
+
+#[derive(Debug)]
+struct Yield {
+    name: Expression,
+}
+
+impl Renderable for Yield {
+    fn render_to(&self, writer: &mut dyn Write, runtime: &dyn Runtime) -> Result<()> {
+        let name = self
+            .name
+            .evaluate(runtime)
+            .trace_with(|| format!("{{% yield {} %}}", self.name).into())?
+            .to_kstr()
+            .into_owned();
+        let captured = runtime.registers().get_mut::<ContentForRegister>();
+        if let Some(content) = captured.get(&name) {
+            write!(writer, "{}", content).replace("Failed to render")?;
+        }
+        Ok(())
+    }
+
+    fn collect_globals(&self, globals: &mut GlobalReferences<'_>) {
+        globals.read(&self.name);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use liquid_core::parser;
+    use liquid_core::runtime;
+    use liquid_core::runtime::{Registers, RuntimeBuilder};
+
+    fn options() -> Language {
+        let mut options = Language::default();
+        options.tags.register("yield".to_string(), YieldTag.into());
+        options
+    }
+
+    #[test]
+    fn yield_captured_content() {
+        let template = parser::parse(
+            "<main>{% yield %}</main>{% yield 'footer' %}{% yield 'missing' %}",
+            &options(),
+        )
+        .map(runtime::Template::new)
+        .unwrap();
+        let registers = Registers::default();
+        {
+            let mut captured = registers.get_mut::<ContentForRegister>();
+            captured.set("layout", "<p>page</p>".into());
+            captured.set("footer", "<footer></footer>".into());
+        }
+        let runtime = RuntimeBuilder::new().set_registers(&registers).build();
+        assert_eq!(
+            template.render(&runtime).unwrap(),
+            "<main><p>page</p></main><footer></footer>"
+        );
+    }
+}
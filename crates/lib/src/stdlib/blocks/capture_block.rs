@@ -2,6 +2,8 @@ use std::io::Write;
 
 use liquid_core::error::ResultLiquidExt;
 use liquid_core::model::Value;
+use liquid_core::runtime::GlobalReferences;
+use liquid_core::runtime::{BlockRenderer, Program};
 use liquid_core::Language;
 use liquid_core::Renderable;
 use liquid_core::Result;
@@ -77,14 +79,36 @@ impl Capture {
 }
 
 impl Renderable for Capture {
-    fn render_to(&self, _writer: &mut dyn Write, runtime: &dyn Runtime) -> Result<()> {
+    fn render_to(&self, writer: &mut dyn Write, runtime: &dyn Runtime) -> Result<()> {
+        self.render_bodies_to(&[&self.template], writer, runtime)
+    }
+
+    fn lower<'t>(&'t self, program: &mut Program<'t>) -> bool {
+        program.block(self, &[&self.template]);
+        true
+    }
+
+    fn collect_globals(&self, globals: &mut GlobalReferences<'_>) {
+        self.template.collect_globals(globals);
+        globals.assign(&self.id);
+    }
+}
+
+impl BlockRenderer for Capture {
+    // `bodies` is the captured body.
+    fn render_bodies_to(
+        &self,
+        bodies: &[&dyn Renderable],
+        _writer: &mut dyn Write,
+        runtime: &dyn Runtime,
+    ) -> Result<()> {
         let mut captured = Vec::new();
-        self.template
+        bodies[0]
             .render_to(&mut captured, runtime)
             .trace_with(|| self.trace().into())?;
 
         let output = String::from_utf8(captured).expect("render only writes UTF-8");
-        runtime.set_global(self.id.clone(), Value::scalar(output));
+        runtime.set_global(self.id.clone(), Value::safe_output(output));
         Ok(())
     }
 }
@@ -130,6 +154,23 @@ mod test {
         assert_eq!(output, "");
     }
 
+    #[test]
+    fn compiled_capture() {
+        let text = "{% capture greeting %}Hi {{ name }}{% endcapture %}{{ greeting }}!";
+        let template = parser::parse(text, &options())
+            .map(runtime::Template::new)
+            .unwrap();
+        let program = Program::compile(&template);
+        assert!(matches!(
+            program.instructions()[0],
+            runtime::Instruction::Block { .. }
+        ));
+
+        let rt = RuntimeBuilder::new().build();
+        rt.set_global("name".into(), Value::scalar("Jane"));
+        assert_eq!(program.render(&rt).unwrap(), "Hi Jane!");
+    }
+
     #[test]
     fn trailing_tokens_are_an_error() {
         let text = concat!(
@@ -4,6 +4,9 @@ use liquid_core::error::ResultLiquidExt;
 use liquid_core::model::{ValueView, ValueViewCmp};
 use liquid_core::parser::BlockElement;
 use liquid_core::parser::TryMatchToken;
+use liquid_core::runtime::GlobalReferences;
+use liquid_core::runtime::RuntimeBuilder;
+use liquid_core::runtime::{BlockRenderer, Program};
 use liquid_core::Expression;
 use liquid_core::Language;
 use liquid_core::Renderable;
@@ -84,11 +87,12 @@ impl ParseBlock for CaseBlock {
         let else_block = else_block.map(Template::new);
 
         tokens.assert_empty();
-        Ok(Box::new(Case {
+        Ok(Case {
             target,
             cases,
             else_block,
-        }))
+        }
+        .fold())
     }
 
     fn reflection(&self) -> &dyn BlockReflection {
@@ -132,6 +136,45 @@ struct Case {
 }
 
 impl Case {
+    /// Keeps only the branch taken when the target and all cases are literals.
+    fn fold(self) -> Box<dyn Renderable> {
+        let is_literal = self.target.is_literal()
+            && self
+                .cases
+                .iter()
+                .all(|case| case.args.iter().all(Expression::is_literal));
+        if !is_literal {
+            return Box::new(self);
+        }
+
+        let runtime = RuntimeBuilder::new().build();
+        let value = match self.target.evaluate(&runtime) {
+            Ok(value) => value.to_value(),
+            Err(_) => return Box::new(self),
+        };
+        let mut taken = None;
+        for (index, case) in self.cases.iter().enumerate() {
+            match case.evaluate(&value, &runtime) {
+                Ok(true) => {
+                    taken = Some(index);
+                    break;
+                }
+                Ok(false) => {}
+                Err(_) => return Box::new(self),
+            }
+        }
+
+        let Case {
+            mut cases,
+            else_block,
+            ..
+        } = self;
+        match taken {
+            Some(index) => Box::new(cases.swap_remove(index).template),
+            None => Box::new(else_block.unwrap_or_else(|| Template::new(Vec::new()))),
+        }
+    }
+
     fn trace(&self) -> String {
         format!("{{% case {} %}}", self.target)
     }
@@ -139,11 +182,50 @@ impl Case {
 
 impl Renderable for Case {
     fn render_to(&self, writer: &mut dyn Write, runtime: &dyn Runtime) -> Result<()> {
-        let value = self.target.evaluate(runtime)?.to_value();
+        let mut bodies: Vec<&dyn Renderable> = self
+            .cases
+            .iter()
+            .map(|case| &case.template as &dyn Renderable)
+            .collect();
+        if let Some(ref t) = self.else_block {
+            bodies.push(t);
+        }
+        self.render_bodies_to(&bodies, writer, runtime)
+    }
+
+    fn lower<'t>(&'t self, program: &mut Program<'t>) -> bool {
+        let mut bodies: Vec<_> = self.cases.iter().map(|case| &case.template).collect();
+        bodies.extend(self.else_block.as_ref());
+        program.block(self, &bodies);
+        true
+    }
+
+    fn collect_globals(&self, globals: &mut GlobalReferences<'_>) {
+        globals.read(&self.target);
         for case in &self.cases {
+            for arg in &case.args {
+                globals.read(arg);
+            }
+            case.template.collect_globals(globals);
+        }
+        if let Some(ref t) = self.else_block {
+            t.collect_globals(globals);
+        }
+    }
+}
+
+impl BlockRenderer for Case {
+    // `bodies` are the `when` bodies, then the `else` body, if any.
+    fn render_bodies_to(
+        &self,
+        bodies: &[&dyn Renderable],
+        writer: &mut dyn Write,
+        runtime: &dyn Runtime,
+    ) -> Result<()> {
+        let value = self.target.evaluate(runtime)?.to_value();
+        for (case, body) in self.cases.iter().zip(bodies) {
             if case.evaluate(&value, runtime)? {
-                return case
-                    .template
+                return body
                     .render_to(writer, runtime)
                     .trace_with(|| case.trace().into())
                     .trace_with(|| self.trace().into())
@@ -152,8 +234,8 @@ impl Renderable for Case {
             }
         }
 
-        if let Some(ref t) = self.else_block {
-            return t
+        if let Some(body) = bodies.get(self.cases.len()) {
+            return body
                 .render_to(writer, runtime)
                 .trace("{{% else %}}")
                 .trace_with(|| self.trace().into())
@@ -239,6 +321,25 @@ mod test {
         assert_eq!(template.render(&runtime).unwrap(), "otherwise");
     }
 
+    #[test]
+    fn compiled_case() {
+        let text = "{% case x %}{% when 2 %}two{% else %}{{ x }}{% endcase %}";
+        let template = parser::parse(text, &options())
+            .map(runtime::Template::new)
+            .unwrap();
+        let program = Program::compile(&template);
+        assert!(matches!(
+            program.instructions(),
+            [runtime::Instruction::Block { .. }]
+        ));
+
+        let runtime = RuntimeBuilder::new().build();
+        runtime.set_global("x".into(), Value::scalar(2f64));
+        assert_eq!(program.render(&runtime).unwrap(), "two");
+        runtime.set_global("x".into(), Value::scalar(3f64));
+        assert_eq!(program.render(&runtime).unwrap(), "3");
+    }
+
     #[test]
     fn test_no_matches_returns_empty_string() {
         let text = concat!(
@@ -275,4 +376,23 @@ mod test {
         let template = parser::parse(text, &options).map(runtime::Template::new);
         assert!(template.is_err());
     }
+
+    #[test]
+    fn literal_case_is_folded() {
+        let text = concat!(
+            "{% case 3 %}",
+            "{% when 2 %}",
+            "{{ x }}",
+            "{% when 3 or 4 %}",
+            "three",
+            "{% else %}",
+            "{{ x }}",
+            "{% endcase %}"
+        );
+        let options = options();
+        let template = parser::parse(text, &options)
+            .map(runtime::Template::new)
+            .unwrap();
+        assert_eq!(template.static_output(), Some("three"));
+    }
 }
@@ -5,7 +5,8 @@ use liquid_core::error::{ResultLiquidExt, ResultLiquidReplaceExt};
 use liquid_core::model::{Object, ObjectView, Value, ValueCow, ValueView};
 use liquid_core::parser::BlockElement;
 use liquid_core::parser::TryMatchToken;
-use liquid_core::runtime::{Interrupt, InterruptRegister};
+use liquid_core::runtime::{BlockRenderer, Program};
+use liquid_core::runtime::{GlobalReferences, Interrupt, InterruptRegister};
 use liquid_core::Expression;
 use liquid_core::Language;
 use liquid_core::Renderable;
@@ -172,6 +173,45 @@ fn trace_for_tag(
 
 impl Renderable for For {
     fn render_to(&self, writer: &mut dyn Write, runtime: &dyn Runtime) -> Result<()> {
+        match self.else_template {
+            Some(ref else_template) => {
+                self.render_bodies_to(&[&self.item_template, else_template], writer, runtime)
+            }
+            None => self.render_bodies_to(&[&self.item_template], writer, runtime),
+        }
+    }
+
+    fn lower<'t>(&'t self, program: &mut Program<'t>) -> bool {
+        let mut bodies = vec![&self.item_template];
+        bodies.extend(self.else_template.as_ref());
+        program.block(self, &bodies);
+        true
+    }
+
+    fn collect_globals(&self, globals: &mut GlobalReferences<'_>) {
+        self.range.collect_globals(globals);
+        for attr in self.limit.iter().chain(&self.offset) {
+            globals.read(attr);
+        }
+
+        globals.push_scope(&[self.var_name.as_str(), "forloop"]);
+        self.item_template.collect_globals(globals);
+        globals.pop_scope();
+
+        if let Some(ref t) = self.else_template {
+            t.collect_globals(globals);
+        }
+    }
+}
+
+impl BlockRenderer for For {
+    // `bodies` are the loop body and the `else` body, if any.
+    fn render_bodies_to(
+        &self,
+        bodies: &[&dyn Renderable],
+        writer: &mut dyn Write,
+        runtime: &dyn Runtime,
+    ) -> Result<()> {
         let range = self
             .range
             .evaluate(runtime)
@@ -183,8 +223,9 @@ impl Renderable for For {
 
         match array.len() {
             0 => {
-                if let Some(ref t) = self.else_template {
-                    t.render_to(writer, runtime)
+                if let Some(else_template) = bodies.get(1) {
+                    else_template
+                        .render_to(writer, runtime)
                         .trace("{{% else %}}")
                         .trace_with(|| self.trace().into())?;
                 }
@@ -203,7 +244,7 @@ impl Renderable for For {
                     root.insert(self.var_name.as_ref(), &v);
 
                     let scope = StackFrame::new(runtime, &root);
-                    self.item_template
+                    bodies[0]
                         .render_to(writer, &scope)
                         .trace_with(|| self.trace().into())
                         .context_key("index")
@@ -447,6 +488,17 @@ impl Renderable for TableRow {
 
         Ok(())
     }
+
+    fn collect_globals(&self, globals: &mut GlobalReferences<'_>) {
+        self.range.collect_globals(globals);
+        for attr in self.cols.iter().chain(&self.limit).chain(&self.offset) {
+            globals.read(attr);
+        }
+
+        globals.push_scope(&[self.var_name.as_str(), "tablerow"]);
+        self.item_template.collect_globals(globals);
+        globals.pop_scope();
+    }
 }
 
 #[derive(Debug, Clone, ValueView, ObjectView)]
@@ -542,6 +594,16 @@ impl RangeExpression {
 
         Ok(range)
     }
+
+    fn collect_globals(&self, globals: &mut GlobalReferences<'_>) {
+        match *self {
+            RangeExpression::Array(ref array_id) => globals.read(array_id),
+            RangeExpression::Counted(ref start, ref stop) => {
+                globals.read(start);
+                globals.read(stop);
+            }
+        }
+    }
 }
 
 impl fmt::Display for RangeExpression {
@@ -684,6 +746,29 @@ mod test {
         assert_eq!(output, "test 22 test 23 test 24 test wat ");
     }
 
+    #[test]
+    fn loop_over_object_in_insertion_order() {
+        let text = concat!(
+            "{% for pair in object %}",
+            "{{pair[0]}}={{pair[1]}} ",
+            "{% endfor %}",
+        );
+
+        let template = parser::parse(text, &options())
+            .map(runtime::Template::new)
+            .unwrap();
+
+        let runtime = RuntimeBuilder::new().build();
+        let object: Object = ["zeta", "alpha", "mu", "beta", "omega"]
+            .iter()
+            .enumerate()
+            .map(|(i, key)| ((*key).into(), Value::scalar(i as i64)))
+            .collect();
+        runtime.set_global("object".into(), Value::Object(object));
+        let output = template.render(&runtime).unwrap();
+        assert_eq!(output, "zeta=0 alpha=1 mu=2 beta=3 omega=4 ");
+    }
+
     #[test]
     fn loop_over_range_literals() {
         let text = concat!(
@@ -5,6 +5,9 @@ use liquid_core::error::ResultLiquidExt;
 use liquid_core::model::{ValueView, ValueViewCmp};
 use liquid_core::parser::BlockElement;
 use liquid_core::parser::TagToken;
+use liquid_core::runtime::GlobalReferences;
+use liquid_core::runtime::RuntimeBuilder;
+use liquid_core::runtime::{Predicate, Program};
 use liquid_core::Expression;
 use liquid_core::Language;
 use liquid_core::Renderable;
@@ -84,12 +87,13 @@ fn parse_if(
     let if_true = Template::new(if_true);
     let if_false = if_false.map(Template::new);
 
-    Ok(Box::new(Conditional {
+    Ok(Conditional {
         condition,
         mode: true,
         if_true,
         if_false,
-    }))
+    }
+    .fold())
 }
 
 #[derive(Copy, Clone, Debug, Default)]
@@ -144,12 +148,13 @@ impl ParseBlock for UnlessBlock {
         let if_false = if_false.map(Template::new);
 
         tokens.assert_empty();
-        Ok(Box::new(Conditional {
+        Ok(Conditional {
             condition,
             mode: false,
             if_true,
             if_false,
-        }))
+        }
+        .fold())
     }
 
     fn reflection(&self) -> &dyn BlockReflection {
@@ -172,6 +177,19 @@ impl Conditional {
         Ok(result == self.mode)
     }
 
+    /// Drops the branch not taken when the condition only compares literals.
+    fn fold(self) -> Box<dyn Renderable> {
+        if !self.condition.is_literal() {
+            return Box::new(self);
+        }
+        let runtime = RuntimeBuilder::new().build();
+        match self.compare(&runtime) {
+            Ok(true) => Box::new(self.if_true),
+            Ok(false) => Box::new(self.if_false.unwrap_or_else(|| Template::new(Vec::new()))),
+            Err(_) => Box::new(self),
+        }
+    }
+
     fn trace(&self) -> String {
         format!("{{% if {} %}}", self.condition)
     }
@@ -193,6 +211,43 @@ impl Renderable for Conditional {
 
         Ok(())
     }
+
+    fn lower<'t>(&'t self, program: &mut Program<'t>) -> bool {
+        let traced = program.begin_trace(self.trace());
+        let otherwise = program.branch(self);
+        program.append(&self.if_true);
+        match self.if_false {
+            Some(ref template) => {
+                let end = program.jump();
+                program.target(otherwise);
+                let traced_else = program.begin_trace("{{% else %}}".to_owned());
+                program.append(template);
+                program.end_trace(traced_else);
+                program.target(end);
+            }
+            None => program.target(otherwise),
+        }
+        program.end_trace(traced);
+        true
+    }
+
+    fn collect_globals(&self, globals: &mut GlobalReferences<'_>) {
+        // Only `if` bodies know their existence tests passed.
+        globals.push_scope(&[]);
+        self.condition.collect_globals(globals, self.mode);
+        self.if_true.collect_globals(globals);
+        globals.pop_scope();
+        if let Some(ref template) = self.if_false {
+            template.collect_globals(globals);
+        }
+    }
+}
+
+impl Predicate for Conditional {
+    // Traced by `lower`, along with the bodies.
+    fn test(&self, runtime: &dyn Runtime) -> Result<bool> {
+        self.compare(runtime)
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -216,6 +271,36 @@ impl Condition {
             }
         }
     }
+
+    fn is_literal(&self) -> bool {
+        match *self {
+            Condition::Binary(ref c) => c.lh.is_literal() && c.rh.is_literal(),
+            Condition::Existence(ref c) => c.lh.is_literal(),
+            Condition::Conjunction(ref left, ref right)
+            | Condition::Disjunction(ref left, ref right) => {
+                left.is_literal() && right.is_literal()
+            }
+        }
+    }
+
+    fn collect_globals(&self, globals: &mut GlobalReferences<'_>, guard: bool) {
+        match *self {
+            Condition::Binary(ref c) => {
+                globals.read(&c.lh);
+                globals.read(&c.rh);
+            }
+            Condition::Existence(ref c) if guard => globals.guard(&c.lh),
+            Condition::Existence(ref c) => globals.test(&c.lh),
+            Condition::Conjunction(ref left, ref right) => {
+                left.collect_globals(globals, guard);
+                right.collect_globals(globals, guard);
+            }
+            Condition::Disjunction(ref left, ref right) => {
+                left.collect_globals(globals, false);
+                right.collect_globals(globals, false);
+            }
+        }
+    }
 }
 
 impl fmt::Display for Condition {
@@ -797,4 +882,34 @@ mod test {
         let output = template.render(&runtime).unwrap();
         assert_eq!(output, "if true");
     }
+
+    #[test]
+    fn literal_conditions_are_folded() {
+        let text =
+            "a{% if 1 == 1 %}b{% else %}{{ x }}{% endif %}{% unless true %}{{ x }}{% endunless %}c";
+        let template = parser::parse(text, &options())
+            .map(runtime::Template::new)
+            .unwrap();
+        assert_eq!(template.static_output(), Some("abc"));
+
+        let runtime = RuntimeBuilder::new().build();
+        assert_eq!(template.render(&runtime).unwrap(), "abc");
+    }
+
+    #[test]
+    fn compiled_bodies_keep_their_trace() {
+        let text = "{% if x %}{{ missing.a }}{% else %}{{ missing.b }}{% endif %}";
+        let template = parser::parse(text, &options())
+            .map(runtime::Template::new)
+            .unwrap();
+        let program = Program::compile(&template);
+
+        for x in [true, false] {
+            let runtime = RuntimeBuilder::new().build();
+            runtime.set_global("x".into(), Value::scalar(x));
+            let error = template.render(&runtime).unwrap_err().to_string();
+            assert!(error.contains("{% if x %}"), "{}", error);
+            assert_eq!(program.render(&runtime).unwrap_err().to_string(), error);
+        }
+    }
 }
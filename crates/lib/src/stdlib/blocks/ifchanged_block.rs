@@ -1,6 +1,7 @@
 use std::io::Write;
 
 use liquid_core::error::{ResultLiquidExt, ResultLiquidReplaceExt};
+use liquid_core::runtime::GlobalReferences;
 use liquid_core::Language;
 use liquid_core::Renderable;
 use liquid_core::Result;
@@ -81,6 +82,10 @@ impl Renderable for IfChanged {
 
         Ok(())
     }
+
+    fn collect_globals(&self, globals: &mut GlobalReferences<'_>) {
+        self.if_changed.collect_globals(globals);
+    }
 }
 
 /// Remembers the content of the last rendered `ifstate` block.
@@ -61,6 +61,10 @@ impl Renderable for RawT {
         write!(writer, "{}", self.content).replace("Failed to render")?;
         Ok(())
     }
+
+    fn static_output(&self) -> Option<&str> {
+        Some(&self.content)
+    }
 }
 
 #[cfg(test)]
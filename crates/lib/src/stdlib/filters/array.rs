@@ -35,6 +35,7 @@ struct JoinArgs {
     name = "join",
     description = "Combines the items in an array into a single string using the argument as a separator.",
     parameters(JoinArgs),
+    pure,
     parsed(JoinFilter)
 )]
 pub struct Join;
@@ -112,11 +113,11 @@ struct SortFilter {
     args: PropertyArgs,
 }
 
-fn safe_property_getter<'a>(value: &'a Value, property: &str) -> &'a dyn ValueView {
+fn safe_property_getter<'a>(value: &'a Value, property: &str) -> ValueCow<'a> {
     value
         .as_object()
-        .and_then(|obj| obj.get(property))
-        .unwrap_or(&Value::Nil)
+        .and_then(|obj| obj.get_cow(property))
+        .unwrap_or(ValueCow::Borrowed(&Value::Nil))
 }
 
 impl Filter for SortFilter {
@@ -133,8 +134,8 @@ impl Filter for SortFilter {
             // Using unwrap is ok since all of the elements are objects
             sorted.sort_by(|a, b| {
                 nil_safe_compare(
-                    safe_property_getter(a, property),
-                    safe_property_getter(b, property),
+                    safe_property_getter(a, property).as_view(),
+                    safe_property_getter(b, property).as_view(),
                 )
                 .unwrap_or(cmp::Ordering::Equal)
             });
@@ -217,20 +218,25 @@ pub struct Where;
 
 #[derive(Debug, FromFilterParameters, Display_filter)]
 #[name = "where"]
+#[filter(borrows)]
 struct WhereFilter {
     #[parameters]
     args: WhereArgs,
 }
 
-impl Filter for WhereFilter {
-    fn evaluate(&self, input: &dyn ValueView, runtime: &dyn Runtime) -> Result<Value> {
+impl WhereFilter {
+    fn evaluate_cow<'v>(
+        &'v self,
+        input: &'v dyn ValueView,
+        runtime: &'v dyn Runtime,
+    ) -> Result<ValueCow<'v>> {
         let args = self.args.evaluate(runtime)?;
         let property: &str = &args.property;
         let target_value: Option<ValueCow<'_>> = args.target_value;
 
         if let Some(array) = input.as_array() {
             if !array.values().all(|v| v.is_object()) {
-                return Ok(Value::Nil);
+                return Ok(ValueCow::Owned(Value::Nil));
             }
         } else if !input.is_object() {
             return Err(invalid_input(
@@ -241,26 +247,26 @@ impl Filter for WhereFilter {
         let input = as_sequence(input);
         let array: Vec<_> = match target_value {
             None => input
-                .filter_map(|v| v.as_object())
-                .filter(|object| {
-                    object
-                        .get(property)
+                .filter(|v| {
+                    v.as_object()
+                        .and_then(|object| object.get_cow(property))
                         .map_or(false, |v| v.query_state(liquid_core::model::State::Truthy))
                 })
-                .map(|object| object.to_value())
+                .map(ValueCow::Borrowed)
                 .collect(),
             Some(target_value) => input
-                .filter_map(|v| v.as_object())
-                .filter(|object| {
-                    object.get(property).map_or(false, |value| {
-                        let value = ValueViewCmp::new(value);
-                        target_value == value
-                    })
+                .filter(|v| {
+                    v.as_object()
+                        .and_then(|object| object.get_cow(property))
+                        .map_or(false, |value| {
+                            let value = ValueViewCmp::new(value.as_view());
+                            target_value == value
+                        })
                 })
-                .map(|object| object.to_value())
+                .map(ValueCow::Borrowed)
                 .collect(),
         };
-        Ok(Value::array(array))
+        Ok(ValueCow::Array(array))
     }
 }
 
@@ -303,6 +309,7 @@ impl Filter for UniqFilter {
 #[filter(
     name = "reverse",
     description = "Reverses the order of the items in an array.",
+    pure,
     parsed(ReverseFilter)
 )]
 pub struct Reverse;
@@ -344,13 +351,18 @@ pub struct Map;
 
 #[derive(Debug, FromFilterParameters, Display_filter)]
 #[name = "map"]
+#[filter(borrows)]
 struct MapFilter {
     #[parameters]
     args: MapArgs,
 }
 
-impl Filter for MapFilter {
-    fn evaluate(&self, input: &dyn ValueView, runtime: &dyn Runtime) -> Result<Value> {
+impl MapFilter {
+    fn evaluate_cow<'v>(
+        &'v self,
+        input: &'v dyn ValueView,
+        runtime: &'v dyn Runtime,
+    ) -> Result<ValueCow<'v>> {
         let args = self.args.evaluate(runtime)?;
 
         let array = input
@@ -359,13 +371,9 @@ impl Filter for MapFilter {
 
         let result: Vec<_> = array
             .values()
-            .filter_map(|v| {
-                v.as_object()
-                    .and_then(|v| v.get(&args.property))
-                    .map(|v| v.to_value())
-            })
+            .filter_map(|v| v.as_object().and_then(|v| v.get_cow(&args.property)))
             .collect();
-        Ok(Value::array(result))
+        Ok(ValueCow::Array(result))
     }
 }
 
@@ -402,7 +410,7 @@ impl Filter for CompactFilter {
                 .values()
                 .filter(|v| {
                     !v.as_object()
-                        .and_then(|obj| obj.get(property.as_str()))
+                        .and_then(|obj| obj.get_cow(property.as_str()))
                         .map_or(true, |v| v.is_nil())
                 })
                 .map(|v| v.to_value())
@@ -466,16 +474,22 @@ impl Filter for ConcatFilter {
 #[filter(
     name = "first",
     description = "Returns the first item of an array.",
+    pure,
     parsed(FirstFilter)
 )]
 pub struct First;
 
 #[derive(Debug, Default, Display_filter)]
 #[name = "first"]
+#[filter(borrows)]
 struct FirstFilter;
 
-impl Filter for FirstFilter {
-    fn evaluate(&self, input: &dyn ValueView, _runtime: &dyn Runtime) -> Result<Value> {
+impl FirstFilter {
+    fn evaluate_cow<'v>(
+        &'v self,
+        input: &'v dyn ValueView,
+        _runtime: &'v dyn Runtime,
+    ) -> Result<ValueCow<'v>> {
         if let Some(x) = input.as_scalar() {
             let c = x
                 .to_kstr()
@@ -483,11 +497,11 @@ impl Filter for FirstFilter {
                 .next()
                 .map(|c| c.to_string())
                 .unwrap_or_else(|| "".to_owned());
-            Ok(Value::scalar(c))
+            Ok(ValueCow::Owned(Value::scalar(c)))
         } else if let Some(x) = input.as_array() {
             Ok(x.first()
-                .map(|v| v.to_value())
-                .unwrap_or_else(|| Value::Nil))
+                .map(ValueCow::Borrowed)
+                .unwrap_or_else(|| ValueCow::Owned(Value::Nil)))
         } else {
             Err(invalid_input("String or Array expected"))
         }
@@ -498,16 +512,22 @@ impl Filter for FirstFilter {
 #[filter(
     name = "last",
     description = "Returns the last item of an array.",
+    pure,
     parsed(LastFilter)
 )]
 pub struct Last;
 
 #[derive(Debug, Default, Display_filter)]
 #[name = "last"]
+#[filter(borrows)]
 struct LastFilter;
 
-impl Filter for LastFilter {
-    fn evaluate(&self, input: &dyn ValueView, _runtime: &dyn Runtime) -> Result<Value> {
+impl LastFilter {
+    fn evaluate_cow<'v>(
+        &'v self,
+        input: &'v dyn ValueView,
+        _runtime: &'v dyn Runtime,
+    ) -> Result<ValueCow<'v>> {
         if let Some(x) = input.as_scalar() {
             let c = x
                 .to_kstr()
@@ -515,9 +535,11 @@ impl Filter for LastFilter {
                 .last()
                 .map(|c| c.to_string())
                 .unwrap_or_else(|| "".to_owned());
-            Ok(Value::scalar(c))
+            Ok(ValueCow::Owned(Value::scalar(c)))
         } else if let Some(x) = input.as_array() {
-            Ok(x.last().map(|v| v.to_value()).unwrap_or_else(|| Value::Nil))
+            Ok(x.last()
+                .map(ValueCow::Borrowed)
+                .unwrap_or_else(|| ValueCow::Owned(Value::Nil)))
         } else {
             Err(invalid_input("String or Array expected"))
         }
@@ -673,6 +695,20 @@ mod tests {
         );
     }
 
+    #[test]
+    fn unit_first_and_last_borrow_input() {
+        let input = liquid_core::value!([["a"], ["b"]]);
+        let runtime = liquid_core::runtime::RuntimeBuilder::new().build();
+
+        let first = FirstFilter.evaluate_cow(&input, &runtime).unwrap();
+        assert!(matches!(first, ValueCow::Borrowed(_)));
+        assert_eq!(first, liquid_core::value!(["a"]));
+
+        let last = LastFilter.evaluate_cow(&input, &runtime).unwrap();
+        assert!(matches!(last, ValueCow::Borrowed(_)));
+        assert_eq!(last, liquid_core::value!(["b"]));
+    }
+
     #[test]
     fn unit_reverse_apples_oranges_peaches_plums() {
         // First example from https://shopify.github.io/liquid/filters/reverse/
@@ -1,3 +1,8 @@
+use std::sync::Arc;
+
+use liquid_core::model::{DateLocales, DateTime};
+use liquid_core::parser::FilterArguments;
+use liquid_core::runtime::TimeZoneRegister;
 use liquid_core::Expression;
 use liquid_core::Runtime;
 use liquid_core::{
@@ -6,10 +11,19 @@ use liquid_core::{
 use liquid_core::{Error, Result};
 use liquid_core::{Value, ValueView};
 
+use crate::{date_locale, BUNDLED_DATE_LOCALES};
+
 #[derive(Debug, FilterParameters)]
 struct DateArgs {
     #[parameter(description = "The format to return the date in.", arg_type = "str")]
     format: Expression,
+
+    #[parameter(
+        description = "The locale for month and weekday names. Defaults to the active locale.",
+        arg_type = "str",
+        mode = "keyword"
+    )]
+    locale: Option<Expression>,
 }
 
 #[derive(Clone, ParseFilter, FilterReflection)]
@@ -21,21 +35,77 @@ struct DateArgs {
 )]
 pub struct Date;
 
+/// The `date` filter, with month and weekday names from the app's own `DateLocales`.
+///
+/// Register it in place of `Date`, e.g.
+/// `ParserBuilder::with_stdlib().filter(LocalizedDate::new(locales))`.
+#[derive(Clone, FilterReflection)]
+#[filter(
+    name = "date",
+    description = "Converts a timestamp into another date format.",
+    parameters(DateArgs)
+)]
+pub struct LocalizedDate {
+    locales: Arc<DateLocales>,
+}
+
+impl LocalizedDate {
+    /// Create a `date` filter looking up locales in `locales`.
+    pub fn new<L: Into<Arc<DateLocales>>>(locales: L) -> Self {
+        Self {
+            locales: locales.into(),
+        }
+    }
+}
+
+impl ParseFilter for LocalizedDate {
+    fn parse(&self, arguments: FilterArguments) -> Result<Box<dyn Filter>> {
+        let args = <DateArgs as liquid_core::parser::FilterParameters>::from_args(arguments)?;
+        Ok(Box::new(DateFilter {
+            args,
+            locales: Some(self.locales.clone()),
+        }))
+    }
+
+    fn reflection(&self) -> &dyn liquid_core::FilterReflection {
+        self
+    }
+}
+
 #[derive(Debug, FromFilterParameters, Display_filter)]
 #[name = "date"]
 struct DateFilter {
     #[parameters]
     args: DateArgs,
+    /// `None` for the bundled locales.
+    locales: Option<Arc<DateLocales>>,
 }
 
 impl Filter for DateFilter {
     fn evaluate(&self, input: &dyn ValueView, runtime: &dyn Runtime) -> Result<Value> {
         let args = self.args.evaluate(runtime)?;
 
-        let date = input.as_scalar().and_then(|s| s.to_date_time());
+        // Like Ruby, numbers are Unix timestamps.
+        let date = input.as_scalar().and_then(|s| {
+            s.to_date_time()
+                .or_else(|| s.to_integer().and_then(DateTime::from_unix_timestamp))
+                .or_else(|| {
+                    s.to_float()
+                        .and_then(DateTime::from_fractional_unix_timestamp)
+                })
+        });
         match date {
             Some(date) if !args.format.is_empty() => {
-                let s = date.format(args.format.as_str()).map_err(|_err| {
+                let locales = self.locales.as_deref().unwrap_or(&BUNDLED_DATE_LOCALES);
+                let locale =
+                    date_locale(locales, args.locale.as_ref().map(|l| l.as_str()), runtime)?;
+                let zone = runtime.registers().get_mut::<TimeZoneRegister>().get();
+
+                let s = match zone {
+                    Some(zone) => date.format_in_time_zone(args.format.as_str(), &zone, locale),
+                    None => date.format_with_locale(args.format.as_str(), locale),
+                }
+                .map_err(|_err| {
                     Error::with_msg(format!("Invalid date-format string: {}", args.format))
                 })?;
 
@@ -74,8 +144,37 @@ mod tests {
     #[test]
     fn unit_date_bad_input_type() {
         assert_eq!(
-            liquid_core::call_filter!(Date, 0f64, "%Y-%m-%d").unwrap(),
-            Value::scalar(0f64)
+            liquid_core::call_filter!(Date, true, "%Y-%m-%d").unwrap(),
+            Value::scalar(true)
+        );
+    }
+
+    #[test]
+    fn unit_date_unix_timestamp() {
+        assert_eq!(
+            liquid_core::call_filter!(Date, 1455613200i64, "%Y-%m-%d %H:%M").unwrap(),
+            liquid_core::value!("2016-02-16 09:00")
+        );
+        assert_eq!(
+            liquid_core::call_filter!(Date, "1455613200.5", "%S.%L").unwrap(),
+            liquid_core::value!("00.500")
+        );
+        assert_eq!(
+            liquid_core::call_filter!(Date, 1455613200.5f64, "%S.%L").unwrap(),
+            liquid_core::value!("00.500")
+        );
+        assert_eq!(
+            liquid_core::call_filter!(Date, "1455613200", "%Y-%m-%d").unwrap(),
+            liquid_core::value!("2016-02-16")
+        );
+    }
+
+    #[test]
+    fn unit_date_iso8601() {
+        assert_eq!(
+            liquid_core::call_filter!(Date, "2016-02-16T10:00:00+01:00", "%Y-%m-%d %H:%M %z")
+                .unwrap(),
+            liquid_core::value!("2016-02-16 10:00 +0100")
         );
     }
 
@@ -112,4 +211,149 @@ mod tests {
     fn unit_date_extra_param() {
         liquid_core::call_filter!(Date, "13 Jun 2016 02:30:00 +0300", 0f64, 1f64).unwrap_err();
     }
+
+    fn render(text: &str, registers: &liquid_core::runtime::Registers) -> Result<String> {
+        render_with(Date, text, registers)
+    }
+
+    fn render_with<F: ParseFilter + 'static>(
+        filter: F,
+        text: &str,
+        registers: &liquid_core::runtime::Registers,
+    ) -> Result<String> {
+        use liquid_core::Renderable;
+
+        let mut options = liquid_core::Language::default();
+        options
+            .filters
+            .register("date".to_string(), Box::new(filter));
+        let template =
+            liquid_core::parser::parse(text, &options).map(liquid_core::runtime::Template::new)?;
+        let runtime = liquid_core::runtime::RuntimeBuilder::new()
+            .set_registers(registers)
+            .build();
+        template.render(&runtime)
+    }
+
+    #[test]
+    fn unit_date_locale_argument() {
+        let registers = Default::default();
+        assert_eq!(
+            render(
+                r#"{{ "2016-11-13 02:30:00 +0300" | date: "%A %-d %B", locale: "fr" }}"#,
+                &registers
+            )
+            .unwrap(),
+            "dimanche 13 novembre"
+        );
+        render(
+            r#"{{ "2016-11-13 02:30:00 +0300" | date: "%B", locale: "xx" }}"#,
+            &registers,
+        )
+        .unwrap_err();
+    }
+
+    #[test]
+    fn unit_date_active_locale() {
+        let registers = liquid_core::runtime::Registers::default();
+        registers
+            .get_mut::<liquid_core::runtime::LocaleRegister>()
+            .set("de-AT");
+        assert_eq!(
+            render(
+                r#"{{ "2016-03-13 02:30:00 +0300" | date: "%b %o" }}"#,
+                &registers
+            )
+            .unwrap(),
+            "Mär 13."
+        );
+
+        registers
+            .get_mut::<liquid_core::runtime::LocaleRegister>()
+            .set("ja");
+        assert_eq!(
+            render(
+                r#"{{ "2016-03-13 02:30:00 +0300" | date: "%b %o" }}"#,
+                &registers
+            )
+            .unwrap(),
+            "Mar 13th"
+        );
+    }
+
+    #[test]
+    fn unit_date_custom_locale() {
+        use liquid_core::model::DateLocaleTable;
+
+        let mut locales = DateLocales::new();
+        locales.insert(
+            "en-x-shout",
+            DateLocaleTable {
+                months: DateLocaleTable::ENGLISH.months.map(|_| "MONTH"),
+                ..DateLocaleTable::ENGLISH
+            },
+        );
+        let filter = LocalizedDate::new(locales);
+
+        let registers = liquid_core::runtime::Registers::default();
+        assert_eq!(
+            render_with(
+                filter.clone(),
+                r#"{{ "2016-03-13 02:30:00 +0300" | date: "%B", locale: "en-x-shout" }}"#,
+                &registers
+            )
+            .unwrap(),
+            "MONTH"
+        );
+
+        registers
+            .get_mut::<liquid_core::runtime::LocaleRegister>()
+            .set("en-x-shout");
+        assert_eq!(
+            render_with(filter, r#"{{ "2016-03-13" | date: "%B %Y" }}"#, &registers).unwrap(),
+            "MONTH 2016"
+        );
+        // The bundled `date` filter doesn't know about it.
+        assert_eq!(
+            render(r#"{{ "2016-03-13" | date: "%B" }}"#, &registers).unwrap(),
+            "March"
+        );
+    }
+
+    #[test]
+    fn unit_date_active_time_zone() {
+        use liquid_core::model::TimeZone;
+
+        let registers = liquid_core::runtime::Registers::default();
+        registers
+            .get_mut::<TimeZoneRegister>()
+            .set(TimeZone::parse("-0500").unwrap());
+        assert_eq!(
+            render(
+                r#"{{ "2016-03-13 02:30:00 +0300" | date: "%F %R %z" }}"#,
+                &registers
+            )
+            .unwrap(),
+            "2016-03-12 18:30 -0500"
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "tzdb")]
+    fn unit_date_active_named_time_zone() {
+        use liquid_core::model::TimeZone;
+
+        let registers = liquid_core::runtime::Registers::default();
+        registers
+            .get_mut::<TimeZoneRegister>()
+            .set(TimeZone::parse("America/New_York").unwrap());
+        assert_eq!(
+            render(
+                r#"{{ "2016-01-13 12:00:00 +0000" | date: "%R %Z" }}, {{ "2016-06-13 12:00:00 +0000" | date: "%R %Z" }}"#,
+                &registers
+            )
+            .unwrap(),
+            "07:00 EST, 08:00 EDT"
+        );
+    }
 }
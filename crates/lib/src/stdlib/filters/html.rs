@@ -1,9 +1,16 @@
+use liquid_core::model::Scalar;
+use liquid_core::runtime::{AutoEscapeRegister, Escape as Strategy};
+use liquid_core::Expression;
 use liquid_core::Result;
 use liquid_core::Runtime;
-use liquid_core::{Display_filter, Filter, FilterReflection, ParseFilter};
+use liquid_core::{
+    Display_filter, Filter, FilterParameters, FilterReflection, FromFilterParameters, ParseFilter,
+};
 use liquid_core::{Value, ValueView};
 use regex::Regex;
 
+use crate::invalid_argument;
+
 /// Returns the number of already escaped characters.
 fn nr_escaped(text: &str) -> usize {
     for prefix in &["lt;", "gt;", "#39;", "quot;", "amp;"] {
@@ -17,7 +24,7 @@ fn nr_escaped(text: &str) -> usize {
 // The code is adapted from
 // https://github.com/rust-lang/rust/blob/master/src/librustdoc/html/escape.rs
 // Retrieved 2016-11-19.
-fn escape(input: &dyn ValueView, once_p: bool) -> Result<Value> {
+fn escape(input: &dyn ValueView, once_p: bool, runtime: &dyn Runtime) -> Result<Value> {
     if input.is_nil() {
         return Ok(Value::Nil);
     }
@@ -59,24 +66,62 @@ fn escape(input: &dyn ValueView, once_p: bool) -> Result<Value> {
     if last < s.len() {
         result.push_str(&s[last..]);
     }
-    Ok(Value::scalar(result))
+    Ok(escaped_value(result, Strategy::Html, runtime))
+}
+
+#[derive(Debug, FilterParameters)]
+struct EscapeArgs {
+    #[parameter(
+        description = "Where the string is output: `html` (the default), `attribute`, `js`, `css`, `url`, `json`, `csv` or `shell`.",
+        arg_type = "str"
+    )]
+    strategy: Option<Expression>,
 }
 
 #[derive(Clone, ParseFilter, FilterReflection)]
 #[filter(
     name = "escape",
     description = "Escapes a string by replacing characters with escape sequences.",
+    parameters(EscapeArgs),
     parsed(EscapeFilter)
 )]
 pub struct Escape;
 
-#[derive(Debug, Default, Display_filter)]
+#[derive(Debug, FromFilterParameters, Display_filter)]
 #[name = "escape"]
-struct EscapeFilter;
+struct EscapeFilter {
+    #[parameters]
+    args: EscapeArgs,
+}
 
 impl Filter for EscapeFilter {
-    fn evaluate(&self, input: &dyn ValueView, _runtime: &dyn Runtime) -> Result<Value> {
-        escape(input, false)
+    fn evaluate(&self, input: &dyn ValueView, runtime: &dyn Runtime) -> Result<Value> {
+        let args = self.args.evaluate(runtime)?;
+
+        let strategy = match args.strategy {
+            Some(name) => Strategy::from_name(name.as_str()).ok_or_else(|| {
+                invalid_argument(
+                    "strategy",
+                    "html, attribute, js, css, url, json, csv or shell expected",
+                )
+            })?,
+            None => Strategy::Html,
+        };
+        if strategy == Strategy::Html || input.is_nil() {
+            return escape(input, false, runtime);
+        }
+        let escaped = strategy.escape(input.to_kstr().as_str()).into_owned();
+        Ok(escaped_value(escaped, strategy, runtime))
+    }
+}
+
+/// Text escaped with `strategy`, which autoescaping may only skip when it uses the same strategy.
+fn escaped_value(escaped: String, strategy: Strategy, runtime: &dyn Runtime) -> Value {
+    let autoescape = runtime.registers().get_mut::<AutoEscapeRegister>();
+    if autoescape.escapes_with(strategy) {
+        Value::scalar(Scalar::safe(escaped))
+    } else {
+        Value::scalar(escaped)
     }
 }
 
@@ -93,15 +138,60 @@ pub struct EscapeOnce;
 struct EscapeOnceFilter;
 
 impl Filter for EscapeOnceFilter {
+    fn evaluate(&self, input: &dyn ValueView, runtime: &dyn Runtime) -> Result<Value> {
+        escape(input, true, runtime)
+    }
+}
+
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+    name = "safe",
+    description = "Marks a string as safe to output, so autoescaping leaves it alone.",
+    parsed(SafeFilter)
+)]
+pub struct Safe;
+
+#[derive(Debug, Default, Display_filter)]
+#[name = "safe"]
+struct SafeFilter;
+
+impl Filter for SafeFilter {
     fn evaluate(&self, input: &dyn ValueView, _runtime: &dyn Runtime) -> Result<Value> {
-        escape(input, true)
+        mark_safe(input)
     }
 }
 
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+    name = "raw",
+    description = "Outputs a string without autoescaping; the same as `safe`.",
+    parsed(RawFilter)
+)]
+pub struct Raw;
+
+#[derive(Debug, Default, Display_filter)]
+#[name = "raw"]
+struct RawFilter;
+
+impl Filter for RawFilter {
+    fn evaluate(&self, input: &dyn ValueView, _runtime: &dyn Runtime) -> Result<Value> {
+        mark_safe(input)
+    }
+}
+
+fn mark_safe(input: &dyn ValueView) -> Result<Value> {
+    if input.is_nil() {
+        return Ok(Value::Nil);
+    }
+    let s = input.to_kstr().into_owned();
+    Ok(Value::scalar(Scalar::safe(s)))
+}
+
 #[derive(Clone, ParseFilter, FilterReflection)]
 #[filter(
     name = "strip_html",
     description = "Removes any HTML tags from a string.",
+    pure,
     parsed(StripHtmlFilter)
 )]
 pub struct StripHtml;
@@ -134,6 +224,7 @@ impl Filter for StripHtmlFilter {
 #[filter(
     name = "newline_to_br",
     description = "Replaces every newline (`\\n`) with an HTML line break (`<br>`).",
+    pure,
     parsed(NewlineToBrFilter)
 )]
 pub struct NewlineToBr;
@@ -166,6 +257,50 @@ mod tests {
         );
     }
 
+    #[test]
+    fn unit_escape_strategy() {
+        assert_eq!(
+            liquid_core::call_filter!(Escape, "a b&c", "url").unwrap(),
+            liquid_core::value!("a%20b%26c")
+        );
+        assert_eq!(
+            liquid_core::call_filter!(Escape, "it's", "js").unwrap(),
+            liquid_core::value!("it\\x27s")
+        );
+        liquid_core::call_filter!(Escape, "text", "xml").unwrap_err();
+    }
+
+    #[test]
+    fn unit_escape_is_safe_under_html_autoescape() {
+        let output = liquid_core::call_filter!(Escape, "<b>").unwrap();
+        assert!(!output.as_scalar().unwrap().is_safe());
+
+        let registers = liquid_core::runtime::Registers::default();
+        registers
+            .get_mut::<AutoEscapeRegister>()
+            .set(std::sync::Arc::new(Strategy::Html));
+        let runtime = liquid_core::runtime::RuntimeBuilder::new()
+            .set_registers(&registers)
+            .build();
+        let output = EscapeOnceFilter
+            .evaluate(&Value::scalar("<b>"), &runtime)
+            .unwrap();
+        assert!(output.as_scalar().unwrap().is_safe());
+    }
+
+    #[test]
+    fn unit_safe() {
+        let output = liquid_core::call_filter!(Safe, "<b>").unwrap();
+        assert_eq!(output, liquid_core::value!("<b>"));
+        assert!(output.as_scalar().unwrap().is_safe());
+        let output = liquid_core::call_filter!(Raw, 5).unwrap();
+        assert!(output.as_scalar().unwrap().is_safe());
+        assert_eq!(
+            liquid_core::call_filter!(Safe, Value::Nil).unwrap(),
+            Value::Nil
+        );
+    }
+
     #[test]
     fn unit_escape_non_ascii() {
         assert_eq!(
@@ -1,5 +1,7 @@
 use std::convert::TryInto;
 
+use liquid_core::model::{Scalar, ScalarCow};
+use liquid_core::runtime::IntegerOverflow;
 use liquid_core::Expression;
 use liquid_core::Result;
 use liquid_core::Runtime;
@@ -8,12 +10,194 @@ use liquid_core::{
 };
 use liquid_core::{Value, ValueView};
 
+#[cfg(feature = "bigint")]
+use liquid_core::model::BigInt;
+#[cfg(feature = "decimal")]
+use liquid_core::model::Decimal;
+
 use crate::{invalid_argument, invalid_input};
 
+/// Operands for exact arithmetic, used when either of them is a decimal.
+#[cfg(feature = "decimal")]
+fn decimal_operands(input: &ScalarCow<'_>, operand: &ScalarCow<'_>) -> Option<(Decimal, Decimal)> {
+    if input.as_decimal().is_none() && operand.as_decimal().is_none() {
+        return None;
+    }
+    Some((input.to_decimal()?, operand.to_decimal()?))
+}
+
+#[cfg(feature = "decimal")]
+fn decimal_overflow() -> liquid_core::Error {
+    invalid_input("Decimal overflow")
+}
+
+#[cfg(feature = "bigint")]
+fn bigint_operands(input: &ScalarCow<'_>, operand: &ScalarCow<'_>) -> Option<(BigInt, BigInt)> {
+    if input.as_bigint().is_none() && operand.as_bigint().is_none() {
+        return None;
+    }
+    Some((input.to_bigint()?, operand.to_bigint()?))
+}
+
+/// Big integers that fit go back to being plain whole numbers.
+#[cfg(feature = "bigint")]
+fn bigint_scalar(value: BigInt) -> Scalar {
+    match (&value).try_into() {
+        Ok(i) => Scalar::new::<i64>(i),
+        Err(_) => Scalar::new(value),
+    }
+}
+
+/// Handles a whole-number result that doesn't fit in an `i64`, per the runtime's
+/// `IntegerOverflow`.
+fn overflow(
+    runtime: &dyn Runtime,
+    float: impl FnOnce() -> f64,
+    #[cfg(feature = "bigint")] bigint: impl FnOnce() -> BigInt,
+) -> Result<Scalar> {
+    let mode = *runtime.registers().get_mut::<IntegerOverflow>();
+    match mode {
+        IntegerOverflow::Error => Err(invalid_input("Integer overflow")),
+        IntegerOverflow::Float => Ok(Scalar::new(float())),
+        #[cfg(feature = "bigint")]
+        IntegerOverflow::BigInt => Ok(bigint_scalar(bigint())),
+    }
+}
+
+/// Converts a rounded float to a whole number, treating values outside of `i64` as an overflow.
+fn float_to_integer(n: f64, runtime: &dyn Runtime) -> Result<Scalar> {
+    const LIMIT: f64 = 9_223_372_036_854_775_808.0;
+    if (-LIMIT..LIMIT).contains(&n) {
+        return Ok(Scalar::new(n as i64));
+    }
+
+    #[cfg(feature = "bigint")]
+    if !n.is_finite()
+        && *runtime.registers().get_mut::<IntegerOverflow>() == IntegerOverflow::BigInt
+    {
+        return Err(invalid_input("Number too large"));
+    }
+    overflow(
+        runtime,
+        || n,
+        #[cfg(feature = "bigint")]
+        || num_traits::FromPrimitive::from_f64(n).unwrap_or_default(),
+    )
+}
+
+/// Whole numbers round to themselves; going through a float would lose precision.
+fn as_whole_number(input: &dyn ValueView) -> Option<Scalar> {
+    let input = input.as_scalar()?;
+
+    #[cfg(feature = "bigint")]
+    if let Some(b) = input.as_bigint() {
+        return Some(Scalar::new(b.clone()));
+    }
+
+    input.to_integer().map(Scalar::new)
+}
+
+/// An arithmetic operator for each kind of number.
+struct Operator {
+    integer: fn(i64, i64) -> Option<i64>,
+    float: fn(f64, f64) -> f64,
+    #[cfg(feature = "decimal")]
+    decimal: fn(Decimal, Decimal) -> Option<Decimal>,
+    #[cfg(feature = "bigint")]
+    bigint: fn(&BigInt, &BigInt) -> BigInt,
+}
+
+const ADD: Operator = Operator {
+    integer: i64::checked_add,
+    float: |i, o| i + o,
+    #[cfg(feature = "decimal")]
+    decimal: Decimal::checked_add,
+    #[cfg(feature = "bigint")]
+    bigint: |i, o| i + o,
+};
+
+const SUB: Operator = Operator {
+    integer: i64::checked_sub,
+    float: |i, o| i - o,
+    #[cfg(feature = "decimal")]
+    decimal: Decimal::checked_sub,
+    #[cfg(feature = "bigint")]
+    bigint: |i, o| i - o,
+};
+
+const MUL: Operator = Operator {
+    integer: i64::checked_mul,
+    float: |i, o| i * o,
+    #[cfg(feature = "decimal")]
+    decimal: Decimal::checked_mul,
+    #[cfg(feature = "bigint")]
+    bigint: |i, o| i * o,
+};
+
+/// Callers must reject a zero divisor.
+const DIV: Operator = Operator {
+    integer: i64::checked_div,
+    float: |i, o| i / o,
+    #[cfg(feature = "decimal")]
+    decimal: Decimal::checked_div,
+    #[cfg(feature = "bigint")]
+    bigint: |i, o| i / o,
+};
+
+/// Callers must reject a zero divisor. `i64::MIN % -1` is `0` rather than an overflow.
+const REM: Operator = Operator {
+    integer: |i, o| Some(i.wrapping_rem(o)),
+    float: |i, o| i % o,
+    #[cfg(feature = "decimal")]
+    decimal: Decimal::checked_rem,
+    #[cfg(feature = "bigint")]
+    bigint: |i, o| i % o,
+};
+
+/// Applies `op` to two numbers, exactly if either is a decimal or a big integer.
+///
+/// Returns `None` if either isn't a number.
+fn apply(
+    op: &Operator,
+    input: &ScalarCow<'_>,
+    operand: &ScalarCow<'_>,
+    runtime: &dyn Runtime,
+) -> Option<Result<Scalar>> {
+    #[cfg(feature = "decimal")]
+    if let Some((i, o)) = decimal_operands(input, operand) {
+        return Some(
+            (op.decimal)(i, o)
+                .map(Scalar::new)
+                .ok_or_else(decimal_overflow),
+        );
+    }
+
+    #[cfg(feature = "bigint")]
+    if let Some((i, o)) = bigint_operands(input, operand) {
+        return Some(Ok(bigint_scalar((op.bigint)(&i, &o))));
+    }
+
+    if let (Some(i), Some(o)) = (input.to_integer(), operand.to_integer()) {
+        return Some(match (op.integer)(i, o) {
+            Some(result) => Ok(Scalar::new(result)),
+            None => overflow(
+                runtime,
+                || (op.float)(i as f64, o as f64),
+                #[cfg(feature = "bigint")]
+                || (op.bigint)(&i.into(), &o.into()),
+            ),
+        });
+    }
+
+    let (i, o) = (input.to_float()?, operand.to_float()?);
+    Some(Ok(Scalar::new((op.float)(i, o))))
+}
+
 #[derive(Clone, ParseFilter, FilterReflection)]
 #[filter(
     name = "abs",
     description = "Returns the absolute value of a number.",
+    pure,
     parsed(AbsFilter)
 )]
 pub struct Abs;
@@ -23,14 +207,37 @@ pub struct Abs;
 struct AbsFilter;
 
 impl Filter for AbsFilter {
-    fn evaluate(&self, input: &dyn ValueView, _runtime: &dyn Runtime) -> Result<Value> {
+    fn evaluate(&self, input: &dyn ValueView, runtime: &dyn Runtime) -> Result<Value> {
         let input = input
             .as_scalar()
             .ok_or_else(|| invalid_input("Number expected"))?;
+
+        #[cfg(feature = "decimal")]
+        if let Some(d) = input.as_decimal() {
+            return Ok(Value::scalar(d.abs()));
+        }
+
+        #[cfg(feature = "bigint")]
+        if let Some(b) = input.as_bigint() {
+            return Ok(Value::scalar(bigint_scalar(b.magnitude().clone().into())));
+        }
+
+        if let Some(i) = input.to_integer() {
+            let result = match i.checked_abs() {
+                Some(result) => Scalar::new(result),
+                None => overflow(
+                    runtime,
+                    || (i as f64).abs(),
+                    #[cfg(feature = "bigint")]
+                    || BigInt::from(i.unsigned_abs()),
+                )?,
+            };
+            return Ok(Value::scalar(result));
+        }
+
         input
-            .to_integer()
+            .to_float()
             .map(|i| Value::scalar(i.abs()))
-            .or_else(|| input.to_float().map(|i| Value::scalar(i.abs())))
             .ok_or_else(|| invalid_input("Number expected"))
     }
 }
@@ -46,6 +253,7 @@ struct AtLeastArgs {
     name = "at_least",
     description = "Limits a number to a minimum value.",
     parameters(AtLeastArgs),
+    pure,
     parsed(AtLeastFilter)
 )]
 pub struct AtLeast;
@@ -70,6 +278,11 @@ impl Filter for AtLeastFilter {
             .as_scalar()
             .ok_or_else(|| invalid_argument("operand", "Number expected"))?;
 
+        #[cfg(feature = "decimal")]
+        if let Some((i, min)) = decimal_operands(&input, &min) {
+            return Ok(Value::scalar(i.max(min)));
+        }
+
         let result = input
             .to_integer()
             .and_then(|i| min.to_integer().map(|min| Value::scalar(i.max(min))))
@@ -95,6 +308,7 @@ struct AtMostArgs {
     name = "at_most",
     description = "Limits a number to a maximum value.",
     parameters(AtMostArgs),
+    pure,
     parsed(AtMostFilter)
 )]
 pub struct AtMost;
@@ -119,6 +333,11 @@ impl Filter for AtMostFilter {
             .as_scalar()
             .ok_or_else(|| invalid_argument("operand", "Number expected"))?;
 
+        #[cfg(feature = "decimal")]
+        if let Some((i, max)) = decimal_operands(&input, &max) {
+            return Ok(Value::scalar(i.min(max)));
+        }
+
         let result = input
             .to_integer()
             .and_then(|i| max.to_integer().map(|max| Value::scalar(i.min(max))))
@@ -144,6 +363,7 @@ struct PlusArgs {
     name = "plus",
     description = "Sums a number with the given operand.",
     parameters(PlusArgs),
+    pure,
     parsed(PlusFilter)
 )]
 pub struct Plus;
@@ -168,17 +388,10 @@ impl Filter for PlusFilter {
             .as_scalar()
             .ok_or_else(|| invalid_argument("operand", "Number expected"))?;
 
-        let result = input
-            .to_integer()
-            .and_then(|i| operand.to_integer().map(|o| Value::scalar(i + o)))
-            .or_else(|| {
-                input
-                    .to_float()
-                    .and_then(|i| operand.to_float().map(|o| Value::scalar(i + o)))
-            })
-            .ok_or_else(|| invalid_argument("operand", "Number expected"))?;
+        let result = apply(&ADD, &input, &operand, runtime)
+            .ok_or_else(|| invalid_argument("operand", "Number expected"))??;
 
-        Ok(result)
+        Ok(Value::scalar(result))
     }
 }
 
@@ -193,6 +406,7 @@ struct MinusArgs {
     name = "minus",
     description = "Subtracts the given operand from a number.",
     parameters(MinusArgs),
+    pure,
     parsed(MinusFilter)
 )]
 pub struct Minus;
@@ -217,17 +431,10 @@ impl Filter for MinusFilter {
             .as_scalar()
             .ok_or_else(|| invalid_argument("operand", "Number expected"))?;
 
-        let result = input
-            .to_integer()
-            .and_then(|i| operand.to_integer().map(|o| Value::scalar(i - o)))
-            .or_else(|| {
-                input
-                    .to_float()
-                    .and_then(|i| operand.to_float().map(|o| Value::scalar(i - o)))
-            })
-            .ok_or_else(|| invalid_argument("operand", "Number expected"))?;
+        let result = apply(&SUB, &input, &operand, runtime)
+            .ok_or_else(|| invalid_argument("operand", "Number expected"))??;
 
-        Ok(result)
+        Ok(Value::scalar(result))
     }
 }
 
@@ -242,6 +449,7 @@ struct TimesArgs {
     name = "times",
     description = "Multiplies a number by the given operand.",
     parameters(TimesArgs),
+    pure,
     parsed(TimesFilter)
 )]
 pub struct Times;
@@ -266,17 +474,10 @@ impl Filter for TimesFilter {
             .as_scalar()
             .ok_or_else(|| invalid_argument("operand", "Number expected"))?;
 
-        let result = input
-            .to_integer()
-            .and_then(|i| operand.to_integer().map(|o| Value::scalar(i * o)))
-            .or_else(|| {
-                input
-                    .to_float()
-                    .and_then(|i| operand.to_float().map(|o| Value::scalar(i * o)))
-            })
-            .ok_or_else(|| invalid_argument("operand", "Number expected"))?;
+        let result = apply(&MUL, &input, &operand, runtime)
+            .ok_or_else(|| invalid_argument("operand", "Number expected"))??;
 
-        Ok(result)
+        Ok(Value::scalar(result))
     }
 }
 
@@ -291,6 +492,7 @@ struct DividedByArgs {
     name = "divided_by",
     description = "Divides a number by the given operand.",
     parameters(DividedByArgs),
+    pure,
     parsed(DividedByFilter)
 )]
 pub struct DividedBy;
@@ -325,17 +527,10 @@ impl Filter for DividedByFilter {
             }
         }
 
-        let result = input
-            .to_integer()
-            .and_then(|i| operand.to_integer().map(|o| Value::scalar(i / o)))
-            .or_else(|| {
-                input
-                    .to_float()
-                    .and_then(|i| operand.to_float().map(|o| Value::scalar(i / o)))
-            })
-            .ok_or_else(|| invalid_argument("operand", "Number expected"))?;
+        let result = apply(&DIV, &input, &operand, runtime)
+            .ok_or_else(|| invalid_argument("operand", "Number expected"))??;
 
-        Ok(result)
+        Ok(Value::scalar(result))
     }
 }
 
@@ -350,6 +545,7 @@ struct ModuloArgs {
     name = "modulo",
     description = "The remainder of a division operation of a number by the given operand.",
     parameters(ModuloArgs),
+    pure,
     parsed(ModuloFilter)
 )]
 pub struct Modulo;
@@ -384,17 +580,10 @@ impl Filter for ModuloFilter {
             }
         }
 
-        let result = input
-            .to_integer()
-            .and_then(|i| operand.to_integer().map(|o| Value::scalar(i % o)))
-            .or_else(|| {
-                input
-                    .to_float()
-                    .and_then(|i| operand.to_float().map(|o| Value::scalar(i % o)))
-            })
-            .ok_or_else(|| invalid_argument("operand", "Number expected"))?;
+        let result = apply(&REM, &input, &operand, runtime)
+            .ok_or_else(|| invalid_argument("operand", "Number expected"))??;
 
-        Ok(result)
+        Ok(Value::scalar(result))
     }
 }
 
@@ -412,6 +601,7 @@ struct RoundArgs {
     name = "round",
     description = "Rounds an input number to the nearest integer or, if a number is specified as an argument, to that number of decimal places.",
     parameters(RoundArgs),
+    pure,
     parsed(RoundFilter)
 )]
 pub struct Round;
@@ -429,14 +619,29 @@ impl Filter for RoundFilter {
 
         let n = args.decimal_places.unwrap_or(0);
 
+        #[cfg(feature = "decimal")]
+        if let Some(d) = input.as_scalar().and_then(|s| s.as_decimal()) {
+            return round_decimal(d, n);
+        }
+
+        if n <= 0 {
+            if let Some(whole) = as_whole_number(input) {
+                return Ok(Value::scalar(whole));
+            }
+        }
+
         let input = input
             .as_scalar()
             .and_then(|s| s.to_float())
             .ok_or_else(|| invalid_input("Number expected"))?;
 
         match n.cmp(&0) {
-            std::cmp::Ordering::Equal => Ok(Value::scalar(input.round() as i64)),
-            std::cmp::Ordering::Less => Ok(Value::scalar(input.round() as i64)),
+            std::cmp::Ordering::Equal => {
+                Ok(Value::scalar(float_to_integer(input.round(), runtime)?))
+            }
+            std::cmp::Ordering::Less => {
+                Ok(Value::scalar(float_to_integer(input.round(), runtime)?))
+            }
             _ => {
                 let multiplier = 10.0_f64.powi(
                     n.try_into()
@@ -448,10 +653,34 @@ impl Filter for RoundFilter {
     }
 }
 
+/// Rounds half away from zero, like `f64::round`.
+#[cfg(feature = "decimal")]
+fn round_decimal(input: Decimal, decimal_places: i64) -> Result<Value> {
+    use rust_decimal::prelude::ToPrimitive;
+    use rust_decimal::RoundingStrategy;
+
+    if decimal_places <= 0 {
+        input
+            .round_dp_with_strategy(0, RoundingStrategy::MidpointAwayFromZero)
+            .to_i64()
+            .map(Value::scalar)
+            .ok_or_else(|| invalid_input("Number too large"))
+    } else {
+        let decimal_places = decimal_places
+            .try_into()
+            .map_err(|_| invalid_input("decimal-places was too large"))?;
+        Ok(Value::scalar(input.round_dp_with_strategy(
+            decimal_places,
+            RoundingStrategy::MidpointAwayFromZero,
+        )))
+    }
+}
+
 #[derive(Clone, ParseFilter, FilterReflection)]
 #[filter(
     name = "ceil",
     description = "Rounds the input up to the nearest whole number.",
+    pure,
     parsed(CeilFilter)
 )]
 pub struct Ceil;
@@ -461,12 +690,23 @@ pub struct Ceil;
 struct CeilFilter;
 
 impl Filter for CeilFilter {
-    fn evaluate(&self, input: &dyn ValueView, _runtime: &dyn Runtime) -> Result<Value> {
+    fn evaluate(&self, input: &dyn ValueView, runtime: &dyn Runtime) -> Result<Value> {
+        #[cfg(feature = "decimal")]
+        if let Some(d) = input.as_scalar().and_then(|s| s.as_decimal()) {
+            return rust_decimal::prelude::ToPrimitive::to_i64(&d.ceil())
+                .map(Value::scalar)
+                .ok_or_else(|| invalid_input("Number too large"));
+        }
+
+        if let Some(whole) = as_whole_number(input) {
+            return Ok(Value::scalar(whole));
+        }
+
         let n = input
             .as_scalar()
             .and_then(|s| s.to_float())
             .ok_or_else(|| invalid_input("Number expected"))?;
-        Ok(Value::scalar(n.ceil() as i64))
+        Ok(Value::scalar(float_to_integer(n.ceil(), runtime)?))
     }
 }
 
@@ -474,6 +714,7 @@ impl Filter for CeilFilter {
 #[filter(
     name = "floor",
     description = "Rounds a number down to the nearest whole number.",
+    pure,
     parsed(FloorFilter)
 )]
 pub struct Floor;
@@ -483,13 +724,92 @@ pub struct Floor;
 struct FloorFilter;
 
 impl Filter for FloorFilter {
-    fn evaluate(&self, input: &dyn ValueView, _runtime: &dyn Runtime) -> Result<Value> {
+    fn evaluate(&self, input: &dyn ValueView, runtime: &dyn Runtime) -> Result<Value> {
+        #[cfg(feature = "decimal")]
+        if let Some(d) = input.as_scalar().and_then(|s| s.as_decimal()) {
+            return rust_decimal::prelude::ToPrimitive::to_i64(&d.floor())
+                .map(Value::scalar)
+                .ok_or_else(|| invalid_input("Number too large"));
+        }
+
+        if let Some(whole) = as_whole_number(input) {
+            return Ok(Value::scalar(whole));
+        }
+
         let n = input
             .as_scalar()
             .and_then(|s| s.to_float())
             .ok_or_else(|| invalid_input("Number expected"))?;
-        Ok(Value::scalar(n.floor() as i64))
+        Ok(Value::scalar(float_to_integer(n.floor(), runtime)?))
+    }
+}
+
+#[derive(Debug, Default, FilterParameters)]
+struct SumArgs {
+    #[parameter(
+        description = "The property to sum from each object.",
+        arg_type = "str"
+    )]
+    property: Option<Expression>,
+}
+
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+    name = "sum",
+    description = "Sums the numbers in an array, or a property of the objects in an array. Anything that isn't a number counts as zero.",
+    parameters(SumArgs),
+    parsed(SumFilter)
+)]
+pub struct Sum;
+
+#[derive(Debug, Default, FromFilterParameters, Display_filter)]
+#[name = "sum"]
+struct SumFilter {
+    #[parameters]
+    args: SumArgs,
+}
+
+impl Filter for SumFilter {
+    fn evaluate(&self, input: &dyn ValueView, runtime: &dyn Runtime) -> Result<Value> {
+        let args = self.args.evaluate(runtime)?;
+
+        let array = input
+            .as_array()
+            .ok_or_else(|| invalid_input("Array expected"))?;
+
+        let mut sum = Scalar::new(0i64);
+        sum_into(&mut sum, array.values(), args.property.as_deref(), runtime)?;
+        Ok(Value::scalar(sum))
+    }
+}
+
+/// Flattens nested arrays, like Ruby's `InputIterator`.
+fn sum_into<'v>(
+    sum: &mut Scalar,
+    values: impl Iterator<Item = &'v dyn ValueView>,
+    property: Option<&str>,
+    runtime: &dyn Runtime,
+) -> Result<()> {
+    for value in values {
+        if let Some(array) = value.as_array() {
+            sum_into(sum, array.values(), property, runtime)?;
+            continue;
+        }
+
+        let value = match property {
+            Some(property) => value
+                .as_object()
+                .ok_or_else(|| invalid_input("Array of objects expected"))?
+                .get_cow(property),
+            None => Some(liquid_core::ValueCow::Borrowed(value)),
+        };
+        if let Some(item) = value.as_ref().and_then(|v| v.as_scalar()) {
+            if let Some(result) = apply(&ADD, &sum.as_ref(), &item, runtime) {
+                *sum = result?;
+            }
+        }
     }
+    Ok(())
 }
 
 #[cfg(test)]
@@ -708,4 +1028,355 @@ mod tests {
             Value::scalar(1.235f64)
         );
     }
+
+    #[test]
+    fn unit_sum() {
+        assert_eq!(
+            liquid_core::call_filter!(Sum, liquid_core::value!([1, 2, "3", "foo", [4, [5]]]))
+                .unwrap(),
+            Value::scalar(15i64)
+        );
+        assert_eq!(
+            liquid_core::call_filter!(Sum, liquid_core::value!([1, 0.5])).unwrap(),
+            Value::scalar(1.5f64)
+        );
+        assert_eq!(
+            liquid_core::call_filter!(Sum, liquid_core::value!([])).unwrap(),
+            Value::scalar(0i64)
+        );
+        liquid_core::call_filter!(Sum, 1i64).unwrap_err();
+    }
+
+    #[test]
+    fn unit_sum_property() {
+        let input = liquid_core::value!([
+            {"quantity": 1},
+            {"quantity": 2, "weight": 3},
+            {"weight": 4},
+        ]);
+        assert_eq!(
+            liquid_core::call_filter!(Sum, input.clone(), "quantity").unwrap(),
+            Value::scalar(3i64)
+        );
+        assert_eq!(
+            liquid_core::call_filter!(Sum, input.clone(), "weight").unwrap(),
+            Value::scalar(7i64)
+        );
+        assert_eq!(
+            liquid_core::call_filter!(Sum, input, "subtotal").unwrap(),
+            Value::scalar(0i64)
+        );
+        liquid_core::call_filter!(Sum, liquid_core::value!([1, 2]), "quantity").unwrap_err();
+    }
+
+    #[cfg(feature = "decimal")]
+    fn decimal(s: &str) -> Value {
+        Value::scalar(s.parse::<Decimal>().unwrap())
+    }
+
+    #[test]
+    #[cfg(feature = "decimal")]
+    fn unit_decimal_arithmetic() {
+        let result = liquid_core::call_filter!(Plus, decimal("0.1"), decimal("0.2")).unwrap();
+        assert_eq!(result.to_kstr(), "0.3");
+        let result = liquid_core::call_filter!(Minus, decimal("19.90"), 1i64).unwrap();
+        assert_eq!(result.to_kstr(), "18.90");
+        let result = liquid_core::call_filter!(Times, decimal("19.90"), 3i64).unwrap();
+        assert_eq!(result.to_kstr(), "59.70");
+        let result = liquid_core::call_filter!(DividedBy, decimal("10.00"), 4i64).unwrap();
+        assert_eq!(result.to_kstr(), "2.50");
+        let result = liquid_core::call_filter!(Modulo, decimal("7.5"), 2i64).unwrap();
+        assert_eq!(result.to_kstr(), "1.5");
+        liquid_core::call_filter!(DividedBy, decimal("1.0"), 0i64).unwrap_err();
+        liquid_core::call_filter!(Times, decimal("79228162514264337593543950335"), 2i64)
+            .unwrap_err();
+    }
+
+    #[test]
+    #[cfg(feature = "decimal")]
+    fn unit_decimal_rounding() {
+        assert_eq!(
+            liquid_core::call_filter!(Round, decimal("2.5")).unwrap(),
+            Value::scalar(3i64)
+        );
+        let result = liquid_core::call_filter!(Round, decimal("1.005"), 2i64).unwrap();
+        assert_eq!(result.to_kstr(), "1.01");
+        assert_eq!(
+            liquid_core::call_filter!(Ceil, decimal("1.1")).unwrap(),
+            Value::scalar(2i64)
+        );
+        assert_eq!(
+            liquid_core::call_filter!(Floor, decimal("-1.1")).unwrap(),
+            Value::scalar(-2i64)
+        );
+        let result = liquid_core::call_filter!(Abs, decimal("-1.50")).unwrap();
+        assert_eq!(result.to_kstr(), "1.50");
+        let result = liquid_core::call_filter!(AtLeast, decimal("4.5"), 5i64).unwrap();
+        assert_eq!(result.to_kstr(), "5");
+    }
+
+    #[test]
+    #[cfg(feature = "decimal")]
+    fn unit_decimal_sum() {
+        let input = Value::Array(vec![decimal("0.1"), decimal("0.2"), Value::scalar(1i64)]);
+        let result = liquid_core::call_filter!(Sum, input).unwrap();
+        assert_eq!(result.to_kstr(), "1.3");
+    }
+
+    fn call_with_overflow(
+        mode: IntegerOverflow,
+        filter: &dyn ParseFilter,
+        input: Value,
+        operand: Option<Value>,
+    ) -> Result<Value> {
+        let positional = Box::new(operand.map(Expression::Literal).into_iter());
+        let keyword = Box::new(Vec::new().into_iter());
+        let args = liquid_core::parser::FilterArguments {
+            positional,
+            keyword,
+        };
+
+        let registers = liquid_core::runtime::Registers::default();
+        *registers.get_mut::<IntegerOverflow>() = mode;
+        let runtime = liquid_core::runtime::RuntimeBuilder::new()
+            .set_registers(&registers)
+            .build();
+
+        filter.parse(args)?.evaluate(&input, &runtime)
+    }
+
+    /// `(filter, input, operand, float result, big integer result)` for results outside of `i64`.
+    #[allow(clippy::type_complexity)]
+    fn overflowing_cases() -> Vec<(
+        &'static dyn ParseFilter,
+        i64,
+        Option<i64>,
+        f64,
+        &'static str,
+    )> {
+        vec![
+            (
+                &Plus,
+                i64::MAX,
+                Some(1),
+                9.223372036854776e18,
+                "9223372036854775808",
+            ),
+            (
+                &Plus,
+                i64::MIN,
+                Some(-1),
+                -9.223372036854776e18,
+                "-9223372036854775809",
+            ),
+            (
+                &Minus,
+                i64::MIN,
+                Some(1),
+                -9.223372036854776e18,
+                "-9223372036854775809",
+            ),
+            (
+                &Minus,
+                i64::MAX,
+                Some(-1),
+                9.223372036854776e18,
+                "9223372036854775808",
+            ),
+            (
+                &Times,
+                i64::MAX,
+                Some(2),
+                1.8446744073709552e19,
+                "18446744073709551614",
+            ),
+            (
+                &Times,
+                i64::MIN,
+                Some(-1),
+                9.223372036854776e18,
+                "9223372036854775808",
+            ),
+            (
+                &DividedBy,
+                i64::MIN,
+                Some(-1),
+                9.223372036854776e18,
+                "9223372036854775808",
+            ),
+            (
+                &Abs,
+                i64::MIN,
+                None,
+                9.223372036854776e18,
+                "9223372036854775808",
+            ),
+        ]
+    }
+
+    /// `(filter, input, operand, result)` for results at the edges of `i64`.
+    fn boundary_cases() -> Vec<(&'static dyn ParseFilter, i64, Option<i64>, i64)> {
+        vec![
+            (&Plus, i64::MAX - 1, Some(1), i64::MAX),
+            (&Plus, i64::MIN, Some(i64::MAX), -1),
+            (&Minus, i64::MIN + 1, Some(1), i64::MIN),
+            (&Minus, i64::MAX, Some(i64::MAX), 0),
+            (&Times, i64::MIN, Some(1), i64::MIN),
+            (&Times, i64::MAX, Some(-1), i64::MIN + 1),
+            (&DividedBy, i64::MIN, Some(1), i64::MIN),
+            (&DividedBy, i64::MAX, Some(-1), i64::MIN + 1),
+            (&Modulo, i64::MIN, Some(-1), 0),
+            (&Modulo, i64::MAX, Some(i64::MIN), i64::MAX),
+            (&Abs, i64::MIN + 1, None, i64::MAX),
+            (&Abs, i64::MAX, None, i64::MAX),
+        ]
+    }
+
+    fn overflow_modes() -> Vec<IntegerOverflow> {
+        #[allow(unused_mut)]
+        let mut modes = vec![IntegerOverflow::Error, IntegerOverflow::Float];
+        #[cfg(feature = "bigint")]
+        modes.push(IntegerOverflow::BigInt);
+        modes
+    }
+
+    #[test]
+    fn unit_integer_boundaries() {
+        for mode in overflow_modes() {
+            for (filter, input, operand, expected) in boundary_cases() {
+                let name = filter.reflection().name();
+                let actual = call_with_overflow(
+                    mode,
+                    filter,
+                    Value::scalar(input),
+                    operand.map(Value::scalar),
+                )
+                .unwrap();
+                assert_eq!(
+                    actual,
+                    Value::scalar(expected),
+                    "{} {:?} {:?} in {:?}",
+                    name,
+                    input,
+                    operand,
+                    mode
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn unit_integer_overflow_error() {
+        for (filter, input, operand, _, _) in overflowing_cases() {
+            let name = filter.reflection().name();
+            let err = call_with_overflow(
+                IntegerOverflow::Error,
+                filter,
+                Value::scalar(input),
+                operand.map(Value::scalar),
+            )
+            .unwrap_err();
+            assert!(
+                err.to_string().contains("Integer overflow"),
+                "{} {:?} {:?}: {}",
+                name,
+                input,
+                operand,
+                err
+            );
+        }
+        liquid_core::call_filter!(Plus, i64::MAX, 1i64).unwrap_err();
+    }
+
+    #[test]
+    fn unit_integer_overflow_float() {
+        for (filter, input, operand, expected, _) in overflowing_cases() {
+            let name = filter.reflection().name();
+            let actual = call_with_overflow(
+                IntegerOverflow::Float,
+                filter,
+                Value::scalar(input),
+                operand.map(Value::scalar),
+            )
+            .unwrap();
+            assert_eq!(
+                actual,
+                Value::scalar(expected),
+                "{} {:?} {:?}",
+                name,
+                input,
+                operand
+            );
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "bigint")]
+    fn unit_integer_overflow_bigint() {
+        for (filter, input, operand, _, expected) in overflowing_cases() {
+            let name = filter.reflection().name();
+            let actual = call_with_overflow(
+                IntegerOverflow::BigInt,
+                filter,
+                Value::scalar(input),
+                operand.map(Value::scalar),
+            )
+            .unwrap();
+            assert_eq!(
+                actual.to_kstr(),
+                expected,
+                "{} {:?} {:?}",
+                name,
+                input,
+                operand
+            );
+            assert_eq!(actual.as_scalar().unwrap().to_integer(), None);
+        }
+    }
+
+    #[test]
+    fn unit_rounding_overflow() {
+        call_with_overflow(IntegerOverflow::Error, &Ceil, Value::scalar(1e30), None).unwrap_err();
+        assert_eq!(
+            call_with_overflow(IntegerOverflow::Float, &Floor, Value::scalar(-1e30), None).unwrap(),
+            Value::scalar(-1e30)
+        );
+        assert_eq!(
+            liquid_core::call_filter!(Round, i64::MAX).unwrap(),
+            Value::scalar(i64::MAX)
+        );
+    }
+
+    #[test]
+    fn unit_sum_overflow() {
+        let input = Value::Array(vec![Value::scalar(i64::MAX), Value::scalar(1i64)]);
+        call_with_overflow(IntegerOverflow::Error, &Sum, input.clone(), None).unwrap_err();
+        assert_eq!(
+            call_with_overflow(IntegerOverflow::Float, &Sum, input, None).unwrap(),
+            Value::scalar(9.223372036854776e18)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "bigint")]
+    fn unit_bigint_arithmetic() {
+        let big = Value::scalar(BigInt::from(i64::MAX) + 1);
+        // Big integers stay exact regardless of the mode, and shrink back once they fit.
+        let result = liquid_core::call_filter!(Minus, big.clone(), 1i64).unwrap();
+        assert_eq!(result, Value::scalar(i64::MAX));
+        assert!(result.as_scalar().unwrap().as_bigint().is_none());
+        let result = liquid_core::call_filter!(Times, big.clone(), 2i64).unwrap();
+        assert_eq!(result.to_kstr(), "18446744073709551616");
+        let result = liquid_core::call_filter!(Modulo, big.clone(), 10i64).unwrap();
+        assert_eq!(result, Value::scalar(8i64));
+        let result = liquid_core::call_filter!(Ceil, big.clone()).unwrap();
+        assert_eq!(result.to_kstr(), "9223372036854775808");
+        let result = liquid_core::call_filter!(Plus, big.clone(), 0.5f64).unwrap();
+        assert_eq!(result, Value::scalar(9.223372036854776e18));
+        liquid_core::call_filter!(DividedBy, big, 0i64).unwrap_err();
+
+        let result =
+            call_with_overflow(IntegerOverflow::BigInt, &Round, Value::scalar(1e20), None).unwrap();
+        assert_eq!(result.to_kstr(), "100000000000000000000");
+    }
 }
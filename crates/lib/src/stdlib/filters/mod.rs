@@ -4,7 +4,7 @@ use liquid_core::Runtime;
 use liquid_core::{
     Display_filter, Filter, FilterParameters, FilterReflection, FromFilterParameters, ParseFilter,
 };
-use liquid_core::{Value, ValueView};
+use liquid_core::{Value, ValueCow, ValueView};
 
 mod array;
 mod date;
@@ -17,10 +17,10 @@ mod url;
 pub use self::array::{
     Compact, Concat, First, Join, Last, Map, Reverse, Sort, SortNatural, Uniq, Where,
 };
-pub use self::date::Date;
-pub use self::html::{Escape, EscapeOnce, NewlineToBr, StripHtml};
+pub use self::date::{Date, LocalizedDate};
+pub use self::html::{Escape, EscapeOnce, NewlineToBr, Raw, Safe, StripHtml};
 pub use self::math::{
-    Abs, AtLeast, AtMost, Ceil, DividedBy, Floor, Minus, Modulo, Plus, Round, Times,
+    Abs, AtLeast, AtMost, Ceil, DividedBy, Floor, Minus, Modulo, Plus, Round, Sum, Times,
 };
 pub use self::slice::Slice;
 pub use self::string::case::{Capitalize, Downcase, Upcase};
@@ -34,6 +34,7 @@ pub use self::url::{UrlDecode, UrlEncode};
 #[filter(
     name = "size",
     description = "Returns the size of the input. For an array or object this is the number of elements. For other values it's the length of its string representation.",
+    pure,
     parsed(SizeFilter)
 )]
 pub struct Size;
@@ -67,25 +68,31 @@ struct DefaultArgs {
     name = "default",
     description = "Sets a default value for the given input.",
     parameters(DefaultArgs),
+    pure,
     parsed(DefaultFilter)
 )]
 pub struct Default;
 
 #[derive(Debug, FromFilterParameters, Display_filter)]
 #[name = "default"]
+#[filter(borrows)]
 struct DefaultFilter {
     #[parameters]
     args: DefaultArgs,
 }
 
-impl Filter for DefaultFilter {
-    fn evaluate(&self, input: &dyn ValueView, runtime: &dyn Runtime) -> Result<Value> {
+impl DefaultFilter {
+    fn evaluate_cow<'v>(
+        &'v self,
+        input: &'v dyn ValueView,
+        runtime: &'v dyn Runtime,
+    ) -> Result<ValueCow<'v>> {
         let args = self.args.evaluate(runtime)?;
 
         if input.query_state(liquid_core::model::State::DefaultValue) {
-            Ok(args.default.to_value())
+            Ok(args.default)
         } else {
-            Ok(input.to_value())
+            Ok(ValueCow::Borrowed(input))
         }
     }
 }
@@ -4,9 +4,9 @@ use liquid_core::Expression;
 use liquid_core::Result;
 use liquid_core::Runtime;
 use liquid_core::{
-    Display_filter, Filter, FilterParameters, FilterReflection, FromFilterParameters, ParseFilter,
+    Display_filter, FilterParameters, FilterReflection, FromFilterParameters, ParseFilter,
 };
-use liquid_core::{Value, ValueView};
+use liquid_core::{Value, ValueCow, ValueView};
 
 use crate::invalid_argument;
 
@@ -50,19 +50,25 @@ struct SliceArgs {
     name = "slice",
     description = "Takes a slice of a given string or array.",
     parameters(SliceArgs),
+    pure,
     parsed(SliceFilter)
 )]
 pub struct Slice;
 
 #[derive(Debug, FromFilterParameters, Display_filter)]
 #[name = "slice"]
+#[filter(borrows)]
 struct SliceFilter {
     #[parameters]
     args: SliceArgs,
 }
 
-impl Filter for SliceFilter {
-    fn evaluate(&self, input: &dyn ValueView, runtime: &dyn Runtime) -> Result<Value> {
+impl SliceFilter {
+    fn evaluate_cow<'v>(
+        &'v self,
+        input: &'v dyn ValueView,
+        runtime: &'v dyn Runtime,
+    ) -> Result<ValueCow<'v>> {
         let args = self.args.evaluate(runtime)?;
 
         let offset = args.offset as isize;
@@ -74,19 +80,20 @@ impl Filter for SliceFilter {
 
         if let Some(input) = input.as_array() {
             let (offset, length) = canonicalize_slice(offset, length, input.size() as usize);
-            Ok(Value::array(
+            Ok(ValueCow::Array(
                 input
                     .values()
                     .skip(offset)
                     .take(length)
-                    .map(|s| s.to_value()),
+                    .map(ValueCow::Borrowed)
+                    .collect(),
             ))
         } else {
             let input = input.to_kstr();
             let (offset, length) = canonicalize_slice(offset, length, input.len());
-            Ok(Value::scalar(
+            Ok(ValueCow::Owned(Value::scalar(
                 input.chars().skip(offset).take(length).collect::<String>(),
-            ))
+            )))
         }
     }
 }
@@ -109,6 +116,25 @@ mod tests {
         );
     }
 
+    #[test]
+    fn unit_slice_borrows_array_items() {
+        let positional = Box::new(vec![Expression::Literal(liquid_core::value!(1))].into_iter());
+        let keyword = Box::new(Vec::new().into_iter());
+        let args = liquid_core::parser::FilterArguments {
+            positional,
+            keyword,
+        };
+        let filter = ParseFilter::parse(&Slice, args).unwrap();
+        let runtime = liquid_core::runtime::RuntimeBuilder::new().build();
+
+        let input = liquid_core::value!([["a"], ["b"], ["c"]]);
+        let result = filter.evaluate_cow(&input, &runtime).unwrap();
+        assert!(
+            matches!(result, ValueCow::Array(ref items) if matches!(items[..], [ValueCow::Borrowed(_)]))
+        );
+        assert_eq!(result, liquid_core::value!([["b"]]));
+    }
+
     #[test]
     fn unit_slice_no_length_specified() {
         assert_eq!(
@@ -9,6 +9,7 @@ use liquid_core::{Value, ValueView};
 #[filter(
     name = "downcase",
     description = "Makes each character in a string downcase.",
+    pure,
     parsed(DowncaseFilter)
 )]
 pub struct Downcase;
@@ -28,6 +29,7 @@ impl Filter for DowncaseFilter {
 #[filter(
     name = "upcase",
     description = "Makes each character in a string uppercase.",
+    pure,
     parsed(UpcaseFilter)
 )]
 pub struct Upcase;
@@ -47,6 +49,7 @@ impl Filter for UpcaseFilter {
 #[filter(
     name = "capitalize",
     description = "Makes the first character of a string capitalized.",
+    pure,
     parsed(CapitalizeFilter)
 )]
 pub struct Capitalize;
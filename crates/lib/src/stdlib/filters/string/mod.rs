@@ -25,6 +25,7 @@ struct SplitArgs {
     name = "split",
     description = "Divides an input string into an array using the argument as a separator.",
     parameters(SplitArgs),
+    pure,
     parsed(SplitFilter)
 )]
 pub struct Split;
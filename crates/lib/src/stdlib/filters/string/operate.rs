@@ -22,6 +22,7 @@ struct ReplaceArgs {
     name = "replace",
     description = "Replaces the occurrences of the `search` with `replace`. If `replace` is not given, just deletes occurrences of `search`.",
     parameters(ReplaceArgs),
+    pure,
     parsed(ReplaceFilter)
 )]
 pub struct Replace;
@@ -63,6 +64,7 @@ struct ReplaceFirstArgs {
     name = "replace_first",
     description = "Replaces the first occurrence of the `search` with `replace`. If `replace` is not given, just deletes the occurrence.",
     parameters(ReplaceFirstArgs),
+    pure,
     parsed(ReplaceFirstFilter)
 )]
 pub struct ReplaceFirst;
@@ -105,6 +107,7 @@ struct RemoveArgs {
     name = "remove",
     description = "Removes all occurrences of the given string.",
     parameters(RemoveArgs),
+    pure,
     parsed(RemoveFilter)
 )]
 pub struct Remove;
@@ -137,6 +140,7 @@ struct RemoveFirstArgs {
     name = "remove_first",
     description = "Removes the first occurrence of the given string.",
     parameters(RemoveFirstArgs),
+    pure,
     parsed(RemoveFirstFilter)
 )]
 pub struct RemoveFirst;
@@ -171,6 +175,7 @@ struct AppendArgs {
     name = "append",
     description = "Appends the given text to a string.",
     parameters(AppendArgs),
+    pure,
     parsed(AppendFilter)
 )]
 pub struct Append;
@@ -204,6 +209,7 @@ struct PrependArgs {
     name = "prepend",
     description = "Prepends the given text to a string.",
     parameters(PrependArgs),
+    pure,
     parsed(PrependFilter)
 )]
 pub struct Prepend;
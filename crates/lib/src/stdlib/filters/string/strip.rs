@@ -14,6 +14,7 @@ use liquid_core::{Value, ValueView};
 #[filter(
     name = "strip",
     description = "Removes all whitespace (tabs, spaces, and newlines) from both the left and right side of a string.",
+    pure,
     parsed(StripFilter)
 )]
 pub struct Strip;
@@ -39,6 +40,7 @@ impl Filter for StripFilter {
 #[filter(
     name = "lstrip",
     description = "Removes all whitespaces (tabs, spaces, and newlines) from the beginning of a string.",
+    pure,
     parsed(LstripFilter)
 )]
 pub struct Lstrip;
@@ -64,6 +66,7 @@ impl Filter for LstripFilter {
 #[filter(
     name = "rstrip",
     description = "Removes all whitespace (tabs, spaces, and newlines) from the right side of a string.",
+    pure,
     parsed(RstripFilter)
 )]
 pub struct Rstrip;
@@ -83,6 +86,7 @@ impl Filter for RstripFilter {
 #[filter(
     name = "strip_newlines",
     description = "Removes any newline characters (line breaks) from a string.",
+    pure,
     parsed(StripNewlinesFilter)
 )]
 pub struct StripNewlines;
@@ -55,6 +55,7 @@ struct TruncateArgs {
     name = "truncate",
     description = "Shortens a string down to the number of characters passed as a parameter.",
     parameters(TruncateArgs),
+    pure,
     parsed(TruncateFilter)
 )]
 pub struct Truncate;
@@ -114,6 +115,7 @@ struct TruncateWordsArgs {
     name = "truncatewords",
     description = "Shortens a string down to the number of characters passed as a parameter.",
     parameters(TruncateWordsArgs),
+    pure,
     parsed(TruncateWordsFilter)
 )]
 pub struct TruncateWords;
@@ -14,6 +14,7 @@ const FRAGMENT: &percent_encoding::AsciiSet = &percent_encoding::NON_ALPHANUMERI
 #[filter(
     name = "url_encode",
     description = "Converts any URL-unsafe characters in a string into percent-encoded characters.",
+    pure,
     parsed(UrlEncodeFilter)
 )]
 pub struct UrlEncode;
@@ -39,6 +40,7 @@ impl Filter for UrlEncodeFilter {
 #[filter(
     name = "url_decode",
     description = "Decodes a string that has been encoded as a URL or by url_encode.",
+    pure,
     parsed(UrlDecodeFilter)
 )]
 pub struct UrlDecode;
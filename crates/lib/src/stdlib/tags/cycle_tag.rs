@@ -1,9 +1,10 @@
 use std::collections::HashMap;
 use std::io::Write;
 
-use liquid_core::error::{ResultLiquidExt, ResultLiquidReplaceExt};
+use liquid_core::error::ResultLiquidExt;
 use liquid_core::parser::TagToken;
 use liquid_core::parser::TryMatchToken;
+use liquid_core::runtime::{write_output, GlobalReferences};
 use liquid_core::Expression;
 use liquid_core::Language;
 use liquid_core::Renderable;
@@ -129,8 +130,13 @@ impl Renderable for Cycle {
             .cycle(&self.name, &self.values)
             .trace_with(|| self.trace().into())?;
         let value = expr.evaluate(runtime).trace_with(|| self.trace().into())?;
-        write!(writer, "{}", value.render()).replace("Failed to render")?;
-        Ok(())
+        write_output(writer, value.as_view(), runtime)
+    }
+
+    fn collect_globals(&self, globals: &mut GlobalReferences<'_>) {
+        for value in &self.values {
+            globals.read(value);
+        }
     }
 }
 
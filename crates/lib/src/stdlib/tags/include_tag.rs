@@ -2,6 +2,7 @@ use std::io::Write;
 
 use liquid_core::error::ResultLiquidExt;
 use liquid_core::model::KString;
+use liquid_core::runtime::GlobalReferences;
 use liquid_core::Expression;
 use liquid_core::Language;
 use liquid_core::Renderable;
@@ -121,6 +122,18 @@ impl Renderable for Include {
 
         Ok(())
     }
+
+    fn collect_globals(&self, globals: &mut GlobalReferences<'_>) {
+        globals.read(&self.partial);
+        for (_, val) in &self.vars {
+            globals.read(val);
+        }
+
+        if let Expression::Literal(ref name) = self.partial {
+            let locals: Vec<&str> = self.vars.iter().map(|(id, _)| id.as_str()).collect();
+            globals.include(&name.to_kstr(), &locals);
+        }
+    }
 }
 
 #[cfg(test)]
@@ -2,6 +2,7 @@ use std::io::Write;
 
 use liquid_core::error::ResultLiquidReplaceExt;
 use liquid_core::model::{Value, ValueView};
+use liquid_core::runtime::GlobalReferences;
 use liquid_core::Language;
 use liquid_core::Renderable;
 use liquid_core::Result;
@@ -68,6 +69,10 @@ impl Renderable for Increment {
         runtime.set_index(self.id.clone(), Value::scalar(val));
         Ok(())
     }
+
+    fn collect_globals(&self, globals: &mut GlobalReferences<'_>) {
+        globals.assign(&self.id);
+    }
 }
 
 #[derive(Copy, Clone, Debug, Default)]
@@ -130,6 +135,10 @@ impl Renderable for Decrement {
         runtime.set_index(self.id.clone(), Value::scalar(val));
         Ok(())
     }
+
+    fn collect_globals(&self, globals: &mut GlobalReferences<'_>) {
+        globals.assign(&self.id);
+    }
 }
 
 #[cfg(test)]
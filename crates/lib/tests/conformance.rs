@@ -3,3 +3,5 @@ mod test_helper;
 
 #[cfg(feature = "jekyll")]
 mod conformance_jekyll;
+#[cfg(feature = "shopify")]
+mod conformance_shopify;
@@ -0,0 +1 @@
+mod standard_filter_test;
@@ -0,0 +1,128 @@
+use crate::test_helper::*;
+use liquid_lib::shopify;
+
+#[test]
+fn test_handleize() {
+    assert_eq!(
+        liquid_core::call_filter!(shopify::Handleize, "100% M & Ms!!!").unwrap(),
+        v!("100-m-ms"),
+    );
+    assert_eq!(
+        liquid_core::call_filter!(shopify::Handle, "Gregory's shirt").unwrap(),
+        v!("gregorys-shirt"),
+    );
+}
+
+#[test]
+fn test_camelcase() {
+    assert_eq!(
+        liquid_core::call_filter!(shopify::Camelcase, "coming-soon").unwrap(),
+        v!("ComingSoon"),
+    );
+}
+
+#[test]
+fn test_url_escape() {
+    assert_eq!(
+        liquid_core::call_filter!(shopify::UrlEscape, "<p>Health & Love potions</p>").unwrap(),
+        v!("%3Cp%3EHealth%20&%20Love%20potions%3C/p%3E"),
+    );
+}
+
+#[test]
+fn test_url_param_escape() {
+    assert_eq!(
+        liquid_core::call_filter!(shopify::UrlParamEscape, "<p>Health & Love potions</p>").unwrap(),
+        v!("%3Cp%3EHealth%20%26%20Love%20potions%3C/p%3E"),
+    );
+}
+
+#[test]
+fn test_base64_encode() {
+    assert_eq!(
+        liquid_core::call_filter!(shopify::Base64Encode, "one two three").unwrap(),
+        v!("b25lIHR3byB0aHJlZQ=="),
+    );
+    assert_eq!(
+        liquid_core::call_filter!(shopify::Base64Encode, Nil).unwrap(),
+        v!(""),
+    );
+}
+
+#[test]
+fn test_base64_decode() {
+    assert_eq!(
+        liquid_core::call_filter!(shopify::Base64Decode, "b25lIHR3byB0aHJlZQ==").unwrap(),
+        v!("one two three"),
+    );
+    liquid_core::call_filter!(shopify::Base64Decode, "invalidbase64").unwrap_err();
+}
+
+#[test]
+fn test_base64_url_safe_encode() {
+    assert_eq!(
+        liquid_core::call_filter!(
+            shopify::Base64UrlSafeEncode,
+            "abcdefghijklmnopqrstuvwxyz ABCDEFGHIJKLMNOPQRSTUVWXYZ 1234567890 !@#$%^&*()-=_+/?.:;[]{}\\|"
+        )
+        .unwrap(),
+        v!("YWJjZGVmZ2hpamtsbW5vcHFyc3R1dnd4eXogQUJDREVGR0hJSktMTU5PUFFSU1RVVldYWVogMTIzNDU2Nzg5MCAhQCMkJV4mKigpLT1fKy8_Ljo7W117fVx8"),
+    );
+    assert_eq!(
+        liquid_core::call_filter!(shopify::Base64UrlSafeEncode, Nil).unwrap(),
+        v!(""),
+    );
+}
+
+#[test]
+fn test_base64_url_safe_decode() {
+    assert_eq!(
+        liquid_core::call_filter!(
+            shopify::Base64UrlSafeDecode,
+            "YWJjZGVmZ2hpamtsbW5vcHFyc3R1dnd4eXogQUJDREVGR0hJSktMTU5PUFFSU1RVVldYWVogMTIzNDU2Nzg5MCAhQCMkJV4mKigpLT1fKy8_Ljo7W117fVx8"
+        )
+        .unwrap(),
+        v!("abcdefghijklmnopqrstuvwxyz ABCDEFGHIJKLMNOPQRSTUVWXYZ 1234567890 !@#$%^&*()-=_+/?.:;[]{}\\|"),
+    );
+    liquid_core::call_filter!(shopify::Base64UrlSafeDecode, "invalidbase64").unwrap_err();
+}
+
+#[test]
+fn test_md5() {
+    assert_eq!(
+        liquid_core::call_filter!(shopify::Md5, "Polina").unwrap(),
+        v!("71d0ea493b78a6a33e7a68ae74254017"),
+    );
+}
+
+#[test]
+fn test_sha1() {
+    assert_eq!(
+        liquid_core::call_filter!(shopify::Sha1, "Polina").unwrap(),
+        v!("f429155c2cac473dc23ff4a0a353bd7fb3c1cfe7"),
+    );
+}
+
+#[test]
+fn test_sha256() {
+    assert_eq!(
+        liquid_core::call_filter!(shopify::Sha256, "Polina").unwrap(),
+        v!("2e78aebdaa6e03cd6f6567338006cccb33b2a13d254ef3c4c8b00701e0d8a325"),
+    );
+}
+
+#[test]
+fn test_hmac_sha1() {
+    assert_eq!(
+        liquid_core::call_filter!(shopify::HmacSha1, "ShopifyIsAwesome!", "secret_key").unwrap(),
+        v!("30ab3459e46e7b209b45dba8378fcbba67297304"),
+    );
+}
+
+#[test]
+fn test_hmac_sha256() {
+    assert_eq!(
+        liquid_core::call_filter!(shopify::HmacSha256, "ShopifyIsAwesome!", "secret_key").unwrap(),
+        v!("c21f97cf997fac667c9bac39462a5813b1a41ce1b811743b0e9157393efbcc3c"),
+    );
+}
@@ -0,0 +1,40 @@
+use std::io::Write;
+
+use liquid_core::error::Result;
+use liquid_core::runtime::{Program, Registers, Renderable};
+
+use crate::template::convert_buffer;
+use crate::Template;
+
+/// A template compiled to a flat list of instructions, see [`Template::compile`].
+pub struct CompiledTemplate<'t> {
+    template: &'t Template,
+    program: Program<'t>,
+}
+
+impl<'t> CompiledTemplate<'t> {
+    pub(crate) fn new(template: &'t Template) -> Self {
+        Self {
+            template,
+            program: Program::compile(&template.template),
+        }
+    }
+
+    /// Renders the template, using the given globals.
+    pub fn render(&self, globals: &dyn crate::ObjectView) -> Result<String> {
+        const BEST_GUESS: usize = 10_000;
+        let mut data = Vec::with_capacity(BEST_GUESS);
+        self.render_to(&mut data, globals)?;
+
+        Ok(convert_buffer(data))
+    }
+
+    /// Renders the template, using the given globals.
+    pub fn render_to(&self, writer: &mut dyn Write, globals: &dyn crate::ObjectView) -> Result<()> {
+        let registers = Registers::default();
+        self.template
+            .with_runtime(globals, &registers, self.template.partials(), |runtime| {
+                self.program.render_to(writer, runtime)
+            })
+    }
+}
@@ -21,7 +21,9 @@
 //! assert_eq!(output, "Liquid! 2".to_string());
 //! ```
 
+mod compiled;
 mod parser;
+mod stream;
 mod template;
 
 pub mod reflection;
@@ -35,16 +37,30 @@ pub mod model {
     pub use liquid_core::scalar;
     pub use liquid_core::value;
 }
+/// Plugin state for rendering.
+pub mod runtime {
+    #[cfg(feature = "async")]
+    pub use liquid_core::runtime::{AsyncGlobals, AsyncValue};
+    pub use liquid_core::runtime::{
+        AutoEscapeRegister, ContentForRegister, Escape, Escaper, IntegerOverflow, LocaleRegister,
+        Registers, TimeZoneRegister,
+    };
+}
 
+pub use crate::compiled::*;
 pub use crate::parser::*;
+pub use crate::stream::*;
 pub use crate::template::*;
 pub use liquid_core::model::{_ObjectView as ObjectView, _ValueView as ValueView};
 pub use liquid_core::object;
 pub use liquid_core::to_object;
 pub use liquid_core::Error;
 pub use liquid_core::Object;
+pub use liquid_derive::object_methods;
 #[doc(hidden)]
-pub use liquid_derive::{ObjectView, ValueView};
+pub use liquid_derive::{ObjectView, ToSchema, ValueView};
+#[cfg(feature = "jekyll")]
+pub use liquid_lib::jekyll::LayoutCompiler;
 
 #[macro_use]
 extern crate doc_comment;
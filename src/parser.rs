@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::fs::File;
 use std::io::prelude::Read;
 use std::path;
@@ -23,6 +24,11 @@ where
     tags: parser::PluginRegistry<Box<dyn parser::ParseTag>>,
     filters: parser::PluginRegistry<Box<dyn parser::ParseFilter>>,
     partials: Option<P>,
+    integer_overflow: runtime::IntegerOverflow,
+    autoescape: Option<sync::Arc<dyn runtime::Escaper>>,
+    autoescape_extensions: HashMap<String, sync::Arc<dyn runtime::Escaper>>,
+    #[cfg(feature = "async")]
+    async_partials: Option<sync::Arc<dyn partials::AsyncPartialSource>>,
 }
 
 impl ParserBuilder<Partials> {
@@ -85,6 +91,7 @@ where
             .filter(stdlib::NewlineToBr)
             .filter(stdlib::Plus)
             .filter(stdlib::Prepend)
+            .filter(stdlib::Raw)
             .filter(stdlib::Remove)
             .filter(stdlib::RemoveFirst)
             .filter(stdlib::Replace)
@@ -92,6 +99,7 @@ where
             .filter(stdlib::Reverse)
             .filter(stdlib::Round)
             .filter(stdlib::Rstrip)
+            .filter(stdlib::Safe)
             .filter(stdlib::Size)
             .filter(stdlib::Slice)
             .filter(stdlib::Sort)
@@ -100,6 +108,7 @@ where
             .filter(stdlib::Strip)
             .filter(stdlib::StripHtml)
             .filter(stdlib::StripNewlines)
+            .filter(stdlib::Sum)
             .filter(stdlib::Times)
             .filter(stdlib::Truncate)
             .filter(stdlib::TruncateWords)
@@ -133,6 +142,48 @@ where
         self
     }
 
+    /// Set what math filters do when a whole-number result overflows an `i64`.
+    ///
+    /// Defaults to [`IntegerOverflow::Error`](runtime::IntegerOverflow::Error).
+    pub fn integer_overflow(mut self, integer_overflow: runtime::IntegerOverflow) -> Self {
+        self.integer_overflow = integer_overflow;
+        self
+    }
+
+    /// Pass every `{{ }}` output of templates through `escaper`, except for strings marked safe
+    /// (e.g. with the `safe` filter).
+    ///
+    /// Use one of the built-in [`Escape`](runtime::Escape) strategies or your own
+    /// [`Escaper`](runtime::Escaper).  Off by default.  [`Template::with_autoescape`] overrides
+    /// it per template.
+    pub fn autoescape<E: runtime::Escaper + 'static>(mut self, escaper: E) -> Self {
+        self.autoescape = Some(sync::Arc::new(escaper));
+        self
+    }
+
+    /// Escape the output of templates parsed from files with this extension, like `"html"`.
+    ///
+    /// Takes precedence over [`ParserBuilder::autoescape`] in [`Parser::parse_file`].
+    pub fn autoescape_extension<E: runtime::Escaper + 'static>(
+        mut self,
+        extension: &str,
+        escaper: E,
+    ) -> Self {
+        self.autoescape_extensions
+            .insert(extension.to_owned(), sync::Arc::new(escaper));
+        self
+    }
+
+    /// Set where [`Template::render_async`] loads partial-templates from, instead of
+    /// [`ParserBuilder::partials`].
+    ///
+    /// Only the partials a template includes by name are loaded, when first rendering it.
+    #[cfg(feature = "async")]
+    pub fn async_partials<S: partials::AsyncPartialSource + 'static>(mut self, source: S) -> Self {
+        self.async_partials = Some(sync::Arc::new(source));
+        self
+    }
+
     /// Set which partial-templates will be available.
     pub fn partials<N: partials::PartialCompiler>(self, partials: N) -> ParserBuilder<N> {
         let Self {
@@ -140,12 +191,22 @@ where
             tags,
             filters,
             partials: _partials,
+            integer_overflow,
+            autoescape,
+            autoescape_extensions,
+            #[cfg(feature = "async")]
+            async_partials,
         } = self;
         ParserBuilder {
             blocks,
             tags,
             filters,
             partials: Some(partials),
+            integer_overflow,
+            autoescape,
+            autoescape_extensions,
+            #[cfg(feature = "async")]
+            async_partials,
         }
     }
 
@@ -156,6 +217,11 @@ where
             tags,
             filters,
             partials,
+            integer_overflow,
+            autoescape,
+            autoescape_extensions,
+            #[cfg(feature = "async")]
+            async_partials,
         } = self;
 
         let mut options = parser::Language::empty();
@@ -167,7 +233,15 @@ where
             .map(|p| p.compile(options.clone()))
             .map_or(Ok(None), |r| r.map(Some))?
             .map(|p| p.into());
-        let p = Parser { options, partials };
+        let p = Parser {
+            options,
+            partials,
+            integer_overflow,
+            autoescape,
+            autoescape_extensions,
+            #[cfg(feature = "async")]
+            async_partials,
+        };
         Ok(p)
     }
 }
@@ -182,6 +256,11 @@ where
             tags: Default::default(),
             filters: Default::default(),
             partials: Default::default(),
+            integer_overflow: Default::default(),
+            autoescape: Default::default(),
+            autoescape_extensions: Default::default(),
+            #[cfg(feature = "async")]
+            async_partials: Default::default(),
         }
     }
 }
@@ -216,6 +295,11 @@ where
 pub struct Parser {
     options: sync::Arc<parser::Language>,
     partials: Option<sync::Arc<dyn runtime::PartialStore + Send + Sync>>,
+    integer_overflow: runtime::IntegerOverflow,
+    autoescape: Option<sync::Arc<dyn runtime::Escaper>>,
+    autoescape_extensions: HashMap<String, sync::Arc<dyn runtime::Escaper>>,
+    #[cfg(feature = "async")]
+    async_partials: Option<sync::Arc<dyn partials::AsyncPartialSource>>,
 }
 
 impl Parser {
@@ -243,9 +327,40 @@ impl Parser {
         Ok(Template {
             template,
             partials: self.partials.clone(),
+            integer_overflow: self.integer_overflow,
+            schema: None,
+            autoescape: self.autoescape.clone(),
+            #[cfg(feature = "async")]
+            async_partials: self.async_partials(),
         })
     }
 
+    /// Parses a liquid template that may start with YAML front matter, like Jekyll pages.
+    ///
+    /// Returns the front matter, empty if there is none, and the rest as a Template.
+    ///
+    /// ```
+    /// let (front_matter, template) = liquid::ParserBuilder::with_stdlib()
+    ///     .build().unwrap()
+    ///     .parse_document("---\ntitle: Home\n---\n{{ title }}").unwrap();
+    ///
+    /// assert_eq!(template.render(&front_matter).unwrap(), "Home");
+    /// ```
+    #[cfg(feature = "front-matter")]
+    pub fn parse_document(&self, text: &str) -> Result<(liquid_core::Object, Template)> {
+        let (front_matter, elements) = parser::parse_document(text, &self.options)?;
+        let template = Template {
+            template: runtime::Template::new(elements),
+            partials: self.partials.clone(),
+            integer_overflow: self.integer_overflow,
+            schema: None,
+            autoescape: self.autoescape.clone(),
+            #[cfg(feature = "async")]
+            async_partials: self.async_partials(),
+        };
+        Ok((front_matter, template))
+    }
+
     /// Parse a liquid template from a file, returning a `Result<Template, Error>`.
     /// # Examples
     ///
@@ -286,7 +401,29 @@ impl Parser {
             .context_key("path")
             .value_with(|| file.to_string_lossy().into_owned().into())?;
 
-        self.parse(&buf)
+        let template = self.parse(&buf)?;
+        let escaper = file.extension().and_then(|ext| {
+            self.autoescape_extensions
+                .get(ext.to_string_lossy().as_ref())
+        });
+        match escaper {
+            Some(escaper) => Ok(Template {
+                autoescape: Some(escaper.clone()),
+                ..template
+            }),
+            None => Ok(template),
+        }
+    }
+
+    #[cfg(feature = "async")]
+    fn async_partials(&self) -> Option<crate::template::AsyncPartials> {
+        self.async_partials
+            .clone()
+            .map(|source| crate::template::AsyncPartials {
+                source,
+                language: self.options.clone(),
+                loaded: Default::default(),
+            })
     }
 }
 
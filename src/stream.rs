@@ -0,0 +1,155 @@
+use std::io::Write;
+
+use liquid_core::error::Result;
+use liquid_core::runtime;
+
+use crate::template::convert_buffer;
+use crate::Template;
+
+/// The output of a template, one top-level element at a time, see
+/// [`Template::render_chunks`].
+///
+/// Elements are only rendered when the next chunk is asked for, so a slow reader holds up
+/// rendering instead of output piling up.  Elements with no output are skipped.  Rendering
+/// stops after the first error.
+///
+/// Chunks follow the elements left after parsing, so they're only as fine as those: a
+/// top-level block, like a `for` loop, is one chunk however long it is, while neighbouring
+/// text, including from branches picked while parsing, is merged into one.  Use
+/// [`Template::stream_to`] to hand output to a writer as it is rendered.
+pub struct Chunks<'t> {
+    template: &'t Template,
+    runtime: Box<dyn runtime::Runtime + 't>,
+    index: usize,
+    done: bool,
+}
+
+impl<'t> Chunks<'t> {
+    pub(crate) fn new(template: &'t Template, runtime: Box<dyn runtime::Runtime + 't>) -> Self {
+        Self {
+            template,
+            runtime,
+            index: 0,
+            done: false,
+        }
+    }
+}
+
+impl<'t> Chunks<'t> {
+    /// Renders the next element straight to `writer`, `None` once done.
+    pub(crate) fn write_next(&mut self, writer: &mut dyn Write) -> Option<Result<()>> {
+        if self.done {
+            return None;
+        }
+        let rendered =
+            self.template
+                .template
+                .render_element_to(self.index, writer, self.runtime.as_ref());
+        match rendered {
+            Ok(more) => {
+                self.index += 1;
+                self.done = !more;
+                Some(Ok(()))
+            }
+            Err(err) => {
+                self.done = true;
+                Some(Err(err))
+            }
+        }
+    }
+}
+
+impl<'t> Iterator for Chunks<'t> {
+    type Item = Result<String>;
+
+    fn next(&mut self) -> Option<Result<String>> {
+        loop {
+            let mut chunk = Vec::new();
+            if let Err(err) = self.write_next(&mut chunk)? {
+                return Some(Err(err));
+            }
+            if !chunk.is_empty() {
+                return Some(Ok(convert_buffer(chunk)));
+            }
+        }
+    }
+}
+
+impl<'t> std::iter::FusedIterator for Chunks<'t> {}
+
+/// [`Chunks`] for [`Template::render_async`], whose future may move between threads.
+#[cfg(feature = "async")]
+pub(crate) struct SendChunks<'t>(Chunks<'t>);
+
+#[cfg(feature = "async")]
+impl<'t> SendChunks<'t> {
+    pub(crate) fn new(
+        template: &'t Template,
+        globals: &'t liquid_core::Object,
+        loaded: &'t liquid_core::Object,
+        partials: Option<&'t (dyn runtime::PartialStore + Send + Sync)>,
+    ) -> Self {
+        let runtime = runtime::RuntimeBuilder::new().set_globals(globals);
+        let runtime = match partials {
+            Some(partials) => runtime.set_partials(partials),
+            None => runtime,
+        };
+        let runtime = runtime.build();
+        template.prepare(runtime::Runtime::registers(&runtime));
+        let runtime = runtime::StackFrame::new(runtime, loaded);
+        Self(Chunks::new(template, Box::new(runtime)))
+    }
+}
+
+// SAFETY: `Chunks` is only `!Send` because its runtime is type-erased.  This one borrows `Sync`
+// data (the template, its partials and the globals) and owns the rest (its frames and
+// registers, which are `Send`), so it can move to another thread with all of its state.
+#[cfg(feature = "async")]
+unsafe impl<'t> Send for SendChunks<'t> {}
+
+#[cfg(feature = "async")]
+impl<'t> Iterator for SendChunks<'t> {
+    type Item = Result<String>;
+
+    fn next(&mut self) -> Option<Result<String>> {
+        self.0.next()
+    }
+}
+
+/// How [`Template::stream_to`] writes its output.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct StreamOptions {
+    buffered: bool,
+    flush: bool,
+}
+
+impl StreamOptions {
+    /// Write output as it is rendered, leaving flushing to the writer.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Write nothing until the whole template rendered, so errors leave no partial output.
+    pub fn buffered(mut self, buffered: bool) -> Self {
+        self.buffered = buffered;
+        self
+    }
+
+    /// Flush the writer after each top-level element, like for each part of an HTTP response.
+    ///
+    /// Buffered output is flushed once, after it is written.
+    pub fn flush_each_element(mut self, flush: bool) -> Self {
+        self.flush = flush;
+        self
+    }
+
+    /// Whether output is held back until the whole template rendered.
+    pub fn is_buffered(&self) -> bool {
+        self.buffered
+    }
+
+    /// Whether the writer is flushed after each top-level element.
+    pub fn flushes_each_element(&self) -> bool {
+        self.flush
+    }
+}
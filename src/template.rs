@@ -1,17 +1,136 @@
 use std::io::Write;
 use std::sync;
 
-use liquid_core::error::Result;
+use liquid_core::error::{Result, ResultLiquidReplaceExt};
+use liquid_core::model::{try_find, ScalarCow, Schema, Violations};
 use liquid_core::runtime;
 use liquid_core::runtime::PartialStore;
+use liquid_core::runtime::Registers;
 use liquid_core::runtime::Renderable;
+use liquid_core::runtime::Runtime;
+use liquid_core::runtime::{GlobalReference, GlobalReferences};
 
 pub struct Template {
     pub(crate) template: runtime::Template,
     pub(crate) partials: Option<sync::Arc<dyn PartialStore + Send + Sync>>,
+    pub(crate) integer_overflow: runtime::IntegerOverflow,
+    pub(crate) schema: Option<Schema>,
+    pub(crate) autoescape: Option<sync::Arc<dyn runtime::Escaper>>,
+    #[cfg(feature = "async")]
+    pub(crate) async_partials: Option<AsyncPartials>,
+}
+
+/// Where [`Template::render_async`] loads partials from, and how to parse them.
+#[cfg(feature = "async")]
+pub(crate) struct AsyncPartials {
+    pub(crate) source: sync::Arc<dyn liquid_core::partials::AsyncPartialSource>,
+    pub(crate) language: sync::Arc<liquid_core::Language>,
+    /// The partials, once loaded by the first render.
+    pub(crate) loaded: sync::Mutex<Option<sync::Arc<dyn PartialStore + Send + Sync>>>,
+}
+
+#[cfg(feature = "async")]
+impl AsyncPartials {
+    /// The partials `template` includes, loaded on first use.
+    async fn load(
+        &self,
+        template: &runtime::Template,
+    ) -> Result<sync::Arc<dyn PartialStore + Send + Sync>> {
+        if let Some(ref loaded) = *self
+            .loaded
+            .lock()
+            .unwrap_or_else(sync::PoisonError::into_inner)
+        {
+            return Ok(loaded.clone());
+        }
+        let loaded: sync::Arc<dyn PartialStore + Send + Sync> =
+            liquid_core::partials::load_partials(self.source.as_ref(), template, &self.language)
+                .await?
+                .into();
+        *self
+            .loaded
+            .lock()
+            .unwrap_or_else(sync::PoisonError::into_inner) = Some(loaded.clone());
+        Ok(loaded)
+    }
 }
 
 impl Template {
+    /// Declares the globals this template expects, checked by [`Template::validate`].
+    ///
+    /// Describe them in Rust with `#[derive(ToSchema)]` or deserialize a JSON-Schema-like
+    /// description into a [`Schema`](crate::model::Schema).
+    pub fn with_schema(mut self, schema: Schema) -> Self {
+        self.schema = Some(schema);
+        self
+    }
+
+    /// Pass this template's output through `escaper`, regardless of the parser's autoescaping.
+    pub fn with_autoescape<E: runtime::Escaper + 'static>(mut self, escaper: E) -> Self {
+        self.autoescape = Some(sync::Arc::new(escaper));
+        self
+    }
+
+    /// Write this template's output as is, regardless of the parser's autoescaping.
+    pub fn without_autoescape(mut self) -> Self {
+        self.autoescape = None;
+        self
+    }
+
+    /// How this template's output is escaped, if at all.
+    pub fn autoescape(&self) -> Option<&dyn runtime::Escaper> {
+        self.autoescape.as_deref()
+    }
+
+    /// The globals this template expects, if declared.
+    pub fn schema(&self) -> Option<&Schema> {
+        self.schema.as_ref()
+    }
+
+    /// The globals this template (and the partials it includes by name) reads, in order.
+    ///
+    /// Paths stop at the first index only known while rendering.  Variables defined by the
+    /// template itself, like with `assign` or `for`, are skipped.
+    pub fn referenced_globals(&self) -> Vec<GlobalReference> {
+        self.collect_globals(self.partials.as_deref())
+    }
+
+    fn collect_globals(
+        &self,
+        partials: Option<&(dyn PartialStore + Send + Sync)>,
+    ) -> Vec<GlobalReference> {
+        let mut globals = match partials {
+            Some(partials) => GlobalReferences::with_partials(partials),
+            None => GlobalReferences::new(),
+        };
+        self.template.collect_globals(&mut globals);
+        globals.into_vec()
+    }
+
+    /// Checks `globals` before rendering.
+    ///
+    /// Reports every mistyped or missing global against the schema, if any, and every global
+    /// the template reads without testing for it first, like `{{ user.name }}` outside of
+    /// `{% if user %}`.
+    pub fn validate(&self, globals: &dyn crate::ObjectView) -> Result<()> {
+        let mut violations = Violations::new();
+        if let Some(ref schema) = self.schema {
+            schema.check(globals.as_value(), "", &mut violations);
+        }
+
+        for reference in self.referenced_globals() {
+            if reference.is_optional() {
+                continue;
+            }
+            let path: Vec<ScalarCow<'_>> = reference.path().iter().map(|p| p.as_ref()).collect();
+            if try_find(globals.as_value(), &path).is_none() {
+                violations.missing(reference.to_string());
+            }
+        }
+
+        violations.into_result()
+    }
+
     /// Renders an instance of the Template, using the given globals.
     pub fn render(&self, globals: &dyn crate::ObjectView) -> Result<String> {
         const BEST_GUESS: usize = 10_000;
@@ -23,23 +142,255 @@ impl Template {
 
     /// Renders an instance of the Template, using the given globals.
     pub fn render_to(&self, writer: &mut dyn Write, globals: &dyn crate::ObjectView) -> Result<()> {
+        let registers = Registers::default();
+        self.render_to_with_registers(writer, globals, &registers)
+    }
+
+    /// Compiles the Template to a flat list of instructions, which renders faster than walking
+    /// its tree.
+    ///
+    /// Text, outputs and their filters, `if`, `unless`, `case`, `for` and `capture` are
+    /// compiled; other tags, like custom ones, render as usual.  Partials aren't compiled.
+    ///
+    /// ```
+    /// let template = liquid::ParserBuilder::with_stdlib()
+    ///     .build().unwrap()
+    ///     .parse("{% for i in (1..3) %}{% if i > 1 %}{{ i }}{% endif %}{% endfor %}").unwrap();
+    ///
+    /// let compiled = template.compile();
+    /// assert_eq!(compiled.render(&liquid::Object::new()).unwrap(), "23");
+    /// ```
+    pub fn compile(&self) -> crate::CompiledTemplate<'_> {
+        crate::CompiledTemplate::new(self)
+    }
+
+    /// Renders an instance of the Template one top-level element at a time, using the given
+    /// globals.
+    ///
+    /// Each chunk is rendered when asked for, so the start of the output can be sent, like
+    /// in an HTTP response, before the rest is rendered.  See [`Chunks`](crate::Chunks) for
+    /// how the output is split.
+    ///
+    /// ```
+    /// let template = liquid::ParserBuilder::with_stdlib()
+    ///     .build().unwrap()
+    ///     .parse("Hello {{ name }}!").unwrap();
+    ///
+    /// let globals = liquid::object!({"name": "World"});
+    /// let chunks: Result<Vec<_>, _> = template.render_chunks(&globals).collect();
+    /// assert_eq!(chunks.unwrap(), vec!["Hello ", "World", "!"]);
+    /// ```
+    pub fn render_chunks<'t>(&'t self, globals: &'t dyn crate::ObjectView) -> crate::Chunks<'t> {
         let runtime = runtime::RuntimeBuilder::new().set_globals(globals);
-        let runtime = match self.partials {
-            Some(ref partials) => runtime.set_partials(partials.as_ref()),
+        let runtime = match self.partials() {
+            Some(partials) => runtime.set_partials(partials),
+            None => runtime,
+        };
+        let runtime = runtime.build();
+        self.prepare(runtime.registers());
+        crate::Chunks::new(self, Box::new(runtime))
+    }
+
+    /// Renders an instance of the Template to `writer`, using the given globals, as set by
+    /// `options`.
+    ///
+    /// Unless buffered, output is written to `writer` as it is rendered.  Unlike
+    /// [`Template::render_to`], it can hold back the output until the whole template rendered,
+    /// or flush `writer` after each top-level element.
+    pub fn stream_to(
+        &self,
+        writer: &mut dyn Write,
+        globals: &dyn crate::ObjectView,
+        options: crate::StreamOptions,
+    ) -> Result<()> {
+        let mut chunks = self.render_chunks(globals);
+        if options.is_buffered() {
+            let output = chunks.collect::<Result<String>>()?;
+            writer
+                .write_all(output.as_bytes())
+                .replace("Failed to render")?;
+            if options.flushes_each_element() {
+                writer.flush().replace("Failed to render")?;
+            }
+        } else {
+            while let Some(rendered) = chunks.write_next(writer) {
+                rendered?;
+                if options.flushes_each_element() {
+                    writer.flush().replace("Failed to render")?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Renders an instance of the Template, using the given globals and plugin state.
+    ///
+    /// `registers` can be seeded before rendering (e.g. with a `LocaleRegister`) and inspected
+    /// afterwards. The parser's `integer_overflow` option replaces any seeded `IntegerOverflow`,
+    /// and the template's autoescaping any seeded `AutoEscapeRegister`.
+    pub fn render_with_registers(
+        &self,
+        globals: &dyn crate::ObjectView,
+        registers: &Registers,
+    ) -> Result<String> {
+        const BEST_GUESS: usize = 10_000;
+        let mut data = Vec::with_capacity(BEST_GUESS);
+        self.render_to_with_registers(&mut data, globals, registers)?;
+
+        Ok(convert_buffer(data))
+    }
+
+    /// Renders an instance of the Template, using the given globals and plugin state.
+    pub fn render_to_with_registers(
+        &self,
+        writer: &mut dyn Write,
+        globals: &dyn crate::ObjectView,
+        registers: &Registers,
+    ) -> Result<()> {
+        self.with_runtime(globals, registers, self.partials(), |runtime| {
+            self.template.render_to(writer, runtime)
+        })
+    }
+
+    /// Renders an instance of the Template to `writer`, without blocking on IO.
+    ///
+    /// First loads the partials this template includes by name, from the parser's
+    /// [`async_partials`](crate::ParserBuilder::async_partials) if any, and the globals it
+    /// reads, then renders the output one top-level element at a time, like
+    /// [`Template::render_chunks`], waiting for `writer` to take each one before rendering the
+    /// next.  Partials included by a name only known while rendering can't be loaded ahead of
+    /// time.  The partials are loaded and compiled by the first successful render, and reused
+    /// by later ones.
+    ///
+    /// Filters, tags and drops still run synchronously and can't await: load what they need
+    /// as [`AsyncValue`](crate::runtime::AsyncValue) globals instead.
+    ///
+    /// ```
+    /// let template = liquid::ParserBuilder::with_stdlib()
+    ///     .build().unwrap()
+    ///     .parse("Hello {{ name }}").unwrap();
+    ///
+    /// let globals = liquid::runtime::AsyncGlobals::from(liquid::object!({"name": "World"}));
+    /// let mut output = Vec::new();
+    /// futures_executor::block_on(template.render_async(&globals, &mut output)).unwrap();
+    /// assert_eq!(output, b"Hello World");
+    /// ```
+    #[cfg(feature = "async")]
+    pub async fn render_async<W>(
+        &self,
+        globals: &runtime::AsyncGlobals,
+        writer: &mut W,
+    ) -> Result<()>
+    where
+        W: futures_util::io::AsyncWrite + Unpin + ?Sized,
+    {
+        use futures_util::io::AsyncWriteExt;
+        use liquid_core::ValueView;
+
+        let partials = match self.async_partials {
+            Some(ref async_partials) => Some(async_partials.load(&self.template).await?),
+            None => self.partials.clone(),
+        };
+        let partials = partials.as_deref();
+
+        let roots: Vec<_> = self
+            .collect_globals(partials)
+            .iter()
+            .filter_map(|reference| reference.path().first())
+            .map(|root| root.to_kstr().into_owned())
+            .collect();
+        let loaded = globals
+            .load(roots.iter().map(liquid_core::model::KString::as_str))
+            .await?;
+
+        let chunks = crate::stream::SendChunks::new(self, globals.values(), &loaded, partials);
+        for chunk in chunks {
+            writer
+                .write_all(chunk?.as_bytes())
+                .await
+                .replace("Failed to render")?;
+        }
+        writer.flush().await.replace("Failed to render")?;
+        Ok(())
+    }
+
+    /// Renders this template as a page, with `front_matter` added to the `page` global, then
+    /// the chain of layouts it names, like Jekyll.
+    ///
+    /// The layouts are the parser's partials, compiled with
+    /// [`LayoutCompiler`](crate::LayoutCompiler) so they can name layouts in
+    /// turn.
+    #[cfg(feature = "jekyll")]
+    pub fn render_with_layouts(
+        &self,
+        front_matter: &crate::Object,
+        globals: &dyn crate::ObjectView,
+    ) -> Result<String> {
+        let registers = Registers::default();
+        self.with_runtime(globals, &registers, self.partials(), |runtime| {
+            liquid_lib::jekyll::render_page(front_matter, &self.template, runtime)
+        })
+    }
+
+    /// Renders this template in its layout, like Shopify themes.
+    ///
+    /// The layouts are the parser's partials, `layout/theme.liquid` unless the template picks
+    /// another with `{% layout %}`.  Content for the layout, like scripts for
+    /// `content_for_header`, can be seeded in the `ContentForRegister` of `registers`.
+    #[cfg(feature = "shopify")]
+    pub fn render_in_layout(
+        &self,
+        globals: &dyn crate::ObjectView,
+        registers: &Registers,
+    ) -> Result<String> {
+        self.with_runtime(globals, registers, self.partials(), |runtime| {
+            liquid_lib::shopify::render_in_layout(&self.template, runtime)
+        })
+    }
+
+    pub(crate) fn partials(&self) -> Option<&dyn PartialStore> {
+        self.partials
+            .as_ref()
+            .map(|partials| partials.as_ref() as &dyn PartialStore)
+    }
+
+    pub(crate) fn with_runtime<R>(
+        &self,
+        globals: &dyn crate::ObjectView,
+        registers: &Registers,
+        partials: Option<&dyn PartialStore>,
+        render: impl FnOnce(&dyn runtime::Runtime) -> Result<R>,
+    ) -> Result<R> {
+        self.prepare(registers);
+        let runtime = runtime::RuntimeBuilder::new()
+            .set_globals(globals)
+            .set_registers(registers);
+        let runtime = match partials {
+            Some(partials) => runtime.set_partials(partials),
             None => runtime,
         };
         let runtime = runtime.build();
-        self.template.render_to(writer, &runtime)
+        render(&runtime)
+    }
+
+    /// Sets the parser's `integer_overflow` option and the template's autoescaping.
+    pub(crate) fn prepare(&self, registers: &Registers) {
+        *registers.get_mut::<runtime::IntegerOverflow>() = self.integer_overflow;
+        let mut autoescape = registers.get_mut::<runtime::AutoEscapeRegister>();
+        match self.autoescape {
+            Some(ref escaper) => autoescape.set(escaper.clone()),
+            None => autoescape.reset(),
+        };
     }
 }
 
 #[cfg(debug_assertions)]
-fn convert_buffer(buffer: Vec<u8>) -> String {
+pub(crate) fn convert_buffer(buffer: Vec<u8>) -> String {
     String::from_utf8(buffer)
         .expect("render can only write UTF-8 because all inputs and processing preserve utf-8")
 }
 
 #[cfg(not(debug_assertions))]
-fn convert_buffer(buffer: Vec<u8>) -> String {
+pub(crate) fn convert_buffer(buffer: Vec<u8>) -> String {
     unsafe { String::from_utf8_unchecked(buffer) }
 }
@@ -0,0 +1,179 @@
+#![cfg(feature = "async")]
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+
+use futures_executor::block_on;
+use liquid::model::Value;
+use liquid::partials::AsyncPartialSource;
+use liquid::runtime::{AsyncGlobals, AsyncValue};
+use liquid::Error;
+
+type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+/// Stands in for partials stored in a database.
+#[derive(Debug, Default)]
+struct Database {
+    partials: HashMap<&'static str, &'static str>,
+    requested: Arc<Mutex<Vec<String>>>,
+}
+
+impl AsyncPartialSource for Database {
+    fn try_get<'a>(&'a self, name: &'a str) -> BoxFuture<'a, Result<Option<Cow<'a, str>>, Error>> {
+        Box::pin(async move {
+            self.requested.lock().unwrap().push(name.to_owned());
+            if name == "offline" {
+                return Err(Error::with_msg("Connection refused"));
+            }
+            Ok(self.partials.get(name).map(|text| Cow::Borrowed(*text)))
+        })
+    }
+}
+
+/// Stands in for a global queried from a database.
+#[derive(Debug)]
+struct Query {
+    value: &'static str,
+    loaded: Arc<Mutex<usize>>,
+}
+
+impl AsyncValue for Query {
+    fn load(&self) -> BoxFuture<'_, Result<Value, Error>> {
+        Box::pin(async move {
+            *self.loaded.lock().unwrap() += 1;
+            Ok(Value::scalar(self.value))
+        })
+    }
+}
+
+fn parser(requested: Arc<Mutex<Vec<String>>>) -> liquid::Parser {
+    let mut partials = HashMap::new();
+    partials.insert("header", "<h1>{{ title }}</h1>{% include 'nav' %}");
+    partials.insert("nav", "<nav>{{ user }}</nav>");
+    partials.insert("footer", "<footer></footer>");
+    liquid::ParserBuilder::with_stdlib()
+        .async_partials(Database {
+            partials,
+            requested,
+        })
+        .build()
+        .unwrap()
+}
+
+fn render(template: &liquid::Template, globals: &AsyncGlobals) -> Result<String, Error> {
+    let mut output = Vec::new();
+    block_on(template.render_async(globals, &mut output))?;
+    Ok(String::from_utf8(output).unwrap())
+}
+
+#[test]
+fn render_with_async_partials() {
+    let requested = Arc::new(Mutex::new(Vec::new()));
+    let template = parser(requested.clone())
+        .parse("{% include 'header' %}<main></main>")
+        .unwrap();
+    let globals = AsyncGlobals::from(liquid::object!({"title": "Home", "user": "Jane"}));
+    assert_eq!(
+        render(&template, &globals).unwrap(),
+        "<h1>Home</h1><nav>Jane</nav><main></main>"
+    );
+    assert_eq!(*requested.lock().unwrap(), vec!["header", "nav"]);
+
+    // Partials are only loaded once.
+    render(&template, &globals).unwrap();
+    assert_eq!(*requested.lock().unwrap(), vec!["header", "nav"]);
+}
+
+#[test]
+fn render_with_async_globals() {
+    let template = parser(Default::default())
+        .parse("{% include 'nav' %}")
+        .unwrap();
+    let loaded = Arc::new(Mutex::new(0));
+    let mut globals = AsyncGlobals::new();
+    globals.insert_async(
+        "user",
+        Query {
+            value: "Jane",
+            loaded: loaded.clone(),
+        },
+    );
+    globals.insert_async(
+        "orders",
+        Query {
+            value: "none",
+            loaded: loaded.clone(),
+        },
+    );
+    assert_eq!(render(&template, &globals).unwrap(), "<nav>Jane</nav>");
+    // `orders` isn't read by the template.
+    assert_eq!(*loaded.lock().unwrap(), 1);
+}
+
+#[test]
+fn async_partial_errors() {
+    let parser = parser(Default::default());
+    let globals = AsyncGlobals::new();
+
+    let template = parser.parse("{% include 'offline' %}").unwrap();
+    let err = render(&template, &globals).unwrap_err().to_string();
+    assert!(err.contains("Connection refused"), "{}", err);
+
+    let template = parser.parse("{% include 'missing' %}").unwrap();
+    let err = render(&template, &globals).unwrap_err().to_string();
+    assert!(err.contains("Unknown partial-template"), "{}", err);
+}
+
+#[test]
+fn render_async_is_send() {
+    fn assert_send<T: Send>(_: T) {}
+
+    let template = parser(Default::default()).parse("").unwrap();
+    let globals = AsyncGlobals::new();
+    let mut output = Vec::new();
+    assert_send(template.render_async(&globals, &mut output));
+}
+
+/// Records each write, as a stand-in for a socket.
+#[derive(Default)]
+struct Writes(Vec<String>);
+
+impl futures_util::io::AsyncWrite for Writes {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        _cx: &mut std::task::Context<'_>,
+        buf: &[u8],
+    ) -> std::task::Poll<std::io::Result<usize>> {
+        self.0.push(String::from_utf8(buf.to_vec()).unwrap());
+        std::task::Poll::Ready(Ok(buf.len()))
+    }
+
+    fn poll_flush(
+        self: Pin<&mut Self>,
+        _cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<std::io::Result<()>> {
+        std::task::Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(
+        self: Pin<&mut Self>,
+        _cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<std::io::Result<()>> {
+        std::task::Poll::Ready(Ok(()))
+    }
+}
+
+#[test]
+fn render_async_writes_each_element() {
+    let template = parser(Default::default())
+        .parse("<p>{{ title }}</p>{% for i in (1..3) %}{{ i }}{% endfor %}")
+        .unwrap();
+    let globals = AsyncGlobals::from(liquid::object!({"title": "Home"}));
+    let mut writes = Writes::default();
+    block_on(template.render_async(&globals, &mut writes)).unwrap();
+    // A top-level element is written once it rendered, before the next one is rendered.
+    assert_eq!(writes.0, vec!["<p>", "Home", "</p>", "123"]);
+}
@@ -0,0 +1,193 @@
+use std::borrow::Cow;
+
+use liquid::runtime::{Escape, Escaper};
+
+fn parser() -> liquid::Parser {
+    liquid::ParserBuilder::with_stdlib()
+        .autoescape(Escape::Html)
+        .build()
+        .unwrap()
+}
+
+fn render(text: &str, globals: &liquid::Object) -> String {
+    parser().parse(text).unwrap().render(globals).unwrap()
+}
+
+#[test]
+fn test_output_is_escaped() {
+    let globals = liquid::object!({ "name": "<b>Tom & Jerry</b>", "count": 5 });
+    assert_eq!(
+        render("<p>{{ name }} {{ count }}</p>", &globals),
+        "<p>&lt;b&gt;Tom &amp; Jerry&lt;/b&gt; 5</p>"
+    );
+}
+
+#[test]
+fn test_safe_strings_are_not_escaped() {
+    let globals = liquid::object!({ "body": "<em>hi</em>", "name": "<b>" });
+    assert_eq!(
+        render(
+            "{{ body | safe }}{{ body | raw }}{{ name | escape }}{{ body | safe | default: 'x' }}",
+            &globals
+        ),
+        "<em>hi</em><em>hi</em>&lt;b&gt;<em>hi</em>"
+    );
+    // Changing a safe string makes it unsafe again.
+    assert_eq!(
+        render("{{ body | safe | append: '<br>' }}", &globals),
+        "&lt;em&gt;hi&lt;/em&gt;&lt;br&gt;"
+    );
+}
+
+#[test]
+fn test_capture_is_not_escaped_twice() {
+    let globals = liquid::object!({ "name": "<b>" });
+    assert_eq!(
+        render(
+            "{% capture greeting %}<i>{{ name }}</i>{% endcapture %}{{ greeting }}",
+            &globals
+        ),
+        "<i>&lt;b&gt;</i>"
+    );
+}
+
+#[test]
+fn test_escaping_strategies() {
+    let globals = liquid::object!({ "q": "a b&c", "msg": "it's" });
+    assert_eq!(
+        render(
+            "<a href=\"?q={{ q | escape: 'url' }}\" onclick=\"alert('{{ msg | escape: 'js' }}')\">",
+            &globals
+        ),
+        "<a href=\"?q=a%20b%26c\" onclick=\"alert('it\\x27s')\">"
+    );
+}
+
+#[test]
+fn test_other_strategies_are_escaped_again() {
+    let globals = liquid::object!({ "x": "</script><img src=x onerror=alert(1)>" });
+    assert_eq!(
+        render("{{ x | escape: 'json' }}|{{ x | escape: 'csv' }}", &globals),
+        "&lt;/script&gt;&lt;img src=x onerror=alert(1)&gt;|&lt;/script&gt;&lt;img src=x onerror=alert(1)&gt;"
+    );
+
+    let template = liquid::ParserBuilder::with_stdlib()
+        .autoescape(Escape::Json)
+        .build()
+        .unwrap()
+        .parse("\"{{ x | escape: 'json' }}\"")
+        .unwrap();
+    let globals = liquid::object!({ "x": "a\"b\n" });
+    assert_eq!(template.render(&globals).unwrap(), "\"a\\\"b\\n\"");
+}
+
+#[test]
+fn test_html_escape_is_escaped_again_by_other_escapers() {
+    let template = liquid::ParserBuilder::with_stdlib()
+        .autoescape(Escape::Json)
+        .build()
+        .unwrap()
+        .parse("\"{{ x | escape }}\"")
+        .unwrap();
+    let globals = liquid::object!({ "x": "a\"b\n" });
+    assert_eq!(template.render(&globals).unwrap(), "\"a&quot;b\\n\"");
+}
+
+#[test]
+fn test_autoescape_per_template() {
+    let globals = liquid::object!({ "name": "<b>" });
+    let template = parser().parse("{{ name }}").unwrap().without_autoescape();
+    assert_eq!(template.render(&globals).unwrap(), "<b>");
+
+    let template = liquid::ParserBuilder::with_stdlib()
+        .build()
+        .unwrap()
+        .parse("{{ name }}")
+        .unwrap();
+    assert!(template.autoescape().is_none());
+    let template = template.with_autoescape(Escape::Url);
+    assert_eq!(template.render(&globals).unwrap(), "%3Cb%3E");
+}
+
+#[test]
+fn test_autoescape_by_extension() {
+    let dir = std::env::temp_dir().join(format!("liquid-autoescape-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let html = dir.join("page.html");
+    let text = dir.join("page.txt");
+    std::fs::write(&html, "{{ name }}").unwrap();
+    std::fs::write(&text, "{{ name }}").unwrap();
+
+    let parser = liquid::ParserBuilder::with_stdlib()
+        .autoescape_extension("html", Escape::Html)
+        .build()
+        .unwrap();
+    let globals = liquid::object!({ "name": "<b>" });
+    let html = parser.parse_file(&html).unwrap().render(&globals).unwrap();
+    let text = parser.parse_file(&text).unwrap().render(&globals).unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    assert_eq!(html, "&lt;b&gt;");
+    assert_eq!(text, "<b>");
+}
+
+#[test]
+fn test_output_formats() {
+    let globals = liquid::object!({ "name": "Ann \"A\", Jr", "path": "my file's.txt" });
+    let render = |escape: Escape, text: &str| {
+        liquid::ParserBuilder::with_stdlib()
+            .autoescape(escape)
+            .build()
+            .unwrap()
+            .parse(text)
+            .unwrap()
+            .render(&globals)
+            .unwrap()
+    };
+    assert_eq!(
+        render(Escape::Json, "{\"name\": \"{{ name }}\"}"),
+        "{\"name\": \"Ann \\\"A\\\", Jr\"}"
+    );
+    assert_eq!(
+        render(Escape::Csv, "{{ name }},{{ path }}"),
+        "\"Ann \"\"A\"\", Jr\",my file's.txt"
+    );
+    assert_eq!(
+        render(Escape::Shell, "cat {{ path }}"),
+        "cat 'my file'\\''s.txt'"
+    );
+}
+
+#[derive(Debug)]
+struct Latex;
+
+impl Escaper for Latex {
+    fn escape<'s>(&self, text: &'s str) -> Cow<'s, str> {
+        if !text.contains(&['%', '$', '&', '#', '_'][..]) {
+            return Cow::Borrowed(text);
+        }
+        let mut escaped = String::with_capacity(text.len());
+        for c in text.chars() {
+            if matches!(c, '%' | '$' | '&' | '#' | '_') {
+                escaped.push('\\');
+            }
+            escaped.push(c);
+        }
+        Cow::Owned(escaped)
+    }
+}
+
+#[test]
+fn test_custom_escaper() {
+    let template = liquid::ParserBuilder::with_stdlib()
+        .autoescape(Latex)
+        .build()
+        .unwrap()
+        .parse("\\textbf{ {{- discount -}} } {{ '\\%' | raw }}")
+        .unwrap();
+    let globals = liquid::object!({ "discount": "50% off_now" });
+    assert_eq!(
+        template.render(&globals).unwrap(),
+        "\\textbf{50\\% off\\_now} \\%"
+    );
+}
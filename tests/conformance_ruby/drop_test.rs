@@ -1,7 +1,83 @@
+use liquid::model::{DropCache, Value};
+
+#[derive(Debug, Default, liquid::ObjectView, liquid::ValueView)]
+#[liquid(methods)]
+struct TextDrop {
+    #[liquid(cache)]
+    cache: DropCache,
+}
+
+#[liquid::object_methods]
+impl TextDrop {
+    #[liquid(method)]
+    fn array(&self) -> Vec<&'static str> {
+        vec!["text1", "text2"]
+    }
+
+    #[liquid(method)]
+    fn text(&self) -> &'static str {
+        "text1"
+    }
+}
+
+thread_local! {
+    static PRODUCT_DROP_CALLS: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+}
+
+#[derive(Debug, Default, liquid::ObjectView, liquid::ValueView)]
+#[liquid(methods)]
+struct CatchallDrop {
+    #[liquid(cache)]
+    cache: DropCache,
+}
+
+#[liquid::object_methods]
+impl CatchallDrop {
+    #[liquid(method_missing)]
+    fn liquid_method_missing(&self, method: &str) -> Option<String> {
+        Some(format!("catchall_method: {}", method))
+    }
+}
+
+#[derive(Debug, Default, liquid::ObjectView, liquid::ValueView)]
+#[liquid(methods)]
+struct ProductDrop {
+    // Modified: a field rather than a method, since method results are plain values.
+    catchall: CatchallDrop,
+    #[liquid(cache)]
+    cache: DropCache,
+}
+
+#[liquid::object_methods]
+impl ProductDrop {
+    #[liquid(method)]
+    fn texts(&self) -> TextDrop {
+        PRODUCT_DROP_CALLS.with(|calls| calls.set(calls.get() + 1));
+        TextDrop::default()
+    }
+
+    #[allow(dead_code)]
+    fn callmenot(&self) -> &'static str {
+        "protected"
+    }
+}
+
+#[derive(Debug, Default, liquid::ObjectView, liquid::ValueView)]
+struct Assigns {
+    product: ProductDrop,
+}
+
+#[derive(Debug, liquid::ObjectView, liquid::ValueView)]
+struct ValueAssigns {
+    product: ProductDrop,
+    value: Value,
+}
+
 #[test]
-#[should_panic]
 fn test_product_drop() {
-    panic!("Implementation specific: drops");
+    assert_template_result!("  ", "  ", Assigns::default());
+    // Nothing is computed until the template reads it.
+    assert_eq!(PRODUCT_DROP_CALLS.with(|calls| calls.get()), 0);
 }
 
 #[test]
@@ -23,9 +99,17 @@ fn test_rendering_doesnt_raise_on_escaped_tainted_attr() {
 }
 
 #[test]
-#[should_panic]
 fn test_drop_does_only_respond_to_whitelisted_methods() {
-    panic!("Implementation specific: drops");
+    // Modified due to strict_variables: true
+    assert_render_error!("{{ product.inspect }}", Assigns::default());
+    assert_render_error!("{{ product.pretty_inspect }}", Assigns::default());
+    assert_render_error!("{{ product.whatever }}", Assigns::default());
+    assert_render_error!(r#"{{ product | map: "inspect" }}"#, Assigns::default());
+    assert_render_error!(
+        r#"{{ product | map: "pretty_inspect" }}"#,
+        Assigns::default()
+    );
+    assert_render_error!(r#"{{ product | map: "whatever" }}"#, Assigns::default());
 }
 
 #[test]
@@ -35,27 +119,35 @@ fn test_drops_respond_to_to_liquid() {
 }
 
 #[test]
-#[should_panic]
 fn test_text_drop() {
-    panic!("Implementation specific: drops");
+    assert_template_result!(" text1 ", " {{ product.texts.text }} ", Assigns::default());
 }
 
 #[test]
-#[should_panic]
 fn test_catchall_unknown_method() {
-    panic!("Implementation specific: drops");
+    assert_template_result!(
+        " catchall_method: unknown ",
+        " {{ product.catchall.unknown }} ",
+        Assigns::default()
+    );
 }
 
 #[test]
-#[should_panic]
 fn test_catchall_integer_argument_drop() {
-    panic!("Implementation specific: drops");
+    assert_template_result!(
+        " catchall_method: 8 ",
+        " {{ product.catchall[8] }} ",
+        Assigns::default()
+    );
 }
 
 #[test]
-#[should_panic]
 fn test_text_array_drop() {
-    panic!("Implementation specific: drops");
+    assert_template_result!(
+        "  text1  text2  ",
+        " {% for text in product.texts.array %} {{text}} {% endfor %} ",
+        Assigns::default()
+    );
 }
 
 #[test]
@@ -71,15 +163,27 @@ fn test_nested_runtime_drop() {
 }
 
 #[test]
-#[should_panic]
 fn test_protected() {
-    panic!("Implementation specific: drops");
+    // Modified due to strict_variables: true
+    assert_render_error!(" {{ product.callmenot }} ", Assigns::default());
 }
 
 #[test]
-#[should_panic]
 fn test_object_methods_not_allowed() {
-    panic!("Implementation specific: drops");
+    // Modified due to strict_variables: true
+    for method in &[
+        "dup",
+        "clone",
+        "singleton_class",
+        "eval",
+        "class_eval",
+        "inspect",
+    ] {
+        assert_render_error!(
+            format!(" {{{{ product.{} }}}} ", method),
+            Assigns::default()
+        );
+    }
 }
 
 #[test]
@@ -137,15 +241,23 @@ fn test_some_enumerable_methods_still_get_invoked() {
 }
 
 #[test]
-#[should_panic]
 fn test_empty_string_value_access() {
-    panic!("Implementation specific: drops");
+    // Modified due to strict_variables: true
+    let assigns = ValueAssigns {
+        product: ProductDrop::default(),
+        value: Value::scalar(""),
+    };
+    assert_render_error!("{{ product[value] }}", assigns);
 }
 
 #[test]
-#[should_panic]
 fn test_nil_value_access() {
-    panic!("Implementation specific: drops");
+    // Modified due to strict_variables: true
+    let assigns = ValueAssigns {
+        product: ProductDrop::default(),
+        value: Value::Nil,
+    };
+    assert_render_error!("{{ product[value] }}", assigns);
 }
 
 #[test]
@@ -1128,6 +1128,48 @@ fn test_at_least() {
     // Implementation specific: use of drops
 }
 
+#[test]
+fn test_sum_with_all_numbers() {
+    let assigns = o!({ "input": [1, 2] });
+    assert_template_result!("3", "{{ input | sum }}", assigns);
+    assert_render_error!(r#"{{ input | sum: "quantity" }}"#, assigns);
+}
+
+#[test]
+fn test_sum_with_numeric_strings() {
+    let assigns = o!({ "input": [1, 2, "3", "4"] });
+    assert_template_result!("10", "{{ input | sum }}", assigns);
+}
+
+#[test]
+fn test_sum_with_nested_arrays() {
+    let assigns = o!({ "input": [1, [2, [3, 4]]] });
+    assert_template_result!("10", "{{ input | sum }}", assigns);
+}
+
+#[test]
+fn test_sum_with_indexable_map_values() {
+    let assigns =
+        o!({ "input": [{ "quantity": 1 }, { "quantity": 2, "weight": 3 }, { "weight": 4 }] });
+    assert_template_result!("0", "{{ input | sum }}", assigns);
+    assert_template_result!("3", r#"{{ input | sum: "quantity" }}"#, assigns);
+    assert_template_result!("7", r#"{{ input | sum: "weight" }}"#, assigns);
+    assert_template_result!("0", r#"{{ input | sum: "subtotal" }}"#, assigns);
+}
+
+#[test]
+fn test_sum_with_floats() {
+    // Modified: floats don't sum exactly like Ruby's `BigDecimal`, so `[0.1, 0.2, 0.3]` isn't `0.6`
+    let assigns = o!({ "input": [0.5, 0.25] });
+    assert_template_result!("0.75", "{{ input | sum }}", assigns);
+}
+
+#[test]
+fn test_sum_with_non_numeric_strings() {
+    let assigns = o!({ "input": [1, 2, "foo", "bar"] });
+    assert_template_result!("3", "{{ input | sum }}", assigns);
+}
+
 #[test]
 fn test_append() {
     let assigns = o!({ "a": "bc", "b": "d" });
@@ -252,6 +252,51 @@ pub fn test_derive_parameterless_filter_reflection() {
     assert!(filter.keyword_parameters().is_empty());
 }
 
+#[test]
+pub fn test_derive_pure_filter() {
+    use std::sync::atomic::Ordering;
+
+    let parser = ParserBuilder::new()
+        .filter(derive_macros_test_filters::TestPureFilterParser)
+        .build()
+        .unwrap();
+    let calls = || derive_macros_test_filters::PURE_CALLS.load(Ordering::SeqCst);
+
+    // Filtering a literal is done once, when parsing.
+    let template = parser.parse("{{ 'a' | pure }}{{ x | pure }}").unwrap();
+    let parsed = calls();
+    let globals = liquid::object!({"x": "b"});
+    assert_eq!(template.render(&globals).unwrap(), "<a><b>");
+    assert_eq!(template.render(&globals).unwrap(), "<a><b>");
+    assert_eq!(calls(), parsed + 2);
+}
+
+#[test]
+pub fn test_derive_pure_filter_reflection() {
+    assert!(derive_macros_test_filters::TestPureFilterParser.is_pure());
+    assert!(!derive_macros_test_filters::TestParameterlessFilterParser.is_pure());
+}
+
+#[test]
+pub fn test_derive_borrowing_filter() {
+    use liquid_core::{Filter, ValueCow};
+
+    let parser = ParserBuilder::new()
+        .filter(derive_macros_test_filters::TestBorrowsFilterParser)
+        .build()
+        .unwrap();
+    let template = parser.parse("{{ x | borrows }}{{ y | borrows }}").unwrap();
+    let globals = liquid::object!({"x": "a", "y": nil});
+    assert_eq!(template.render(&globals).unwrap(), "anil");
+
+    let filter: &dyn Filter = &derive_macros_test_filters::TestBorrowsFilter;
+    let runtime = liquid_core::runtime::RuntimeBuilder::new().build();
+    let input = liquid_core::value!(["a"]);
+    let borrowed = filter.evaluate_cow(&input, &runtime).unwrap();
+    assert!(matches!(borrowed, ValueCow::Borrowed(_)));
+    assert_eq!(filter.evaluate(&input, &runtime).unwrap(), input);
+}
+
 #[test]
 pub fn test_derive_stateful_filter() {
     let globals = liquid::Object::new();
@@ -0,0 +1,31 @@
+use liquid_core::Result;
+use liquid_core::Runtime;
+use liquid_core::{Display_filter, FilterReflection, ParseFilter};
+use liquid_core::{Value, ValueCow, ValueView};
+
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+    name = "borrows",
+    description = "Filter returning its input.",
+    parsed(TestBorrowsFilter)
+)]
+pub struct TestBorrowsFilterParser;
+
+#[derive(Debug, Default, Display_filter)]
+#[name = "borrows"]
+#[filter(borrows)]
+pub struct TestBorrowsFilter;
+
+impl TestBorrowsFilter {
+    fn evaluate_cow<'v>(
+        &'v self,
+        input: &'v dyn ValueView,
+        _runtime: &'v dyn Runtime,
+    ) -> Result<ValueCow<'v>> {
+        if input.is_nil() {
+            Ok(ValueCow::Owned(Value::scalar("nil")))
+        } else {
+            Ok(ValueCow::Borrowed(input))
+        }
+    }
+}
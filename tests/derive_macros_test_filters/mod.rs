@@ -1,11 +1,15 @@
+mod borrows;
 mod keyword;
 mod mixed;
 mod parameterless;
 mod positional;
+mod pure;
 mod stateful;
 
+pub use self::borrows::{TestBorrowsFilter, TestBorrowsFilterParser};
 pub use self::keyword::TestKeywordFilterParser;
 pub use self::mixed::TestMixedFilterParser;
 pub use self::parameterless::TestParameterlessFilterParser;
 pub use self::positional::TestPositionalFilterParser;
+pub use self::pure::{TestPureFilterParser, PURE_CALLS};
 pub use self::stateful::TestStatefulFilterParser;
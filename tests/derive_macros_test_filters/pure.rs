@@ -0,0 +1,29 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use liquid_core::Result;
+use liquid_core::Runtime;
+use liquid_core::{Display_filter, Filter, FilterReflection, ParseFilter};
+use liquid_core::{Value, ValueView};
+
+/// How many times `pure` was evaluated.
+pub static PURE_CALLS: AtomicUsize = AtomicUsize::new(0);
+
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+    name = "pure",
+    description = "Filter depending only on its input.",
+    pure,
+    parsed(TestPureFilter)
+)]
+pub struct TestPureFilterParser;
+
+#[derive(Debug, Default, Display_filter)]
+#[name = "pure"]
+pub struct TestPureFilter;
+
+impl Filter for TestPureFilter {
+    fn evaluate(&self, input: &dyn ValueView, _runtime: &dyn Runtime) -> Result<Value> {
+        PURE_CALLS.fetch_add(1, Ordering::SeqCst);
+        Ok(Value::scalar(format!("<{}>", input.to_kstr())))
+    }
+}
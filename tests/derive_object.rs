@@ -118,3 +118,408 @@ fn test_borrow_object() {
     assert_eq!(uut.contains_key("s"), true);
     assert!(uut.get("s").is_some());
 }
+
+#[derive(ObjectView, ValueView, Debug, Default)]
+#[liquid(methods)]
+struct TestDrop {
+    name: &'static str,
+    #[liquid(cache)]
+    cache: liquid::model::DropCache,
+}
+
+thread_local! {
+    static GREETING_CALLS: std::cell::Cell<i64> = const { std::cell::Cell::new(0) };
+}
+
+#[liquid::object_methods]
+impl TestDrop {
+    #[liquid(method)]
+    fn greeting(&self) -> String {
+        GREETING_CALLS.with(|calls| calls.set(calls.get() + 1));
+        format!("Hello {}", self.name)
+    }
+}
+
+#[test]
+fn test_drop_object() {
+    let uut = TestDrop {
+        name: "world",
+        ..Default::default()
+    };
+
+    assert_eq!(uut.size(), 2i64);
+    let keys: Vec<_> = uut.keys().map(|k| k.into_owned()).collect();
+    assert_eq!(keys, ["name", "greeting"]);
+    assert_eq!(uut.contains_key("greeting"), true);
+    assert_eq!(uut.contains_key("cache"), false);
+    assert_eq!(
+        uut.get("greeting").unwrap().to_value(),
+        liquid::model::value!("Hello world")
+    );
+    assert_eq!(uut.values().count(), 2);
+    assert_eq!(
+        uut.to_value(),
+        liquid::model::value!({"name": "world", "greeting": "Hello world"})
+    );
+}
+
+#[derive(ObjectView, ValueView, Debug)]
+struct DropGlobals<'d> {
+    drop: &'d TestDrop,
+    drops: Vec<&'d TestDrop>,
+}
+
+fn render_drop(template: &str, drop: &TestDrop) -> String {
+    let template = liquid::ParserBuilder::with_stdlib()
+        .build()
+        .unwrap()
+        .parse(template)
+        .unwrap();
+    let globals = DropGlobals {
+        drop,
+        drops: vec![drop, drop],
+    };
+    template.render(&globals).unwrap()
+}
+
+#[test]
+fn test_drop_render() {
+    let calls = GREETING_CALLS.with(|calls| calls.get());
+    let drop = TestDrop {
+        name: "world",
+        ..Default::default()
+    };
+    assert_eq!(
+        render_drop("{{ drop.greeting }}, {{ drop.greeting | upcase }}", &drop),
+        "Hello world, HELLO WORLD"
+    );
+    assert_eq!(GREETING_CALLS.with(|calls| calls.get()), calls + 1);
+
+    assert_eq!(
+        render_drop("{% assign d = drop %}{{ d.greeting }}", &drop),
+        "Hello world"
+    );
+    assert_eq!(
+        render_drop("{{ drops | map: 'greeting' | join: ', ' }}", &drop),
+        "Hello world, Hello world"
+    );
+    assert_eq!(
+        render_drop(
+            "{{ drops | where: 'greeting', 'Hello world' | size }}",
+            &drop
+        ),
+        "2"
+    );
+    assert_eq!(
+        render_drop(
+            "{% for kv in drop %}{{ kv[0] }}={{ kv[1] }};{% endfor %}",
+            &drop
+        ),
+        "name=world;greeting=Hello world;"
+    );
+    // Once per render.
+    assert_eq!(GREETING_CALLS.with(|calls| calls.get()), calls + 5);
+}
+
+#[test]
+fn test_drop_methods_are_lazy() {
+    fn assert_sync<T: Sync>(_: &T) {}
+
+    let drop = TestDrop {
+        name: "world",
+        ..Default::default()
+    };
+    assert_sync(&drop);
+
+    let calls = GREETING_CALLS.with(|calls| calls.get());
+    assert_eq!(drop.values().next().unwrap().to_kstr(), "world");
+    assert_eq!(drop.iter().next().unwrap().0, "name");
+    assert_eq!(GREETING_CALLS.with(|calls| calls.get()), calls);
+    assert_eq!(drop.values().count(), 2);
+    assert_eq!(GREETING_CALLS.with(|calls| calls.get()), calls + 1);
+}
+
+#[derive(ObjectView, ValueView, Debug, Default)]
+#[liquid(methods)]
+struct SettingsDrop {
+    theme: &'static str,
+    #[liquid(cache)]
+    cache: liquid::model::DropCache,
+}
+
+#[liquid::object_methods]
+impl SettingsDrop {
+    #[liquid(method_missing)]
+    fn setting(&self, name: &str) -> Option<String> {
+        name.strip_prefix("color_")
+            .map(|color| color.to_uppercase())
+    }
+}
+
+#[test]
+fn test_drop_method_missing() {
+    let settings = SettingsDrop {
+        theme: "dark",
+        ..Default::default()
+    };
+    assert_eq!(settings.keys().count(), 1);
+    assert!(settings.get("color_red").is_none());
+
+    let template = liquid::ParserBuilder::with_stdlib()
+        .build()
+        .unwrap()
+        .parse("{{ settings.theme }} {{ settings.color_red }} {% if settings.color_blue %}blue{% endif %}")
+        .unwrap();
+    let globals = liquid::object!({ "settings": settings.to_value() });
+    // Converting to a `Value` only keeps the listed keys.
+    assert!(template.render(&globals).is_err());
+
+    #[derive(ObjectView, ValueView, Debug)]
+    struct Globals<'s> {
+        settings: &'s SettingsDrop,
+    }
+    let globals = Globals {
+        settings: &settings,
+    };
+    assert_eq!(template.render(&globals).unwrap(), "dark RED blue");
+
+    let template = liquid::ParserBuilder::with_stdlib()
+        .build()
+        .unwrap()
+        .parse("{{ settings.font }}")
+        .unwrap();
+    assert!(template.render(&globals).is_err());
+}
+
+#[derive(ObjectView, ValueView, Debug, Default)]
+struct TestAddress {
+    city: &'static str,
+    zip_code: &'static str,
+}
+
+fn cents(amount: &i64) -> String {
+    format!("{}.{:02}", amount / 100, amount % 100)
+}
+
+#[derive(ObjectView, ValueView, Debug, Default)]
+#[liquid(rename_all = "camelCase")]
+struct TestCustomer {
+    first_name: &'static str,
+    #[liquid(rename = "type")]
+    kind: &'static str,
+    r#ref: i64,
+    #[liquid(skip)]
+    #[allow(dead_code)]
+    password: &'static str,
+    #[liquid(flatten)]
+    address: TestAddress,
+    #[liquid(with = "cents")]
+    total_spent: i64,
+    #[liquid(cache)]
+    cache: liquid::model::DropCache,
+}
+
+#[test]
+fn test_field_attributes() {
+    let uut = TestCustomer {
+        first_name: "Ada",
+        kind: "vip",
+        r#ref: 7,
+        password: "hunter2",
+        address: TestAddress {
+            city: "Paris",
+            zip_code: "75001",
+        },
+        total_spent: 12345,
+        ..Default::default()
+    };
+
+    let keys: Vec<_> = uut.keys().map(|k| k.into_owned()).collect();
+    assert_eq!(
+        keys,
+        ["firstName", "type", "ref", "totalSpent", "city", "zip_code"]
+    );
+    assert_eq!(uut.size(), 6);
+    assert_eq!(uut.values().count(), 6);
+    assert_eq!(uut.contains_key("password"), false);
+    assert_eq!(uut.contains_key("first_name"), false);
+    assert_eq!(uut.contains_key("city"), true);
+    assert_eq!(uut.get("zip_code").unwrap().to_kstr(), "75001");
+    assert_eq!(uut.get("totalSpent").unwrap().to_kstr(), "123.45");
+    assert_eq!(
+        uut.to_value(),
+        liquid::model::value!({
+            "firstName": "Ada",
+            "type": "vip",
+            "ref": 7,
+            "totalSpent": "123.45",
+            "city": "Paris",
+            "zip_code": "75001",
+        })
+    );
+
+    let template = liquid::ParserBuilder::with_stdlib()
+        .build()
+        .unwrap()
+        .parse("{{ customer.firstName }} ({{ customer.type }}) from {{ customer.city }}: {{ customer.totalSpent }}")
+        .unwrap();
+    #[derive(ObjectView, ValueView, Debug)]
+    struct Globals<'c> {
+        customer: &'c TestCustomer,
+    }
+    let globals = Globals { customer: &uut };
+    assert_eq!(
+        template.render(&globals).unwrap(),
+        "Ada (vip) from Paris: 123.45"
+    );
+}
+
+#[derive(ObjectView, ValueView, Debug, Default)]
+#[liquid(methods, rename_all = "SCREAMING-KEBAB-CASE")]
+struct TestKebabDrop {
+    #[liquid(with = "cents")]
+    unit_price: i64,
+    #[liquid(cache)]
+    cache: liquid::model::DropCache,
+}
+
+#[liquid::object_methods]
+impl TestKebabDrop {
+    #[liquid(method)]
+    fn double_price(&self) -> String {
+        cents(&(self.unit_price * 2))
+    }
+}
+
+#[test]
+fn test_computed_fields_with_methods() {
+    let uut = TestKebabDrop {
+        unit_price: 250,
+        ..Default::default()
+    };
+    assert_eq!(
+        uut.to_value(),
+        liquid::model::value!({ "UNIT-PRICE": "2.50", "double_price": "5.00" })
+    );
+}
+
+#[derive(ValueView, Debug, Clone, Copy)]
+#[liquid(rename_all = "snake_case")]
+enum TestOrderStatus {
+    Pending,
+    PartiallyRefunded,
+    #[liquid(rename = "done")]
+    Fulfilled,
+}
+
+#[test]
+fn test_unit_enum() {
+    let uut = TestOrderStatus::PartiallyRefunded;
+    assert_eq!(uut.render().to_string(), "partially_refunded");
+    assert_eq!(uut.source().to_string(), r#""partially_refunded""#);
+    assert_eq!(uut.type_name(), "string");
+    assert_eq!(uut.to_value(), liquid::model::value!("partially_refunded"));
+    assert!(uut.as_scalar().is_some());
+    assert!(uut.as_object().is_none());
+    assert_eq!(TestOrderStatus::Pending.to_kstr(), "pending");
+    assert_eq!(TestOrderStatus::Fulfilled.to_kstr(), "done");
+}
+
+#[derive(ObjectView, ValueView, Debug)]
+#[liquid(tag = "kind")]
+enum TestPayment {
+    Cash,
+    Card {
+        last4: &'static str,
+        #[liquid(skip)]
+        #[allow(dead_code)]
+        token: &'static str,
+    },
+}
+
+#[test]
+fn test_tagged_enum() {
+    let uut = TestPayment::Card {
+        last4: "4242",
+        token: "secret",
+    };
+    assert_eq!(uut.type_name(), "object");
+    assert_eq!(uut.size(), 2);
+    assert_eq!(uut.get("kind").unwrap().to_kstr(), "Card");
+    assert_eq!(uut.contains_key("token"), false);
+    assert_eq!(
+        uut.to_value(),
+        liquid::model::value!({ "kind": "Card", "last4": "4242" })
+    );
+
+    assert_eq!(TestPayment::Cash.to_value(), liquid::model::value!("Cash"));
+    assert!(TestPayment::Cash.as_object().is_none());
+}
+
+#[derive(ValueView, Debug)]
+struct TestMoney(i64);
+
+#[derive(ValueView, Debug)]
+struct TestPoint(i64, i64, &'static str);
+
+#[test]
+fn test_tuple_structs() {
+    let money = TestMoney(1250);
+    assert_eq!(money.render().to_string(), "1250");
+    assert_eq!(money.type_name(), "whole number");
+    assert_eq!(money.to_value(), liquid::model::value!(1250));
+
+    let point = TestPoint(3, 4, "a");
+    assert_eq!(point.type_name(), "array");
+    assert_eq!(point.source().to_string(), "[3, 4, a, ]");
+    assert_eq!(point.to_value(), liquid::model::value!([3, 4, "a"]));
+    let array = point.as_array().unwrap();
+    assert_eq!(array.size(), 3);
+    assert_eq!(array.get(-1).unwrap().to_kstr(), "a");
+    assert!(array.get(3).is_none());
+    assert!(!array.contains_key(-4));
+}
+
+#[test]
+fn test_enums_and_tuple_structs_render() {
+    #[derive(ObjectView, ValueView, Debug)]
+    struct Order {
+        status: TestOrderStatus,
+        payment: TestPayment,
+        total: TestMoney,
+        point: TestPoint,
+    }
+    let order = Order {
+        status: TestOrderStatus::Pending,
+        payment: TestPayment::Card {
+            last4: "4242",
+            token: "secret",
+        },
+        total: TestMoney(1250),
+        point: TestPoint(3, 4, "a"),
+    };
+
+    let template = liquid::ParserBuilder::with_stdlib()
+        .build()
+        .unwrap()
+        .parse(concat!(
+            "{% if order.status == 'pending' %}{{ order.status | upcase }}{% endif %} ",
+            "{{ order.payment.kind }}:{{ order.payment.last4 }} ",
+            "{{ order.total | plus: 1 }} ",
+            "{{ order.point | join: ',' }} {{ order.point.last }}",
+        ))
+        .unwrap();
+    let globals = liquid::object!({ "order": order.to_value() });
+    assert_eq!(
+        template.render(&globals).unwrap(),
+        "PENDING Card:4242 1251 3,4,a a"
+    );
+    #[derive(ObjectView, ValueView, Debug)]
+    struct Globals<'o> {
+        order: &'o Order,
+    }
+    assert_eq!(
+        template.render(&Globals { order: &order }).unwrap(),
+        "PENDING Card:4242 1251 3,4,a a"
+    );
+}
@@ -88,6 +88,33 @@ pub fn minus_error() {
     assert!(output.is_err());
 }
 
+#[test]
+pub fn plus_overflow_error() {
+    let text = "{{ 9223372036854775807 | plus: 1 }}";
+    let globals = liquid::Object::new();
+    let output = liquid::ParserBuilder::with_stdlib()
+        .build()
+        .unwrap()
+        .parse(text)
+        .and_then(|template| template.render(&globals));
+
+    assert!(output.is_err());
+}
+
+#[test]
+pub fn plus_overflow_float() {
+    let text = "{{ 9223372036854775807 | plus: 1 }}";
+    let globals = liquid::Object::new();
+    let template = liquid::ParserBuilder::with_stdlib()
+        .integer_overflow(liquid::runtime::IntegerOverflow::Float)
+        .build()
+        .unwrap()
+        .parse(text)
+        .unwrap();
+    let output = template.render(&globals).unwrap();
+    assert_eq!(output, "9223372036854776000".to_string());
+}
+
 #[test]
 pub fn first_numeric_array() {
     let text = "{{ nums | first }}";
@@ -0,0 +1,54 @@
+#![cfg(feature = "jekyll")]
+
+use liquid::partials::InMemorySource;
+use liquid::LayoutCompiler;
+
+fn parser() -> liquid::Parser {
+    let mut layouts = LayoutCompiler::<InMemorySource>::empty();
+    layouts.add(
+        "default",
+        "<html><title>{{ page.title }} | {{ site.name }}</title>{{ content }}</html>",
+    );
+    layouts.add(
+        "post",
+        "---\nlayout: default\n---\n<article>{{ content }}</article>",
+    );
+    liquid::ParserBuilder::with_stdlib()
+        .partials(layouts)
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn render_with_layouts() {
+    let (front_matter, template) = parser()
+        .parse_document("---\nlayout: post\ntitle: Hello\n---\n{{ page.title | upcase }}")
+        .unwrap();
+    let globals = liquid::object!({"site": {"name": "Blog"}});
+    assert_eq!(
+        template
+            .render_with_layouts(&front_matter, &globals)
+            .unwrap(),
+        "<html><title>Hello | Blog</title><article>HELLO</article></html>"
+    );
+}
+
+#[test]
+fn render_without_layout() {
+    let (front_matter, template) = parser()
+        .parse_document("---\ntitle: Hello\n---\n{{ page.title }}")
+        .unwrap();
+    assert_eq!(
+        template
+            .render_with_layouts(&front_matter, &liquid::Object::new())
+            .unwrap(),
+        "Hello"
+    );
+}
+
+#[test]
+fn render_document_without_front_matter() {
+    let (front_matter, template) = parser().parse_document("{{ 'text' }}").unwrap();
+    assert!(front_matter.is_empty());
+    assert_eq!(template.render(&front_matter).unwrap(), "text");
+}
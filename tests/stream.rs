@@ -0,0 +1,134 @@
+use std::io::{self, Write};
+
+use liquid::StreamOptions;
+
+fn parse(text: &str) -> liquid::Template {
+    liquid::ParserBuilder::with_stdlib()
+        .build()
+        .unwrap()
+        .parse(text)
+        .unwrap()
+}
+
+/// Records what was written before each flush.
+#[derive(Default)]
+struct Flushes {
+    pending: Vec<u8>,
+    flushed: Vec<String>,
+}
+
+impl Write for Flushes {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.pending.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        let pending = std::mem::take(&mut self.pending);
+        self.flushed.push(String::from_utf8(pending).unwrap());
+        Ok(())
+    }
+}
+
+#[test]
+fn render_chunks() {
+    let template =
+        parse("{% assign n = 2 %}<ul>{% for i in (1..n) %}<li>{{ i }}</li>{% endfor %}</ul>");
+    let globals = liquid::object!({});
+    let chunks: Vec<_> = template
+        .render_chunks(&globals)
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(chunks, vec!["<ul>", "<li>1</li><li>2</li>", "</ul>"]);
+}
+
+#[test]
+fn render_chunks_stops_at_error() {
+    let template = parse("<p>{{ missing.name }}</p>");
+    let globals = liquid::object!({});
+    let mut chunks = template.render_chunks(&globals);
+    assert_eq!(chunks.next().unwrap().unwrap(), "<p>");
+    chunks.next().unwrap().unwrap_err();
+    assert!(chunks.next().is_none());
+}
+
+#[test]
+fn stream_to_flushes_each_element() {
+    let template = parse("<p>{{ name }}</p>");
+    let globals = liquid::object!({"name": "Jane"});
+    let mut writer = Flushes::default();
+    template
+        .stream_to(
+            &mut writer,
+            &globals,
+            StreamOptions::new().flush_each_element(true),
+        )
+        .unwrap();
+    assert_eq!(writer.flushed, vec!["<p>", "Jane", "</p>"]);
+}
+
+#[test]
+fn stream_to_buffered() {
+    let template = parse("<p>{{ name }}</p>");
+    let options = StreamOptions::new().buffered(true).flush_each_element(true);
+
+    let mut writer = Flushes::default();
+    let globals = liquid::object!({"name": "Jane"});
+    template.stream_to(&mut writer, &globals, options).unwrap();
+    assert_eq!(writer.flushed, vec!["<p>Jane</p>"]);
+
+    let mut output = Vec::new();
+    let globals = liquid::object!({});
+    template
+        .stream_to(&mut output, &globals, options)
+        .unwrap_err();
+    assert!(output.is_empty());
+
+    template
+        .stream_to(&mut output, &globals, StreamOptions::new())
+        .unwrap_err();
+    assert_eq!(output, b"<p>");
+}
+
+#[test]
+fn render_chunks_follow_parsed_elements() {
+    // Neighbouring text makes one chunk, including branches picked while parsing.
+    let template =
+        parse("a{% if true %}b{% endif %}{% comment %}c{% endcomment %}d{{ 'x' | upcase }}{{ n }}");
+    let globals = liquid::object!({"n": 1});
+    let chunks: Vec<_> = template
+        .render_chunks(&globals)
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(chunks, vec!["abd", "X", "1"]);
+}
+
+/// Records each write.
+#[derive(Default)]
+struct Writes(Vec<String>);
+
+impl Write for Writes {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.push(String::from_utf8(buf.to_vec()).unwrap());
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn stream_to_writes_as_rendered() {
+    let template = parse("<ul>{% for i in (1..2) %}<li>{{ i }}</li>{% endfor %}</ul>");
+    let globals = liquid::object!({});
+    let mut writer = Writes::default();
+    template
+        .stream_to(&mut writer, &globals, StreamOptions::new())
+        .unwrap();
+    // The loop's output isn't held back until it ends.
+    assert_eq!(
+        writer.0,
+        vec!["<ul>", "<li>", "1", "</li>", "<li>", "2", "</li>", "</ul>"]
+    );
+}
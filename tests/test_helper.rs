@@ -63,6 +63,8 @@ macro_rules! assert_template_result {
         let template = $liquid.parse($template.as_ref()).unwrap();
         let rendered = template.render(&$assigns).unwrap();
         assert_eq!($expected, rendered);
+        let rendered = template.compile().render(&$assigns).unwrap();
+        assert_eq!($expected, rendered, "compiled");
     };
 }
 
@@ -86,12 +88,15 @@ macro_rules! assert_template_matches {
             .parse($template.as_ref())
             .unwrap();
         let rendered = template.render(&$assigns).unwrap();
+        let compiled = template.compile().render(&$assigns).unwrap();
 
         let expected = $expected;
         println!("pattern={}", expected);
         let expected = regex::Regex::new(expected).unwrap();
         println!("rendered={}", rendered);
         assert!(expected.is_match(&rendered));
+        println!("compiled={}", compiled);
+        assert!(expected.is_match(&compiled));
     };
 }
 
@@ -134,6 +139,8 @@ macro_rules! assert_render_error {
             .unwrap()
             .parse($template.as_ref())
             .unwrap();
-        template.render(&$assigns).unwrap_err();
+        let error = template.render(&$assigns).unwrap_err();
+        let compiled = template.compile().render(&$assigns).unwrap_err();
+        assert_eq!(error.to_string(), compiled.to_string(), "compiled");
     };
 }
@@ -0,0 +1,140 @@
+use liquid::model::{Schema, SchemaType, ToSchema};
+use liquid::{ObjectView, ValueView};
+
+fn parse(text: &str) -> liquid::Template {
+    liquid::ParserBuilder::with_stdlib()
+        .build()
+        .unwrap()
+        .parse(text)
+        .unwrap()
+}
+
+fn referenced(template: &liquid::Template) -> Vec<String> {
+    template
+        .referenced_globals()
+        .iter()
+        .map(|r| {
+            if r.is_optional() {
+                format!("{}?", r)
+            } else {
+                r.to_string()
+            }
+        })
+        .collect()
+}
+
+#[test]
+fn test_referenced_globals() {
+    let template = parse(concat!(
+        "{% assign title = page.title | default: site.title %}{{ title }}",
+        "{% if user %}{{ user.name }}{% else %}{{ guest.greeting }}{% endif %}",
+        "{% for product in collection.products limit: page.limit %}",
+        "{{ forloop.index }}{{ product.title }}{{ prices[product.id] }}",
+        "{% endfor %}",
+        "{{ items[0].name }}",
+    ));
+    assert_eq!(
+        referenced(&template),
+        vec![
+            "page.title",
+            "site.title",
+            "user?",
+            "user.name?",
+            "guest.greeting",
+            "collection.products",
+            "page.limit",
+            "prices",
+            "items[0].name",
+        ]
+    );
+}
+
+#[test]
+fn test_referenced_globals_of_partials() {
+    let mut partials = liquid::partials::InMemorySource::new();
+    partials.add("card", "{{ card.title }}{{ theme.color }}");
+    let template = liquid::ParserBuilder::with_stdlib()
+        .partials(liquid::partials::EagerCompiler::new(partials))
+        .build()
+        .unwrap()
+        .parse("{% include 'card' card: featured %}")
+        .unwrap();
+    assert_eq!(referenced(&template), vec!["featured", "theme.color"]);
+}
+
+#[test]
+fn test_validate_references() {
+    let template = parse("{% if user %}{{ user.name }}{% endif %}{{ site.name }}");
+
+    let globals = liquid::object!({ "site": { "name": "Blog" } });
+    template.validate(&globals).unwrap();
+
+    let globals = liquid::object!({ "site": {} });
+    let err = template.validate(&globals).unwrap_err().to_string();
+    assert!(err.contains("missing=site.name"), "{}", err);
+}
+
+#[derive(ObjectView, ValueView, liquid::ToSchema, Debug)]
+struct Author {
+    name: String,
+    #[liquid(rename = "mail")]
+    email: Option<String>,
+}
+
+#[derive(ObjectView, ValueView, liquid::ToSchema, Debug)]
+struct Post<'a> {
+    title: &'a str,
+    tags: Vec<String>,
+    author: Author,
+    #[liquid(skip)]
+    #[allow(dead_code)]
+    draft: bool,
+}
+
+#[test]
+fn test_derive_schema() {
+    let expected = Schema::object()
+        .property("title", Schema::new(SchemaType::String))
+        .property("tags", Schema::array(Schema::new(SchemaType::String)))
+        .property(
+            "author",
+            Schema::object()
+                .property("name", Schema::new(SchemaType::String))
+                .optional_property("mail", Schema::new(SchemaType::String)),
+        );
+    assert_eq!(Post::schema(), expected);
+}
+
+#[test]
+fn test_validate_schema() {
+    let template = parse("{{ post.title }} by {{ post.author.name }}")
+        .with_schema(Schema::object().field::<Post<'_>>("post"));
+
+    #[derive(ObjectView, ValueView, Debug)]
+    struct Globals<'p> {
+        post: Post<'p>,
+    }
+    let globals = Globals {
+        post: Post {
+            title: "Hello",
+            tags: vec!["intro".to_owned()],
+            author: Author {
+                name: "Ann".to_owned(),
+                email: None,
+            },
+            draft: false,
+        },
+    };
+    template.validate(&globals).unwrap();
+
+    let globals = liquid::object!({
+        "post": { "title": 5, "tags": [], "author": {} },
+    });
+    let err = template.validate(&globals).unwrap_err().to_string();
+    assert!(err.contains("missing=post.author.name"), "{}", err);
+    assert!(
+        err.contains("mistyped=post.title (expected string, found whole number)"),
+        "{}",
+        err
+    );
+}